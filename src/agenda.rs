@@ -0,0 +1,111 @@
+//! Day templates: named runs of blocks ("Morning: 4×50/10, Afternoon: 6×25/5")
+//! in config.json, one picked at launch with `--template <name>`. The timer takes
+//! each block's lengths in turn. Work sessions completed in a block carry its
+//! name into the history, so a relaunch on the same day picks up where it was.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::history::{self, SessionRecord};
+
+/// A run of identical pomodoros within a day template
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgendaBlock {
+    pub name: String,
+    pub pomodoros: u8,
+    pub work_minutes: u64,
+    pub break_minutes: u64,
+}
+
+impl AgendaBlock {
+    /// "4×50/10"
+    pub fn shape(&self) -> String {
+        format!("{}×{}/{}", self.pomodoros, self.work_minutes, self.break_minutes)
+    }
+}
+
+/// The day template being walked through
+#[derive(Debug, Clone)]
+pub struct Agenda {
+    pub name: String,
+    pub blocks: Vec<AgendaBlock>,
+    /// Work sessions completed in each block
+    done: Vec<u8>,
+}
+
+impl Agenda {
+    pub fn new(name: &str, blocks: Vec<AgendaBlock>) -> Self {
+        Self {
+            name: name.to_string(),
+            done: vec![0; blocks.len()],
+            blocks,
+        }
+    }
+
+    /// Count the day's completed work sessions back into their blocks
+    pub fn seed(&mut self, records: &[SessionRecord], day: NaiveDate) {
+        for (block, done) in self.blocks.iter().zip(&mut self.done) {
+            let completed = history::completed_in_block(records, day, &block.name);
+            *done = completed.min(block.pomodoros as usize) as u8;
+        }
+    }
+
+    /// Index of the block in progress; None once every block is done
+    pub fn current(&self) -> Option<usize> {
+        self.blocks
+            .iter()
+            .zip(&self.done)
+            .position(|(block, &done)| done < block.pomodoros)
+    }
+
+    pub fn current_block(&self) -> Option<&AgendaBlock> {
+        self.current().map(|index| &self.blocks[index])
+    }
+
+    /// Work sessions completed in a block
+    pub fn done(&self, index: usize) -> u8 {
+        self.done[index]
+    }
+
+    /// Count a completed work session in the block in progress
+    pub fn complete_one(&mut self) {
+        if let Some(index) = self.current() {
+            self.done[index] += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::{SessionKind, SessionOutcome};
+    use std::time::Duration;
+
+    fn block(name: &str, pomodoros: u8, work_minutes: u64, break_minutes: u64) -> AgendaBlock {
+        AgendaBlock {
+            name: name.to_string(),
+            pomodoros,
+            work_minutes,
+            break_minutes,
+        }
+    }
+
+    #[test]
+    fn test_agenda_walks_blocks_in_order() {
+        let mut agenda = Agenda::new("deep day", vec![block("Morning", 2, 50, 10), block("Afternoon", 6, 25, 5)]);
+        assert_eq!(agenda.blocks[0].shape(), "2×50/10");
+        assert_eq!(agenda.current(), Some(0));
+
+        agenda.complete_one();
+        agenda.complete_one();
+        assert_eq!(agenda.current_block().map(|b| b.name.as_str()), Some("Afternoon"));
+
+        // Today's history fills the blocks back in, capped at their size
+        let mut record = SessionRecord::new(SessionKind::Work, SessionOutcome::Completed, Duration::from_secs(1500));
+        record.block = Some("Morning".to_string());
+        let today = chrono::Local::now().date_naive();
+        let mut fresh = Agenda::new("deep day", agenda.blocks.clone());
+        fresh.seed(&[record.clone(), record.clone(), record], today);
+        assert_eq!((fresh.done(0), fresh.done(1), fresh.current()), (2, 0, Some(1)));
+    }
+}
@@ -0,0 +1,86 @@
+//! Alarm screen (`alarm` in config.json): when a work session ends, the alert
+//! (the silent-mode pulse and banner) takes over the whole screen with a pulsing
+//! "BREAK TIME" until a key is pressed. It rings the terminal bell as it opens,
+//! which most terminals (and tmux) turn into an urgency hint on the window, and
+//! with `bell` on keeps ringing, more and more often, for as long as it goes
+//! unnoticed.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// Seconds between the first rings; after these the bell rings every `LAST_GAP`
+const BELL_GAPS: [u64; 3] = [30, 20, 10];
+const LAST_GAP: u64 = 5;
+
+/// Alarm screen settings (the `alarm` key in config.json)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AlarmConfig {
+    /// Take over the screen when a work session ends, until a key is pressed
+    pub enabled: bool,
+    /// Keep ringing the bell, more and more often, until then
+    pub bell: bool,
+}
+
+/// Words across the alarm screen
+pub const MESSAGE: &str = "BREAK TIME";
+
+/// The alarm screen's bell while it waits for a key
+#[derive(Debug, Clone)]
+pub struct Bell {
+    since: Instant,
+    /// Bells rung so far
+    rung: u32,
+    escalate: bool,
+}
+
+impl Bell {
+    /// A bell for an alarm opening at `now` (from the app's clock)
+    pub fn new(escalate: bool, now: Instant) -> Self {
+        Self {
+            since: now,
+            rung: 0,
+            escalate,
+        }
+    }
+
+    /// Whether the bell should ring now: once as the alarm opens, then on the
+    /// escalating schedule when `bell` is on
+    pub fn ring_due(&mut self, now: Instant) -> bool {
+        self.due_at(now.saturating_duration_since(self.since))
+    }
+
+    fn due_at(&mut self, elapsed: Duration) -> bool {
+        let due = (self.rung == 0 || self.escalate) && elapsed >= ring_time(self.rung);
+        if due {
+            self.rung += 1;
+        }
+        due
+    }
+}
+
+/// When ring number `n` (from 0) is due
+fn ring_time(n: u32) -> Duration {
+    let secs: u64 = (0..n as usize).map(|i| BELL_GAPS.get(i).copied().unwrap_or(LAST_GAP)).sum();
+    Duration::from_secs(secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rings(bell: &mut Bell, up_to_secs: u64) -> Vec<u64> {
+        (0..=up_to_secs).filter(|&s| bell.due_at(Duration::from_secs(s))).collect()
+    }
+
+    #[test]
+    fn test_bell_rings_more_often_until_acknowledged() {
+        let mut bell = Bell::new(true, Instant::now());
+        assert_eq!(rings(&mut bell, 75), [0, 30, 50, 60, 65, 70, 75]);
+
+        // Without `bell`, only the attention ring as it opens
+        let mut quiet = Bell::new(false, Instant::now());
+        assert_eq!(rings(&mut quiet, 75), [0]);
+    }
+}
@@ -0,0 +1,34 @@
+//! Theme background fills
+//! Themes paint their backdrop through here so the "use terminal background"
+//! mode (`transparent_background`) can swap every fill for `Color::Reset`,
+//! leaving a transparent or hand-tuned terminal background visible behind the
+//! foreground effects.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use ratatui::prelude::*;
+use ratatui::widgets::Block;
+
+static TRANSPARENT: AtomicBool = AtomicBool::new(false);
+
+pub fn set_transparent(enabled: bool) {
+    TRANSPARENT.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_transparent() -> bool {
+    TRANSPARENT.load(Ordering::Relaxed)
+}
+
+/// The color to paint a background with: `color`, or the terminal's own in transparent mode
+pub fn color(color: Color) -> Color {
+    if is_transparent() {
+        Color::Reset
+    } else {
+        color
+    }
+}
+
+/// Fill `area` with a theme's backdrop
+pub fn fill(frame: &mut Frame, area: Rect, bg: Color) {
+    frame.render_widget(Block::default().style(Style::default().bg(color(bg))), area);
+}
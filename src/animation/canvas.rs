@@ -0,0 +1,202 @@
+//! Sub-cell drawing surface for smooth curves
+//! Themes plot in dots rather than cells: with `braille` on, each cell holds a
+//! 2x4 grid of braille dots (like btop's graphs); otherwise it falls back to
+//! half blocks, two dots per cell stacked vertically. Dots are square at the
+//! usual 2:1 cell; `circle` corrects for the canvas's cell aspect. A cell
+//! shows the color of the last dot plotted in it and keeps its background.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use ratatui::prelude::*;
+
+use crate::scaling::DEFAULT_CELL_ASPECT;
+
+static HIGH_RES: AtomicBool = AtomicBool::new(false);
+
+pub fn set_high_res(enabled: bool) {
+    HIGH_RES.store(enabled, Ordering::Relaxed);
+}
+
+pub fn is_high_res() -> bool {
+    HIGH_RES.load(Ordering::Relaxed)
+}
+
+/// How a cell is split into dots
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    /// 2x4 braille dots
+    Braille,
+    /// Upper and lower half blocks
+    HalfBlock,
+}
+
+impl Resolution {
+    /// Dots per cell, across and down
+    pub fn dots(self) -> (u16, u16) {
+        match self {
+            Resolution::Braille => (2, 4),
+            Resolution::HalfBlock => (1, 2),
+        }
+    }
+
+    /// Bit of a dot in the cell mask; for braille the bits of U+2800 onwards
+    fn bit(self, col: u16, row: u16) -> u8 {
+        match self {
+            Resolution::Braille => [[0x01, 0x08], [0x02, 0x10], [0x04, 0x20], [0x40, 0x80]][row as usize][col as usize],
+            Resolution::HalfBlock => 1 << row,
+        }
+    }
+
+    fn symbol(self, mask: u8) -> char {
+        match self {
+            Resolution::Braille => char::from_u32(0x2800 + mask as u32).unwrap_or(' '),
+            Resolution::HalfBlock => match mask {
+                1 => '▀',
+                2 => '▄',
+                _ => '█',
+            },
+        }
+    }
+}
+
+/// Dots to draw over `area`, flushed to the frame with [`Canvas::render`]
+pub struct Canvas {
+    area: Rect,
+    resolution: Resolution,
+    /// Height of a cell over its width
+    aspect: f32,
+    /// Lit dots and color per cell, row by row
+    cells: Vec<(u8, Color)>,
+}
+
+impl Canvas {
+    /// Canvas in braille or half blocks, following the `braille` setting, for
+    /// cells `aspect` times as tall as they are wide
+    pub fn new(area: Rect, aspect: f32) -> Self {
+        let resolution = if is_high_res() { Resolution::Braille } else { Resolution::HalfBlock };
+        Self { aspect, ..Self::with_resolution(area, resolution) }
+    }
+
+    pub fn with_resolution(area: Rect, resolution: Resolution) -> Self {
+        Self {
+            area,
+            resolution,
+            aspect: DEFAULT_CELL_ASPECT,
+            cells: vec![(0, Color::Reset); area.area() as usize],
+        }
+    }
+
+    /// Dots per cell, across and down
+    pub fn dots_per_cell(&self) -> (f32, f32) {
+        let (x, y) = self.resolution.dots();
+        (x as f32, y as f32)
+    }
+
+    /// Width in dots
+    pub fn width(&self) -> f32 {
+        (self.area.width * self.resolution.dots().0) as f32
+    }
+
+    /// Height in dots
+    pub fn height(&self) -> f32 {
+        (self.area.height * self.resolution.dots().1) as f32
+    }
+
+    /// Light the dot at (`x`, `y`); anything off the canvas is ignored
+    pub fn point(&mut self, x: f32, y: f32, color: Color) {
+        if !(0.0..self.width()).contains(&x) || !(0.0..self.height()).contains(&y) {
+            return;
+        }
+        let (dots_x, dots_y) = self.resolution.dots();
+        let (x, y) = (x as u16, y as u16);
+        let index = (y / dots_y) as usize * self.area.width as usize + (x / dots_x) as usize;
+        let cell = &mut self.cells[index];
+        cell.0 |= self.resolution.bit(x % dots_x, y % dots_y);
+        cell.1 = color;
+    }
+
+    pub fn line(&mut self, from: (f32, f32), to: (f32, f32), color: Color) {
+        let steps = (to.0 - from.0).abs().max((to.1 - from.1).abs()).ceil().max(1.0) as usize;
+        for step in 0..=steps {
+            let t = step as f32 / steps as f32;
+            self.point(from.0 + (to.0 - from.0) * t, from.1 + (to.1 - from.1) * t, color);
+        }
+    }
+
+    /// Height of a dot over its width
+    pub fn dot_aspect(&self) -> f32 {
+        let (dots_x, dots_y) = self.dots_per_cell();
+        self.aspect * dots_x / dots_y
+    }
+
+    /// Circle `radius` dots across, kept round on screen
+    pub fn circle(&mut self, center: (f32, f32), radius: f32, color: Color) {
+        let radius_y = radius / self.dot_aspect();
+        // About one point per dot of circumference
+        let steps = (radius * std::f32::consts::TAU).ceil().max(8.0) as usize;
+        for step in 0..steps {
+            let angle = step as f32 / steps as f32 * std::f32::consts::TAU;
+            self.point(center.0 + radius * angle.cos(), center.1 + radius_y * angle.sin(), color);
+        }
+    }
+
+    /// Fill column `x` from `y` down to the bottom, for hills and water
+    pub fn fill_below(&mut self, x: f32, y: f32, color: Color) {
+        let mut dot = y.max(0.0).floor();
+        while dot < self.height() {
+            self.point(x, dot, color);
+            dot += 1.0;
+        }
+    }
+
+    /// Draw the lit cells, leaving the others as they are
+    pub fn render(&self, frame: &mut Frame) {
+        let buf = frame.buffer_mut();
+        let visible = self.area.intersection(buf.area);
+        for y in visible.top()..visible.bottom() {
+            for x in visible.left()..visible.right() {
+                let index = (y - self.area.y) as usize * self.area.width as usize + (x - self.area.x) as usize;
+                let (mask, color) = self.cells[index];
+                if mask != 0 {
+                    buf[(x, y)].set_char(self.resolution.symbol(mask)).set_fg(color);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+
+    fn draw(resolution: Resolution) -> String {
+        let area = Rect::new(0, 0, 2, 1);
+        let mut canvas = Canvas::with_resolution(area, resolution);
+        canvas.point(0.0, 0.0, Color::White);
+        canvas.point(canvas.width() - 1.0, canvas.height() - 1.0, Color::White);
+        let mut terminal = ratatui::Terminal::new(TestBackend::new(2, 1)).unwrap();
+        terminal.draw(|frame| canvas.render(frame)).unwrap();
+        let buf = terminal.backend().buffer();
+        (0..2).map(|x| buf[(x, 0)].symbol()).collect()
+    }
+
+    #[test]
+    fn test_braille_and_half_block_dots() {
+        // Top-left dot of the first cell, bottom-right of the second
+        assert_eq!(draw(Resolution::Braille), "⠁⢀");
+        assert_eq!(draw(Resolution::HalfBlock), "▀▄");
+    }
+
+    #[test]
+    fn test_circle_is_flatter_in_taller_cells() {
+        let rows = |aspect: f32| {
+            let mut canvas = Canvas { aspect, ..Canvas::with_resolution(Rect::new(0, 0, 20, 20), Resolution::HalfBlock) };
+            canvas.circle((10.0, 20.0), 8.0, Color::White);
+            canvas.cells.chunks(20).filter(|row| row.iter().any(|cell| cell.0 != 0)).count()
+        };
+        // A radius of 8 dots spans 16 half-block dots (eight rows) at 2:1, half that at 4:1
+        assert_eq!(rows(2.0), 8);
+        assert_eq!(rows(4.0), 4);
+    }
+}
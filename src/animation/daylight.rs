@@ -0,0 +1,96 @@
+//! Real daylight for the Landscape theme (`landscape_sky` in config.json)
+//! Off by default, so the scene runs its own quick day/night cycle. With
+//! `local_time` on, the sun follows the local clock instead: up at sunrise, high
+//! at noon, down at sunset. Sunrise and sunset are 06:00 and 18:00, or worked
+//! out for the day of the year from `latitude` (solar time, so a few minutes off
+//! the official times).
+
+use std::f32::consts::PI;
+use std::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+use chrono::{Datelike, Local, NaiveDateTime, Timelike};
+use serde::{Deserialize, Serialize};
+
+static LOCAL_TIME: AtomicBool = AtomicBool::new(false);
+
+/// Bits of the `f32` latitude; NaN while unset
+static LATITUDE: AtomicU32 = AtomicU32::new(0x7fc0_0000);
+
+/// Earth's axial tilt, in degrees
+const AXIAL_TILT: f64 = 23.44;
+
+/// Landscape sky settings (the `landscape_sky` key in config.json)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SkyConfig {
+    /// Follow the local clock instead of the animated cycle
+    pub local_time: bool,
+    /// Degrees north (negative south) for seasonal sunrise and sunset; unset is 06:00-18:00
+    pub latitude: Option<f64>,
+}
+
+pub fn set(config: &SkyConfig) {
+    LOCAL_TIME.store(config.local_time, Ordering::Relaxed);
+    let latitude = config.latitude.map_or(f32::NAN, |l| l.clamp(-90.0, 90.0) as f32);
+    LATITUDE.store(latitude.to_bits(), Ordering::Relaxed);
+}
+
+/// Where the sun is on its circle right now (0 rising, π/2 noon, π setting,
+/// 3π/2 midnight), or None to run the animated cycle
+pub fn sun_angle() -> Option<f32> {
+    if !LOCAL_TIME.load(Ordering::Relaxed) {
+        return None;
+    }
+    let latitude = f32::from_bits(LATITUDE.load(Ordering::Relaxed));
+    let now = Local::now().naive_local();
+    Some(angle_at(now, (!latitude.is_nan()).then_some(latitude as f64)))
+}
+
+fn angle_at(now: NaiveDateTime, latitude: Option<f64>) -> f32 {
+    let (sunrise, sunset) = sun_hours(now.ordinal(), latitude);
+    let hour = now.hour() as f32 + now.minute() as f32 / 60.0 + now.second() as f32 / 3600.0;
+    if (sunrise..sunset).contains(&hour) {
+        PI * (hour - sunrise) / (sunset - sunrise)
+    } else {
+        let night = 24.0 - (sunset - sunrise);
+        PI + PI * (hour - sunset).rem_euclid(24.0) / night
+    }
+}
+
+/// Sunrise and sunset as hours of the (solar) day
+fn sun_hours(day_of_year: u32, latitude: Option<f64>) -> (f32, f32) {
+    let Some(latitude) = latitude else {
+        return (6.0, 18.0);
+    };
+    let declination = AXIAL_TILT.to_radians() * (2.0 * std::f64::consts::PI * (284.0 + day_of_year as f64) / 365.0).sin();
+    let cos_hour_angle = -latitude.to_radians().tan() * declination.tan();
+    // Keep a sliver of day or night under the midnight sun and the polar night
+    let hour_angle = cos_hour_angle.clamp(-1.0, 1.0).acos().clamp(0.05, std::f64::consts::PI - 0.05);
+    let half_day = (hour_angle.to_degrees() / 15.0) as f32;
+    (12.0 - half_day, 12.0 + half_day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn at(month: u32, day: u32, hour: u32, minute: u32) -> NaiveDateTime {
+        NaiveDate::from_ymd_opt(2025, month, day).unwrap().and_hms_opt(hour, minute, 0).unwrap()
+    }
+
+    #[test]
+    fn test_sun_follows_the_clock() {
+        let close = |a: f32, b: f32| (a - b).abs() < 0.01;
+        assert!(close(angle_at(at(3, 20, 6, 0), None), 0.0));
+        assert!(close(angle_at(at(3, 20, 12, 0), None), PI / 2.0));
+        assert!(close(angle_at(at(3, 20, 0, 0), None), 1.5 * PI));
+        assert!(close(angle_at(at(3, 20, 19, 30), None), PI + PI / 8.0));
+
+        // Long summer days and short winter ones up north
+        let (june_rise, june_set) = sun_hours(172, Some(60.0));
+        let (december_rise, _) = sun_hours(355, Some(60.0));
+        assert!(june_rise < 3.0 && june_set > 21.0);
+        assert!(december_rise > 9.0);
+    }
+}
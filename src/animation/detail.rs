@@ -0,0 +1,111 @@
+//! Scene detail for the heavy themes, from 0 (bare scene) to 3 (everything)
+//! The terminal size caps it (`ScalingContext::background_detail_level`) and the
+//! engine tunes it below that cap from how long the theme takes to draw: a scene
+//! eating into the frame budget sheds particles and layers a tier at a time, and
+//! earns them back once drawing is cheap again. Themes get it in
+//! `RenderParams::detail`.
+
+use std::time::Duration;
+
+/// Every layer and particle
+pub const FULL: u8 = 3;
+
+/// Mean draw time over a window above which detail drops a tier
+const SLOW: Duration = Duration::from_millis(25);
+
+/// Mean draw time below which detail climbs back a tier
+const FAST: Duration = Duration::from_millis(8);
+
+/// Frames averaged before each decision (two seconds at 10 FPS)
+const WINDOW: u32 = 20;
+
+/// Windows to wait after a slow one before climbing again, so a scene on the edge
+/// doesn't flip between tiers
+const HOLD_WINDOWS: u32 = 15;
+
+/// Picks the detail tier from measured draw times
+#[derive(Debug)]
+pub struct DetailTuner {
+    cap: u8,
+    level: u8,
+    total: Duration,
+    frames: u32,
+    hold: u32,
+}
+
+impl Default for DetailTuner {
+    fn default() -> Self {
+        Self {
+            cap: FULL,
+            level: FULL,
+            total: Duration::ZERO,
+            frames: 0,
+            hold: 0,
+        }
+    }
+}
+
+impl DetailTuner {
+    /// Most detail the terminal size allows
+    pub fn set_cap(&mut self, cap: u8) {
+        self.cap = cap.min(FULL);
+        self.level = self.level.min(self.cap);
+    }
+
+    pub fn level(&self) -> u8 {
+        self.level
+    }
+
+    /// Count one frame's draw time, moving a tier once a window is full
+    pub fn record(&mut self, took: Duration) {
+        self.total += took;
+        self.frames += 1;
+        if self.frames < WINDOW {
+            return;
+        }
+        let mean = self.total / self.frames;
+        self.total = Duration::ZERO;
+        self.frames = 0;
+        self.hold = self.hold.saturating_sub(1);
+        if mean > SLOW {
+            self.level = self.level.saturating_sub(1);
+            self.hold = HOLD_WINDOWS;
+        } else if mean < FAST && self.level < self.cap && self.hold == 0 {
+            self.level += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn window(tuner: &mut DetailTuner, each: Duration) {
+        for _ in 0..WINDOW {
+            tuner.record(each);
+        }
+    }
+
+    #[test]
+    fn test_slow_frames_shed_detail_and_fast_ones_earn_it_back() {
+        let mut tuner = DetailTuner::default();
+        tuner.set_cap(2);
+        assert_eq!(tuner.level(), 2);
+
+        window(&mut tuner, SLOW * 2);
+        assert_eq!(tuner.level(), 1);
+        window(&mut tuner, SLOW * 2);
+        assert_eq!(tuner.level(), 0);
+        window(&mut tuner, SLOW * 2);
+        assert_eq!(tuner.level(), 0);
+
+        // Held down for a while, then back up to the cap and no further
+        for _ in 0..HOLD_WINDOWS {
+            window(&mut tuner, Duration::from_millis(1));
+        }
+        assert_eq!(tuner.level(), 1);
+        window(&mut tuner, Duration::from_millis(1));
+        window(&mut tuner, Duration::from_millis(1));
+        assert_eq!(tuner.level(), 2);
+    }
+}
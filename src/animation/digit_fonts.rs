@@ -37,6 +37,8 @@ pub enum DigitFont {
     SeasonalFont,
     /// African acacia branch style with organic angular shapes (9x9) - matches Landscape theme
     Savanna,
+    /// Classic 7-segment LED geometry with faint unlit segments (6x7)
+    SevenSegment,
 }
 
 impl DigitFont {
@@ -69,9 +71,52 @@ impl DigitFont {
             DigitFont::Hairline => "Hairline",
             DigitFont::SeasonalFont => "Seasonal",
             DigitFont::Savanna => "Savanna",
+            DigitFont::SevenSegment => "Seven Segment",
         }
     }
 
+    /// Every font, including theme-specific ones not in the `f` cycle
+    pub fn every() -> &'static [DigitFont] {
+        &[
+            DigitFont::Classic,
+            DigitFont::Block3D,
+            DigitFont::Outlined,
+            DigitFont::Isometric,
+            DigitFont::LCD,
+            DigitFont::Organic,
+            DigitFont::ClaudeFont,
+            DigitFont::Terminal,
+            DigitFont::Gothic,
+            DigitFont::Neon,
+            DigitFont::Bamboo,
+            DigitFont::Angular,
+            DigitFont::Fragmented,
+            DigitFont::Hairline,
+            DigitFont::SeasonalFont,
+            DigitFont::Savanna,
+            DigitFont::SevenSegment,
+        ]
+    }
+
+    /// Command-friendly identifier, e.g. "3d-blocks"
+    pub fn slug(&self) -> String {
+        self.name().to_lowercase().replace(' ', "-")
+    }
+
+    /// Look up a font by slug or slug prefix ("lcd", "3d")
+    pub fn from_name(query: &str) -> Option<DigitFont> {
+        let query = query.trim().to_lowercase().replace(' ', "-");
+        if query.is_empty() {
+            return None;
+        }
+        let fonts = Self::every();
+        fonts
+            .iter()
+            .find(|f| f.slug() == query)
+            .or_else(|| fonts.iter().find(|f| f.slug().starts_with(&query)))
+            .copied()
+    }
+
     pub fn height(&self) -> u16 {
         match self {
             DigitFont::Classic => 5,
@@ -90,6 +135,7 @@ impl DigitFont {
             DigitFont::Hairline => 7,
             DigitFont::SeasonalFont => 8,
             DigitFont::Savanna => 9,
+            DigitFont::SevenSegment => 7,
         }
     }
 
@@ -111,6 +157,7 @@ impl DigitFont {
             DigitFont::Hairline => 5,
             DigitFont::SeasonalFont => 6,
             DigitFont::Savanna => 9,
+            DigitFont::SevenSegment => 6,
         }
     }
 
@@ -132,6 +179,7 @@ impl DigitFont {
             DigitFont::Hairline => 1,
             DigitFont::SeasonalFont => 2,
             DigitFont::Savanna => 3,
+            DigitFont::SevenSegment => 2,
         }
     }
 
@@ -154,6 +202,7 @@ impl DigitFont {
             DigitFont::Hairline => &HAIRLINE_DIGITS[digit],
             DigitFont::SeasonalFont => &SEASONALFONT_DIGITS[digit],
             DigitFont::Savanna => &SAVANNA_DIGITS[digit],
+            DigitFont::SevenSegment => &SEVEN_SEGMENT_DIGITS[digit],
         }
     }
 
@@ -175,6 +224,7 @@ impl DigitFont {
             DigitFont::Hairline => &HAIRLINE_COLON,
             DigitFont::SeasonalFont => &SEASONALFONT_COLON,
             DigitFont::Savanna => &SAVANNA_COLON,
+            DigitFont::SevenSegment => &SEVEN_SEGMENT_COLON,
         }
     }
 
@@ -197,6 +247,7 @@ impl DigitFont {
             DigitFont::Hairline => &['│', '─', '┌', '┐', '└', '┘', '├', '┤', '┬', '┴', '┼'],
             DigitFont::SeasonalFont => &['█', '▀', '▄', '▐', '▌', '●', '○', '◐', '◑'],
             DigitFont::Savanna => &['█', '▀', '▄'],
+            DigitFont::SevenSegment => &['█', '▀', '▄'],
         }
     }
 
@@ -219,6 +270,7 @@ impl DigitFont {
             DigitFont::Hairline => &['·', '.'],
             DigitFont::SeasonalFont => &['░', '○'],
             DigitFont::Savanna => &['▐', '▌', '│', '╱', '╲'],
+            DigitFont::SevenSegment => &[],
         }
     }
 
@@ -2272,6 +2324,140 @@ const SAVANNA_COLON: [&str; 9] = [
     "   ",
 ];
 
+// ============================================================================
+// SEVEN SEGMENT FONT (6x7) - Classic LED display geometry
+// Unlit segments are left blank; `digits::LedDecay` draws their faint ghosts
+// ============================================================================
+
+const SEVEN_SEGMENT_DIGITS: [[&str; 7]; 10] = [
+    // 0
+    [
+        " ▄▄▄▄ ",
+        "█    █",
+        "█    █",
+        "      ",
+        "█    █",
+        "█    █",
+        " ▀▀▀▀ ",
+    ],
+    // 1
+    [
+        "      ",
+        "     █",
+        "     █",
+        "      ",
+        "     █",
+        "     █",
+        "      ",
+    ],
+    // 2
+    [
+        " ▄▄▄▄ ",
+        "     █",
+        "     █",
+        " ▄▄▄▄ ",
+        "█     ",
+        "█     ",
+        " ▀▀▀▀ ",
+    ],
+    // 3
+    [
+        " ▄▄▄▄ ",
+        "     █",
+        "     █",
+        " ▄▄▄▄ ",
+        "     █",
+        "     █",
+        " ▀▀▀▀ ",
+    ],
+    // 4
+    [
+        "      ",
+        "█    █",
+        "█    █",
+        " ▄▄▄▄ ",
+        "     █",
+        "     █",
+        "      ",
+    ],
+    // 5
+    [
+        " ▄▄▄▄ ",
+        "█     ",
+        "█     ",
+        " ▄▄▄▄ ",
+        "     █",
+        "     █",
+        " ▀▀▀▀ ",
+    ],
+    // 6
+    [
+        " ▄▄▄▄ ",
+        "█     ",
+        "█     ",
+        " ▄▄▄▄ ",
+        "█    █",
+        "█    █",
+        " ▀▀▀▀ ",
+    ],
+    // 7
+    [
+        " ▄▄▄▄ ",
+        "     █",
+        "     █",
+        "      ",
+        "     █",
+        "     █",
+        "      ",
+    ],
+    // 8
+    [
+        " ▄▄▄▄ ",
+        "█    █",
+        "█    █",
+        " ▄▄▄▄ ",
+        "█    █",
+        "█    █",
+        " ▀▀▀▀ ",
+    ],
+    // 9
+    [
+        " ▄▄▄▄ ",
+        "█    █",
+        "█    █",
+        " ▄▄▄▄ ",
+        "     █",
+        "     █",
+        " ▀▀▀▀ ",
+    ],
+];
+
+const SEVEN_SEGMENT_COLON: [&str; 7] = ["  ", "  ", "▀▀", "  ", "▄▄", "  ", "  "];
+
+/// Segments lit for each digit, bit 0 (a, top) clockwise to bit 5 (f, upper left), bit 6 (g, middle)
+pub const SEVEN_SEGMENT_MASKS: [u8; 10] = [0x3F, 0x06, 0x5B, 0x4F, 0x66, 0x6D, 0x7D, 0x07, 0x7F, 0x6F];
+
+/// Segment drawn at a cell of a seven-segment glyph, with the character it is drawn in
+pub fn seven_segment_at(row: usize, col: usize) -> Option<(u8, char)> {
+    let horizontal = (1..=4).contains(&col);
+    let segment = match (row, col) {
+        (0, _) if horizontal => 0,
+        (1 | 2, 5) => 1,
+        (4 | 5, 5) => 2,
+        (6, _) if horizontal => 3,
+        (4 | 5, 0) => 4,
+        (1 | 2, 0) => 5,
+        (3, _) if horizontal => 6,
+        _ => return None,
+    };
+    let ch = match row {
+        0 | 3 => '▄',
+        6 => '▀',
+        _ => '█',
+    };
+    Some((segment, ch))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2299,6 +2485,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_seven_segment_glyphs_match_masks() {
+        for (digit, mask) in SEVEN_SEGMENT_MASKS.iter().enumerate() {
+            let lines = DigitFont::SevenSegment.get_digit(digit);
+            for (row, line) in lines.iter().enumerate() {
+                for (col, ch) in line.chars().enumerate() {
+                    let expected = match seven_segment_at(row, col) {
+                        Some((segment, ch)) if mask & (1 << segment) != 0 => ch,
+                        _ => ' ',
+                    };
+                    assert_eq!(ch, expected, "digit {} row {} col {}", digit, row, col);
+                }
+            }
+        }
+    }
+
     #[test]
     fn test_font_cycle() {
         let mut font = DigitFont::Classic;
@@ -1,7 +1,116 @@
 use ratatui::prelude::*;
 use ratatui::widgets::Paragraph;
+use serde::{Deserialize, Serialize};
 
-use super::digit_fonts::DigitFont;
+use std::time::Duration;
+
+use super::digit_fonts::{self, DigitFont};
+
+/// How the digits' primary color is chosen; the secondary (shadow) color stays the theme's
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DigitColorMode {
+    /// The theme's primary color
+    #[default]
+    Theme,
+    /// Top-to-bottom blend from the primary into the secondary color
+    Gradient,
+    /// Hue cycling slowly through the spectrum
+    Rainbow,
+    /// Green at the start of the session, through yellow, to red as time runs out
+    Heat,
+}
+
+impl DigitColorMode {
+    pub fn all() -> &'static [DigitColorMode] {
+        &[
+            DigitColorMode::Theme,
+            DigitColorMode::Gradient,
+            DigitColorMode::Rainbow,
+            DigitColorMode::Heat,
+        ]
+    }
+
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            DigitColorMode::Theme => "Theme",
+            DigitColorMode::Gradient => "Gradient",
+            DigitColorMode::Rainbow => "Rainbow",
+            DigitColorMode::Heat => "Progress Heat",
+        }
+    }
+
+    /// Next mode in the cycle
+    pub fn next(&self) -> DigitColorMode {
+        let modes = Self::all();
+        let i = modes.iter().position(|m| m == self).unwrap_or(0);
+        modes[(i + 1) % modes.len()]
+    }
+
+    /// Primary color for one row of the digits.
+    /// `progress` is the elapsed fraction of the session (0.0-1.0).
+    pub fn row_color(
+        &self,
+        primary: Color,
+        secondary: Color,
+        row: usize,
+        height: usize,
+        frame_index: usize,
+        progress: f64,
+    ) -> Color {
+        match self {
+            DigitColorMode::Theme => primary,
+            DigitColorMode::Gradient => {
+                let t = row as f32 / height.saturating_sub(1).max(1) as f32;
+                // Stop short of the secondary so the bottom row stays readable
+                blend(primary, secondary, t * 0.7)
+            }
+            DigitColorMode::Rainbow => {
+                // A full turn every ~20s at 10fps, rows slightly offset
+                let hue = (frame_index as f32 * 0.005 + row as f32 * 0.02) % 1.0;
+                hsv_to_rgb(hue, 0.7, 1.0)
+            }
+            DigitColorMode::Heat => {
+                // Hue 1/3 (green) down to 0 (red)
+                let hue = (1.0 - progress.clamp(0.0, 1.0) as f32) / 3.0;
+                hsv_to_rgb(hue, 0.85, 1.0)
+            }
+        }
+    }
+}
+
+/// Linear blend between two RGB colors; named colors can't be mixed, so `from` is kept
+fn blend(from: Color, to: Color, t: f32) -> Color {
+    match (from, to) {
+        (Color::Rgb(r1, g1, b1), Color::Rgb(r2, g2, b2)) => {
+            let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+            Color::Rgb(mix(r1, r2), mix(g1, g2), mix(b1, b2))
+        }
+        _ => from,
+    }
+}
+
+fn hsv_to_rgb(h: f32, s: f32, v: f32) -> Color {
+    let h = h * 6.0;
+    let i = h.floor() as i32;
+    let f = h - i as f32;
+
+    let p = v * (1.0 - s);
+    let q = v * (1.0 - s * f);
+    let t = v * (1.0 - s * (1.0 - f));
+
+    let (r, g, b) = match i % 6 {
+        0 => (v, t, p),
+        1 => (q, v, p),
+        2 => (p, v, t),
+        3 => (p, q, v),
+        4 => (t, p, v),
+        _ => (v, p, q),
+    };
+
+    Color::Rgb((r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8)
+}
 
 /// Render big digits for the timer display
 /// Format: MM:SS centered in the given area
@@ -34,10 +143,57 @@ pub fn render_time_with_font(
     secondary_color: Color,
     font: DigitFont,
 ) {
-    let m1 = (minutes / 10) as usize;
-    let m2 = (minutes % 10) as usize;
-    let s1 = (seconds / 10) as usize;
-    let s2 = (seconds % 10) as usize;
+    render_time_painted(frame, area, minutes, seconds, |_| primary_color, secondary_color, font);
+}
+
+/// Render big digits with the primary color picked per row (0 = top row)
+pub fn render_time_painted(
+    frame: &mut Frame,
+    area: Rect,
+    minutes: u8,
+    seconds: u8,
+    primary_for_row: impl Fn(usize) -> Color,
+    secondary_color: Color,
+    font: DigitFont,
+) {
+    render_time_with_effect(
+        frame,
+        area,
+        minutes,
+        seconds,
+        primary_for_row,
+        secondary_color,
+        font,
+        |_, ch, style| (ch, style),
+    );
+}
+
+/// A cell of the big time display, as an effect sees it
+#[derive(Debug, Clone, Copy)]
+pub struct GlyphCell {
+    /// Digit position, 0-3 from the left; None in the colon
+    pub slot: Option<usize>,
+    pub row: usize,
+    pub col: usize,
+    /// Color a lit cell of this row gets
+    pub primary: Color,
+}
+
+/// Render big digits, passing every cell of the glyphs (blank ones included)
+/// through `effect` after the font's styling, so the picture can change from
+/// frame to frame without the font knowing about it
+#[allow(clippy::too_many_arguments)]
+pub fn render_time_with_effect(
+    frame: &mut Frame,
+    area: Rect,
+    minutes: u8,
+    seconds: u8,
+    primary_for_row: impl Fn(usize) -> Color,
+    secondary_color: Color,
+    font: DigitFont,
+    effect: impl Fn(GlyphCell, char, Style) -> (char, Style),
+) {
+    let digits = [minutes / 10, minutes % 10, seconds / 10, seconds % 10];
 
     let digit_width = font.width();
     let digit_height = font.height();
@@ -48,140 +204,186 @@ pub fn render_time_with_font(
     let start_x = area.x + area.width.saturating_sub(total_width) / 2;
     let start_y = area.y + area.height.saturating_sub(digit_height) / 2;
 
-    // Render each digit and colon
+    // MM, colon, SS
     let mut x_offset = start_x;
-
-    // First minute digit
-    render_digit_with_font(
-        frame,
-        x_offset,
-        start_y,
-        m1,
-        primary_color,
-        secondary_color,
-        font,
-    );
-    x_offset += digit_width + 1;
-
-    // Second minute digit
-    render_digit_with_font(
-        frame,
-        x_offset,
-        start_y,
-        m2,
-        primary_color,
-        secondary_color,
-        font,
-    );
-    x_offset += digit_width + 1;
-
-    // Colon
-    render_colon_with_font(frame, x_offset, start_y, primary_color, secondary_color, font);
-    x_offset += colon_width + 1;
-
-    // First second digit
-    render_digit_with_font(
-        frame,
-        x_offset,
-        start_y,
-        s1,
-        primary_color,
-        secondary_color,
-        font,
-    );
-    x_offset += digit_width + 1;
-
-    // Second second digit
-    render_digit_with_font(
-        frame,
-        x_offset,
-        start_y,
-        s2,
-        primary_color,
-        secondary_color,
-        font,
-    );
+    for (slot, digit) in digits.into_iter().enumerate() {
+        if slot == 2 {
+            let pattern = font.get_colon();
+            render_glyph(frame, x_offset, start_y, pattern, None, &primary_for_row, secondary_color, font, &effect);
+            x_offset += colon_width + 1;
+        }
+        let pattern = font.get_digit(digit as usize);
+        render_glyph(frame, x_offset, start_y, pattern, Some(slot), &primary_for_row, secondary_color, font, &effect);
+        x_offset += digit_width + 1;
+    }
 }
 
-fn render_digit_with_font(
+#[allow(clippy::too_many_arguments)]
+fn render_glyph(
     frame: &mut Frame,
     x: u16,
     y: u16,
-    digit: usize,
-    primary: Color,
+    pattern: &[&str],
+    slot: Option<usize>,
+    primary: &impl Fn(usize) -> Color,
     secondary: Color,
     font: DigitFont,
+    effect: &impl Fn(GlyphCell, char, Style) -> (char, Style),
 ) {
-    let digit = digit.min(9);
-    let pattern = font.get_digit(digit);
     let frame_area = frame.area();
-    let primary_chars = font.primary_chars();
-    let secondary_chars = font.secondary_chars();
+    let glyph_width = if slot.is_some() { font.width() } else { font.colon_width() };
 
-    for (i, line) in pattern.iter().enumerate() {
-        let line_y = y + i as u16;
+    for (row, line) in pattern.iter().enumerate() {
+        let line_y = y + row as u16;
         if line_y >= frame_area.height || x >= frame_area.width {
             continue;
         }
 
-        let styled_line = style_line(line, primary, secondary, primary_chars, secondary_chars);
-        let width = font.width().min(frame_area.width.saturating_sub(x));
-        frame.render_widget(
-            Paragraph::new(styled_line),
-            Rect::new(x, line_y, width, 1),
-        );
+        let primary = primary(row);
+        let spans: Vec<Span> = line
+            .chars()
+            .enumerate()
+            .map(|(col, ch)| {
+                let style = font_style(ch, primary, secondary, font);
+                let (ch, style) = effect(GlyphCell { slot, row, col, primary }, ch, style);
+                Span::styled(ch.to_string(), style)
+            })
+            .collect();
+        let width = glyph_width.min(frame_area.width.saturating_sub(x));
+        frame.render_widget(Paragraph::new(Line::from(spans)), Rect::new(x, line_y, width, 1));
     }
 }
 
-fn render_colon_with_font(
-    frame: &mut Frame,
-    x: u16,
-    y: u16,
-    primary: Color,
-    secondary: Color,
-    font: DigitFont,
-) {
-    let frame_area = frame.area();
-    let pattern = font.get_colon();
-    let primary_chars = font.primary_chars();
-    let secondary_chars = font.secondary_chars();
+fn font_style(ch: char, primary: Color, secondary: Color, font: DigitFont) -> Style {
+    if font.primary_chars().contains(&ch) {
+        Style::default().fg(primary)
+    } else if font.secondary_chars().contains(&ch) {
+        Style::default().fg(secondary)
+    } else {
+        Style::default()
+    }
+}
 
-    for (i, line) in pattern.iter().enumerate() {
-        let line_y = y + i as u16;
-        if line_y >= frame_area.height || x >= frame_area.width {
-            continue;
+/// How long a segment that just went out keeps fading
+const LED_DECAY_SECS: f32 = 0.35;
+
+/// Brightness of an unlit segment's ghost, as a share of the lit color
+const LED_GHOST: f32 = 0.12;
+
+/// Effect for the seven-segment font: unlit segments glow faintly, and the
+/// ones that went out at the last tick of the countdown fade rather than cut
+pub struct LedDecay {
+    digits: [u8; 4],
+    previous: [u8; 4],
+    /// Brightness left in the segments that just went out, 1.0 to 0.0
+    afterglow: f32,
+}
+
+impl LedDecay {
+    /// For the countdown at `remaining`; paused (`running` false), nothing is fading
+    pub fn new(remaining: Duration, running: bool) -> Self {
+        let secs = remaining.as_secs();
+        // The display dropped to `secs` this long ago
+        let since_change = 1.0 - remaining.subsec_nanos() as f32 / 1e9;
+        let afterglow = if running { (1.0 - since_change / LED_DECAY_SECS).max(0.0) } else { 0.0 };
+        Self {
+            digits: clock_digits(secs),
+            previous: clock_digits(secs + 1),
+            afterglow,
         }
+    }
 
-        let styled_line = style_line(line, primary, secondary, primary_chars, secondary_chars);
-        let width = font.colon_width().min(frame_area.width.saturating_sub(x));
-        frame.render_widget(
-            Paragraph::new(styled_line),
-            Rect::new(x, line_y, width, 1),
-        );
+    pub fn apply(&self, cell: GlyphCell, ch: char, style: Style) -> (char, Style) {
+        let Some(slot) = cell.slot else {
+            return (ch, style);
+        };
+        let Some((segment, segment_ch)) = digit_fonts::seven_segment_at(cell.row, cell.col) else {
+            return (ch, style);
+        };
+        let lit = |digit: u8| digit_fonts::SEVEN_SEGMENT_MASKS[digit as usize] & (1 << segment) != 0;
+        if lit(self.digits[slot]) {
+            return (ch, style);
+        }
+        let ghost = blend(cell.primary, Color::Rgb(0, 0, 0), 1.0 - LED_GHOST);
+        let color = if lit(self.previous[slot]) && self.afterglow > 0.0 {
+            blend(ghost, cell.primary, self.afterglow)
+        } else {
+            ghost
+        };
+        (segment_ch, style.fg(color))
     }
 }
 
-fn style_line(
-    line: &str,
-    primary: Color,
-    secondary: Color,
-    primary_chars: &[char],
-    secondary_chars: &[char],
-) -> Line<'static> {
-    let spans: Vec<Span> = line
-        .chars()
-        .map(|ch| {
-            let style = if primary_chars.contains(&ch) {
-                Style::default().fg(primary)
-            } else if secondary_chars.contains(&ch) {
-                Style::default().fg(secondary)
-            } else {
-                Style::default()
-            };
-            Span::styled(ch.to_string(), style)
-        })
-        .collect();
-    Line::from(spans)
+/// Frames a digit takes to roll over to its new value
+pub const ROLL_FRAMES: u32 = 3;
+
+/// Split-flap roll for the non-LED fonts: a digit that changes doesn't swap in
+/// one frame, the new glyph scrolls down over the old one for `ROLL_FRAMES`
+#[derive(Debug, Clone, Default)]
+pub struct DigitRoll {
+    /// Digits on screen as of the last update
+    shown: Option<[u8; 4]>,
+    /// What each digit showed before its last change
+    previous: [u8; 4],
+    /// Frames left in each digit's roll
+    left: [u32; 4],
+}
+
+impl DigitRoll {
+    /// Follow the countdown at `remaining_secs`; called once a frame
+    pub fn update(&mut self, remaining_secs: u64) {
+        let digits = clock_digits(remaining_secs);
+        for left in &mut self.left {
+            *left = left.saturating_sub(1);
+        }
+        if let Some(shown) = self.shown {
+            for slot in 0..4 {
+                if shown[slot] != digits[slot] {
+                    self.previous[slot] = shown[slot];
+                    self.left[slot] = ROLL_FRAMES;
+                }
+            }
+        }
+        self.shown = Some(digits);
+    }
+
+    /// Forget the digits shown, so the next update starts still
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Whether any digit is mid-roll
+    pub fn is_rolling(&self) -> bool {
+        self.left.iter().any(|&left| left > 0)
+    }
+
+    /// Cell of a rolling digit: the top `entered` rows show the bottom of the
+    /// new glyph coming in, the rest the old glyph pushed down
+    pub fn apply(&self, cell: GlyphCell, ch: char, style: Style, secondary: Color, font: DigitFont) -> (char, Style) {
+        let (Some(slot), Some(shown)) = (cell.slot, self.shown) else {
+            return (ch, style);
+        };
+        let left = self.left[slot];
+        if left == 0 {
+            return (ch, style);
+        }
+        let height = font.height() as usize;
+        let entered = height * (ROLL_FRAMES + 1 - left) as usize / (ROLL_FRAMES + 1) as usize;
+        let (digit, row) = if cell.row < entered {
+            (shown[slot], cell.row + height - entered)
+        } else {
+            (self.previous[slot], cell.row - entered)
+        };
+        let ch = font.get_digit(digit as usize)[row].chars().nth(cell.col).unwrap_or(' ');
+        (ch, font_style(ch, cell.primary, secondary, font))
+    }
+}
+
+/// MM:SS digits of a countdown, minutes capped at 99 like the display
+fn clock_digits(secs: u64) -> [u8; 4] {
+    let minutes = (secs / 60).min(99) as u8;
+    let seconds = (secs % 60) as u8;
+    [minutes / 10, minutes % 10, seconds / 10, seconds % 10]
 }
 
 /// Get the dimensions needed for the timer display with default font
@@ -196,3 +398,81 @@ pub fn timer_dimensions_for_font(font: DigitFont) -> (u16, u16) {
     let height = font.height();
     (width, height)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digit_color_modes() {
+        let primary = Color::Rgb(200, 100, 0);
+        let secondary = Color::Rgb(0, 0, 100);
+
+        assert_eq!(DigitColorMode::Theme.row_color(primary, secondary, 3, 5, 42, 0.5), primary);
+        assert_eq!(DigitColorMode::Gradient.row_color(primary, secondary, 0, 5, 0, 0.0), primary);
+        assert_ne!(DigitColorMode::Gradient.row_color(primary, secondary, 4, 5, 0, 0.0), primary);
+
+        // Heat runs from green to red
+        assert_eq!(DigitColorMode::Heat.row_color(primary, secondary, 0, 5, 0, 0.0), Color::Rgb(38, 255, 38));
+        assert_eq!(DigitColorMode::Heat.row_color(primary, secondary, 0, 5, 0, 1.0), Color::Rgb(255, 38, 38));
+
+        assert_eq!(DigitColorMode::Heat.next(), DigitColorMode::Theme);
+    }
+
+    #[test]
+    fn test_digit_roll() {
+        let font = DigitFont::default();
+        let height = font.height() as usize;
+        let primary = Color::Rgb(200, 200, 200);
+        let cell = |slot, row| GlyphCell { slot: Some(slot), row, col: 1, primary };
+        let glyph_ch = |digit: usize, row: usize| font.get_digit(digit)[row].chars().nth(1).unwrap();
+
+        let mut roll = DigitRoll::default();
+        roll.update(125);
+        assert!(!roll.is_rolling());
+
+        // 02:05 -> 02:04: the last digit rolls, the others stay put
+        roll.update(124);
+        assert!(roll.is_rolling());
+        let style = Style::default();
+        assert_eq!(roll.apply(cell(2, 0), 'x', style, Color::Black, font).0, 'x');
+        let entered = height / (ROLL_FRAMES as usize + 1);
+        let (top, _) = roll.apply(cell(3, 0), 'x', style, Color::Black, font);
+        assert_eq!(top, glyph_ch(4, height - entered));
+        let (bottom, _) = roll.apply(cell(3, height - 1), 'x', style, Color::Black, font);
+        assert_eq!(bottom, glyph_ch(5, height - 1 - entered));
+
+        for _ in 0..ROLL_FRAMES {
+            roll.update(124);
+        }
+        assert!(!roll.is_rolling());
+        assert_eq!(roll.apply(cell(3, 0), 'x', style, Color::Black, font).0, 'x');
+    }
+
+    #[test]
+    fn test_led_decay() {
+        let primary = Color::Rgb(200, 200, 200);
+        let cell = |slot, row, col| GlyphCell { slot: Some(slot), row, col, primary };
+        // 02:00 -> 01:59 a moment ago
+        let just_ticked = LedDecay::new(Duration::from_millis(119_950), true);
+
+        // Lit segments are left alone: b (top right) of the 9
+        let lit = Style::default().fg(primary);
+        assert_eq!(just_ticked.apply(cell(3, 1, 5), '█', lit), ('█', lit));
+
+        // a (top) of the 2 that became a 1 is out but still glowing
+        let (ch, style) = just_ticked.apply(cell(1, 0, 2), ' ', Style::default());
+        assert_eq!(ch, '▄');
+        let fading = style.fg.unwrap();
+
+        // Half a second on it is down to a ghost
+        let later = LedDecay::new(Duration::from_millis(119_500), true);
+        let ghost = later.apply(cell(1, 0, 2), ' ', Style::default()).1.fg.unwrap();
+        assert_eq!(ghost, Color::Rgb(24, 24, 24));
+        assert_ne!(fading, ghost);
+
+        // Paused: ghosts only
+        let paused = LedDecay::new(Duration::from_millis(119_950), false);
+        assert_eq!(paused.apply(cell(1, 0, 2), ' ', Style::default()).1.fg, Some(ghost));
+    }
+}
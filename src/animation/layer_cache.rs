@@ -0,0 +1,51 @@
+//! Caches for static scene layers
+//! Geometry that only depends on the terminal size (castle stone, hill outlines)
+//! is built once per resize and reused every frame; dynamic layers are drawn on top.
+
+use ratatui::prelude::*;
+
+/// Data derived from the render area, rebuilt when the area or variant changes
+pub struct LayerCache<T> {
+    entry: Option<(Rect, u64, T)>,
+}
+
+impl<T> LayerCache<T> {
+    pub const fn new() -> Self {
+        Self { entry: None }
+    }
+
+    /// Cached data for this area/variant, building it first if stale.
+    /// `variant` distinguishes alternate looks of the same layer (e.g. lightning lit).
+    pub fn get_or_build(&mut self, area: Rect, variant: u64, build: impl FnOnce() -> T) -> &T {
+        let fresh = matches!(&self.entry, Some((a, v, _)) if *a == area && *v == variant);
+        if !fresh {
+            self.entry = Some((area, variant, build()));
+        }
+        &self.entry.as_ref().expect("layer cache entry was just built").2
+    }
+}
+
+/// Offscreen cells of a static layer: symbol and foreground, positioned relative to the area
+#[derive(Default)]
+pub struct CellLayer {
+    cells: Vec<(u16, u16, char, Color)>,
+}
+
+impl CellLayer {
+    pub fn put(&mut self, x: u16, y: u16, ch: char, fg: Color) {
+        self.cells.push((x, y, ch, fg));
+    }
+
+    /// Composite onto the frame, keeping whatever background is already there
+    /// (same result as rendering a 1x1 fg-only Paragraph per cell, without the widget cost)
+    pub fn draw(&self, frame: &mut Frame, area: Rect) {
+        let bounds = frame.area();
+        let buf = frame.buffer_mut();
+        for &(x, y, ch, fg) in &self.cells {
+            let pos = Position::new(area.x + x, area.y + y);
+            if bounds.contains(pos) {
+                buf[pos].set_char(ch).set_fg(fg);
+            }
+        }
+    }
+}
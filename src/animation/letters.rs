@@ -0,0 +1,87 @@
+//! Block letters for big words on screen ("WORK", "BREAK"), drawn like the
+//! Classic digit font: 5x5 cells of full blocks, one column between letters
+
+use ratatui::prelude::*;
+
+pub const LETTER_WIDTH: u16 = 5;
+pub const LETTER_HEIGHT: u16 = 5;
+
+type Glyph = [&'static str; LETTER_HEIGHT as usize];
+
+const BLANK: Glyph = ["     "; 5];
+
+const LETTERS: [Glyph; 26] = [
+    [" ███ ", "█   █", "█████", "█   █", "█   █"], // A
+    ["████ ", "█   █", "████ ", "█   █", "████ "], // B
+    [" ████", "█    ", "█    ", "█    ", " ████"], // C
+    ["████ ", "█   █", "█   █", "█   █", "████ "], // D
+    ["█████", "█    ", "████ ", "█    ", "█████"], // E
+    ["█████", "█    ", "████ ", "█    ", "█    "], // F
+    [" ████", "█    ", "█  ██", "█   █", " ████"], // G
+    ["█   █", "█   █", "█████", "█   █", "█   █"], // H
+    ["█████", "  █  ", "  █  ", "  █  ", "█████"], // I
+    ["█████", "   █ ", "   █ ", "█  █ ", " ██  "], // J
+    ["█   █", "█  █ ", "███  ", "█  █ ", "█   █"], // K
+    ["█    ", "█    ", "█    ", "█    ", "█████"], // L
+    ["█   █", "██ ██", "█ █ █", "█   █", "█   █"], // M
+    ["█   █", "██  █", "█ █ █", "█  ██", "█   █"], // N
+    [" ███ ", "█   █", "█   █", "█   █", " ███ "], // O
+    ["████ ", "█   █", "████ ", "█    ", "█    "], // P
+    [" ███ ", "█   █", "█ █ █", "█  █ ", " ██ █"], // Q
+    ["████ ", "█   █", "████ ", "█  █ ", "█   █"], // R
+    [" ████", "█    ", " ███ ", "    █", "████ "], // S
+    ["█████", "  █  ", "  █  ", "  █  ", "  █  "], // T
+    ["█   █", "█   █", "█   █", "█   █", " ███ "], // U
+    ["█   █", "█   █", "█   █", " █ █ ", "  █  "], // V
+    ["█   █", "█   █", "█ █ █", "██ ██", "█   █"], // W
+    ["█   █", " █ █ ", "  █  ", " █ █ ", "█   █"], // X
+    ["█   █", " █ █ ", "  █  ", "  █  ", "  █  "], // Y
+    ["█████", "   █ ", "  █  ", " █   ", "█████"], // Z
+];
+
+/// Glyph for a letter, either case; anything else is blank
+fn glyph(ch: char) -> &'static Glyph {
+    match ch.to_ascii_uppercase() {
+        c @ 'A'..='Z' => &LETTERS[(c as u8 - b'A') as usize],
+        _ => &BLANK,
+    }
+}
+
+/// Columns `text` takes in block letters
+pub fn text_width(text: &str) -> u16 {
+    let letters = text.chars().count() as u16;
+    (letters * (LETTER_WIDTH + 1)).saturating_sub(1)
+}
+
+/// Draw `text` in block letters, centered in `area` and clipped to it; blank
+/// cells are left alone so the theme shows between the strokes
+pub fn render_text(frame: &mut Frame, area: Rect, text: &str, color: Color) {
+    let x = area.x + area.width.saturating_sub(text_width(text)) / 2;
+    let y = area.y + area.height.saturating_sub(LETTER_HEIGHT) / 2;
+    let buf = frame.buffer_mut();
+    let visible = area.intersection(buf.area);
+    for row in 0..LETTER_HEIGHT {
+        let line: Vec<&str> = text.chars().map(|ch| glyph(ch)[row as usize]).collect();
+        for (col, ch) in line.join(" ").chars().enumerate() {
+            let position = Position::new(x + col as u16, y + row);
+            if ch != ' ' && visible.contains(position) {
+                buf[position].set_char(ch).set_fg(color);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_letter_glyphs() {
+        assert_eq!(text_width("WORK"), 23);
+        assert_eq!(glyph('w'), glyph('W'));
+        assert_eq!(glyph('-'), &BLANK);
+        for letter in LETTERS {
+            assert!(letter.iter().all(|row| row.chars().count() == LETTER_WIDTH as usize));
+        }
+    }
+}
@@ -0,0 +1,28 @@
+//! Small numeric helpers shared by themes and animated UI chrome
+
+use std::f32::consts::PI;
+
+/// Fast approximation of sine (parabola per half period)
+pub fn fast_sin(x: f32) -> f32 {
+    // Normalize to 0..2π range then approximate
+    let x = x % (2.0 * PI);
+    let x = if x < 0.0 { x + 2.0 * PI } else { x };
+
+    if x < PI {
+        let t = x / PI;
+        4.0 * t * (1.0 - t)
+    } else {
+        let t = (x - PI) / PI;
+        -(4.0 * t * (1.0 - t))
+    }
+}
+
+/// Map a coordinate laid out across `from` cells onto `to` cells, keeping its
+/// relative position (used when the terminal is resized)
+pub fn rescale(value: f32, from: u16, to: u16) -> f32 {
+    if from == 0 {
+        value
+    } else {
+        value * to as f32 / from as f32
+    }
+}
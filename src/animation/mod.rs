@@ -1,13 +1,32 @@
+pub mod background;
+pub mod canvas;
+pub mod daylight;
+pub mod detail;
 pub mod themes;
 pub mod digits;
 pub mod digit_fonts;
+pub mod layer_cache;
+pub mod letters;
+pub mod math;
+pub mod motion;
+pub mod precipitation;
+pub mod schedule;
+pub mod sequence;
 
 pub use digit_fonts::DigitFont;
 
+use std::cell::RefCell;
 use std::time::{Duration, Instant};
 
-use pomowise::timer::TimerState;
-use themes::ThemeType;
+use ratatui::prelude::*;
+use ratatui::widgets::Clear;
+
+use crate::clock::SharedClock;
+use crate::crash;
+use crate::timer::TimerState;
+use sequence::{Phase, Switch};
+use detail::DetailTuner;
+use themes::{RenderParams, ThemeFilter, ThemeState, ThemeType};
 
 /// Theme rotation interval: 2.5 minutes
 const THEME_ROTATION_SECS: u64 = 150;
@@ -19,32 +38,83 @@ pub struct AnimationEngine {
     last_frame_time: Instant,
     last_theme_change: Instant,
     fps: u8,
+    /// Theme time in frames, advanced by the motion scale (see `motion`)
+    scene_time: f64,
+    clock: SharedClock,
+    /// Particles of the current theme, kept across frames and resizes
+    /// (drawing only borrows the app, hence the cell)
+    scene: RefCell<Option<(ThemeType, ThemeState)>>,
+    /// Set when the theme panicked while drawing, until the app picks it up
+    failure: RefCell<Option<ThemeFailure>>,
+    /// Themes that panicked this run, left out of the rotation
+    crashed: Vec<ThemeType>,
+    /// Play theme intros and outros on changes (set by the app from config)
+    pub sequences: bool,
+    /// Theme change whose outro or intro is playing
+    switch: Option<Switch>,
+    /// Detail tier from the terminal size and measured draw times (see `detail`)
+    detail: RefCell<DetailTuner>,
+    /// Handed to the themes each frame, with the tuner's detail
+    params: RenderParams,
+}
+
+/// A theme that panicked while drawing
+#[derive(Debug, Clone)]
+pub struct ThemeFailure {
+    pub theme: ThemeType,
+    pub message: String,
 }
 
 impl AnimationEngine {
-    pub fn new() -> Self {
+    /// Engine pacing frames and theme rotation by `clock` (a `MockClock` in tests)
+    pub fn with_clock(clock: SharedClock) -> Self {
+        let now = clock.now();
         Self {
             frame_index: 0,
             current_theme: ThemeType::random(),
             current_font: DigitFont::Block3D, // Start with the fancier font
-            last_frame_time: Instant::now(),
-            last_theme_change: Instant::now(),
+            last_frame_time: now,
+            last_theme_change: now,
             fps: 10,
+            scene_time: 0.0,
+            clock,
+            scene: RefCell::new(None),
+            failure: RefCell::new(None),
+            crashed: Vec::new(),
+            sequences: false,
+            switch: None,
+            detail: RefCell::new(DetailTuner::default()),
+            params: RenderParams::default(),
         }
     }
 
     pub fn reset(&mut self) {
         self.frame_index = 0;
-        self.last_frame_time = Instant::now();
+        self.scene_time = 0.0;
+        self.last_frame_time = self.clock.now();
         // Keep the current theme on reset
     }
 
-    pub fn tick(&mut self, state: &TimerState, auto_rotate: bool) {
+    /// Jump the animation to a given frame, theme time included
+    pub fn jump_to_frame(&mut self, frame_index: usize) {
+        self.frame_index = frame_index;
+        self.scene_time = frame_index as f64;
+    }
+
+    /// Frame the themes draw: `frame_index` slowed (or stopped) by reduced motion
+    pub fn scene_frame(&self) -> usize {
+        self.scene_time as usize
+    }
+
+    /// Advance the animation; `filter` constrains automatic theme rotation
+    pub fn tick(&mut self, state: &TimerState, auto_rotate: bool, filter: &ThemeFilter) {
         let frame_duration = Duration::from_millis(1000 / self.fps as u64);
 
-        if self.last_frame_time.elapsed() >= frame_duration {
+        let now = self.clock.now();
+        if now.saturating_duration_since(self.last_frame_time) >= frame_duration {
             self.frame_index = self.frame_index.wrapping_add(1);
-            self.last_frame_time = Instant::now();
+            self.scene_time += self.params.motion as f64;
+            self.last_frame_time = now;
 
             // Slower animation for breaks
             if matches!(state, TimerState::ShortBreak { .. }) {
@@ -54,27 +124,120 @@ impl AnimationEngine {
             }
         }
 
+        if self.switch.is_some_and(|s| s.phase(self.frame_index) == Phase::Done) {
+            self.switch = None;
+        }
+
         // Check for automatic theme rotation (only if enabled)
         if auto_rotate && self.should_rotate_theme() {
-            self.rotate_theme();
+            self.rotate_theme(filter);
         }
     }
 
     /// Check if 2.5 minutes have elapsed since last theme change
     pub fn should_rotate_theme(&self) -> bool {
-        self.last_theme_change.elapsed() >= Duration::from_secs(THEME_ROTATION_SECS)
+        self.clock.now().saturating_duration_since(self.last_theme_change) >= Duration::from_secs(THEME_ROTATION_SECS)
     }
 
-    /// Switch to a random different theme
-    pub fn rotate_theme(&mut self) {
-        self.current_theme = ThemeType::random_except(self.current_theme);
-        self.last_theme_change = Instant::now();
+    /// Switch to a random different theme allowed by the filter
+    pub fn rotate_theme(&mut self, filter: &ThemeFilter) {
+        self.set_theme(ThemeType::random_except(self.current_theme, filter));
     }
 
     /// Force a specific theme (useful for menu preview)
     pub fn set_theme(&mut self, theme: ThemeType) {
+        if self.sequences && theme != self.current_theme {
+            self.switch = Switch::new(self.current_theme, theme, self.frame_index);
+        }
         self.current_theme = theme;
-        self.last_theme_change = Instant::now();
+        self.last_theme_change = self.clock.now();
+    }
+
+    /// Render the current theme's background, moving its particles on from the
+    /// last frame (and rescaling them if the terminal was resized). During a theme
+    /// change, the old theme's outro or the new one's intro shows instead. A theme
+    /// that panics is recorded for `take_failure` and Minimal drawn in its place.
+    pub fn render_background(&self, frame: &mut Frame, area: Rect) {
+        let (current, reveal) = match self.switch.map(|s| s.phase(self.frame_index)) {
+            Some(Phase::Outro(theme, reveal, progress) | Phase::Intro(theme, reveal, progress)) => {
+                (theme, Some((reveal, progress)))
+            }
+            _ => (self.current_theme, None),
+        };
+        let frame_index = self.scene_frame();
+        let mut scene = self.scene.borrow_mut();
+        let params = self.params();
+        let started = Instant::now();
+        let drawn = crash::catch(|| {
+            match scene.as_mut() {
+                Some((theme, state)) if *theme == current => state.update(area, frame_index),
+                _ => *scene = Some((current, ThemeState::new(current, area, frame_index))),
+            }
+            if let Some((theme, state)) = scene.as_ref() {
+                theme.render_with_state(frame, area, frame_index, state, &params);
+            }
+        });
+        self.detail.borrow_mut().record(started.elapsed());
+        if let Err(message) = drawn {
+            *scene = None;
+            *self.failure.borrow_mut() = Some(ThemeFailure { theme: current, message });
+            frame.render_widget(Clear, area);
+            ThemeType::Minimal.render_background(frame, area, frame_index, &params);
+            return;
+        }
+        if let Some((reveal, progress)) = reveal {
+            reveal.apply(frame.buffer_mut(), area, progress);
+        }
+    }
+
+    /// The theme that panicked since the last call, switching to Minimal and
+    /// keeping the rotation away from it
+    pub fn take_failure(&mut self) -> Option<ThemeFailure> {
+        let failure = self.failure.get_mut().take()?;
+        if !self.crashed.contains(&failure.theme) {
+            self.crashed.push(failure.theme);
+        }
+        self.set_theme(ThemeType::Minimal);
+        // Straight to Minimal, without replaying the theme that failed
+        self.switch = None;
+        Some(failure)
+    }
+
+    /// Themes that panicked this run
+    pub fn crashed_themes(&self) -> &[ThemeType] {
+        &self.crashed
+    }
+
+    /// Most scene detail the terminal size allows (`ScalingContext::background_detail_level`)
+    pub fn set_detail_cap(&mut self, cap: u8) {
+        self.detail.get_mut().set_cap(cap);
+    }
+
+    /// What the themes draw with, at the detail tier tuned so far (also for
+    /// previews outside the engine)
+    pub fn params(&self) -> RenderParams {
+        RenderParams { detail: self.detail.borrow().level(), ..self.params }
+    }
+
+    /// Cell height over width the themes draw with (`ScalingContext::cell_aspect`)
+    pub fn set_cell_aspect(&mut self, aspect: f32) {
+        self.params.aspect = aspect;
+    }
+
+    /// How far the running session has come (0.0 – 1.0), for themes that show it
+    /// in their scene; None to have them draw as usual
+    pub fn set_progress(&mut self, progress: Option<f64>) {
+        self.params.progress = progress.map(|p| (p as f32).clamp(0.0, 1.0));
+    }
+
+    /// Motion scale the scene runs at (the `motion` key in config.json)
+    pub fn set_motion(&mut self, scale: f32) {
+        self.params.motion = motion::clamp(scale);
+    }
+
+    /// Whether motion is turned down at all: no strobes, flashes or slides
+    pub fn reduced_motion(&self) -> bool {
+        self.params.reduced_motion()
     }
 
     /// Cycle to the next font style
@@ -87,3 +250,48 @@ impl AnimationEngine {
         self.current_font = font;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+
+    #[test]
+    fn test_frames_and_rotation_follow_the_clock() {
+        let clock = MockClock::new();
+        let mut engine = AnimationEngine::with_clock(clock.shared());
+        let work = TimerState::Work { lap: 1 };
+        let filter = ThemeFilter::default();
+
+        engine.tick(&work, true, &filter);
+        assert_eq!(engine.frame_index, 0);
+        clock.advance(Duration::from_millis(100));
+        engine.tick(&work, true, &filter);
+        assert_eq!(engine.frame_index, 1);
+        assert_eq!(engine.scene_frame(), 1);
+
+        let theme = engine.current_theme;
+        clock.advance(Duration::from_secs(THEME_ROTATION_SECS - 1));
+        assert!(!engine.should_rotate_theme());
+        clock.advance(Duration::from_secs(1));
+        engine.tick(&work, true, &filter);
+        assert_ne!(engine.current_theme, theme);
+    }
+
+    #[test]
+    fn test_reduced_motion_slows_the_scene() {
+        let clock = MockClock::new();
+        let mut engine = AnimationEngine::with_clock(clock.shared());
+        engine.set_motion(0.5);
+        assert!(engine.reduced_motion());
+        let work = TimerState::Work { lap: 1 };
+        for _ in 0..4 {
+            clock.advance(Duration::from_millis(100));
+            engine.tick(&work, false, &ThemeFilter::default());
+        }
+        assert_eq!((engine.frame_index, engine.scene_frame()), (4, 2));
+
+        engine.set_motion(f32::NAN);
+        assert!(!engine.reduced_motion());
+    }
+}
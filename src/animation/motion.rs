@@ -0,0 +1,18 @@
+//! Reduced motion (`motion` in config.json)
+//! The engine advances theme time by this scale each frame, so at 0.5 drifting
+//! particles move at half speed and at 0 the scene stands still. Below full
+//! motion, themes also leave out their sudden effects (lightning, RGB splits,
+//! glitch bursts, flashes); gentle fades stay. Themes get the scale in
+//! `RenderParams::motion`.
+
+/// Everything moves at its own pace
+pub const FULL: f32 = 1.0;
+
+/// A configured motion scale, clamped to 0.0 (still) – 1.0 (full motion)
+pub fn clamp(scale: f32) -> f32 {
+    if scale.is_finite() {
+        scale.clamp(0.0, FULL)
+    } else {
+        FULL
+    }
+}
@@ -0,0 +1,132 @@
+//! Weather drawn over a scene (see `crate::weather`): rain streaks, a storm's
+//! heavier slanted rain and lightning, snowflakes, fog banks, and the grey of an
+//! overcast sky. Only the outdoor themes (Landscape, Seasonal) use it.
+
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+
+use crate::animation::themes::RenderParams;
+use crate::weather::Conditions;
+
+fn simple_hash(x: usize, seed: usize) -> usize {
+    let mut h = x.wrapping_mul(2654435761);
+    h ^= seed;
+    h = h.wrapping_mul(2654435761);
+    h ^ (h >> 16)
+}
+
+fn put(frame: &mut Frame, area: Rect, x: u16, y: u16, ch: char, color: Color) {
+    if x < area.width && y < area.height {
+        frame.render_widget(
+            Paragraph::new(ch.to_string()).style(Style::default().fg(color)),
+            Rect::new(area.x + x, area.y + y, 1, 1),
+        );
+    }
+}
+
+/// `color` washed toward its own grey, `amount` 0 (untouched) to 1 (fully grey)
+pub fn overcast(color: Color, amount: f32) -> Color {
+    let Color::Rgb(r, g, b) = color else { return color };
+    let grey = 0.3 * r as f32 + 0.59 * g as f32 + 0.11 * b as f32;
+    // A clouded sky is a little darker too
+    let mix = |c: u8| ((c as f32 * (1.0 - amount) + grey * amount) * (1.0 - amount * 0.2)) as u8;
+    Color::Rgb(mix(r), mix(g), mix(b))
+}
+
+/// Drifting grey cloud cover over the top quarter of the scene
+pub fn render_cloud_cover(frame: &mut Frame, area: Rect, frame_index: usize) {
+    let drift = frame_index / 8;
+    for y in 0..area.height / 4 {
+        // Thickest at the top
+        let density = 4 - (y * 4 / (area.height / 4).max(1)).min(3) as usize;
+        for x in 0..area.width {
+            let cell = simple_hash(x as usize + drift + y as usize * 997, 41);
+            if cell % 5 >= density {
+                continue;
+            }
+            let (ch, shade) = match cell % 3 {
+                0 => ('▓', 120),
+                1 => ('▒', 140),
+                _ => ('░', 160),
+            };
+            put(frame, area, x, y, ch, Color::Rgb(shade, shade, shade + 10));
+        }
+    }
+}
+
+/// Rain, snow, fog or lightning over the scene for the given conditions
+pub fn render(frame: &mut Frame, area: Rect, frame_index: usize, conditions: Conditions, params: &RenderParams) {
+    match conditions {
+        Conditions::Clear | Conditions::Cloudy => {}
+        Conditions::Rain => render_rain(frame, area, frame_index, false),
+        Conditions::Storm => {
+            render_rain(frame, area, frame_index, true);
+            render_lightning(frame, area, frame_index, params);
+        }
+        Conditions::Snow => render_snow(frame, area, frame_index),
+        Conditions::Fog => render_fog(frame, area, frame_index),
+    }
+}
+
+fn render_rain(frame: &mut Frame, area: Rect, frame_index: usize, heavy: bool) {
+    let height = area.height.max(1) as usize;
+    let drops = area.width as usize * area.height as usize / if heavy { 12 } else { 25 };
+    let color = if heavy { Color::Rgb(170, 180, 210) } else { Color::Rgb(140, 160, 200) };
+    for i in 0..drops {
+        let speed = 1 + simple_hash(i, 3) % 2;
+        let y = (simple_hash(i, 2) + frame_index * speed) % height;
+        // Storm rain is driven sideways by the wind
+        let slant = if heavy { frame_index * speed / 2 } else { 0 };
+        let x = (simple_hash(i, 1) + slant) % area.width.max(1) as usize;
+        let ch = if heavy { '╱' } else if speed > 1 { '│' } else { '╷' };
+        put(frame, area, x as u16, y as u16, ch, color);
+    }
+}
+
+/// A bolt for a few frames every so often; none with reduced motion (it strobes)
+fn render_lightning(frame: &mut Frame, area: Rect, frame_index: usize, params: &RenderParams) {
+    const PERIOD: usize = 180;
+    if params.reduced_motion() || frame_index % PERIOD >= 4 {
+        return;
+    }
+    let strike = frame_index / PERIOD;
+    let mut x = (simple_hash(strike, 60) % area.width.max(1) as usize) as i32;
+    let color = Color::Rgb(255, 255, 220);
+    for y in 0..area.height * 2 / 3 {
+        let step = (simple_hash(strike * 31 + y as usize, 61) % 3) as i32 - 1;
+        let ch = match step {
+            -1 => '╱',
+            1 => '╲',
+            _ => '│',
+        };
+        put(frame, area, x.max(0) as u16, y, ch, color);
+        x -= step;
+    }
+}
+
+fn render_snow(frame: &mut Frame, area: Rect, frame_index: usize) {
+    let height = area.height.max(1) as usize;
+    let flakes = area.width as usize * area.height as usize / 18;
+    let chars = ['*', '·', '•', '+'];
+    for i in 0..flakes {
+        let y = (simple_hash(i, 12) + frame_index / (2 + simple_hash(i, 13) % 3)) % height;
+        let sway = ((frame_index as f32 * 0.05 + i as f32).sin() * 2.0) as i32;
+        let x = (simple_hash(i, 11) as i32 + sway).rem_euclid(area.width.max(1) as i32);
+        let shade = 210 + (simple_hash(i, 14) % 45) as u8;
+        put(frame, area, x as u16, y as u16, chars[i % chars.len()], Color::Rgb(shade, shade, 255));
+    }
+}
+
+/// Slow banks of haze over the lower half
+fn render_fog(frame: &mut Frame, area: Rect, frame_index: usize) {
+    let drift = frame_index / 12;
+    for y in area.height / 2..area.height {
+        for x in 0..area.width {
+            let bank = simple_hash((x as usize + drift) / 6 + y as usize * 131, 70);
+            // A third of each bank, so the scene still shows through
+            if bank.is_multiple_of(3) {
+                put(frame, area, x, y, '░', Color::Rgb(170, 175, 180));
+            }
+        }
+    }
+}
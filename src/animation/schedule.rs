@@ -0,0 +1,119 @@
+//! Time-of-day theme schedule
+//! Maps local time ranges to the themes auto-rotation may pick from,
+//! e.g. bright scenes during the day and dark ones at night.
+
+use chrono::NaiveTime;
+use serde::{Deserialize, Serialize};
+
+use super::themes::ThemeType;
+
+/// One time window and the themes allowed in it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeRange {
+    /// Start, "HH:MM" local time (inclusive)
+    pub from: String,
+    /// End, "HH:MM" local time (exclusive); earlier than `from` wraps past midnight
+    pub to: String,
+    /// Theme names or prefixes, as accepted by `:theme`
+    pub themes: Vec<String>,
+}
+
+impl TimeRange {
+    fn new(from: &str, to: &str, themes: &[&str]) -> Self {
+        Self {
+            from: from.to_string(),
+            to: to.to_string(),
+            themes: themes.iter().map(|t| t.to_string()).collect(),
+        }
+    }
+
+    /// Whether `time` falls in this window (invalid times never match)
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        let (Some(from), Some(to)) = (parse_time(&self.from), parse_time(&self.to)) else {
+            return false;
+        };
+        if from <= to {
+            from <= time && time < to
+        } else {
+            time >= from || time < to
+        }
+    }
+}
+
+/// Time-of-day schedule settings (the `time_of_day` key in config.json)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ThemeSchedule {
+    pub enabled: bool,
+    /// Checked in order; the first matching range wins
+    pub ranges: Vec<TimeRange>,
+}
+
+impl Default for ThemeSchedule {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ranges: vec![
+                TimeRange::new(
+                    "07:00",
+                    "19:00",
+                    &["landscape", "nature", "ocean", "claude", "seasonal", "bubbles"],
+                ),
+                TimeRange::new(
+                    "19:00",
+                    "07:00",
+                    &["starfield", "medieval", "synthwave", "aurora", "fireworks"],
+                ),
+            ],
+        }
+    }
+}
+
+impl ThemeSchedule {
+    /// Index of the range covering `time`, if the schedule is on
+    pub fn active_range(&self, time: NaiveTime) -> Option<usize> {
+        if !self.enabled {
+            return None;
+        }
+        self.ranges.iter().position(|range| range.contains(time))
+    }
+
+    /// Themes allowed at `time`; empty when no range applies
+    pub fn themes_at(&self, time: NaiveTime) -> Vec<ThemeType> {
+        self.active_range(time)
+            .map(|i| {
+                self.ranges[i]
+                    .themes
+                    .iter()
+                    .filter_map(|name| ThemeType::from_name(name))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+fn parse_time(s: &str) -> Option<NaiveTime> {
+    NaiveTime::parse_from_str(s.trim(), "%H:%M").ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(h: u32, m: u32) -> NaiveTime {
+        NaiveTime::from_hms_opt(h, m, 0).unwrap()
+    }
+
+    #[test]
+    fn test_ranges_wrap_past_midnight() {
+        let schedule = ThemeSchedule {
+            enabled: true,
+            ..ThemeSchedule::default()
+        };
+        assert_eq!(schedule.active_range(at(12, 0)), Some(0));
+        assert_eq!(schedule.active_range(at(19, 0)), Some(1));
+        assert_eq!(schedule.active_range(at(2, 30)), Some(1));
+        assert!(schedule.themes_at(at(23, 0)).contains(&ThemeType::Starfield));
+        assert_eq!(ThemeSchedule::default().active_range(at(12, 0)), None);
+    }
+}
@@ -0,0 +1,160 @@
+//! Intro and outro sequences played on theme changes
+//! A theme may name a `Reveal` for its intro (`ThemeType::intro`) and its outro
+//! (`ThemeType::outro`). The engine draws the theme as usual and the reveal then
+//! blanks the cells not built yet (intro) or already gone (outro), so the castle
+//! rises from the ground and the rain cascades in. Themes without one cut over.
+
+use ratatui::prelude::*;
+
+use crate::animation::themes::ThemeType;
+
+/// Frames of the old theme's outro (about half a second)
+pub const OUTRO_FRAMES: usize = 6;
+
+/// Frames of the new theme's intro (about a second and a half)
+pub const INTRO_FRAMES: usize = 15;
+
+fn simple_hash(x: usize, seed: usize) -> usize {
+    let mut h = x.wrapping_mul(2654435761);
+    h ^= seed;
+    h = h.wrapping_mul(2654435761);
+    h ^ (h >> 16)
+}
+
+/// Order in which the cells of a scene appear
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Reveal {
+    /// Row by row from the ground up, with a ragged top edge (buildings, flames)
+    Rise,
+    /// Columns pouring down from the top, each at its own pace (rain, code)
+    Cascade,
+    /// A circle opening from the center (space, bursts)
+    Iris,
+    /// Scattered cells at random (noise, static)
+    Dissolve,
+}
+
+impl Reveal {
+    /// Whether the cell at `x`, `y` (relative to `area`) shows once `progress`
+    /// (0.0 none – 1.0 all) of the reveal has played
+    pub fn shows(self, area: Rect, x: u16, y: u16, progress: f32) -> bool {
+        if progress >= 1.0 {
+            return true;
+        }
+        let width = area.width.max(1) as f32;
+        let height = area.height.max(1) as f32;
+        let jitter = (simple_hash(x as usize, 7) % 100) as f32 / 100.0;
+        match self {
+            Reveal::Rise => {
+                let from_ground = (height - y as f32 - 1.0) / height;
+                from_ground + jitter * 0.1 < progress * 1.1
+            }
+            Reveal::Cascade => {
+                // Columns start within the first 40% and pour at the same speed
+                let reached = (progress - jitter * 0.4) / 0.6;
+                (y as f32 / height) < reached
+            }
+            Reveal::Iris => {
+                // Cells are about twice as tall as wide
+                let dx = (x as f32 - width / 2.0) / (width / 2.0);
+                let dy = (y as f32 - height / 2.0) / (height / 2.0);
+                (dx * dx + dy * dy).sqrt() / std::f32::consts::SQRT_2 < progress
+            }
+            Reveal::Dissolve => {
+                let cell = simple_hash(y as usize * area.width as usize + x as usize, 31);
+                ((cell % 1000) as f32 / 1000.0) < progress
+            }
+        }
+    }
+
+    /// Blank the cells of `area` that don't show at `progress`
+    pub fn apply(self, buf: &mut Buffer, area: Rect, progress: f32) {
+        let area = area.intersection(buf.area);
+        for y in 0..area.height {
+            for x in 0..area.width {
+                if !self.shows(area, x, y, progress) {
+                    buf[(area.x + x, area.y + y)].reset();
+                }
+            }
+        }
+    }
+}
+
+/// What a theme change shows at some frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Phase {
+    /// The old theme going away; the reveal runs backwards to `progress`
+    Outro(ThemeType, Reveal, f32),
+    /// The new theme coming in
+    Intro(ThemeType, Reveal, f32),
+    Done,
+}
+
+/// A theme change being played: the outro of the old theme, then the intro of the new one
+#[derive(Debug, Clone, Copy)]
+pub struct Switch {
+    from: ThemeType,
+    to: ThemeType,
+    started: usize,
+}
+
+impl Switch {
+    /// Change from `from` to `to` starting at `frame_index`; None when neither theme
+    /// has a sequence to play
+    pub fn new(from: ThemeType, to: ThemeType, frame_index: usize) -> Option<Self> {
+        (from != to && (from.outro().is_some() || to.intro().is_some())).then_some(Self {
+            from,
+            to,
+            started: frame_index,
+        })
+    }
+
+    pub fn phase(&self, frame_index: usize) -> Phase {
+        let elapsed = frame_index.wrapping_sub(self.started);
+        let outro = self.from.outro().map_or(0, |_| OUTRO_FRAMES);
+        match (self.from.outro(), self.to.intro()) {
+            (Some(reveal), _) if elapsed < outro => {
+                Phase::Outro(self.from, reveal, 1.0 - elapsed as f32 / OUTRO_FRAMES as f32)
+            }
+            (_, Some(reveal)) if elapsed - outro < INTRO_FRAMES => {
+                Phase::Intro(self.to, reveal, (elapsed - outro + 1) as f32 / INTRO_FRAMES as f32)
+            }
+            _ => Phase::Done,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_switch_plays_outro_then_intro() {
+        let switch = Switch::new(ThemeType::Matrix, ThemeType::Medieval, 100).unwrap();
+        assert!(matches!(switch.phase(100), Phase::Outro(ThemeType::Matrix, Reveal::Dissolve, p) if p == 1.0));
+        assert!(matches!(switch.phase(100 + OUTRO_FRAMES), Phase::Intro(ThemeType::Medieval, Reveal::Rise, _)));
+        assert_eq!(switch.phase(100 + OUTRO_FRAMES + INTRO_FRAMES), Phase::Done);
+
+        assert!(Switch::new(ThemeType::Minimal, ThemeType::Ocean, 0).is_none());
+        assert!(Switch::new(ThemeType::Matrix, ThemeType::Matrix, 0).is_none());
+    }
+
+    #[test]
+    fn test_reveals_run_from_nothing_to_everything() {
+        let area = Rect::new(0, 0, 40, 12);
+        for reveal in [Reveal::Rise, Reveal::Cascade, Reveal::Iris, Reveal::Dissolve] {
+            let shown = |progress| {
+                (0..area.height)
+                    .flat_map(|y| (0..area.width).map(move |x| (x, y)))
+                    .filter(|&(x, y)| reveal.shows(area, x, y, progress))
+                    .count()
+            };
+            assert_eq!(shown(0.0), 0, "{:?}", reveal);
+            assert!(shown(0.5) > shown(0.2), "{:?}", reveal);
+            assert_eq!(shown(1.0), 480, "{:?}", reveal);
+        }
+        // The castle is built from the ground up
+        assert!(Reveal::Rise.shows(area, 5, 11, 0.2));
+        assert!(!Reveal::Rise.shows(area, 5, 0, 0.2));
+    }
+}
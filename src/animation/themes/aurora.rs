@@ -1,5 +1,7 @@
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Paragraph};
+use ratatui::widgets::Paragraph;
+
+use crate::animation::background;
 
 /// Aurora Borealis - flowing curtains of colorful light
 
@@ -72,7 +74,7 @@ fn aurora_color(x: u16, width: u16, intensity: f32, frame_index: usize) -> Color
         Color::Rgb(i / 4, i, i / 3)
     } else if color_phase < 2.0 {
         // Cyan to blue
-        Color::Rgb(i / 6, i * 3 / 4, i)
+        Color::Rgb(i / 6, (i as u16 * 3 / 4) as u8, i)
     } else {
         // Purple/pink (rare aurora)
         Color::Rgb(i / 2, i / 4, i)
@@ -95,8 +97,7 @@ fn aurora_char(intensity: f32) -> char {
 
 pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
     // Dark night sky background
-    let bg = Block::default().style(Style::default().bg(Color::Rgb(5, 5, 15)));
-    frame.render_widget(bg, area);
+    background::fill(frame, area, Color::Rgb(5, 5, 15));
     
     // Render aurora
     for y in 0..area.height {
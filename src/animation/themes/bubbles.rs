@@ -1,5 +1,9 @@
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Paragraph};
+use ratatui::widgets::Paragraph;
+
+use super::MAX_CATCH_UP;
+use crate::animation::background;
+use crate::animation::math::rescale;
 
 /// Rising bubbles animation
 
@@ -14,6 +18,8 @@ fn simple_hash(seed: usize, salt: usize) -> usize {
 struct Bubble {
     x: f32,
     start_y: f32,
+    /// Current height, moved on every frame
+    y: f32,
     size: u8,     // 0-2 for different sizes
     speed: f32,
     wobble_freq: f32,
@@ -28,9 +34,11 @@ impl Bubble {
         let h4 = simple_hash(seed, 4);
         let h5 = simple_hash(seed, 5);
         
+        let start_y = height as f32 + (h2 % 50) as f32;
         Self {
             x: (h1 % width as usize) as f32,
-            start_y: height as f32 + (h2 % 50) as f32,
+            start_y,
+            y: start_y,
             size: (h3 % 3) as u8,
             speed: 0.3 + (h4 % 100) as f32 / 200.0,
             wobble_freq: 0.05 + (h5 % 50) as f32 / 500.0,
@@ -38,16 +46,25 @@ impl Bubble {
         }
     }
     
-    fn position(&self, frame_index: usize, height: u16) -> (f32, f32) {
+    /// Height the bubble would be at on `frame_index` if it had been rising all along
+    fn rise_to(&mut self, frame_index: usize, height: u16) {
         let total_dist = height as f32 + 60.0;
-        let y_offset = (frame_index as f32 * self.speed) % total_dist;
-        let y = self.start_y - y_offset;
-        
+        self.y = self.start_y - (frame_index as f32 * self.speed) % total_dist;
+    }
+
+    /// Rise one frame, wrapping back below the bottom
+    fn step(&mut self, height: u16) {
+        let total_dist = height as f32 + 60.0;
+        self.y -= self.speed;
+        if self.y <= self.start_y - total_dist {
+            self.y += total_dist;
+        }
+    }
+
+    fn position(&self, frame_index: usize) -> (f32, f32) {
         // Wobble side to side
         let wobble = fast_sin(frame_index as f32 * self.wobble_freq) * self.wobble_amp;
-        let x = self.x + wobble;
-        
-        (x, y)
+        (self.x + wobble, self.y)
     }
     
     fn char(&self) -> char {
@@ -92,10 +109,56 @@ fn fast_sin(x: f32) -> f32 {
     }
 }
 
-pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
+const NUM_BUBBLES: usize = 40;
+
+/// Bubbles kept between frames
+pub struct State {
+    bubbles: Vec<Bubble>,
+    size: (u16, u16),
+    frame_index: usize,
+}
+
+impl State {
+    pub fn new(area: Rect, frame_index: usize) -> Self {
+        let bubbles = (0..NUM_BUBBLES)
+            .map(|i| {
+                let mut bubble = Bubble::new(i * 7919, area.width, area.height);
+                bubble.rise_to(frame_index, area.height);
+                bubble
+            })
+            .collect();
+        Self {
+            bubbles,
+            size: (area.width, area.height),
+            frame_index,
+        }
+    }
+
+    /// Rise on to `frame_index`, first moving bubbles proportionally if the area was resized
+    pub fn update(&mut self, area: Rect, frame_index: usize) {
+        let (width, height) = self.size;
+        if (area.width, area.height) != self.size {
+            for bubble in &mut self.bubbles {
+                bubble.x = rescale(bubble.x, width, area.width);
+                bubble.y = rescale(bubble.y, height, area.height);
+                bubble.start_y = rescale(bubble.start_y, height, area.height);
+            }
+            self.size = (area.width, area.height);
+        }
+
+        let steps = frame_index.saturating_sub(self.frame_index).min(MAX_CATCH_UP);
+        for _ in 0..steps {
+            for bubble in &mut self.bubbles {
+                bubble.step(area.height);
+            }
+        }
+        self.frame_index = frame_index;
+    }
+}
+
+pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize, state: &State) {
     // Deep water gradient background
-    let bg = Block::default().style(Style::default().bg(Color::Rgb(5, 15, 35)));
-    frame.render_widget(bg, area);
+    background::fill(frame, area, Color::Rgb(5, 15, 35));
     
     // Render gradient effect (darker at bottom)
     for y in 0..area.height {
@@ -116,12 +179,9 @@ pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
         }
     }
     
-    // Create and render bubbles
-    let num_bubbles = 40;
-    
-    for i in 0..num_bubbles {
-        let bubble = Bubble::new(i * 7919, area.width, area.height);
-        let (bx, by) = bubble.position(frame_index, area.height);
+    // Render bubbles
+    for (i, bubble) in state.bubbles.iter().enumerate() {
+        let (bx, by) = bubble.position(frame_index);
         
         let x = bx as i16;
         let y = by as i16;
@@ -1,9 +1,13 @@
 use ratatui::prelude::*;
 use ratatui::widgets::Paragraph;
 
+use super::RenderParams;
+use crate::animation::background;
+
 /// Claude/Anthropic themed - An artistic visualization of AI consciousness
 /// Warm orange/amber gradients, hexagonal patterns, neural networks,
 /// breathing pulses, and flowing geometric shapes
+/// Lower detail tiers draw fewer shapes and trails and skip the hexagons and constellations
 
 // ============================================================================
 // ANTHROPIC BRAND COLORS
@@ -270,9 +274,9 @@ fn get_floating_shapes(width: u16, height: u16, frame_index: usize, count: usize
     shapes
 }
 
-fn shape_distance(px: f32, py: f32, shape: &FloatingShape) -> f32 {
+fn shape_distance(px: f32, py: f32, shape: &FloatingShape, aspect: f32) -> f32 {
     let dx = px - shape.x;
-    let dy = (py - shape.y) * 2.0; // Terminal aspect ratio
+    let dy = (py - shape.y) * aspect;
 
     // Rotate point
     let cos_r = fast_cos(shape.rotation);
@@ -309,7 +313,7 @@ fn shape_distance(px: f32, py: f32, shape: &FloatingShape) -> f32 {
 // THINKING PULSE - Central glow that breathes
 // ============================================================================
 
-fn thinking_pulse(x: u16, y: u16, width: u16, height: u16, frame_index: usize) -> f32 {
+fn thinking_pulse(x: u16, y: u16, width: u16, height: u16, frame_index: usize, aspect: f32) -> f32 {
     let t = frame_index as f32 * 0.03;
 
     // Center of the screen
@@ -317,7 +321,7 @@ fn thinking_pulse(x: u16, y: u16, width: u16, height: u16, frame_index: usize) -
     let cy = height as f32 / 2.0;
 
     let dx = x as f32 - cx;
-    let dy = (y as f32 - cy) * 2.0; // Terminal aspect
+    let dy = (y as f32 - cy) * aspect;
     let dist = (dx * dx + dy * dy).sqrt();
 
     // Breathing rhythm (slower, calmer)
@@ -391,7 +395,7 @@ fn get_trail_particles(width: u16, height: u16, frame_index: usize, count: usize
 // CONSTELLATION PATTERNS - Dots that connect briefly
 // ============================================================================
 
-fn constellation_pattern(x: u16, y: u16, width: u16, height: u16, frame_index: usize) -> f32 {
+fn constellation_pattern(x: u16, y: u16, width: u16, height: u16, frame_index: usize, aspect: f32) -> f32 {
     let t = frame_index as f32 * 0.02;
     let num_stars = 12;
     let px = x as f32;
@@ -450,7 +454,7 @@ fn constellation_pattern(x: u16, y: u16, width: u16, height: u16, frame_index: u
 
     // Check if on a star
     for (sx, sy) in &stars {
-        let dist = ((px - sx).powi(2) + (py - sy).powi(2) * 4.0).sqrt();
+        let dist = ((px - sx).powi(2) + ((py - sy) * aspect).powi(2)).sqrt();
         if dist < 1.5 {
             intensity = intensity.max(1.0 - dist / 1.5);
         }
@@ -572,13 +576,17 @@ fn trail_char(age: usize) -> char {
 // MAIN RENDER FUNCTION
 // ============================================================================
 
-pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
+/// Floating shapes and trail particles per detail tier
+const SHAPES_BY_DETAIL: [usize; 4] = [4, 6, 9, 12];
+const TRAILS_BY_DETAIL: [usize; 4] = [0, 8, 14, 20];
+
+pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize, params: &RenderParams) {
     // First pass: render background gradient
     for y in 0..area.height {
         for x in 0..area.width {
             let color = background_color(x, y, area.width, area.height, frame_index);
             frame.render_widget(
-                Paragraph::new(" ").style(Style::default().bg(color)),
+                Paragraph::new(" ").style(Style::default().bg(background::color(color))),
                 Rect::new(area.x + x, area.y + y, 1, 1),
             );
         }
@@ -586,8 +594,9 @@ pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
 
     // Get pre-computed data for neural network and shapes
     let neural_nodes = get_neural_nodes(area.width, area.height, frame_index);
-    let floating_shapes = get_floating_shapes(area.width, area.height, frame_index, 12);
-    let trail_particles = get_trail_particles(area.width, area.height, frame_index, 20);
+    let detail = params.detail as usize;
+    let floating_shapes = get_floating_shapes(area.width, area.height, frame_index, SHAPES_BY_DETAIL[detail]);
+    let trail_particles = get_trail_particles(area.width, area.height, frame_index, TRAILS_BY_DETAIL[detail]);
 
     // Second pass: render all effects
     for y in 0..area.height {
@@ -596,7 +605,7 @@ pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
             let mut effect_type = 0usize;
 
             // 1. Hexagonal grid (subtle background pattern)
-            let hex_intensity = hexagon_field(x as f32, y as f32, 8.0, frame_index);
+            let hex_intensity = if detail >= 2 { hexagon_field(x as f32, y as f32, 8.0, frame_index) } else { 0.0 };
             if hex_intensity > 0.05 {
                 total_intensity = total_intensity.max(hex_intensity);
                 effect_type = 1;
@@ -619,7 +628,7 @@ pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
 
             // 4. Floating shapes (check each shape)
             for (i, shape) in floating_shapes.iter().enumerate() {
-                let dist = shape_distance(x as f32, y as f32, shape);
+                let dist = shape_distance(x as f32, y as f32, shape, params.aspect);
                 if dist < 0.5 {
                     // On the edge of the shape
                     let edge_intensity = (0.5 - dist.abs()) * 2.0 * shape.brightness;
@@ -631,17 +640,19 @@ pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
             }
 
             // 5. Thinking pulse (central breathing glow)
-            let pulse = thinking_pulse(x, y, area.width, area.height, frame_index);
+            let pulse = thinking_pulse(x, y, area.width, area.height, frame_index, params.aspect);
             if pulse > 0.1 && pulse > total_intensity * 0.5 {
                 total_intensity = total_intensity.max(pulse * 0.6);
                 effect_type = 8;
             }
 
             // 6. Constellation patterns
-            let constellation = constellation_pattern(x, y, area.width, area.height, frame_index);
-            if constellation > total_intensity {
-                total_intensity = constellation;
-                effect_type = 9;
+            if detail >= 3 {
+                let constellation = constellation_pattern(x, y, area.width, area.height, frame_index, params.aspect);
+                if constellation > total_intensity {
+                    total_intensity = constellation;
+                    effect_type = 9;
+                }
             }
 
             // Render if there's something to show
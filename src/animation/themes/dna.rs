@@ -1,5 +1,7 @@
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Paragraph};
+use ratatui::widgets::Paragraph;
+
+use crate::animation::background;
 
 /// DNA Double Helix - rotating 3D helix structure
 
@@ -42,8 +44,7 @@ fn complement_color(base_type: usize) -> Color {
 
 pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
     // Dark background
-    let bg = Block::default().style(Style::default().bg(Color::Rgb(5, 10, 20)));
-    frame.render_widget(bg, area);
+    background::fill(frame, area, Color::Rgb(5, 10, 20));
     
     let center_x = area.width as f32 / 2.0;
     let helix_radius = (area.width as f32 / 4.0).min(15.0);
@@ -1,5 +1,8 @@
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Paragraph};
+use ratatui::widgets::Paragraph;
+
+use super::RenderParams;
+use crate::animation::background;
 
 /// Electric/Lightning theme - crackling energy bolts
 
@@ -72,7 +75,7 @@ impl Bolt {
             points.push((x as u16, y.min(height - 1)));
             
             // Occasional branch
-            if h % 10 < 2 && y < height - 5 {
+            if h % 10 < 2 && y + 5 < height {
                 let branch_len = 3 + (h % 4) as u16;
                 let branch_dir = if h % 2 == 0 { 1i16 } else { -1i16 };
                 for i in 1..=branch_len {
@@ -141,10 +144,9 @@ impl Arc {
     }
 }
 
-pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
+pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize, params: &RenderParams) {
     // Dark stormy background
-    let bg = Block::default().style(Style::default().bg(Color::Rgb(10, 10, 20)));
-    frame.render_widget(bg, area);
+    background::fill(frame, area, Color::Rgb(10, 10, 20));
     
     // Ambient electric particles
     for i in 0..50 {
@@ -163,8 +165,8 @@ pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
         }
     }
     
-    // Main lightning bolts
-    let num_bolts = 3;
+    // Main lightning bolts (none with reduced motion: they strobe)
+    let num_bolts = if params.reduced_motion() { 0 } else { 3 };
     for i in 0..num_bolts {
         let bolt = Bolt::new(i * 7919 + (frame_index / 30) * 1000, area.width, area.height, frame_index);
         
@@ -245,7 +247,7 @@ pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
         }
         
         // Sparks from coil top
-        let spark_active = frame_index % 5 < 3;
+        let spark_active = frame_index % 5 < 3 && !params.reduced_motion();
         if spark_active && coil_y > 0 {
             let spark_color = Color::Rgb(200, 220, 255);
             for dx in [-1i16, 0, 1] {
@@ -1,5 +1,7 @@
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Paragraph};
+use ratatui::widgets::Paragraph;
+
+use crate::animation::background;
 
 /// Fire characters from dense to sparse
 const FIRE_CHARS: &[char] = &['█', '▓', '▒', '░', '∙', ' '];
@@ -65,8 +67,7 @@ fn fire_char(intensity: f32) -> char {
 
 pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
     // Dark reddish background
-    let bg = Block::default().style(Style::default().bg(Color::Rgb(20, 5, 0)));
-    frame.render_widget(bg, area);
+    background::fill(frame, area, Color::Rgb(20, 5, 0));
 
     // Only render fire in bottom 2/3 of screen
     let fire_start_y = area.height / 3;
@@ -1,5 +1,7 @@
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Paragraph};
+use ratatui::widgets::Paragraph;
+
+use crate::animation::background;
 
 /// Firework burst particle
 struct Particle {
@@ -117,10 +119,53 @@ fn particle_char(life: f32) -> char {
     }
 }
 
+/// Draw one burst's particles (and short trails) at the given frame
+fn draw_firework(frame: &mut Frame, area: Rect, firework: &Firework, frame_index: usize) {
+    for particle in firework.get_particles(frame_index) {
+        let x = particle.x as u16;
+        let y = particle.y as u16;
+
+        if x < area.width && y < area.height {
+            let color = firework_color(particle.color_idx, particle.life);
+            let ch = particle_char(particle.life);
+
+            frame.render_widget(
+                Paragraph::new(ch.to_string()).style(Style::default().fg(color)),
+                Rect::new(area.x + x, area.y + y, 1, 1),
+            );
+
+            // Trail for fast-moving particles
+            if particle.life > 0.5 {
+                let trail_x = (particle.x - particle.vx * 0.3) as u16;
+                let trail_y = (particle.y - particle.vy * 0.3) as u16;
+                if trail_x < area.width && trail_y < area.height {
+                    let trail_color = firework_color(particle.color_idx, particle.life * 0.5);
+                    frame.render_widget(
+                        Paragraph::new("·").style(Style::default().fg(trail_color)),
+                        Rect::new(area.x + trail_x, area.y + trail_y, 1, 1),
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// Celebration overlay: quick staggered bursts drawn over whatever is on screen.
+/// `elapsed` is the number of frames since the celebration started.
+pub fn render_celebration(frame: &mut Frame, area: Rect, elapsed: usize) {
+    let num_bursts = 5;
+
+    for burst in 0..num_bursts {
+        let mut firework = Firework::new(burst * 7919 + 31, area.width, area.height);
+        // Stagger births over the first ~1.5 s instead of the theme's long cycle
+        firework.birth_frame = burst * 3;
+        draw_firework(frame, area, &firework, elapsed);
+    }
+}
+
 pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
     // Dark night sky
-    let bg = Block::default().style(Style::default().bg(Color::Rgb(5, 5, 15)));
-    frame.render_widget(bg, area);
+    background::fill(frame, area, Color::Rgb(5, 5, 15));
 
     // Create multiple fireworks
     let num_fireworks = 6;
@@ -135,33 +180,7 @@ pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
         );
 
         let adjusted_frame = frame_index.wrapping_add(cycle_offset);
-        for particle in firework.get_particles(adjusted_frame) {
-            let x = particle.x as u16;
-            let y = particle.y as u16;
-
-            if x < area.width && y < area.height {
-                let color = firework_color(particle.color_idx, particle.life);
-                let ch = particle_char(particle.life);
-
-                frame.render_widget(
-                    Paragraph::new(ch.to_string()).style(Style::default().fg(color)),
-                    Rect::new(area.x + x, area.y + y, 1, 1),
-                );
-
-                // Trail for fast-moving particles
-                if particle.life > 0.5 {
-                    let trail_x = (particle.x - particle.vx * 0.3) as u16;
-                    let trail_y = (particle.y - particle.vy * 0.3) as u16;
-                    if trail_x < area.width && trail_y < area.height {
-                        let trail_color = firework_color(particle.color_idx, particle.life * 0.5);
-                        frame.render_widget(
-                            Paragraph::new("·").style(Style::default().fg(trail_color)),
-                            Rect::new(area.x + trail_x, area.y + trail_y, 1, 1),
-                        );
-                    }
-                }
-            }
-        }
+        draw_firework(frame, area, &firework, adjusted_frame);
     }
 
     // Add some twinkling stars in background
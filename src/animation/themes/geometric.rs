@@ -1,5 +1,8 @@
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Paragraph};
+use ratatui::widgets::Paragraph;
+
+use super::RenderParams;
+use crate::animation::background;
 
 /// Geometric - Rotating fractals, tessellations, expanding/contracting patterns, mathematical beauty
 
@@ -30,9 +33,9 @@ fn fast_cos(x: f32) -> f32 {
 }
 
 /// Calculate rotating spiral pattern intensity
-fn spiral_intensity(x: u16, y: u16, cx: f32, cy: f32, frame_index: usize) -> f32 {
+fn spiral_intensity(x: u16, y: u16, cx: f32, cy: f32, frame_index: usize, aspect: f32) -> f32 {
     let dx = x as f32 - cx;
-    let dy = (y as f32 - cy) * 2.0; // Adjust for terminal aspect ratio
+    let dy = (y as f32 - cy) * aspect;
 
     let dist = (dx * dx + dy * dy).sqrt();
     let angle = dy.atan2(dx);
@@ -49,13 +52,13 @@ fn spiral_intensity(x: u16, y: u16, cx: f32, cy: f32, frame_index: usize) -> f32
 }
 
 /// Calculate tessellation pattern
-fn tessellation_pattern(x: u16, y: u16, frame_index: usize) -> (f32, usize) {
+fn tessellation_pattern(x: u16, y: u16, frame_index: usize, aspect: f32) -> (f32, usize) {
     let t = frame_index as f32 * 0.02;
 
     // Hexagonal tessellation
     let scale = 6.0;
     let fx = x as f32 / scale;
-    let fy = y as f32 / scale * 1.7; // Adjust for aspect ratio
+    let fy = y as f32 / scale * aspect * 0.85; // Hex rows, about sqrt(3)/2 apart
 
     // Offset every other row
     let row = fy.floor() as i32;
@@ -138,17 +141,16 @@ fn geo_color(x: u16, y: u16, intensity: f32, frame_index: usize) -> Color {
         Color::Rgb(i / 2, i / 4, i)
     } else if phase < 2.0 {
         // Cyan/teal
-        Color::Rgb(i / 4, i * 3 / 4, i)
+        Color::Rgb(i / 4, (i as u16 * 3 / 4) as u8, i)
     } else {
         // Gold/amber
-        Color::Rgb(i, i * 3 / 4, i / 3)
+        Color::Rgb(i, (i as u16 * 3 / 4) as u8, i / 3)
     }
 }
 
-pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
+pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize, params: &RenderParams) {
     // Deep dark background
-    let bg = Block::default().style(Style::default().bg(Color::Rgb(8, 5, 15)));
-    frame.render_widget(bg, area);
+    background::fill(frame, area, Color::Rgb(8, 5, 15));
 
     let cx = area.width as f32 / 2.0;
     let cy = area.height as f32 / 2.0;
@@ -156,8 +158,8 @@ pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
     for y in 0..area.height {
         for x in 0..area.width {
             // Combine multiple patterns
-            let spiral = spiral_intensity(x, y, cx, cy, frame_index);
-            let (tess, pattern) = tessellation_pattern(x, y, frame_index);
+            let spiral = spiral_intensity(x, y, cx, cy, frame_index, params.aspect);
+            let (tess, pattern) = tessellation_pattern(x, y, frame_index, params.aspect);
             let fractal = fractal_edge(x, y, area.width, area.height, frame_index);
 
             // Layer the patterns
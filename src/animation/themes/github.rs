@@ -1,9 +1,14 @@
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Paragraph};
+use ratatui::widgets::Paragraph;
+
+use super::RenderParams;
+use crate::animation::background;
+use crate::contributions;
 
 /// GitHub themed - Developer productivity visualization
 /// Code flowing, commits happening, branches merging
 /// A living codebase in real-time
+/// With `diegetic_progress` on, the contribution grid fills in day by day as the session goes on
 
 // GitHub Dark color palette
 const BG_COLOR: Color = Color::Rgb(13, 17, 23);           // #0D1117
@@ -229,8 +234,9 @@ fn render_branch_lines(frame: &mut Frame, area: Rect, frame_index: usize) {
     }
 }
 
-/// Render the contribution grid with growth animations
-fn render_contribution_grid(frame: &mut Frame, area: Rect, frame_index: usize) {
+/// Render the contribution grid with growth animations: the user's real calendar
+/// (its latest weeks) when `github` is configured, made-up levels otherwise
+fn render_contribution_grid(frame: &mut Frame, area: Rect, frame_index: usize, progress: Option<f32>) {
     let cell_width = 2u16;
     let cell_height = 1u16;
     let gap = 1u16;
@@ -248,10 +254,23 @@ fn render_contribution_grid(frame: &mut Frame, area: Rect, frame_index: usize) {
     let offset_x = (area.width.saturating_sub(grid_width)) / 2;
     let offset_y = 3; // Slight offset from top
 
+    let calendar = contributions::calendar();
+    // Diegetic progress: days filled so far, week by week from the left
+    let filled = progress.map(|p| (p * (grid_cols * grid_rows) as f32) as u16);
     for gy in 0..grid_rows {
         for gx in 0..grid_cols {
             let cell_id = gx as usize * 100 + gy as usize;
-            let base_level = simple_hash(cell_id, 1234) % 5;
+            let real_level = if calendar.is_empty() {
+                None
+            } else {
+                // Days outside the calendar stay empty
+                let week = (calendar.len() + gx as usize).checked_sub(grid_cols as usize);
+                match week.and_then(|w| calendar[w][gy as usize]) {
+                    Some(level) => Some(level),
+                    None => continue,
+                }
+            };
+            let base_level = real_level.map_or(simple_hash(cell_id, 1234) % 5, usize::from);
 
             // Growth animation - cells occasionally "grow"
             let growth_cycle = simple_hash(cell_id, 5678) % 200;
@@ -264,14 +283,18 @@ fn render_contribution_grid(frame: &mut Frame, area: Rect, frame_index: usize) {
                 0.0
             };
 
-            // Temporarily boost level during growth
-            let level = if is_growing && base_level < 4 {
+            // Temporarily boost level during growth; real levels only glow
+            let level = if is_growing && base_level < 4 && real_level.is_none() {
                 (base_level + 1) as u8
             } else {
                 base_level as u8
             };
 
-            let color = contribution_color(level, growth_phase);
+            let color = match filled {
+                Some(filled) if gx * grid_rows + gy >= filled => CONTRIB_0,
+                Some(_) => contribution_color(level.max(1), growth_phase),
+                None => contribution_color(level, growth_phase),
+            };
 
             let px = area.x + offset_x + gx * (cell_width + gap);
             let py = area.y + offset_y + gy * (cell_height + gap);
@@ -357,7 +380,12 @@ fn render_commit_messages(frame: &mut Frame, area: Rect, frame_index: usize) {
             if lane_x < area.width {
                 frame.render_widget(
                     Paragraph::new(prefix).style(Style::default().fg(color)),
-                    Rect::new(area.x + lane_x, area.y + y_pos as u16, prefix.len() as u16, 1),
+                    Rect::new(
+                        area.x + lane_x,
+                        area.y + y_pos as u16,
+                        (prefix.len() as u16).min(area.width - lane_x),
+                        1,
+                    ),
                 );
             }
         }
@@ -470,7 +498,7 @@ fn render_code_rain(frame: &mut Frame, area: Rect, frame_index: usize) {
 }
 
 /// Render PR merge flash effects
-fn render_merge_effects(frame: &mut Frame, area: Rect, frame_index: usize) {
+fn render_merge_effects(frame: &mut Frame, area: Rect, frame_index: usize, aspect: f32) {
     for effect_idx in 0..3 {
         let effect_period = 150 + simple_hash(effect_idx, 5555) % 100;
         let effect_frame = frame_index % effect_period;
@@ -488,7 +516,7 @@ fn render_merge_effects(frame: &mut Frame, area: Rect, frame_index: usize) {
             for angle in 0..8 {
                 let a = angle as f32 * std::f32::consts::PI / 4.0;
                 let dx = (fast_cos(a) * radius as f32) as i16;
-                let dy = (fast_sin(a) * radius as f32 * 0.5) as i16; // Flatten for terminal
+                let dy = (fast_sin(a) * radius as f32 / aspect) as i16;
 
                 let px = (cx as i16 + dx).max(0) as u16;
                 let py = (cy as i16 + dy).max(0) as u16;
@@ -519,10 +547,9 @@ fn render_merge_effects(frame: &mut Frame, area: Rect, frame_index: usize) {
 }
 
 /// Main render function
-pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
+pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize, params: &RenderParams) {
     // Dark GitHub background
-    let bg = Block::default().style(Style::default().bg(BG_COLOR));
-    frame.render_widget(bg, area);
+    background::fill(frame, area, BG_COLOR);
 
     // Layer 1: Very subtle code rain in background
     render_code_rain(frame, area, frame_index);
@@ -534,7 +561,7 @@ pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
     render_branch_lines(frame, area, frame_index);
 
     // Layer 4: Contribution grid (central element)
-    render_contribution_grid(frame, area, frame_index);
+    render_contribution_grid(frame, area, frame_index, params.progress);
 
     // Layer 5: File tree on left side
     render_file_tree(frame, area, frame_index);
@@ -545,8 +572,10 @@ pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
     // Layer 7: Octocat in corner
     render_octocat(frame, area, frame_index);
 
-    // Layer 8: Merge flash effects (on top)
-    render_merge_effects(frame, area, frame_index);
+    // Layer 8: Merge flash effects (on top), left out with reduced motion
+    if !params.reduced_motion() {
+        render_merge_effects(frame, area, frame_index, params.aspect);
+    }
 
     // Corner decoration - repo indicator
     if area.width > 20 && area.height > 3 {
@@ -561,3 +590,23 @@ pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn test_grid_fills_with_progress() {
+        // 10 weeks by 7 days, two cells wide each
+        let mut terminal = Terminal::new(TestBackend::new(30, 20)).unwrap();
+        terminal
+            .draw(|frame| render_contribution_grid(frame, frame.area(), 0, Some(0.5)))
+            .unwrap();
+
+        let days = terminal.backend().buffer().content.iter().filter(|cell| cell.symbol() == "█");
+        let (empty, filled): (Vec<_>, Vec<_>) = days.partition(|cell| cell.fg == CONTRIB_0);
+        assert_eq!(empty.len(), 35 * 2);
+        assert_eq!(filled.len(), 35 * 2);
+    }
+}
@@ -1,5 +1,8 @@
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Paragraph};
+use ratatui::widgets::Paragraph;
+
+use super::RenderParams;
+use crate::animation::background;
 
 /// Glitch - Corrupted scanlines, RGB split effects, digital noise, cyberpunk aesthetic
 
@@ -75,13 +78,13 @@ fn noise_char(x: u16, y: u16, frame_index: usize) -> Option<(char, Color)> {
 }
 
 /// Generate scanline effect
-fn scanline_effect(y: u16, frame_index: usize) -> u8 {
+fn scanline_effect(y: u16, frame_index: usize, flashes: bool) -> u8 {
     // CRT scanline darkness
     let scanline = if y % 2 == 0 { 0 } else { 15 };
 
     // Occasional bright scanline flash
     let flash_line = (frame_index / 3) % 40;
-    let flash = if y as usize == flash_line { 30 } else { 0 };
+    let flash = if flashes && y as usize == flash_line { 30 } else { 0 };
 
     scanline + flash
 }
@@ -148,24 +151,28 @@ fn grid_line(x: u16, y: u16, width: u16, height: u16, frame_index: usize) -> Opt
     }
 }
 
-pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
+pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize, params: &RenderParams) {
     // Dark cyberpunk background
-    let bg = Block::default().style(Style::default().bg(Color::Rgb(5, 5, 12)));
-    frame.render_widget(bg, area);
+    background::fill(frame, area, Color::Rgb(5, 5, 12));
+
+    // Reduced motion keeps the noise and grid but drops the bursts and splits
+    let strobes = !params.reduced_motion();
 
     // Calculate RGB split for this frame
     let (rgb_active, rgb_offset) = rgb_split_intensity(0, 0, frame_index);
+    let rgb_active = rgb_active && strobes;
 
     for y in 0..area.height {
-        let scanline_offset = scanline_glitch(y, frame_index);
-        let scanline_dark = scanline_effect(y, frame_index);
+        let scanline_offset = if strobes { scanline_glitch(y, frame_index) } else { 0 };
+        let scanline_dark = scanline_effect(y, frame_index, strobes);
 
         for x in 0..area.width {
             // Apply scanline offset
             let effective_x = (x as i16 + scanline_offset).clamp(0, area.width as i16 - 1) as u16;
 
             // Check for corruption blocks first
-            if let Some((ch, color)) = corruption_block(effective_x, y, area.width, area.height, frame_index) {
+            let corruption = strobes.then(|| corruption_block(effective_x, y, area.width, area.height, frame_index));
+            if let Some((ch, color)) = corruption.flatten() {
                 frame.render_widget(
                     Paragraph::new(ch.to_string()).style(Style::default().fg(color)),
                     Rect::new(area.x + x, area.y + y, 1, 1),
@@ -1,8 +1,23 @@
+use std::cell::RefCell;
+
 use ratatui::prelude::*;
 use ratatui::widgets::Paragraph;
 
+use super::RenderParams;
+use crate::animation::background;
+use crate::animation::canvas::{self, Canvas};
+use crate::animation::daylight;
+use crate::animation::layer_cache::LayerCache;
+use crate::animation::precipitation;
+use crate::weather::{self, Conditions};
+
 /// Landscape - A serene Bob Ross-inspired pastoral scene
 /// Features: rolling hills with parallax, sun/moon cycle, clouds, river, acacia trees, birds, fireflies
+/// With `weather` configured the sky follows the local conditions: grey and cloudier, rain, snow, fog
+/// With `landscape_sky.local_time` on, day and night follow the local clock
+/// With `diegetic_progress` on, the river rises as the session goes on
+/// Lower detail tiers drop the rays, shimmer and shooting stars, then the small
+/// life (stars, birds, fireflies, grass), then clouds and trees
 
 // Acacia tree patterns - more organic, asymmetric silhouettes
 // Small acacia - scraggly young tree
@@ -38,6 +53,14 @@ const ACACIA_LARGE: &[&str] = &[
     "      ▄█▄      ",
 ];
 
+/// Number of parallax hill layers (0 = closest)
+const HILL_LAYERS: usize = 6;
+
+thread_local! {
+    /// Hill outline of every layer per column; only a resize changes it
+    static HILL_TOPS: RefCell<LayerCache<Vec<[u16; HILL_LAYERS]>>> = const { RefCell::new(LayerCache::new()) };
+}
+
 fn simple_hash(x: usize, seed: usize) -> usize {
     let mut h = x.wrapping_mul(2654435761);
     h ^= seed;
@@ -59,9 +82,15 @@ fn fast_sin(x: f32) -> f32 {
     }
 }
 
+/// Angle of the sun on its circle: the local clock's with `landscape_sky.local_time`,
+/// otherwise the animated cycle's
+fn sun_angle(frame_index: usize) -> f32 {
+    daylight::sun_angle().unwrap_or(frame_index as f32 * 0.003)
+}
+
 /// Get time of day phase: 0 = midnight, 0.25 = dawn, 0.5 = noon, 0.75 = dusk
 fn get_day_phase(frame_index: usize) -> f32 {
-    let t = frame_index as f32 * 0.003;
+    let t = sun_angle(frame_index);
     ((fast_sin(t) + 1.0) / 2.0)
 }
 
@@ -90,6 +119,13 @@ fn hill_height(x: u16, width: u16, height: u16, layer: usize) -> u16 {
     (layer_base + hill_offset).max(0.0) as u16
 }
 
+/// Hill outline of every layer for each column (cached per resize)
+fn build_hill_tops(area: Rect) -> Vec<[u16; HILL_LAYERS]> {
+    (0..area.width)
+        .map(|x| std::array::from_fn(|layer| hill_height(x, area.width, area.height, layer)))
+        .collect()
+}
+
 /// Get river path at x position - dramatic S-curve meander
 fn river_y(x: u16, width: u16, height: u16, _frame_index: usize) -> u16 {
     let fx = x as f32 / width as f32;
@@ -122,13 +158,19 @@ fn river_width_at(x: u16, width: u16) -> u16 {
     base_width + pool_width
 }
 
-/// Check if position is a riverbank rock
-fn is_riverbank_rock(x: u16, y: u16, width: u16, height: u16, frame_index: usize) -> Option<Color> {
+/// Top and bottom rows of the river at x; with diegetic progress the water
+/// rises up the banks as the session goes on, a fifth of the height at the end
+fn river_bounds(x: u16, width: u16, height: u16, frame_index: usize, progress: Option<f32>) -> (u16, u16) {
     let river_center = river_y(x, width, height, frame_index);
     let river_w = river_width_at(x, width);
+    let rise = progress.map_or(0, |p| (p * height as f32 * 0.2) as u16);
 
-    let river_top = river_center.saturating_sub(river_w / 2);
-    let river_bottom = river_center + river_w / 2 + 1;
+    (river_center.saturating_sub(river_w / 2 + rise), river_center + river_w / 2 + 1)
+}
+
+/// Check if position is a riverbank rock
+fn is_riverbank_rock(x: u16, y: u16, width: u16, height: u16, frame_index: usize, progress: Option<f32>) -> Option<Color> {
+    let (river_top, river_bottom) = river_bounds(x, width, height, frame_index, progress);
 
     // Rocks appear 1-2 cells outside the river
     let rock_zone_top = river_top.saturating_sub(2)..river_top;
@@ -149,13 +191,9 @@ fn is_riverbank_rock(x: u16, y: u16, width: u16, height: u16, frame_index: usize
 }
 
 /// Check if position is in the river
-fn is_river(x: u16, y: u16, width: u16, height: u16, frame_index: usize) -> (bool, bool) {
-    let river_center = river_y(x, width, height, frame_index);
+fn is_river(x: u16, y: u16, width: u16, height: u16, frame_index: usize, progress: Option<f32>) -> (bool, bool) {
     let river_w = river_width_at(x, width);
-
-    // River boundaries
-    let river_top = river_center.saturating_sub(river_w / 2);
-    let river_bottom = river_center + river_w / 2 + 1;
+    let (river_top, river_bottom) = river_bounds(x, width, height, frame_index, progress);
 
     let in_river = y >= river_top && y <= river_bottom;
 
@@ -311,12 +349,11 @@ fn river_color(x: u16, y: u16, width: u16, height: u16, day_phase: f32, is_shimm
 }
 
 /// Get character for river based on width and position
-fn river_char(x: u16, y: u16, width: u16, height: u16, frame_index: usize) -> char {
-    let river_center = river_y(x, width, height, frame_index);
+fn river_char(x: u16, y: u16, width: u16, height: u16, frame_index: usize, progress: Option<f32>) -> char {
     let river_w = river_width_at(x, width);
+    let (river_top, river_bottom) = river_bounds(x, width, height, frame_index, progress);
 
-    let is_edge = y == river_center.saturating_sub(river_w / 2) ||
-                  y == river_center + river_w / 2 + 1;
+    let is_edge = y == river_top || y == river_bottom;
 
     // Animation phase
     let phase = (x as usize + frame_index / 4) % 3;
@@ -387,17 +424,17 @@ struct Cloud {
 }
 
 /// Get clouds with depth layering
-fn get_clouds(width: u16, height: u16, frame_index: usize) -> Vec<Cloud> {
+fn get_clouds(width: u16, height: u16, frame_index: usize, count: usize) -> Vec<Cloud> {
     let t = frame_index as f32 * 0.015;
     let mut clouds = Vec::new();
 
-    for i in 0..7 {
+    for i in 0..count {
         let base_x = (simple_hash(i, 100) % (width as usize * 2)) as f32;
         let speed = 1.0 + (simple_hash(i, 150) % 20) as f32 * 0.1;
 
         clouds.push(Cloud {
             x: (base_x + t * speed * 8.0) % (width as f32 * 1.5) - width as f32 * 0.25,
-            y: 2.0 + (simple_hash(i, 200) % (height as usize / 5)) as f32,
+            y: 2.0 + (simple_hash(i, 200) % (height as usize / 5).max(1)) as f32,
             width: 10.0 + (simple_hash(i, 300) % 8) as f32,
             height: 2.0 + (simple_hash(i, 400) % 2) as f32,
             density: 0.6 + (simple_hash(i, 500) % 40) as f32 * 0.01,
@@ -455,7 +492,7 @@ fn cloud_at(x: u16, y: u16, clouds: &[Cloud], day_phase: f32) -> Option<(char, C
 
 /// Get sun/moon position and properties
 fn celestial_body(width: u16, height: u16, frame_index: usize) -> (i16, i16, bool, f32) {
-    let t = frame_index as f32 * 0.003;
+    let t = sun_angle(frame_index);
     let phase = fast_sin(t);
     let day_phase = (phase + 1.0) / 2.0;
 
@@ -778,7 +815,7 @@ const TREE_DATA: [(usize, usize, usize); 12] = [
 
 /// Check if position is in a vegetation zone near a tree
 /// Returns a green tint factor (0.0 = no zone, 1.0 = center of zone)
-fn vegetation_zone_factor(x: u16, y: u16, width: u16, height: u16, layer: usize) -> f32 {
+fn vegetation_zone_factor(x: u16, y: u16, hill_tops: &[[u16; HILL_LAYERS]], layer: usize) -> f32 {
     let mut max_factor = 0.0f32;
 
     for (seed, tree_layer, size) in TREE_DATA {
@@ -787,8 +824,8 @@ fn vegetation_zone_factor(x: u16, y: u16, width: u16, height: u16, layer: usize)
             continue;
         }
 
-        let tree_x = (simple_hash(seed, 8000) % width as usize) as i32;
-        let tree_hill_y = hill_height(tree_x as u16, width, height, tree_layer) as i32;
+        let tree_x = (simple_hash(seed, 8000) % hill_tops.len()) as i32;
+        let tree_hill_y = hill_tops[tree_x as usize][tree_layer] as i32;
 
         // Vegetation radius based on tree size
         let radius = match size {
@@ -847,7 +884,8 @@ fn render_fireflies(frame: &mut Frame, area: Rect, day_phase: f32, frame_index:
 }
 
 /// Render sun rays during transition periods (dawn/dusk)
-fn render_sun_rays(frame: &mut Frame, area: Rect, sun_x: i16, sun_y: i16, day_phase: f32, frame_index: usize) {
+#[allow(clippy::too_many_arguments)]
+fn render_sun_rays(frame: &mut Frame, area: Rect, sun_x: i16, sun_y: i16, day_phase: f32, frame_index: usize, aspect: f32) {
     if !is_transition_period(day_phase) { return; }
 
     let ray_dirs: [(i16, i16); 8] = [
@@ -857,6 +895,23 @@ fn render_sun_rays(frame: &mut Frame, area: Rect, sun_x: i16, sun_y: i16, day_ph
     ];
     let ray_chars = ['/', '|', '\\', '-', '-', '\\', '|', '/'];
 
+    // With `braille` on the rays are traced in dots instead of slashes
+    if canvas::is_high_res() {
+        let mut canvas = Canvas::new(area, aspect);
+        let (dots_x, dots_y) = canvas.dots_per_cell();
+        let sun = ((sun_x as f32 + 0.5) * dots_x, (sun_y as f32 + 0.5) * dots_y);
+        for (dx, dy) in ray_dirs {
+            // Same dashes as the slashes: every third step is a gap, marching outward
+            for dist in (1..15usize).filter(|dist| !(frame_index / 3 + dist).is_multiple_of(3)) {
+                let brightness = (255 - dist * 15).max(50) as u8;
+                let at = |d: f32| (sun.0 + dx as f32 * d * dots_x, sun.1 + dy as f32 * d * dots_y);
+                canvas.line(at(dist as f32 - 0.5), at(dist as f32 + 0.5), Color::Rgb(brightness, (brightness as u16 * 3 / 4) as u8, brightness / 2));
+            }
+        }
+        canvas.render(frame);
+        return;
+    }
+
     for (i, (dx, dy)) in ray_dirs.iter().enumerate() {
         for dist in 1..15 {
             let x = sun_x + dx * dist;
@@ -869,7 +924,7 @@ fn render_sun_rays(frame: &mut Frame, area: Rect, sun_x: i16, sun_y: i16, day_ph
             let brightness = (255i32 - dist as i32 * 15).max(50) as u8;
             frame.render_widget(
                 Paragraph::new(ray_chars[i].to_string())
-                    .style(Style::default().fg(Color::Rgb(brightness, brightness * 3/4, brightness / 2))),
+                    .style(Style::default().fg(Color::Rgb(brightness, (brightness as u16 * 3 / 4) as u8, brightness / 2))),
                 Rect::new(area.x + x as u16, area.y + y as u16, 1, 1),
             );
         }
@@ -897,42 +952,57 @@ fn render_heat_shimmer(frame: &mut Frame, area: Rect, day_phase: f32, frame_inde
     }
 }
 
-pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
+pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize, params: &RenderParams) {
     let day_phase = get_day_phase(frame_index);
-    let clouds = get_clouds(area.width, area.height, frame_index);
+    let conditions = weather::current();
+    let overcast = conditions.is_some_and(Conditions::is_overcast);
+    let clouds = get_clouds(area.width, area.height, frame_index, if overcast { 14 } else { 7 });
     let (sun_x, sun_y, is_sun, _) = celestial_body(area.width, area.height, frame_index);
+    let detail = params.detail;
 
     // Render sky gradient
     for y in 0..area.height {
         for x in 0..area.width {
-            let color = sky_color(y, area.height, day_phase);
+            let mut color = sky_color(y, area.height, day_phase);
+            if overcast {
+                color = precipitation::overcast(color, 0.6);
+            }
             frame.render_widget(
-                Paragraph::new(" ").style(Style::default().bg(color)),
+                Paragraph::new(" ").style(Style::default().bg(background::color(color))),
                 Rect::new(area.x + x, area.y + y, 1, 1),
             );
         }
     }
 
     // Render stars at night
-    render_stars(frame, area, day_phase, frame_index);
-    render_shooting_stars(frame, area, day_phase, frame_index);
+    if !overcast && detail >= 2 {
+        render_stars(frame, area, day_phase, frame_index);
+        if detail >= 3 {
+            render_shooting_stars(frame, area, day_phase, frame_index);
+        }
+    }
 
-    // Render sun/moon
-    if sun_y < area.height as i16 / 2 && sun_y > -5 {
+    // Render sun/moon, hidden behind an overcast sky
+    if !overcast && sun_y < area.height as i16 / 2 && sun_y > -5 {
         if is_sun {
             render_sun(frame, area, sun_x, sun_y, frame_index);
             // Render sun rays during transition periods
-            render_sun_rays(frame, area, sun_x, sun_y, day_phase, frame_index);
+            if detail >= 3 {
+                render_sun_rays(frame, area, sun_x, sun_y, day_phase, frame_index, params.aspect);
+            }
         } else {
             render_moon(frame, area, sun_x, sun_y);
         }
     }
 
     // Render birds
-    render_birds(frame, area, day_phase, frame_index);
+    if detail >= 2 {
+        render_birds(frame, area, day_phase, frame_index);
+    }
 
     // Render clouds with depth and wispy edges
-    for y in 0..area.height / 3 {
+    let cloud_rows = if detail >= 1 { area.height / 3 } else { 0 };
+    for y in 0..cloud_rows {
         for x in 0..area.width {
             if let Some((cloud_char, cloud_color)) = cloud_at(x, y, &clouds, day_phase) {
                 frame.render_widget(
@@ -948,31 +1018,43 @@ pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
     let grass_chars = ['·', '\'', ',', '.', '`'];
     let t = frame_index as f32 * 0.04;
 
-    // Render hills (back to front) - 6 layers now
-    for layer in (0..6).rev() {
-        let base_color = hill_color(layer, day_phase);
+    let hill_colors: [Color; HILL_LAYERS] = std::array::from_fn(|layer| hill_color(layer, day_phase));
+    let river_layer = 1; // River cuts through layer 1
+    // A river rising with the session (diegetic progress) floods the farther valleys too
+    let rising = params.progress.is_some();
+
+    // Hill outlines come from the per-resize cache
+    HILL_TOPS.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        let hill_tops = cache.get_or_build(area, 0, || build_hill_tops(area));
+
+        // Render hills: each cell is drawn once, by the closest layer covering it
         for x in 0..area.width {
-            let hill_y = hill_height(x, area.width, area.height, layer);
+            let tops = &hill_tops[x as usize];
+            let highest = tops.iter().copied().min().unwrap_or(area.height);
 
-            // Skip river area on appropriate layers
-            let river_layer = 1; // River cuts through layer 1
+            for y in highest..area.height {
+                let Some(layer) = (0..HILL_LAYERS).find(|&l| tops[l] <= y) else { continue };
+                let hill_y = tops[layer];
+                let base_color = hill_colors[layer];
 
-            for y in hill_y..area.height {
                 // Check if this is river position
-                if layer == river_layer {
-                    let (in_river, is_shimmer) = is_river(x, y, area.width, area.height, frame_index);
+                if layer == river_layer || (rising && layer > river_layer) {
+                    let (in_river, is_shimmer) = is_river(x, y, area.width, area.height, frame_index, params.progress);
                     if in_river {
                         let river_col = river_color(x, y, area.width, area.height, day_phase, is_shimmer);
-                        let rchar = river_char(x, y, area.width, area.height, frame_index);
+                        let rchar = river_char(x, y, area.width, area.height, frame_index, params.progress);
                         frame.render_widget(
                             Paragraph::new(rchar.to_string()).style(Style::default().fg(river_col)),
                             Rect::new(area.x + x, area.y + y, 1, 1),
                         );
                         continue;
                     }
+                }
 
+                if layer == river_layer {
                     // Check for riverbank rocks
-                    if let Some(rock_color) = is_riverbank_rock(x, y, area.width, area.height, frame_index) {
+                    if let Some(rock_color) = is_riverbank_rock(x, y, area.width, area.height, frame_index, params.progress) {
                         let rock_chars = ['•', '○', '◦'];
                         let rock_idx = simple_hash(x as usize + y as usize * 7, 1234) % rock_chars.len();
                         frame.render_widget(
@@ -984,30 +1066,9 @@ pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
                     }
                 }
 
-                // Apply vegetation zone green tint near trees
-                let veg_factor = vegetation_zone_factor(x, y, area.width, area.height, layer);
-                let hill_col = if veg_factor > 0.0 {
-                    // Tint towards lush green in vegetation zones
-                    if let Color::Rgb(br, bg, bb) = base_color {
-                        // Richer, darker green near trees
-                        let green_r = (br as f32 * 0.7) as u8;
-                        let green_g = (bg as f32 * 1.15).min(255.0) as u8;
-                        let green_b = (bb as f32 * 0.6) as u8;
-                        // Blend based on vegetation factor
-                        let r = (br as f32 * (1.0 - veg_factor) + green_r as f32 * veg_factor) as u8;
-                        let g = (bg as f32 * (1.0 - veg_factor) + green_g as f32 * veg_factor) as u8;
-                        let b = (bb as f32 * (1.0 - veg_factor) + green_b as f32 * veg_factor) as u8;
-                        Color::Rgb(r, g, b)
-                    } else {
-                        base_color
-                    }
-                } else {
-                    base_color
-                };
-
                 // Sparse grass texture only on hill edges (first 2 rows) of close layers
                 let rows_from_top = y.saturating_sub(hill_y);
-                let is_grass_zone = layer <= 1 && rows_from_top <= 2;
+                let is_grass_zone = detail >= 2 && layer <= 1 && rows_from_top <= 2;
 
                 if is_grass_zone {
                     let fx = x as f32;
@@ -1034,37 +1095,55 @@ pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
                                 .style(Style::default().fg(Color::Rgb(r, g, b))),
                             Rect::new(area.x + x, area.y + y, 1, 1),
                         );
-                    } else {
-                        // Solid hill color
-                        let ch = if y == hill_y { '\u{2593}' } else { '\u{2588}' };
-                        frame.render_widget(
-                            Paragraph::new(ch.to_string()).style(Style::default().fg(base_color)),
-                            Rect::new(area.x + x, area.y + y, 1, 1),
-                        );
+                        continue;
                     }
-                } else {
-                    // Standard solid hill rendering
-                    let ch = if y == hill_y { '\u{2593}' } else { '\u{2588}' };
-                    frame.render_widget(
-                        Paragraph::new(ch.to_string()).style(Style::default().fg(base_color)),
-                        Rect::new(area.x + x, area.y + y, 1, 1),
-                    );
                 }
+
+                // Solid hill color, tinted greener near trees
+                let veg_factor = vegetation_zone_factor(x, y, hill_tops, layer);
+                let hill_col = match base_color {
+                    Color::Rgb(br, bg, bb) if veg_factor > 0.0 => {
+                        // Richer, darker green near trees
+                        let green_r = br as f32 * 0.7;
+                        let green_g = (bg as f32 * 1.15).min(255.0);
+                        let green_b = bb as f32 * 0.6;
+                        // Blend based on vegetation factor
+                        let blend = |base: u8, green: f32| (base as f32 * (1.0 - veg_factor) + green * veg_factor) as u8;
+                        Color::Rgb(blend(br, green_r), blend(bg, green_g), blend(bb, green_b))
+                    }
+                    _ => base_color,
+                };
+                let ch = if y == hill_y { '\u{2593}' } else { '\u{2588}' };
+                frame.render_widget(
+                    Paragraph::new(ch.to_string()).style(Style::default().fg(hill_col)),
+                    Rect::new(area.x + x, area.y + y, 1, 1),
+                );
             }
         }
-    }
+    });
 
     // Render heat shimmer during peak day
-    render_heat_shimmer(frame, area, day_phase, frame_index);
+    if !overcast && detail >= 3 {
+        render_heat_shimmer(frame, area, day_phase, frame_index);
+    }
 
     // Render trees on hills
-    render_trees(frame, area, day_phase);
+    if detail >= 1 {
+        render_trees(frame, area, day_phase);
+    }
+
+    if detail >= 2 {
+        // Render fireflies at dusk/dawn
+        render_fireflies(frame, area, day_phase, frame_index);
 
-    // Render fireflies at dusk/dawn
-    render_fireflies(frame, area, day_phase, frame_index);
+        // Add natural grass tufts and wildflowers on closest hill
+        render_grass_and_flowers(frame, area, day_phase, frame_index);
+    }
 
-    // Add natural grass tufts and wildflowers on closest hill
-    render_grass_and_flowers(frame, area, day_phase, frame_index);
+    // Rain, snow or fog over everything
+    if let Some(conditions) = conditions {
+        precipitation::render(frame, area, frame_index, conditions, params);
+    }
 }
 
 /// Render grass field with breathing special characters effect
@@ -1169,3 +1248,21 @@ fn render_grass_and_flowers(frame: &mut Frame, area: Rect, day_phase: f32, frame
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    #[test]
+    fn test_sun_rays_start_bright() {
+        let mut terminal = Terminal::new(TestBackend::new(40, 30)).unwrap();
+        terminal
+            .draw(|frame| render_sun_rays(frame, frame.area(), 20, 15, 0.25, 1, 2.0))
+            .unwrap();
+        // The ray's first step, right of the sun, at its brightest
+        let cell = &terminal.backend().buffer()[(21, 15)];
+        assert_eq!(cell.symbol(), "-");
+        assert_eq!(cell.fg, Color::Rgb(240, 180, 120));
+    }
+}
@@ -1,5 +1,9 @@
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Paragraph};
+use ratatui::widgets::Paragraph;
+
+use super::MAX_CATCH_UP;
+use crate::animation::background;
+use crate::animation::math::rescale;
 
 /// Matrix rain characters (katakana-inspired and symbols)
 const CHARS: &[char] = &[
@@ -60,25 +64,72 @@ fn simple_hash(x: usize, seed: usize) -> usize {
     h ^ (h >> 16)
 }
 
-pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
-    // Dark background
-    let bg = Block::default().style(Style::default().bg(Color::Rgb(0, 10, 0)));
-    frame.render_widget(bg, area);
+/// Column where it would be at `frame_index` if it had been falling all along
+fn column_at(x: u16, height: u16, frame_index: usize) -> Column {
+    let mut col = Column::new(x as usize, 42);
 
-    // Create columns for the width
-    for x in 0..area.width {
-        let mut col = Column::new(x as usize, 42);
+    // Advance column based on frame and speed
+    let advances = frame_index / col.speed as usize;
+    for _ in 0..advances {
+        col.advance(height);
+    }
+    col.y %= (height as i32) + col.length as i32 + 20;
+    if col.y < 0 {
+        col.y += (height as i32) + col.length as i32 + 20;
+    }
+    col
+}
+
+/// One column per cell of width, kept between frames
+pub struct State {
+    columns: Vec<Column>,
+    size: (u16, u16),
+    frame_index: usize,
+}
 
-        // Advance column based on frame and speed
-        let advances = frame_index / col.speed as usize;
-        for _ in 0..advances {
-            col.advance(area.height);
+impl State {
+    pub fn new(area: Rect, frame_index: usize) -> Self {
+        Self {
+            columns: (0..area.width).map(|x| column_at(x, area.height, frame_index)).collect(),
+            size: (area.width, area.height),
+            frame_index,
+        }
+    }
+
+    /// Fall on to `frame_index`; after a resize existing columns keep falling where
+    /// they were (heads moved proportionally) and new columns start above the top
+    pub fn update(&mut self, area: Rect, frame_index: usize) {
+        let (width, height) = self.size;
+        if (area.width, area.height) != self.size {
+            for col in &mut self.columns {
+                col.y = rescale(col.y as f32, height, area.height) as i32;
+            }
+            for x in width..area.width {
+                let mut col = Column::new(x as usize, 42);
+                col.y = col.y.min(-1);
+                self.columns.push(col);
+            }
+            self.columns.truncate(area.width as usize);
+            self.size = (area.width, area.height);
         }
-        col.y = col.y % ((area.height as i32) + col.length as i32 + 20);
-        if col.y < 0 {
-            col.y += (area.height as i32) + col.length as i32 + 20;
+
+        let steps = frame_index.saturating_sub(self.frame_index).min(MAX_CATCH_UP);
+        for frame in frame_index - steps + 1..=frame_index {
+            for col in &mut self.columns {
+                if frame % col.speed as usize == 0 {
+                    col.advance(area.height);
+                }
+            }
         }
+        self.frame_index = frame_index;
+    }
+}
 
+pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize, state: &State) {
+    // Dark background
+    background::fill(frame, area, Color::Rgb(0, 10, 0));
+
+    for (x, col) in (0..area.width).zip(&state.columns) {
         // Render this column
         for y in 0..area.height {
             let screen_y = y as i32;
@@ -95,3 +146,25 @@ pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_columns_survive_a_resize() {
+        let mut state = State::new(Rect::new(0, 0, 40, 12), 50);
+        let heads: Vec<i32> = state.columns.iter().map(|c| c.y).collect();
+
+        state.update(Rect::new(0, 0, 80, 24), 50);
+        assert_eq!(state.columns.len(), 80);
+        for (col, head) in state.columns.iter().zip(&heads) {
+            assert_eq!(col.y, head * 2);
+        }
+        // New columns start above the top instead of appearing mid-screen
+        assert!(state.columns[40..].iter().all(|c| c.y < 0));
+
+        state.update(Rect::new(0, 0, 20, 24), 51);
+        assert_eq!(state.columns.len(), 20);
+    }
+}
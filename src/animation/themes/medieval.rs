@@ -1,10 +1,25 @@
+use std::cell::RefCell;
+
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Paragraph};
+use ratatui::widgets::Paragraph;
+
+use super::RenderParams;
+use crate::animation::background;
+use crate::animation::layer_cache::{CellLayer, LayerCache};
+
+thread_local! {
+    /// Castle stonework only changes on resize (or while lightning lights it up)
+    static CASTLE_STONE: RefCell<LayerCache<CellLayer>> = const { RefCell::new(LayerCache::new()) };
+}
 
 /// Medieval - Epic fantasy castle at night with siege atmosphere
 /// Features: Dragon silhouette, smoke/mist, realistic torches with embers,
 /// patrolling guards, distant army, waving banners, trebuchet, owls/bats,
 /// lightning flashes, glowing windows
+/// With `diegetic_progress` on, the torches go out one by one, left to right,
+/// as the session goes on
+/// Lower detail tiers drop the dragon and trebuchet, then the guards, creatures
+/// and army, then fog, smoke, lightning and the wall torches
 
 fn simple_hash(x: usize, seed: usize) -> usize {
     let mut h = x.wrapping_mul(2654435761);
@@ -32,6 +47,10 @@ fn fast_cos(x: f32) -> f32 {
     fast_sin(x + std::f32::consts::PI / 2.0)
 }
 
+/// Smallest area the full castle scene is laid out for
+const SCENE_MIN_WIDTH: u16 = 40;
+const SCENE_MIN_HEIGHT: u16 = 12;
+
 /// Castle tower structure
 struct Tower {
     x: u16,
@@ -74,7 +93,7 @@ fn get_towers(area_width: u16, area_height: u16) -> Vec<Tower> {
 }
 
 /// Enhanced stone texture - mostly solid blocks with subtle detail
-fn stone_char(x: u16, y: u16, _frame_index: usize) -> char {
+fn stone_char(x: u16, y: u16) -> char {
     let pattern = simple_hash(x as usize * 31 + y as usize * 17, 100);
 
     // Horizontal mortar lines between stone blocks (sparse)
@@ -238,6 +257,43 @@ fn render_torch(frame: &mut Frame, area: Rect, x: u16, y: u16, torch_id: usize,
     }
 }
 
+/// A torch that has gone out: the bare handle and a thin wisp of smoke
+fn render_spent_torch(frame: &mut Frame, area: Rect, x: u16, y: u16, torch_id: usize, frame_index: usize) {
+    if x >= area.width || y >= area.height { return; }
+
+    if x > 0 && y + 1 < area.height {
+        frame.render_widget(
+            Paragraph::new("╢").style(Style::default().fg(Color::Rgb(60, 45, 25))),
+            Rect::new(area.x + x - 1, area.y + y + 1, 1, 1),
+        );
+    }
+    if y + 1 < area.height {
+        frame.render_widget(
+            Paragraph::new("║").style(Style::default().fg(Color::Rgb(70, 45, 25))),
+            Rect::new(area.x + x, area.y + y + 1, 1, 1),
+        );
+    }
+
+    // Charred head
+    frame.render_widget(
+        Paragraph::new("▪").style(Style::default().fg(Color::Rgb(40, 32, 28))),
+        Rect::new(area.x + x, area.y + y, 1, 1),
+    );
+
+    // Smoke curling up and fading
+    let offset = (frame_index / 3 + torch_id * 7) % 12;
+    let smoke_y = y.saturating_sub(1 + offset as u16 / 4);
+    if smoke_y > 0 && smoke_y < y {
+        let fade = 1.0 - offset as f32 / 12.0;
+        let shade = (40.0 + 50.0 * fade) as u8;
+        let smoke_char = if offset % 2 == 0 { '(' } else { ')' };
+        frame.render_widget(
+            Paragraph::new(smoke_char.to_string()).style(Style::default().fg(Color::Rgb(shade, shade, shade + 5))),
+            Rect::new(area.x + x, area.y + smoke_y, 1, 1),
+        );
+    }
+}
+
 /// Render realistic waving banner with cloth physics
 fn render_banner(frame: &mut Frame, area: Rect, x: u16, y: u16, frame_index: usize, color: (u8, u8, u8)) {
     if x >= area.width || y >= area.height { return; }
@@ -321,24 +377,19 @@ fn render_window(frame: &mut Frame, area: Rect, x: u16, y: u16, frame_index: usi
     }
 }
 
-/// Render castle silhouette with enhanced details
-fn render_castle(frame: &mut Frame, area: Rect, frame_index: usize, lightning_flash: bool) {
+/// Build the static castle stonework: tower bodies, walls and crenellations
+fn build_castle_stone(area: Rect, lightning_flash: bool) -> CellLayer {
     let towers = get_towers(area.width, area.height);
+    let mut layer = CellLayer::default();
 
-    for (tower_idx, tower) in towers.iter().enumerate() {
+    for tower in &towers {
         let tower_top = area.height.saturating_sub(tower.height);
 
         // Tower body with enhanced stone
         for y in tower_top..area.height {
             for x in tower.x..tower.x + tower.width {
                 if x < area.width {
-                    let ch = stone_char(x, y, frame_index);
-                    let color = stone_color(x, y, lightning_flash);
-
-                    frame.render_widget(
-                        Paragraph::new(ch.to_string()).style(Style::default().fg(color)),
-                        Rect::new(area.x + x, area.y + y, 1, 1),
-                    );
+                    layer.put(x, y, stone_char(x, y), stone_color(x, y, lightning_flash));
                 }
             }
         }
@@ -349,13 +400,48 @@ fn render_castle(frame: &mut Frame, area: Rect, frame_index: usize, lightning_fl
                 let cx = tower.x + i * 2;
                 if cx < area.width {
                     // Merlon (raised part)
-                    frame.render_widget(
-                        Paragraph::new("▀").style(Style::default().fg(stone_color(cx, tower_top - 1, lightning_flash))),
-                        Rect::new(area.x + cx, area.y + tower_top - 1, 1, 1),
-                    );
+                    layer.put(cx, tower_top - 1, '▀', stone_color(cx, tower_top - 1, lightning_flash));
                 }
             }
         }
+    }
+
+    // Connecting wall between towers
+    let wall_height = area.height / 4;
+    let wall_top = area.height - wall_height;
+    for x in 0..area.width {
+        for y in wall_top..area.height {
+            let in_tower = towers.iter().any(|t| x >= t.x && x < t.x + t.width);
+            if !in_tower {
+                layer.put(x, y, stone_char(x, y), stone_color(x, y, lightning_flash));
+            }
+        }
+    }
+
+    // Wall crenellations
+    for x in 0..area.width {
+        let in_tower = towers.iter().any(|t| x >= t.x && x < t.x + t.width);
+        if !in_tower && x % 3 == 0 && wall_top > 0 {
+            layer.put(x, wall_top - 1, '▀', stone_color(x, wall_top - 1, lightning_flash));
+        }
+    }
+
+    layer
+}
+
+/// Render castle silhouette with enhanced details
+fn render_castle(frame: &mut Frame, area: Rect, frame_index: usize, lightning_flash: bool) {
+    // Static stonework from the cache, rebuilt only on resize or lightning
+    CASTLE_STONE.with(|cache| {
+        cache
+            .borrow_mut()
+            .get_or_build(area, lightning_flash as u64, || build_castle_stone(area, lightning_flash))
+            .draw(frame, area);
+    });
+
+    let towers = get_towers(area.width, area.height);
+    for (tower_idx, tower) in towers.iter().enumerate() {
+        let tower_top = area.height.saturating_sub(tower.height);
 
         // Waving banner
         if tower.has_flag && tower_top > 4 {
@@ -378,35 +464,6 @@ fn render_castle(frame: &mut Frame, area: Rect, frame_index: usize, lightning_fl
             }
         }
     }
-
-    // Connecting wall between towers
-    let wall_height = area.height / 4;
-    let wall_top = area.height - wall_height;
-    for x in 0..area.width {
-        for y in wall_top..area.height {
-            let in_tower = towers.iter().any(|t| x >= t.x && x < t.x + t.width);
-            if !in_tower {
-                let ch = stone_char(x, y, frame_index);
-                let color = stone_color(x, y, lightning_flash);
-
-                frame.render_widget(
-                    Paragraph::new(ch.to_string()).style(Style::default().fg(color)),
-                    Rect::new(area.x + x, area.y + y, 1, 1),
-                );
-            }
-        }
-    }
-
-    // Wall crenellations
-    for x in 0..area.width {
-        let in_tower = towers.iter().any(|t| x >= t.x && x < t.x + t.width);
-        if !in_tower && x % 3 == 0 && wall_top > 0 {
-            frame.render_widget(
-                Paragraph::new("▀").style(Style::default().fg(stone_color(x, wall_top - 1, lightning_flash))),
-                Rect::new(area.x + x, area.y + wall_top - 1, 1, 1),
-            );
-        }
-    }
 }
 
 /// Render patrolling guards on battlements
@@ -734,7 +791,7 @@ fn render_sky(frame: &mut Frame, area: Rect, frame_index: usize, lightning_flash
             };
 
             frame.render_widget(
-                Paragraph::new(" ").style(Style::default().bg(Color::Rgb(r, g, b))),
+                Paragraph::new(" ").style(Style::default().bg(background::color(Color::Rgb(r, g, b)))),
                 Rect::new(area.x + x, area.y + y, 1, 1),
             );
         }
@@ -759,7 +816,7 @@ fn render_sky(frame: &mut Frame, area: Rect, frame_index: usize, lightning_flash
     // Stars - different sizes and twinkle patterns
     for i in 0..40 {
         let x = (simple_hash(i, 600) % area.width as usize) as u16;
-        let y = (simple_hash(i, 700) % (sky_height as usize - 2)) as u16;
+        let y = (simple_hash(i, 700) % (sky_height as usize).saturating_sub(2).max(1)) as u16;
 
         // Twinkle with different rates
         let twinkle_rate = 20 + simple_hash(i, 750) % 20;
@@ -847,15 +904,18 @@ fn render_sky(frame: &mut Frame, area: Rect, frame_index: usize, lightning_flash
 }
 
 /// Check if lightning should flash this frame
-fn is_lightning_flash(frame_index: usize) -> bool {
+fn is_lightning_flash(frame_index: usize, params: &RenderParams) -> bool {
+    if params.reduced_motion() {
+        return false;
+    }
     // Lightning every ~200-300 frames, lasting 3-5 frames
     let lightning_cycle = frame_index % 250;
     lightning_cycle < 4 || (lightning_cycle > 2 && lightning_cycle < 6 && frame_index % 500 < 250)
 }
 
 /// Render distant lightning bolt
-fn render_lightning(frame: &mut Frame, area: Rect, frame_index: usize) {
-    if !is_lightning_flash(frame_index) { return; }
+fn render_lightning(frame: &mut Frame, area: Rect, frame_index: usize, params: &RenderParams) {
+    if !is_lightning_flash(frame_index, params) { return; }
 
     // Lightning bolt position varies
     let bolt_x = (simple_hash(frame_index / 250, 1100) % (area.width as usize / 2)) as u16 + area.width / 4;
@@ -878,9 +938,9 @@ fn render_lightning(frame: &mut Frame, area: Rect, frame_index: usize) {
     }
 }
 
-pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
+pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize, params: &RenderParams) {
     // Check for lightning flash (affects entire scene lighting)
-    let lightning_flash = is_lightning_flash(frame_index);
+    let lightning_flash = is_lightning_flash(frame_index, params);
 
     // Dark ground base
     let ground_color = if lightning_flash {
@@ -888,71 +948,131 @@ pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
     } else {
         Color::Rgb(12, 10, 8)
     };
-    let bg = Block::default().style(Style::default().bg(ground_color));
-    frame.render_widget(bg, area);
+    background::fill(frame, area, ground_color);
 
     // Render night sky with gradient and stars
     render_sky(frame, area, frame_index, lightning_flash);
 
+    // The castle layout needs room; tiny areas (menu preview strip) get the sky only
+    if area.width < SCENE_MIN_WIDTH || area.height < SCENE_MIN_HEIGHT {
+        return;
+    }
+
+    let detail = params.detail;
+
     // Render lightning bolt (when flashing)
-    render_lightning(frame, area, frame_index);
+    if detail >= 1 {
+        render_lightning(frame, area, frame_index, params);
+    }
 
     // Render distant army on horizon
-    render_distant_army(frame, area, frame_index);
+    if detail >= 2 {
+        render_distant_army(frame, area, frame_index);
+    }
 
     // Render trebuchet
-    render_trebuchet(frame, area, frame_index);
+    if detail >= 3 {
+        render_trebuchet(frame, area, frame_index);
+    }
 
     // Render ground fog
-    render_ground_fog(frame, area, frame_index);
+    if detail >= 1 {
+        render_ground_fog(frame, area, frame_index);
+    }
 
     // Render castle with enhanced stone and windows
     render_castle(frame, area, frame_index, lightning_flash);
 
     // Render smoke from chimneys
-    render_smoke(frame, area, frame_index);
+    if detail >= 1 {
+        render_smoke(frame, area, frame_index);
+    }
 
-    // Render patrolling guards
-    render_guards(frame, area, frame_index);
+    if detail >= 2 {
+        // Render patrolling guards
+        render_guards(frame, area, frame_index);
 
-    // Render dragon silhouette
-    render_dragon(frame, area, frame_index);
+        // Render bats and owls
+        render_flying_creatures(frame, area, frame_index);
+    }
 
-    // Render bats and owls
-    render_flying_creatures(frame, area, frame_index);
+    // Render dragon silhouette
+    if detail >= 3 {
+        render_dragon(frame, area, frame_index);
+    }
 
-    // Render torches on tower walls
+    // Torches on the tower walls, then along the curtain wall
     let towers = get_towers(area.width, area.height);
+    let mut torches = Vec::new();
     for (i, tower) in towers.iter().enumerate() {
         let torch_x = tower.x + tower.width / 2;
         let torch_y = area.height.saturating_sub(tower.height) + tower.height / 2;
 
         if torch_x < area.width && torch_y < area.height && torch_y > 0 {
-            render_torch(frame, area, torch_x, torch_y - 1, i, frame_index);
+            torches.push((torch_x, torch_y - 1, i));
         }
 
         // Additional torch on opposite side of tower
         let torch_x2 = tower.x + tower.width - 2;
         if torch_x2 < area.width && torch_y < area.height && torch_y > 0 && tower.width > 6 {
-            render_torch(frame, area, torch_x2, torch_y + 2, i + 20, frame_index);
+            torches.push((torch_x2, torch_y + 2, i + 20));
         }
     }
 
     // Wall torches with more variety
-    let wall_torches = [
-        (area.width / 5, area.height - area.height / 4 - 2),
-        (area.width * 2 / 5, area.height - area.height / 4 - 2),
-        (area.width * 3 / 5, area.height - area.height / 4 - 2),
-        (area.width * 4 / 5, area.height - area.height / 4 - 2),
-    ];
+    if detail >= 1 {
+        let wall_torches = [
+            (area.width / 5, area.height - area.height / 4 - 2),
+            (area.width * 2 / 5, area.height - area.height / 4 - 2),
+            (area.width * 3 / 5, area.height - area.height / 4 - 2),
+            (area.width * 4 / 5, area.height - area.height / 4 - 2),
+        ];
 
-    for (i, (tx, ty)) in wall_torches.iter().enumerate() {
-        if *tx < area.width && *ty < area.height {
-            // Check not inside a tower
-            let in_tower = towers.iter().any(|t| *tx >= t.x && *tx < t.x + t.width);
-            if !in_tower {
-                render_torch(frame, area, *tx, *ty, i + 100, frame_index);
+        for (i, (tx, ty)) in wall_torches.iter().enumerate() {
+            if *tx < area.width && *ty < area.height {
+                // Check not inside a tower
+                let in_tower = towers.iter().any(|t| *tx >= t.x && *tx < t.x + t.width);
+                if !in_tower {
+                    torches.push((*tx, *ty, i + 100));
+                }
             }
         }
     }
+
+    // Diegetic progress: the leftmost torches have burned out
+    let spent = params.progress.map_or(0, |p| (p * torches.len() as f32) as usize);
+    let mut left_to_right: Vec<u16> = torches.iter().map(|&(x, _, _)| x).collect();
+    left_to_right.sort_unstable();
+    let lit_from = left_to_right.get(spent).copied().unwrap_or(u16::MAX);
+    for (x, y, torch_id) in torches {
+        if x < lit_from {
+            render_spent_torch(frame, area, x, y, torch_id, frame_index);
+        } else {
+            render_torch(frame, area, x, y, torch_id, frame_index);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::{backend::TestBackend, Terminal};
+
+    /// Burnt-out torch heads on screen
+    fn spent_torches(progress: Option<f32>) -> usize {
+        let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+        let params = RenderParams { progress, ..RenderParams::default() };
+        terminal.draw(|frame| render_background(frame, frame.area(), 0, &params)).unwrap();
+
+        let buffer = terminal.backend().buffer();
+        buffer.content.iter().filter(|cell| cell.symbol() == "▪" && cell.fg == Color::Rgb(40, 32, 28)).count()
+    }
+
+    #[test]
+    fn test_torches_go_out_with_progress() {
+        let all = spent_torches(Some(1.0));
+        assert!(all >= 2);
+        assert_eq!(spent_torches(None), 0);
+        assert_eq!(spent_torches(Some(0.5)), all / 2);
+    }
 }
@@ -1,5 +1,8 @@
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Paragraph};
+use ratatui::widgets::Paragraph;
+
+use super::RenderParams;
+use crate::animation::background;
 
 /// Minimal - Subtle gradient pulse, zen-like dots, breathing animation, calm and sparse
 
@@ -32,7 +35,7 @@ fn breathing_pulse(frame_index: usize) -> f32 {
 }
 
 /// Calculate gradient intensity based on position
-fn gradient_intensity(x: u16, y: u16, width: u16, height: u16, frame_index: usize) -> f32 {
+fn gradient_intensity(x: u16, y: u16, width: u16, height: u16, frame_index: usize, aspect: f32) -> f32 {
     let t = frame_index as f32 * 0.008;
 
     // Center point with slow drift
@@ -41,7 +44,7 @@ fn gradient_intensity(x: u16, y: u16, width: u16, height: u16, frame_index: usiz
 
     // Distance from center, normalized
     let dx = (x as f32 - cx) / width as f32;
-    let dy = (y as f32 - cy) / height as f32 * 2.0; // Adjust for aspect ratio
+    let dy = (y as f32 - cy) / height as f32 * aspect;
     let dist = (dx * dx + dy * dy).sqrt();
 
     // Soft radial gradient
@@ -116,15 +119,14 @@ fn minimal_color(intensity: f32, variant: usize) -> Color {
     }
 }
 
-pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
+pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize, params: &RenderParams) {
     // Calm, dark background with subtle warmth
-    let bg = Block::default().style(Style::default().bg(Color::Rgb(12, 12, 15)));
-    frame.render_widget(bg, area);
+    background::fill(frame, area, Color::Rgb(12, 12, 15));
 
     // Layer 1: Subtle gradient pulse from center
     for y in 0..area.height {
         for x in 0..area.width {
-            let gradient = gradient_intensity(x, y, area.width, area.height, frame_index);
+            let gradient = gradient_intensity(x, y, area.width, area.height, frame_index, params.aspect);
 
             if gradient > 0.05 {
                 let wave = subtle_wave(x, y, area.width, frame_index);
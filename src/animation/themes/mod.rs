@@ -23,8 +23,109 @@ pub mod github;
 pub mod medieval;
 pub mod synthwave;
 
+use chrono::{Datelike, NaiveDate};
 use ratatui::prelude::*;
+use serde::{Deserialize, Serialize};
+
 use crate::animation::digit_fonts::DigitFont;
+use crate::animation::{detail, motion};
+use crate::animation::sequence::Reveal;
+use crate::scaling::DEFAULT_CELL_ASPECT;
+
+/// Most frames a theme state steps through at once (after the screen wasn't drawn
+/// for a while); beyond this the particles just pick up where they were
+const MAX_CATCH_UP: usize = 600;
+
+/// Particles a theme keeps between frames. They move on from where they were and
+/// are rescaled on resize, instead of being re-derived from the area size each frame.
+pub enum ThemeState {
+    /// Themes that draw purely from the frame index
+    None,
+    Matrix(matrix::State),
+    RainDrops(rain::State),
+    Bubbles(bubbles::State),
+    Snowfall(snowfall::State),
+}
+
+impl ThemeState {
+    /// State for `theme` as it would look at `frame_index`
+    pub fn new(theme: ThemeType, area: Rect, frame_index: usize) -> Self {
+        match theme {
+            ThemeType::Matrix => ThemeState::Matrix(matrix::State::new(area, frame_index)),
+            ThemeType::RainDrops => ThemeState::RainDrops(rain::State::new(area, frame_index)),
+            ThemeType::Bubbles => ThemeState::Bubbles(bubbles::State::new(area, frame_index)),
+            ThemeType::Snowfall => ThemeState::Snowfall(snowfall::State::new(area, frame_index)),
+            _ => ThemeState::None,
+        }
+    }
+
+    /// Move on to `frame_index`, rescaling first if the area changed size
+    pub fn update(&mut self, area: Rect, frame_index: usize) {
+        match self {
+            ThemeState::None => {}
+            ThemeState::Matrix(state) => state.update(area, frame_index),
+            ThemeState::RainDrops(state) => state.update(area, frame_index),
+            ThemeState::Bubbles(state) => state.update(area, frame_index),
+            ThemeState::Snowfall(state) => state.update(area, frame_index),
+        }
+    }
+}
+
+/// What a theme draws with besides its area, frame and particles: settings and
+/// session state handed down from the engine on every frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RenderParams {
+    /// Height of a terminal cell over its width (`ScalingContext::cell_aspect`);
+    /// vertical distances are stretched by it so circles stay round
+    pub aspect: f32,
+    /// Motion scale, 0.0 (still) to 1.0 (see `motion`)
+    pub motion: f32,
+    /// Scene detail, 0 (bare) to `detail::FULL` (see `detail`)
+    pub detail: u8,
+    /// How far the running session has come, 0.0 to 1.0, with `diegetic_progress`
+    /// on: the Landscape river rises, the Medieval torches go out one by one and
+    /// the GitHub contribution grid fills in. None draws them as usual.
+    pub progress: Option<f32>,
+}
+
+impl Default for RenderParams {
+    fn default() -> Self {
+        Self {
+            aspect: DEFAULT_CELL_ASPECT,
+            motion: motion::FULL,
+            detail: detail::FULL,
+            progress: None,
+        }
+    }
+}
+
+impl RenderParams {
+    /// Whether motion is reduced at all, which rules out strobes and flashes
+    pub fn reduced_motion(&self) -> bool {
+        self.motion < motion::FULL
+    }
+}
+
+/// Mood tags used to constrain auto-rotation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Mood {
+    Calm,
+    Energetic,
+    Dark,
+    Nature,
+    Retro,
+}
+
+/// What auto-rotation may pick: a mood and/or an explicit theme list
+#[derive(Debug, Clone, Default)]
+pub struct ThemeFilter {
+    pub mood: Option<Mood>,
+    /// Allowed themes (e.g. from the time-of-day schedule); empty means any
+    pub themes: Vec<ThemeType>,
+    /// Never picked, whatever else allows them (themes that crashed this run)
+    pub exclude: Vec<ThemeType>,
+}
 
 /// All available animation themes
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -86,22 +187,36 @@ impl ThemeType {
         ]
     }
 
-    /// Pick a random theme (different from current)
-    pub fn random_except(current: ThemeType) -> ThemeType {
+    /// Pick a random theme (different from current), restricted to a mood if given.
+    /// Falls back to all themes when no other theme matches the mood.
+    pub fn random_except(current: ThemeType, filter: &ThemeFilter) -> ThemeType {
         use std::time::{SystemTime, UNIX_EPOCH};
         let seed = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap_or_default()
             .as_nanos() as usize;
 
-        let themes = Self::all();
-        let mut idx = seed % themes.len();
+        let candidates: Vec<ThemeType> = Self::all()
+            .iter()
+            .copied()
+            .filter(|&t| t != current && !filter.exclude.contains(&t))
+            .filter(|t| filter.themes.is_empty() || filter.themes.contains(t))
+            .filter(|t| filter.mood.is_none_or(|m| t.moods().contains(&m)))
+            .collect();
 
-        // Make sure we get a different theme
-        while themes[idx] == current {
-            idx = (idx + 1) % themes.len();
+        // Relax the mood first, then the theme list, before giving up
+        if candidates.is_empty() {
+            return if filter.mood.is_some() {
+                let relaxed = ThemeFilter { mood: None, ..filter.clone() };
+                Self::random_except(current, &relaxed)
+            } else if !filter.themes.is_empty() {
+                let relaxed = ThemeFilter { exclude: filter.exclude.clone(), ..ThemeFilter::default() };
+                Self::random_except(current, &relaxed)
+            } else {
+                current
+            };
         }
-        themes[idx]
+        candidates[seed % candidates.len()]
     }
 
     /// Pick a random theme
@@ -116,6 +231,14 @@ impl ThemeType {
         themes[seed % themes.len()]
     }
 
+    /// Theme featured on the menu for a given day. Steps through the list with a
+    /// stride coprime to its length, so every theme gets a turn and neighbours differ.
+    pub fn of_the_day(date: NaiveDate) -> ThemeType {
+        let themes = Self::all();
+        let day = date.num_days_from_ce().unsigned_abs() as usize;
+        themes[day * 7 % themes.len()]
+    }
+
     /// Theme display name
     pub fn name(&self) -> &'static str {
         match self {
@@ -146,33 +269,145 @@ impl ThemeType {
         }
     }
 
-    /// Render the animation background for this theme
-    pub fn render_background(&self, frame: &mut Frame, area: Rect, frame_index: usize) {
+    /// Command-friendly identifier, e.g. "matrix-rain"
+    pub fn slug(&self) -> String {
+        self.name().to_lowercase().replace(' ', "-")
+    }
+
+    /// Look up a theme by slug or slug prefix ("matrix", "rain-drops")
+    pub fn from_name(query: &str) -> Option<ThemeType> {
+        let query = query.trim().to_lowercase().replace(' ', "-");
+        if query.is_empty() {
+            return None;
+        }
+        let themes = Self::all();
+        themes
+            .iter()
+            .find(|t| t.slug() == query)
+            .or_else(|| themes.iter().find(|t| t.slug().starts_with(&query)))
+            .copied()
+    }
+
+    /// Mood tags for this theme
+    pub fn moods(&self) -> &'static [Mood] {
         match self {
-            ThemeType::Matrix => matrix::render_background(frame, area, frame_index),
-            ThemeType::Fire => fire::render_background(frame, area, frame_index),
-            ThemeType::Starfield => starfield::render_background(frame, area, frame_index),
-            ThemeType::Plasma => plasma::render_background(frame, area, frame_index),
-            ThemeType::RainDrops => rain::render_background(frame, area, frame_index),
-            ThemeType::RadioWaves => waves::render_background(frame, area, frame_index),
-            ThemeType::SpinningShapes => shapes::render_background(frame, area, frame_index),
-            ThemeType::Fireworks => fireworks::render_background(frame, area, frame_index),
-            ThemeType::Aurora => aurora::render_background(frame, area, frame_index),
-            ThemeType::Ocean => ocean::render_background(frame, area, frame_index),
-            ThemeType::DNA => dna::render_background(frame, area, frame_index),
-            ThemeType::Bubbles => bubbles::render_background(frame, area, frame_index),
-            ThemeType::Electric => electric::render_background(frame, area, frame_index),
-            ThemeType::Snowfall => snowfall::render_background(frame, area, frame_index),
-            ThemeType::Nature => nature::render_background(frame, area, frame_index),
-            ThemeType::Geometric => geometric::render_background(frame, area, frame_index),
-            ThemeType::Glitch => glitch::render_background(frame, area, frame_index),
-            ThemeType::Minimal => minimal::render_background(frame, area, frame_index),
-            ThemeType::Seasonal => seasonal::render_background(frame, area, frame_index),
-            ThemeType::Landscape => landscape::render_background(frame, area, frame_index),
-            ThemeType::Claude => claude::render_background(frame, area, frame_index),
-            ThemeType::GitHub => github::render_background(frame, area, frame_index),
-            ThemeType::Medieval => medieval::render_background(frame, area, frame_index),
-            ThemeType::Synthwave => synthwave::render_background(frame, area, frame_index),
+            ThemeType::Matrix => &[Mood::Dark, Mood::Retro, Mood::Energetic],
+            ThemeType::Fire => &[Mood::Energetic],
+            ThemeType::Starfield => &[Mood::Calm, Mood::Dark],
+            ThemeType::Plasma => &[Mood::Energetic, Mood::Retro],
+            ThemeType::RainDrops => &[Mood::Calm, Mood::Nature],
+            ThemeType::RadioWaves => &[Mood::Energetic, Mood::Retro],
+            ThemeType::SpinningShapes => &[Mood::Energetic],
+            ThemeType::Fireworks => &[Mood::Energetic],
+            ThemeType::Aurora => &[Mood::Calm, Mood::Nature, Mood::Dark],
+            ThemeType::Ocean => &[Mood::Calm, Mood::Nature],
+            ThemeType::DNA => &[Mood::Calm],
+            ThemeType::Bubbles => &[Mood::Calm],
+            ThemeType::Electric => &[Mood::Energetic, Mood::Dark],
+            ThemeType::Snowfall => &[Mood::Calm, Mood::Nature],
+            ThemeType::Nature => &[Mood::Calm, Mood::Nature],
+            ThemeType::Geometric => &[Mood::Retro],
+            ThemeType::Glitch => &[Mood::Energetic, Mood::Dark, Mood::Retro],
+            ThemeType::Minimal => &[Mood::Calm],
+            ThemeType::Seasonal => &[Mood::Calm, Mood::Nature],
+            ThemeType::Landscape => &[Mood::Calm, Mood::Nature],
+            ThemeType::Claude => &[Mood::Calm],
+            ThemeType::GitHub => &[Mood::Dark, Mood::Retro],
+            ThemeType::Medieval => &[Mood::Dark],
+            ThemeType::Synthwave => &[Mood::Retro, Mood::Energetic, Mood::Dark],
+        }
+    }
+
+    /// Stand-ins for this theme's rare glyphs on terminals that can't show them:
+    /// (glyph, extended, basic), checked before the general fallbacks
+    pub fn glyph_fallbacks(&self) -> &'static [(char, char, char)] {
+        match self {
+            // Katakana rain becomes more of the digits and symbols it falls with
+            ThemeType::Matrix => &[
+                ('ア', '0', '0'), ('イ', '1', '1'), ('ウ', '7', '7'), ('エ', 'Z', 'Z'), ('オ', '#', '#'),
+                ('カ', '4', '4'), ('キ', '$', '$'), ('ク', '%', '%'), ('ケ', 'X', 'X'), ('コ', '=', '='),
+            ],
+            ThemeType::Nature => &[('🍂', '♣', '&'), ('🍃', '♠', '%')],
+            ThemeType::Electric => &[('⚡', '╱', '/')],
+            ThemeType::Starfield => &[('✦', '+', '+'), ('✧', '+', '+')],
+            ThemeType::Snowfall => &[('❄', '☼', '*')],
+            ThemeType::Claude => &[('⬡', '○', 'o')],
+            _ => &[],
+        }
+    }
+
+    /// Whether the theme shows the session's progress in its scene (`RenderParams::progress`)
+    pub fn shows_progress(&self) -> bool {
+        matches!(self, ThemeType::Landscape | ThemeType::Medieval | ThemeType::GitHub)
+    }
+
+    /// Render the animation background for this theme from scratch (previews);
+    /// the live background keeps its state in `AnimationEngine::render_background`
+    pub fn render_background(&self, frame: &mut Frame, area: Rect, frame_index: usize, params: &RenderParams) {
+        let state = ThemeState::new(*self, area, frame_index);
+        self.render_with_state(frame, area, frame_index, &state, params);
+    }
+
+    /// Render the animation background with state kept from earlier frames
+    pub fn render_with_state(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        frame_index: usize,
+        state: &ThemeState,
+        params: &RenderParams,
+    ) {
+        match (self, state) {
+            (ThemeType::Matrix, ThemeState::Matrix(s)) => matrix::render_background(frame, area, frame_index, s),
+            (ThemeType::RainDrops, ThemeState::RainDrops(s)) => rain::render_background(frame, area, frame_index, s),
+            (ThemeType::Bubbles, ThemeState::Bubbles(s)) => bubbles::render_background(frame, area, frame_index, s),
+            (ThemeType::Snowfall, ThemeState::Snowfall(s)) => snowfall::render_background(frame, area, frame_index, s),
+            // State built for another theme: start this one fresh
+            (ThemeType::Matrix | ThemeType::RainDrops | ThemeType::Bubbles | ThemeType::Snowfall, _) => {
+                self.render_background(frame, area, frame_index, params)
+            }
+            (ThemeType::Fire, _) => fire::render_background(frame, area, frame_index),
+            (ThemeType::Starfield, _) => starfield::render_background(frame, area, frame_index),
+            (ThemeType::Plasma, _) => plasma::render_background(frame, area, frame_index),
+            (ThemeType::RadioWaves, _) => waves::render_background(frame, area, frame_index, params),
+            (ThemeType::SpinningShapes, _) => shapes::render_background(frame, area, frame_index),
+            (ThemeType::Fireworks, _) => fireworks::render_background(frame, area, frame_index),
+            (ThemeType::Aurora, _) => aurora::render_background(frame, area, frame_index),
+            (ThemeType::Ocean, _) => ocean::render_background(frame, area, frame_index),
+            (ThemeType::DNA, _) => dna::render_background(frame, area, frame_index),
+            (ThemeType::Electric, _) => electric::render_background(frame, area, frame_index, params),
+            (ThemeType::Nature, _) => nature::render_background(frame, area, frame_index),
+            (ThemeType::Geometric, _) => geometric::render_background(frame, area, frame_index, params),
+            (ThemeType::Glitch, _) => glitch::render_background(frame, area, frame_index, params),
+            (ThemeType::Minimal, _) => minimal::render_background(frame, area, frame_index, params),
+            (ThemeType::Seasonal, _) => seasonal::render_background(frame, area, frame_index, params),
+            (ThemeType::Landscape, _) => landscape::render_background(frame, area, frame_index, params),
+            (ThemeType::Claude, _) => claude::render_background(frame, area, frame_index, params),
+            (ThemeType::GitHub, _) => github::render_background(frame, area, frame_index, params),
+            (ThemeType::Medieval, _) => medieval::render_background(frame, area, frame_index, params),
+            (ThemeType::Synthwave, _) => synthwave::render_background(frame, area, frame_index, params),
+        }
+    }
+
+    /// How the theme comes in when it is switched to, if it has an intro
+    pub fn intro(&self) -> Option<Reveal> {
+        match self {
+            ThemeType::Medieval | ThemeType::Landscape | ThemeType::Fire => Some(Reveal::Rise),
+            ThemeType::Matrix | ThemeType::RainDrops | ThemeType::Snowfall => Some(Reveal::Cascade),
+            ThemeType::Starfield | ThemeType::Fireworks => Some(Reveal::Iris),
+            ThemeType::Glitch => Some(Reveal::Dissolve),
+            _ => None,
+        }
+    }
+
+    /// How the theme goes away when another replaces it, if it has an outro
+    /// (the reveal played backwards)
+    pub fn outro(&self) -> Option<Reveal> {
+        match self {
+            ThemeType::Matrix | ThemeType::Glitch => Some(Reveal::Dissolve),
+            ThemeType::Medieval => Some(Reveal::Rise),
+            ThemeType::Starfield => Some(Reveal::Iris),
+            _ => None,
         }
     }
 
@@ -284,3 +519,66 @@ impl ThemeType {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_random_except_respects_mood() {
+        for _ in 0..50 {
+            let filter = ThemeFilter { mood: Some(Mood::Calm), ..ThemeFilter::default() };
+            let theme = ThemeType::random_except(ThemeType::Minimal, &filter);
+            assert_ne!(theme, ThemeType::Minimal);
+            assert!(theme.moods().contains(&Mood::Calm), "{:?} is not calm", theme);
+        }
+    }
+
+    #[test]
+    fn test_random_except_respects_theme_list() {
+        let filter = ThemeFilter {
+            mood: None,
+            themes: vec![ThemeType::Starfield, ThemeType::Medieval],
+            exclude: vec![],
+        };
+        for _ in 0..20 {
+            assert_eq!(ThemeType::random_except(ThemeType::Medieval, &filter), ThemeType::Starfield);
+        }
+    }
+
+    #[test]
+    fn test_every_theme_has_a_mood() {
+        for theme in ThemeType::all() {
+            assert!(!theme.moods().is_empty(), "{:?} has no mood tags", theme);
+        }
+    }
+
+    #[test]
+    fn test_theme_of_the_day_visits_every_theme() {
+        let start = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let featured: Vec<ThemeType> = (0..ThemeType::all().len() as u64)
+            .map(|i| ThemeType::of_the_day(start + chrono::Days::new(i)))
+            .collect();
+        for theme in ThemeType::all() {
+            assert!(featured.contains(theme), "{:?} is never featured", theme);
+        }
+        assert_ne!(featured[0], featured[1]);
+    }
+
+    #[test]
+    fn test_every_theme_renders_in_a_preview_strip() {
+        use ratatui::{backend::TestBackend, Terminal};
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 12)).unwrap();
+        for theme in ThemeType::all() {
+            for (width, height) in [(10, 3), (26, 3), (30, 3), (34, 4)] {
+                for frame_index in [0, 1, 37, 500] {
+                    let strip = Rect::new(5, 6, width, height);
+                    terminal
+                        .draw(|frame| theme.render_background(frame, strip, frame_index, &RenderParams::default()))
+                        .unwrap();
+                }
+            }
+        }
+    }
+}
@@ -1,5 +1,7 @@
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Paragraph};
+use ratatui::widgets::Paragraph;
+
+use crate::animation::background;
 
 /// Nature - Falling leaves, gentle forest breeze, tree silhouettes, peaceful green palette
 
@@ -155,8 +157,7 @@ fn draw_grass(frame: &mut Frame, area: Rect, frame_index: usize) {
 
 pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
     // Forest green gradient background
-    let bg = Block::default().style(Style::default().bg(Color::Rgb(15, 30, 20)));
-    frame.render_widget(bg, area);
+    background::fill(frame, area, Color::Rgb(15, 30, 20));
 
     // Draw sky gradient (lighter at horizon)
     for y in 0..area.height.saturating_sub(3) {
@@ -1,5 +1,7 @@
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Paragraph};
+use ratatui::widgets::Paragraph;
+
+use crate::animation::background;
 
 /// Ocean waves - rolling waves with foam and depth
 
@@ -53,8 +55,7 @@ fn is_foam(x: u16, y: u16, width: u16, height: u16, frame_index: usize, wave_y:
 
 pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
     // Deep ocean background (gradient would be nice but we'll simulate)
-    let bg = Block::default().style(Style::default().bg(Color::Rgb(0, 20, 40)));
-    frame.render_widget(bg, area);
+    background::fill(frame, area, Color::Rgb(0, 20, 40));
     
     let water_start = area.height / 4; // Horizon line
     
@@ -67,7 +68,7 @@ pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
         
         for x in 0..area.width {
             frame.render_widget(
-                Paragraph::new(" ").style(Style::default().bg(Color::Rgb(r, g, b))),
+                Paragraph::new(" ").style(Style::default().bg(background::color(Color::Rgb(r, g, b)))),
                 Rect::new(area.x + x, area.y + y, 1, 1),
             );
         }
@@ -1,11 +1,13 @@
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Paragraph};
+use ratatui::widgets::Paragraph;
+
+use crate::animation::background;
+use crate::animation::math::fast_sin;
 
 /// Plasma effect using sine wave interference patterns
 pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
     // Dark purple background
-    let bg = Block::default().style(Style::default().bg(Color::Rgb(10, 0, 20)));
-    frame.render_widget(bg, area);
+    background::fill(frame, area, Color::Rgb(10, 0, 20));
 
     let t = frame_index as f32 * 0.05; // Time factor
 
@@ -34,26 +36,6 @@ pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
     }
 }
 
-/// Fast approximation of sine
-fn fast_sin(x: f32) -> f32 {
-    // Normalize to 0..2π range then approximate
-    let x = x % (2.0 * std::f32::consts::PI);
-    let x = if x < 0.0 {
-        x + 2.0 * std::f32::consts::PI
-    } else {
-        x
-    };
-
-    // Parabola approximation
-    if x < std::f32::consts::PI {
-        let t = x / std::f32::consts::PI;
-        4.0 * t * (1.0 - t) - 1.0 + 1.0 // Shift to -1..1
-    } else {
-        let t = (x - std::f32::consts::PI) / std::f32::consts::PI;
-        -(4.0 * t * (1.0 - t) - 1.0 + 1.0)
-    }
-}
-
 /// Get color and character based on plasma value
 fn plasma_color_char(value: f32, frame_index: usize) -> (Color, char) {
     // Cycle through rainbow based on value + time offset
@@ -1,5 +1,9 @@
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Paragraph};
+use ratatui::widgets::Paragraph;
+
+use super::MAX_CATCH_UP;
+use crate::animation::background;
+use crate::animation::math::rescale;
 
 /// Ripple structure for expanding circles
 struct Ripple {
@@ -15,9 +19,10 @@ impl Ripple {
         let h2 = simple_hash(seed, 2);
         let h3 = simple_hash(seed, 3);
 
+        // Placed in thousandths of the area so ripples keep their spot across resizes
         Self {
-            x: (h1 % width as usize) as u16,
-            y: (h2 % height as usize) as u16,
+            x: ((h1 % 1000) * width as usize / 1000) as u16,
+            y: ((h2 % 1000) * height as usize / 1000) as u16,
             birth_frame: h3 % 100,
             max_radius: ((h3 % 10) + 5) as u16,
         }
@@ -68,6 +73,8 @@ struct RainDrop {
     x: u16,
     start_y: i32,
     speed: u8,
+    /// Current row (may be above or below the area), moved on every frame
+    y: i32,
 }
 
 impl RainDrop {
@@ -76,28 +83,86 @@ impl RainDrop {
         let h2 = simple_hash(seed, 2);
         let h3 = simple_hash(seed, 3);
 
+        let start_y = -((h2 % 30) as i32);
         Self {
             x: (h1 % width as usize) as u16,
-            start_y: -((h2 % 30) as i32),
+            start_y,
             speed: ((h3 % 3) + 2) as u8,
+            y: start_y,
         }
     }
 
-    fn y_at(&self, frame_index: usize, height: u16) -> Option<u16> {
+    /// Row the drop would be on at `frame_index` if it had been falling all along
+    fn fall_to(&mut self, frame_index: usize, height: u16) {
         let y = self.start_y + ((frame_index / self.speed as usize) as i32);
-        let y = y % ((height as i32) + 10);
-        if y >= 0 && y < height as i32 {
-            Some(y as u16)
+        self.y = y % ((height as i32) + 10);
+    }
+
+    /// Advance one frame (drops move every `speed` frames), wrapping at the bottom
+    fn step(&mut self, frame_index: usize, height: u16) {
+        if frame_index.is_multiple_of(self.speed as usize) {
+            self.y = (self.y + 1) % ((height as i32) + 10);
+        }
+    }
+
+    fn visible_y(&self, height: u16) -> Option<u16> {
+        if self.y >= 0 && self.y < height as i32 {
+            Some(self.y as u16)
         } else {
             None
         }
     }
 }
 
-pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
+const NUM_DROPS: usize = 30;
+
+/// Falling drops kept between frames (ripples come and go on their own)
+pub struct State {
+    drops: Vec<RainDrop>,
+    size: (u16, u16),
+    frame_index: usize,
+}
+
+impl State {
+    pub fn new(area: Rect, frame_index: usize) -> Self {
+        let drops = (0..NUM_DROPS)
+            .map(|i| {
+                let mut drop = RainDrop::new(i * 3571, area.width);
+                drop.fall_to(frame_index, area.height);
+                drop
+            })
+            .collect();
+        Self {
+            drops,
+            size: (area.width, area.height),
+            frame_index,
+        }
+    }
+
+    /// Fall on to `frame_index`, first moving drops proportionally if the area was resized
+    pub fn update(&mut self, area: Rect, frame_index: usize) {
+        let (width, height) = self.size;
+        if (area.width, area.height) != self.size {
+            for drop in &mut self.drops {
+                drop.x = rescale(drop.x as f32, width, area.width) as u16;
+                drop.y = rescale(drop.y as f32, height, area.height) as i32;
+            }
+            self.size = (area.width, area.height);
+        }
+
+        let steps = frame_index.saturating_sub(self.frame_index).min(MAX_CATCH_UP);
+        for frame in frame_index - steps + 1..=frame_index {
+            for drop in &mut self.drops {
+                drop.step(frame, area.height);
+            }
+        }
+        self.frame_index = frame_index;
+    }
+}
+
+pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize, state: &State) {
     // Dark blue background
-    let bg = Block::default().style(Style::default().bg(Color::Rgb(5, 10, 20)));
-    frame.render_widget(bg, area);
+    background::fill(frame, area, Color::Rgb(5, 10, 20));
 
     // Create ripples
     let num_ripples = 8;
@@ -146,10 +211,8 @@ pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
     }
 
     // Render falling rain drops
-    let num_drops = 30;
-    for i in 0..num_drops {
-        let drop = RainDrop::new(i * 3571, area.width);
-        if let Some(y) = drop.y_at(frame_index, area.height) {
+    for drop in &state.drops {
+        if let Some(y) = drop.visible_y(area.height) {
             if drop.x < area.width && y < area.height {
                 // Draw drop and trail
                 let color = Color::Rgb(100, 180, 220);
@@ -1,8 +1,13 @@
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Paragraph};
+use ratatui::widgets::Paragraph;
 use std::time::SystemTime;
 
+use super::RenderParams;
+use crate::animation::{background, precipitation};
+use crate::weather::{self, Conditions};
+
 /// Seasonal - Changes based on current month: spring flowers, summer sun, autumn leaves, winter snow
+/// With `weather` configured, real conditions show too: cloud cover, rain, fog, and snow turns any season wintry
 
 fn simple_hash(x: usize, seed: usize) -> usize {
     let mut h = x.wrapping_mul(2654435761);
@@ -67,8 +72,7 @@ impl Season {
 
 fn render_spring(frame: &mut Frame, area: Rect, frame_index: usize) {
     // Soft spring sky
-    let bg = Block::default().style(Style::default().bg(Color::Rgb(180, 210, 230)));
-    frame.render_widget(bg, area);
+    background::fill(frame, area, Color::Rgb(180, 210, 230));
 
     // Draw grass
     for x in 0..area.width {
@@ -138,7 +142,7 @@ fn render_summer(frame: &mut Frame, area: Rect, frame_index: usize) {
         let sky_color = Color::Rgb(100 + gradient, 180 + gradient / 2, 255 - gradient);
         for x in 0..area.width {
             frame.render_widget(
-                Paragraph::new(" ").style(Style::default().bg(sky_color)),
+                Paragraph::new(" ").style(Style::default().bg(background::color(sky_color))),
                 Rect::new(area.x + x, area.y + y, 1, 1),
             );
         }
@@ -203,8 +207,7 @@ fn render_summer(frame: &mut Frame, area: Rect, frame_index: usize) {
 
 fn render_autumn(frame: &mut Frame, area: Rect, frame_index: usize) {
     // Warm autumn sky
-    let bg = Block::default().style(Style::default().bg(Color::Rgb(60, 40, 50)));
-    frame.render_widget(bg, area);
+    background::fill(frame, area, Color::Rgb(60, 40, 50));
 
     // Autumn sky gradient
     for y in 0..area.height / 2 {
@@ -274,8 +277,7 @@ fn render_autumn(frame: &mut Frame, area: Rect, frame_index: usize) {
 
 fn render_winter(frame: &mut Frame, area: Rect, frame_index: usize) {
     // Cold winter night sky
-    let bg = Block::default().style(Style::default().bg(Color::Rgb(15, 20, 35)));
-    frame.render_widget(bg, area);
+    background::fill(frame, area, Color::Rgb(15, 20, 35));
 
     // Stars
     for i in 0..20 {
@@ -341,8 +343,12 @@ fn render_winter(frame: &mut Frame, area: Rect, frame_index: usize) {
     }
 }
 
-pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
-    let season = Season::current();
+pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize, params: &RenderParams) {
+    let conditions = weather::current();
+    let season = match conditions {
+        Some(Conditions::Snow) => Season::Winter,
+        _ => Season::current(),
+    };
 
     match season {
         Season::Spring => render_spring(frame, area, frame_index),
@@ -350,4 +356,11 @@ pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
         Season::Autumn => render_autumn(frame, area, frame_index),
         Season::Winter => render_winter(frame, area, frame_index),
     }
+
+    if let Some(conditions) = conditions {
+        if matches!(conditions, Conditions::Cloudy | Conditions::Rain | Conditions::Storm) {
+            precipitation::render_cloud_cover(frame, area, frame_index);
+        }
+        precipitation::render(frame, area, frame_index, conditions, params);
+    }
 }
@@ -1,11 +1,12 @@
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Paragraph};
+use ratatui::widgets::Paragraph;
+
+use crate::animation::background;
 
 /// Spinning ASCII shape patterns
 pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
     // Dark background
-    let bg = Block::default().style(Style::default().bg(Color::Rgb(10, 10, 20)));
-    frame.render_widget(bg, area);
+    background::fill(frame, area, Color::Rgb(10, 10, 20));
 
     let center_x = area.width as f32 / 2.0;
     let center_y = area.height as f32 / 2.0;
@@ -1,5 +1,9 @@
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Paragraph};
+use ratatui::widgets::Paragraph;
+
+use super::MAX_CATCH_UP;
+use crate::animation::background;
+use crate::animation::math::rescale;
 
 /// Gentle snowfall animation
 
@@ -27,10 +31,13 @@ fn fast_sin(x: f32) -> f32 {
 struct Snowflake {
     x: f32,
     start_y: f32,
+    /// Current height, moved on every frame
+    y: f32,
     size: u8,     // 0-2 for different sizes
     speed: f32,
     wobble_freq: f32,
     wobble_amp: f32,
+    wobble_phase: f32,
 }
 
 impl Snowflake {
@@ -41,26 +48,39 @@ impl Snowflake {
         let h4 = simple_hash(seed, 4);
         let h5 = simple_hash(seed, 5);
         
+        let start_y = -((h2 % 30) as f32);
+        let x = (h1 % width as usize) as f32;
         Self {
-            x: (h1 % width as usize) as f32,
-            start_y: -((h2 % 30) as f32),
+            x,
+            start_y,
+            y: start_y,
             size: (h3 % 3) as u8,
             speed: 0.15 + (h4 % 100) as f32 / 400.0,
             wobble_freq: 0.02 + (h5 % 30) as f32 / 600.0,
             wobble_amp: 0.5 + (h5 % 20) as f32 / 10.0,
+            wobble_phase: x * 0.1,
         }
     }
     
-    fn position(&self, frame_index: usize, height: u16) -> (f32, f32) {
+    /// Height the flake would be at on `frame_index` if it had been falling all along
+    fn fall_to(&mut self, frame_index: usize, height: u16) {
         let total_dist = height as f32 + 40.0;
-        let y_offset = (frame_index as f32 * self.speed) % total_dist;
-        let y = self.start_y + y_offset;
-        
+        self.y = self.start_y + (frame_index as f32 * self.speed) % total_dist;
+    }
+
+    /// Fall one frame, wrapping back above the top
+    fn step(&mut self, height: u16) {
+        let total_dist = height as f32 + 40.0;
+        self.y += self.speed;
+        if self.y >= self.start_y + total_dist {
+            self.y -= total_dist;
+        }
+    }
+
+    fn position(&self, frame_index: usize) -> (f32, f32) {
         // Gentle side-to-side drift
-        let wobble = fast_sin(frame_index as f32 * self.wobble_freq + self.x * 0.1) * self.wobble_amp;
-        let x = self.x + wobble;
-        
-        (x, y)
+        let wobble = fast_sin(frame_index as f32 * self.wobble_freq + self.wobble_phase) * self.wobble_amp;
+        (self.x + wobble, self.y)
     }
     
     fn char(&self) -> char {
@@ -80,10 +100,55 @@ impl Snowflake {
     }
 }
 
-pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
+const NUM_FLAKES: usize = 60;
+
+/// Flakes kept between frames
+pub struct State {
+    flakes: Vec<Snowflake>,
+    size: (u16, u16),
+    frame_index: usize,
+}
+
+impl State {
+    pub fn new(area: Rect, frame_index: usize) -> Self {
+        let flakes = (0..NUM_FLAKES)
+            .map(|i| {
+                let mut flake = Snowflake::new(i * 7919, area.width, area.height);
+                flake.fall_to(frame_index, area.height);
+                flake
+            })
+            .collect();
+        Self {
+            flakes,
+            size: (area.width, area.height),
+            frame_index,
+        }
+    }
+
+    /// Fall on to `frame_index`, first moving flakes proportionally if the area was resized
+    pub fn update(&mut self, area: Rect, frame_index: usize) {
+        let (width, height) = self.size;
+        if (area.width, area.height) != self.size {
+            for flake in &mut self.flakes {
+                flake.x = rescale(flake.x, width, area.width);
+                flake.y = rescale(flake.y, height, area.height);
+            }
+            self.size = (area.width, area.height);
+        }
+
+        let steps = frame_index.saturating_sub(self.frame_index).min(MAX_CATCH_UP);
+        for _ in 0..steps {
+            for flake in &mut self.flakes {
+                flake.step(area.height);
+            }
+        }
+        self.frame_index = frame_index;
+    }
+}
+
+pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize, state: &State) {
     // Dark winter night sky
-    let bg = Block::default().style(Style::default().bg(Color::Rgb(10, 15, 25)));
-    frame.render_widget(bg, area);
+    background::fill(frame, area, Color::Rgb(10, 15, 25));
     
     // Ground accumulation (snow buildup at bottom)
     let snow_line = area.height.saturating_sub(3);
@@ -105,11 +170,8 @@ pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
     }
     
     // Render snowflakes
-    let num_flakes = 60;
-    
-    for i in 0..num_flakes {
-        let flake = Snowflake::new(i * 7919, area.width, area.height);
-        let (fx, fy) = flake.position(frame_index, area.height);
+    for flake in &state.flakes {
+        let (fx, fy) = flake.position(frame_index);
         
         let x = fx as i16;
         let y = fy as i16;
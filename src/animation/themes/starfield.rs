@@ -1,5 +1,7 @@
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Paragraph};
+use ratatui::widgets::Paragraph;
+
+use crate::animation::background;
 
 /// Star structure
 struct Star {
@@ -79,18 +81,18 @@ fn star_char(brightness: u8) -> char {
 fn star_color(seed: usize, brightness: u8) -> Color {
     let color_type = seed % 10;
     let b = brightness;
+    let dim = (b as u16 * 9 / 10) as u8;
 
     match color_type {
-        0..=5 => Color::Rgb(b, b, b),                           // White
-        6..=7 => Color::Rgb(b * 9 / 10, b * 9 / 10, b),        // Pale blue
-        _ => Color::Rgb(b, b, b * 9 / 10),                      // Pale yellow
+        0..=5 => Color::Rgb(b, b, b),     // White
+        6..=7 => Color::Rgb(dim, dim, b), // Pale blue
+        _ => Color::Rgb(b, b, dim),       // Pale yellow
     }
 }
 
 pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
     // Deep space background
-    let bg = Block::default().style(Style::default().bg(Color::Rgb(0, 0, 15)));
-    frame.render_widget(bg, area);
+    background::fill(frame, area, Color::Rgb(0, 0, 15));
 
     // Create and render stars
     let num_stars = 150;
@@ -111,12 +113,13 @@ pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
         }
     }
 
-    // Add some distant static stars (small dots)
+    // Add some distant static stars (small dots), placed in thousandths of the
+    // area like the moving stars so a resize doesn't reshuffle them
     for i in 0..50 {
         let h1 = simple_hash(i + 1000, 1);
         let h2 = simple_hash(i + 1000, 2);
-        let x = (h1 % area.width as usize) as u16;
-        let y = (h2 % area.height as usize) as u16;
+        let x = ((h1 % 1000) * area.width as usize / 1000) as u16;
+        let y = ((h2 % 1000) * area.height as usize / 1000) as u16;
         let twinkle = (frame_index + i) % 30 < 25; // Occasional twinkle off
 
         if twinkle && x < area.width && y < area.height {
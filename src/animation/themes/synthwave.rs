@@ -1,6 +1,10 @@
 use ratatui::prelude::*;
 use ratatui::widgets::Paragraph;
 
+use super::RenderParams;
+use crate::animation::background;
+use crate::animation::canvas::Canvas;
+
 /// Synthwave - Minimal sun over mountains with breathing darkness
 
 // ============================================================================
@@ -37,7 +41,7 @@ fn simple_hash(x: usize, seed: usize) -> usize {
 // MAIN RENDER
 // ============================================================================
 
-pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
+pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize, params: &RenderParams) {
     let horizon_y = (area.height as f32 * 0.55) as u16;
     let t = frame_index as f32 * 0.02;
 
@@ -49,17 +53,17 @@ pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
 
         for x in 0..area.width {
             frame.render_widget(
-                Paragraph::new(" ").style(Style::default().bg(color)),
+                Paragraph::new(" ").style(Style::default().bg(background::color(color))),
                 Rect::new(area.x + x, area.y + y, 1, 1),
             );
         }
     }
 
     // Sun
-    render_sun(frame, area, horizon_y, t);
+    render_sun(frame, area, horizon_y, t, params.aspect);
 
     // Mountain silhouette
-    render_mountains(frame, area, horizon_y);
+    render_mountains(frame, area, horizon_y, params.aspect);
 
     // Breathing darkness below
     render_breathing_floor(frame, area, horizon_y, t);
@@ -69,15 +73,16 @@ pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
 // SUN - Special character silhouette with breathing effect
 // ============================================================================
 
-fn render_sun(frame: &mut Frame, area: Rect, horizon_y: u16, t: f32) {
+fn render_sun(frame: &mut Frame, area: Rect, horizon_y: u16, t: f32, aspect: f32) {
     let cx = area.width / 2;
-    let radius = (area.width.min(area.height * 2) / 6).max(4) as f32;
+    // Measured in two cell widths; a row is `aspect` cell widths tall
+    let radius = (area.width.min((area.height as f32 * aspect) as u16) / 6).max(4) as f32;
 
     // Sun-themed special characters
     let sun_chars = ['*', '✦', '✧', '·', '°', '∘', '+', '×', '•', '◦', '⋆', '∙'];
 
     for y in 0..horizon_y {
-        let dy = (horizon_y as f32 - y as f32) / 2.0;
+        let dy = (horizon_y as f32 - y as f32) * aspect / 2.0;
         if dy > radius { continue; }
 
         for x in 0..area.width {
@@ -131,11 +136,14 @@ fn render_sun(frame: &mut Frame, area: Rect, horizon_y: u16, t: f32) {
 // MOUNTAINS - Simple silhouette
 // ============================================================================
 
-fn render_mountains(frame: &mut Frame, area: Rect, horizon_y: u16) {
+fn render_mountains(frame: &mut Frame, area: Rect, horizon_y: u16, aspect: f32) {
     let mountain_color = Color::Rgb(8, 6, 12);
+    // Ridge traced in dots, for slopes finer than whole cells
+    let mut canvas = Canvas::new(Rect::new(area.x, area.y, area.width, horizon_y.min(area.height)), aspect);
+    let (_, dots_y) = canvas.dots_per_cell();
 
-    for x in 0..area.width {
-        let fx = x as f32 / area.width as f32;
+    for x in 0..canvas.width() as usize {
+        let fx = x as f32 / canvas.width();
 
         // Multiple overlapping peaks
         let peak1 = fast_sin(fx * 2.5 + 0.5) * 0.15;
@@ -143,15 +151,10 @@ fn render_mountains(frame: &mut Frame, area: Rect, horizon_y: u16) {
         let peak3 = fast_sin(fx * 7.0 + 0.3) * 0.04;
 
         let mountain_height = (peak1 + peak2 + peak3).max(0.0);
-        let mountain_top = horizon_y.saturating_sub((mountain_height * area.height as f32 * 0.3) as u16);
-
-        for y in mountain_top..horizon_y {
-            frame.render_widget(
-                Paragraph::new("█").style(Style::default().fg(mountain_color)),
-                Rect::new(area.x + x, area.y + y, 1, 1),
-            );
-        }
+        let mountain_top = canvas.height() - (mountain_height * area.height as f32 * 0.3 * dots_y).round();
+        canvas.fill_below(x as f32, mountain_top, mountain_color);
     }
+    canvas.render(frame);
 }
 
 // ============================================================================
@@ -196,7 +199,7 @@ fn render_breathing_floor(frame: &mut Frame, area: Rect, horizon_y: u16, t: f32)
                 // Dark background
                 let bg = (base * 0.5) as u8;
                 frame.render_widget(
-                    Paragraph::new(" ").style(Style::default().bg(Color::Rgb(bg, bg / 2, bg))),
+                    Paragraph::new(" ").style(Style::default().bg(background::color(Color::Rgb(bg, bg / 2, bg)))),
                     Rect::new(area.x + x, area.y + y, 1, 1),
                 );
             }
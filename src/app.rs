@@ -1,63 +1,721 @@
-use crate::animation::AnimationEngine;
-use crate::animation::themes::ThemeType;
-use crate::notification::notify_session_end;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local};
+use ratatui::buffer::Buffer;
+
+use crate::agenda::Agenda;
+use crate::animation::{background, canvas, daylight, AnimationEngine, ThemeFailure};
+use crate::animation::themes::{ThemeFilter, ThemeType};
+use crate::clock::{self, SharedClock};
+use crate::break_activity::{BreakActivity, Breathing, Direction, Snake};
+use crate::coach::Coach;
+use crate::meeting::Meeting;
+use crate::music::{MusicConfig, MusicPlayer};
+use crate::onboarding::{self, Release};
+use crate::preset::{self, Preset};
+use crate::quotes::{self, Quotes};
+use crate::reminders::{self, Scheduler};
+use crate::alarm::Bell;
+use crate::scripting::{Script, ScriptAction};
+use crate::speech::Speaker;
+use crate::tracking::Tracker;
+use crate::commands::{self, Command};
+use crate::config::Config;
+use crate::events::{AppEvent, Event, EventBus, EventLog, Subscriber};
+use crate::idle::{self, IdleMonitor};
+use crate::notification::{self, ring_bell, NotificationDispatcher, SessionEvent};
+use crate::animation::digits::{DigitColorMode, DigitRoll};
+use crate::animation::DigitFont;
 use crate::scaling::ScalingContext;
-use pomowise::timer::{PomodoroTimer, TimerState};
+use crate::summary::{self, DaySummary};
+use crate::sync;
+use crate::weather;
+use crate::contributions;
+use crate::team::{Team, TeamEvent, TeamTimer};
+use crate::wm::WindowManager;
+use crate::world_clock::{self, Zone};
+use crate::ui::chrome::{Chrome, Palette};
+use crate::ui::color_filter::{ColorFilter, MAX_BRIGHTNESS, MIN_BRIGHTNESS};
+use crate::ui::font_gallery;
+use crate::ui::glyphs::Glyphs;
+use crate::ui::slow_link;
+use crate::ui::transition::{Transition, TransitionStyle};
+use crate::ui::widgets::toast::Toast;
+use crate::history::{self, SessionKind, SessionOutcome, SessionRecord, TaskProgress};
+use crate::ipc;
+use crate::last_run::LastRun;
+use crate::timer::{PomodoroTimer, Savestate, TimerSnapshot, TimerState, CYCLE_STEPS};
+
+/// Length of the work-complete celebration (~2.5 s at 10 FPS)
+pub const CELEBRATION_FRAMES: u32 = 25;
+
+/// How long a new session's name is shown in block letters (~2 s at 10 FPS)
+pub const BANNER_FRAMES: u32 = 20;
+
+/// How long a coach tip stays up unless dismissed with `x` (~10 s at 10 FPS)
+const COACH_TIP_FRAMES: u32 = 100;
+
+/// Length of the silent-mode color pulse on a transition (~3 s at 10 FPS)
+pub const ALERT_FRAMES: u32 = 30;
+
+/// Held-`q` key repeats needed to escape a strict break (~1.5 s of autorepeat)
+pub const STRICT_ESCAPE_PRESSES: u32 = 20;
+
+/// Max gap between repeats for them to count as one held key
+const STRICT_ESCAPE_GAP: Duration = Duration::from_millis(300);
+
+/// Max gap between space key repeats for them to count as holding it: a hand
+/// can't tap three times this fast, autorepeat always does
+const SPACE_HOLD_GAP: Duration = Duration::from_millis(120);
+
+/// Longest wait for the first autorepeat after a key goes down
+const SPACE_REPEAT_DELAY: Duration = Duration::from_secs(1);
+
+/// Extra time `+5 min` in the quick actions gives the session under way
+const QUICK_EXTEND: Duration = Duration::from_secs(5 * 60);
+
+/// How often to nag while the terminal is unfocused during a strict break
+const BREAK_NAG_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Start of a break the break checks leave alone, to wrap up what was going on
+const BREAK_CHECK_GRACE: Duration = Duration::from_secs(30);
+
+/// Final stretch of a session shown in the grown font (with `grow_final_minute`)
+const GROW_WINDOW: Duration = Duration::from_secs(60);
+
+/// Age after which another instance's status file counts as left behind
+const MIRROR_MAX_AGE: Duration = Duration::from_secs(2);
+
+/// Percent per press of the brightness hotkeys
+const BRIGHTNESS_STEP: u8 = 10;
+
+/// Longest intention accepted at the prompt
+const MAX_INTENTION_CHARS: usize = 60;
+
+/// How long a skip or reset can still be taken back with `u`
+const UNDO_WINDOW: Duration = Duration::from_secs(10);
+
+/// Skips and resets kept for undo (older ones drop off)
+const UNDO_DEPTH: usize = 5;
+
+/// Time between frames of an animated scene (10 FPS)
+const FRAME_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Slack after a second boundary so the static scene wakes past it, not just before
+const WAKE_MARGIN: Duration = Duration::from_millis(5);
+
+/// A skip or reset that `u` can take back
+struct Undo {
+    /// "skip" or "reset", for the toast
+    action: &'static str,
+    timer: Savestate,
+    intention: Option<String>,
+    task: Option<TaskProgress>,
+    /// The skipped session's record, held back from the history until the window closes
+    record: Option<SessionRecord>,
+}
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AppScreen {
     Menu,
     Timer,
+    FontGallery,
+    Stats,
+    WhatsNew,
+    Reminders,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum MenuItem {
-    Start,
+    /// Pick the cycle up where the last one was left
+    Continue,
+    NewCycle,
+    /// A new cycle with a typed work length
+    Custom,
+    /// Pick one of the installed presets (shown when there are any)
+    Presets,
+    /// The reminders screen (shown when there are any)
+    Reminders,
+    Fonts,
+    Stats,
+    Quit,
+}
+
+/// Entry of the quick actions popup (`.` or holding space)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuickAction {
+    Pause,
+    /// Five more minutes on the session under way
+    AddFive,
+    Skip,
+    /// Stop (or restart) auto-rotating themes
+    LockTheme,
+    /// Hide everything but what zen mode keeps
+    Zen,
     Quit,
 }
 
+impl QuickAction {
+    pub fn all() -> &'static [QuickAction] {
+        &[
+            QuickAction::Pause,
+            QuickAction::AddFive,
+            QuickAction::Skip,
+            QuickAction::LockTheme,
+            QuickAction::Zen,
+            QuickAction::Quit,
+        ]
+    }
+
+    /// Menu label, following what the action would do right now
+    pub fn label(&self, app: &App) -> &'static str {
+        match self {
+            QuickAction::Pause if app.timer.is_paused() => "Resume",
+            QuickAction::Pause => "Pause",
+            QuickAction::AddFive => "+5 min",
+            QuickAction::Skip => "Skip",
+            QuickAction::LockTheme if app.auto_rotate => "Lock theme",
+            QuickAction::LockTheme => "Unlock theme",
+            QuickAction::Zen if app.hints_visible => "Zen",
+            QuickAction::Zen => "Leave zen",
+            QuickAction::Quit => "Quit",
+        }
+    }
+}
+
 pub struct App {
     pub screen: AppScreen,
     pub menu_selection: MenuItem,
+    /// Minutes typed for a custom timer while its menu field is open
+    pub custom_minutes: Option<String>,
+    /// Presets installed at start, by name
+    pub presets: Vec<Preset>,
+    /// Selected row while the menu's preset picker is open: 0 is "No preset",
+    /// then the presets in order
+    pub preset_picker: Option<usize>,
+    /// Preset in use; its font and sounds apply over the config without being saved into it
+    preset: Option<Preset>,
+    /// Work, short and long break lengths from before the preset, put back without one
+    own_lengths: Option<[Duration; 3]>,
+    /// Themes of the preset in use, which auto-rotation keeps to
+    preset_themes: Vec<ThemeType>,
     pub timer: PomodoroTimer,
     pub animation: AnimationEngine,
     pub should_quit: bool,
     pub theme_selector_open: bool,
     pub theme_selector_index: usize,
+    /// Selected cycle step while the go-to menu is open (`G`)
+    pub goto_menu: Option<usize>,
+    /// Project label of the work session under way (`auto_label`)
+    project: Option<String>,
+    /// Selected entry while the quick actions popup is open (`.`)
+    pub quick_actions: Option<usize>,
+    /// Space presses in a row, when the last one came, and whether the timer was
+    /// paused before the first (to tell holding space from tapping it)
+    space_presses: u32,
+    last_space_press: Option<Instant>,
+    space_was_paused: bool,
     pub auto_rotate: bool,
     pub hints_visible: bool,
+    /// Show the secondary elapsed / end-time clock under the digits
+    pub show_elapsed: bool,
     pub hint_flash_frames: u32,
+    /// Brightness of every color in percent, for this run (`[` / `]`)
+    pub brightness: u8,
+    /// Frames left in the work-complete celebration overlay
+    pub celebration_frames: u32,
+    /// Session whose name is shown in block letters as it starts, and for how many frames
+    pub banner: Option<SessionKind>,
+    pub banner_frames: u32,
+    /// Frames left in the alert's color pulse (silent mode, the alarm screen)
+    pub alert_frames: u32,
+    /// The transition shown in a banner until a key is pressed
+    pub alert_banner: Option<SessionEvent>,
+    /// The alert fills the screen over everything, pulsing until then (`alarm`)
+    pub alert_takeover: bool,
+    /// The alarm screen's bell, ringing until the alert is acknowledged
+    alert_bell: Option<Bell>,
+    /// Time zones of the world clock row under the digits (`world_clock`)
+    pub world_clock: Vec<Zone>,
+    /// Previous value of each timer digit, for the roll as it changes (`digit_roll`)
+    pub digit_roll: DigitRoll,
+    /// A theme that crashed and was swapped for Minimal, reported until a key is pressed
+    pub theme_error: Option<ThemeFailure>,
+    /// Screen change being animated, if any
+    transition: Option<Transition>,
+    /// Screen as of the last tick, to notice changes from any source
+    last_screen: AppScreen,
+    /// The last drawn frame before color filtering, the starting picture of a transition
+    last_frame: RefCell<Option<Buffer>>,
+    /// Countdown second and wall-clock second of the last static frame drawn
+    drawn_static: Option<(u64, i64)>,
+    /// Set by input: draw the next frame even when the scene is static
+    pub redraw_requested: bool,
+    /// Slow-link mode (`slow_link`, `--slow-link`): fewer frames, less detail, coalesced colors
+    pub slow_link: bool,
+    /// Glyph profile in use, `auto` resolved
+    pub glyphs: Glyphs,
+    /// When the last animated frame was drawn, to space them out on a slow link
+    last_drawn: Option<Instant>,
+    /// `--mini`: digits, a progress line and the state only, for a tiny floating pane
+    pub mini: bool,
+    /// Session of another running instance that the mini layout follows while idle here
+    pub mirrored: Option<TimerSnapshot>,
+    /// Settings and session remembered when a session was left for the menu,
+    /// written out for `pomowise resume` when the app quits
+    last_run: Option<LastRun>,
+    /// The run the menu's "Continue cycle" picks up, when there is one
+    continue_from: Option<LastRun>,
     /// Current terminal dimensions and scaling context
     pub scaling: ScalingContext,
     /// Whether to use adaptive font (auto-select based on terminal size)
     pub adaptive_font: bool,
+    /// On-screen feedback for theme/font/setting changes
+    pub toast: Option<Toast>,
+    /// Coach tip from the last session boundary, kept apart so theme toasts don't cover it
+    pub coach_tip: Option<Toast>,
+    /// Time-of-day schedule range in effect at the last tick
+    schedule_range: Option<usize>,
+    /// Theme and font as of the last tick, to notice changes from any source
+    last_theme: ThemeType,
+    last_font: DigitFont,
+    /// Theme whose font override (`theme_fonts` in config) was last applied
+    font_theme: Option<ThemeType>,
+    /// Whether the current font is that theme's own, to be undone when it goes
+    theme_font: bool,
+    /// Command palette input (without the leading ':') while it is open
+    pub command_line: Option<String>,
+    /// Result/error message from the last palette command
+    pub command_message: Option<String>,
+    pub command_message_frames: u32,
+    /// Roll from work into a break without waiting (seeded from config, `:autobreak`)
+    pub auto_continue_break: bool,
+    /// Roll from a break into work without waiting (seeded from config, `:autowork`)
+    pub auto_continue_work: bool,
+    /// A session finished and the next one is held until Space is pressed
+    pub awaiting_start: bool,
+    /// Selected card in the font gallery (index into `DigitFont::every()`)
+    pub gallery_index: usize,
+    /// Screen to go back to when the gallery closes
+    gallery_return: AppScreen,
+    /// Share of breaks taken in full, from the history (shown with strict breaks)
+    pub break_compliance: Option<f64>,
+    /// Today's pomodoros, focus time and streak for the menu (None before any work session)
+    pub day_summary: Option<DaySummary>,
+    /// Consecutive held-`q` presses toward escaping a strict break
+    strict_escape_presses: u32,
+    last_escape_press: Option<Instant>,
+    /// Whether the terminal has focus (from focus-change events)
+    focused: bool,
+    last_break_nag: Option<Instant>,
+    /// When the current pause began, and the reminders sent about it so far
+    paused_at: Option<Instant>,
+    pause_reminders: u32,
+    /// Desktop idle time, for the break checks (`break_check`)
+    pub idle: IdleMonitor,
+    /// Cycle step of a break that ran out while the user was away; `e` takes it again, longer
+    pub break_extension: Option<usize>,
+    /// "End current session?" dialog shown when leaving a work session
+    pub quit_confirm_open: bool,
+    /// Whether opening the dialog paused the timer (so cancelling resumes it)
+    quit_confirm_paused: bool,
+    /// LAN team sharing this timer (when `team` is configured)
+    pub team: Option<Team>,
+    /// Unix time (ms) of the newest team timer change applied here
+    team_changed_at: u64,
+    /// Window manager focus feed (when `distractions` is configured)
+    window_manager: Option<WindowManager>,
+    /// What the work session is paused for, while a distraction has it paused
+    distraction: Option<String>,
+    /// Intention prompt input while it is open (before a work session)
+    pub intention_input: Option<String>,
+    /// Whether the prompt is holding a session that should start once answered
+    intention_paused: bool,
+    /// Quote shown as the current session starts
+    pub quote: Option<String>,
+    quotes: Quotes,
+    /// Cycle step the quote was picked for, to notice new sessions
+    quote_step: Option<usize>,
+    /// Intention for the work session in progress
+    pub current_intention: Option<String>,
+    /// Pomodoros done on that intention so far, when it has an estimate
+    pub task: Option<TaskProgress>,
+    /// Completed work session waiting for its 1-5 focus rating
+    pub pending_review: Option<SessionRecord>,
+    /// Recent skips and resets, newest last
+    undo_stack: VecDeque<Undo>,
+    /// History shown on the stats screen, loaded when it opens
+    pub stats_records: Vec<SessionRecord>,
+    stats_return: AppScreen,
+    /// When each recurring reminder is next due
+    pub reminders: Scheduler,
+    /// Selected reminder on the reminders screen
+    pub reminder_selection: usize,
+    reminders_return: AppScreen,
+    /// Breathing exercise or snake game running over the current break
+    pub break_activity: Option<BreakActivity>,
+    /// Player for the configured focus music, following the running session
+    music: MusicPlayer,
+    /// Focus music switched on for this run (`:music`)
+    pub music_enabled: bool,
+    /// Second player, for the current theme's ambient sound
+    ambient: MusicPlayer,
+    /// Ambient sounds switched on for this run (seeded from config, `:ambient`)
+    pub ambient_enabled: bool,
+    /// Music and ambient sounds muted together (`:mute`)
+    pub muted: bool,
+    /// Page of the first-run walkthrough while it is open
+    pub onboarding_step: Option<usize>,
+    /// Changelog entries on the "What's new" screen
+    pub whats_new: Vec<Release>,
+    pub whats_new_scroll: u16,
+    whats_new_return: AppScreen,
+    /// Hard stop that sessions are planned around (`:until`)
+    pub meeting: Option<Meeting>,
+    /// Toggl / Clockify entries for work sessions (when `tracking` is configured)
+    tracker: Option<Tracker>,
+    /// Wall clock pinned for reproducible rendering; unset follows the system clock
+    pub frozen_now: Option<DateTime<Local>>,
+    /// User configuration loaded at startup
+    pub config: Config,
+    /// Enabled notification backends
+    notifier: NotificationDispatcher,
+    speaker: Speaker,
+    /// Session events published since the last tick
+    events: EventBus,
+    /// Recent session events, shown in the side pane (`L`)
+    pub event_log: EventLog,
+    /// Tips from recent behaviour (when `coach` is on)
+    coach: Option<Coach>,
+    /// User script hooked to the session events (`script` in config)
+    script: Option<Script>,
+    /// Day template picked at launch (`--template`), with its progress
+    pub agenda: Option<Agenda>,
+    /// Agenda pane shrunk to the block in progress (`A`)
+    pub agenda_collapsed: bool,
+    /// Cycle step whose start was last published, and whether it was paused then
+    watched_step: Option<usize>,
+    watched_paused: bool,
+    /// Category picked while a work session was under way, for the next one to start with
+    next_category: Option<Option<usize>>,
+    /// Last countdown second a tick was emitted for (avoids repeats within a second)
+    last_countdown_tick: Option<u64>,
 }
 
 impl App {
-    pub fn new() -> Self {
+    pub fn with_config(config: Config) -> Self {
+        Self::with_clock(config, clock::system())
+    }
+
+    /// App whose timer and animation share `clock`
+    pub fn with_clock(config: Config, clock: SharedClock) -> Self {
         // Get initial terminal size
         let (width, height) = crossterm::terminal::size().unwrap_or((80, 24));
-        let scaling = ScalingContext::new(width, height);
+        let mut scaling = ScalingContext::new(width, height);
+        scaling.set_cell_aspect(config.cell_aspect);
+        let saved_font = config.font.as_deref().and_then(DigitFont::from_name);
+
+        background::set_transparent(config.transparent_background);
+        canvas::set_high_res(config.braille);
+        daylight::set(&config.landscape_sky);
+
+        // A font picked in the gallery overrides adaptive selection
+        let mut animation = AnimationEngine::with_clock(clock.clone());
+        animation.set_detail_cap(detail_cap(&scaling, config.slow_link.active()));
+        animation.set_cell_aspect(scaling.cell_aspect);
+        animation.set_motion(config.motion);
+        if let Some(font) = saved_font {
+            animation.set_font(font);
+        }
 
-        Self {
+        let mut app = Self {
             screen: AppScreen::Menu,
-            menu_selection: MenuItem::Start,
-            timer: PomodoroTimer::new(),
-            animation: AnimationEngine::new(),
+            menu_selection: MenuItem::NewCycle,
+            custom_minutes: None,
+            presets: preset::installed(),
+            preset_picker: None,
+            preset: None,
+            own_lengths: None,
+            preset_themes: Vec::new(),
+            timer: PomodoroTimer::with_clock(clock),
             should_quit: false,
             theme_selector_open: false,
             theme_selector_index: 0,
+            goto_menu: None,
+            quick_actions: None,
+            project: None,
+            space_presses: 0,
+            last_space_press: None,
+            space_was_paused: false,
             auto_rotate: true,
             hints_visible: true,
+            show_elapsed: config.show_elapsed,
             hint_flash_frames: 0,
+            brightness: MAX_BRIGHTNESS,
+            celebration_frames: 0,
+            banner: None,
+            banner_frames: 0,
+            alert_frames: 0,
+            alert_banner: None,
+            alert_takeover: false,
+            alert_bell: None,
+            digit_roll: DigitRoll::default(),
+            world_clock: Vec::new(),
+            theme_error: None,
+            transition: None,
+            last_screen: AppScreen::Menu,
+            last_frame: RefCell::new(None),
+            drawn_static: None,
+            redraw_requested: true,
+            slow_link: config.slow_link.active(),
+            glyphs: config.glyphs.resolve(),
+            last_drawn: None,
+            mini: false,
+            mirrored: None,
+            last_run: None,
+            continue_from: None,
             scaling,
-            adaptive_font: true, // Enable adaptive font by default
+            adaptive_font: saved_font.is_none(), // Adaptive unless a font was picked
+            toast: None,
+            coach_tip: None,
+            schedule_range: None,
+            last_theme: animation.current_theme,
+            last_font: animation.current_font,
+            font_theme: None,
+            theme_font: false,
+            animation,
+            command_line: None,
+            command_message: None,
+            command_message_frames: 0,
+            auto_continue_break: config.auto_continue.to_break,
+            auto_continue_work: config.auto_continue.to_work,
+            awaiting_start: false,
+            break_compliance: history::load().ok().and_then(|r| history::break_compliance(&r)),
+            day_summary: None,
+            strict_escape_presses: 0,
+            last_escape_press: None,
+            focused: true,
+            last_break_nag: None,
+            paused_at: None,
+            pause_reminders: 0,
+            idle: IdleMonitor::default(),
+            break_extension: None,
+            gallery_index: 0,
+            gallery_return: AppScreen::Menu,
+            quit_confirm_open: false,
+            quit_confirm_paused: false,
+            notifier: NotificationDispatcher::from_config(&config.notifications),
+            speaker: Speaker::new(&config.speech),
+            events: EventBus::default(),
+            event_log: EventLog::default(),
+            coach: config.coach.then(|| Coach::new(&history::load().unwrap_or_default())),
+            script: None,
+            agenda: None,
+            agenda_collapsed: false,
+            watched_step: None,
+            watched_paused: false,
+            next_category: None,
+            team: config.team.as_ref().and_then(|team| Team::start(team).ok()),
+            team_changed_at: 0,
+            window_manager: config.distractions.as_ref().and_then(|_| WindowManager::connect().ok()),
+            distraction: None,
+            intention_input: None,
+            intention_paused: false,
+            current_intention: None,
+            task: None,
+            quote: None,
+            quotes: Quotes::load(&config.quotes),
+            quote_step: None,
+            pending_review: None,
+            undo_stack: VecDeque::new(),
+            stats_records: Vec::new(),
+            stats_return: AppScreen::Menu,
+            reminders: Scheduler::default(),
+            reminder_selection: 0,
+            reminders_return: AppScreen::Menu,
+            break_activity: None,
+            music: MusicPlayer::default(),
+            music_enabled: true,
+            ambient: MusicPlayer::default(),
+            ambient_enabled: config.ambient.enabled,
+            muted: false,
+            meeting: None,
+            onboarding_step: None,
+            whats_new: Vec::new(),
+            whats_new_scroll: 0,
+            whats_new_return: AppScreen::Menu,
+            // Incognito runs leave no trace, in the tracker either
+            tracker: config
+                .tracking
+                .as_ref()
+                .filter(|_| history::mode().is_persistent())
+                .map(Tracker::new),
+            frozen_now: None,
+            config,
+            last_countdown_tick: None,
+        };
+
+        app.notifier.silent = app.config.silent;
+        app.speaker.silent = app.config.silent;
+        app.offer_continue(LastRun::load());
+        app.seed_laps();
+
+        // Pick up sessions recorded on other machines
+        if let Some(sync_config) = &app.config.sync {
+            sync::sync_in_background(sync_config);
+        }
+        if let Some(weather_config) = &app.config.weather {
+            weather::watch(weather_config);
+        }
+        if let Some(github_config) = &app.config.github {
+            contributions::watch(github_config);
+        }
+
+        if let Some(preset) = app.presets.iter().find(|p| app.config.preset.as_ref() == Some(&p.name)).cloned() {
+            app.use_preset(&preset);
+        }
+        app.reminders = Scheduler::new(&app.config.reminders, app.timer.clock().now());
+        app.refresh_day_summary();
+        let (zones, errors) = world_clock::parse_zones(&app.config.world_clock);
+        app.world_clock = zones;
+        if !errors.is_empty() {
+            app.show_toast(format!("World clock: {}", errors.join(", ")));
+        }
+        let unreadable = app.config.session_accents.unreadable();
+        if !unreadable.is_empty() {
+            app.show_toast(format!("Session accents: can't read {}", unreadable.join(", ")));
+        }
+        if let Some(script_config) = &app.config.script {
+            match Script::load(script_config) {
+                Ok(script) => app.script = Some(script),
+                Err(e) => app.show_toast(format!("Script not loaded: {}", e)),
+            }
+        }
+
+        // The starting theme's own font, without announcing it
+        app.apply_theme_font();
+        app.last_font = app.animation.current_font;
+
+        app
+    }
+
+    /// Start animating a screen change, or count down the one in progress
+    fn tick_transition(&mut self) {
+        if let Some(transition) = self.transition.as_mut() {
+            transition.frames_left -= 1;
+            if transition.frames_left == 0 {
+                self.transition = None;
+            }
+        }
+        if self.screen == self.last_screen {
+            return;
+        }
+        // Moving into a screen slides forward; coming back to the menu slides back
+        let forward = self.screen != AppScreen::Menu;
+        self.last_screen = self.screen;
+        // Reduced motion fades instead of sliding
+        let style = match self.config.transitions {
+            TransitionStyle::Slide if self.animation.reduced_motion() => TransitionStyle::Fade,
+            style => style,
+        };
+        self.transition = match (style, self.last_frame.get_mut().take()) {
+            (TransitionStyle::Off, _) | (_, None) => None,
+            (style, Some(from)) => Some(Transition::new(from, style, forward)),
+        };
+    }
+
+    /// Blend in a running transition and remember the frame for the next one
+    pub fn finish_frame(&self, buf: &mut Buffer) {
+        if let Some(transition) = &self.transition {
+            transition.apply(buf);
+        }
+        *self.last_frame.borrow_mut() = Some(buf.clone());
+    }
+
+    /// Nothing on screen moves between seconds: the mini layout, or the timer in
+    /// digits-only mode, with no animated overlay up
+    pub fn is_static_scene(&self) -> bool {
+        let quote_showing = self.quote.is_some()
+            && self.hints_visible
+            && self.timer.elapsed().as_secs_f32() < quotes::QUOTE_SECS;
+        let still_timer = self.screen == AppScreen::Timer
+            && self.digits_only()
+            && self.config.digit_colors != DigitColorMode::Rainbow
+            // Seven-segment digits fade out within the second
+            && self.display_font() != DigitFont::SevenSegment
+            && self.break_activity.is_none()
+            && !self.theme_selector_open
+            && !quote_showing;
+        let effects = self.transition.is_some()
+            || self.toast.is_some()
+            || self.coach_tip.is_some()
+            || self.command_message.is_some()
+            || self.celebration_frames > 0
+            || self.banner.is_some()
+            || self.alert_frames > 0
+            || self.digit_roll.is_rolling()
+            || self.hint_flash_frames > 0;
+        (self.mini || still_timer) && !effects
+    }
+
+    /// Whether the next frame needs drawing. Animated scenes draw every frame; a
+    /// static one only after input and when the countdown or wall clock ticks over,
+    /// and the terminal diff then only rewrites the digit and progress cells
+    pub fn take_redraw(&mut self) -> bool {
+        if !self.is_static_scene() {
+            self.drawn_static = None;
+            // A slow link gets a couple of frames a second, and one right after input
+            let now = Instant::now();
+            let due = !self.slow_link
+                || self.redraw_requested
+                || self.last_drawn.is_none_or(|last| now.duration_since(last) >= slow_link::FRAME_INTERVAL);
+            if due {
+                self.last_drawn = Some(now);
+                self.redraw_requested = false;
+            }
+            return due;
+        }
+        let key = (self.timer.remaining.as_secs(), Local::now().timestamp());
+        let redraw = self.redraw_requested || self.drawn_static != Some(key);
+        self.drawn_static = Some(key);
+        self.redraw_requested = false;
+        redraw
+    }
+
+    /// How long the run loop may wait for input before the next tick: one frame
+    /// while anything animates, otherwise until the countdown or the wall clock
+    /// ticks over (a paused digits-only timer wakes once a second)
+    pub fn next_wake(&self) -> Duration {
+        if !self.is_static_scene() {
+            return FRAME_INTERVAL;
         }
+        let second = Duration::from_secs(1);
+        let wall = second.saturating_sub(Duration::from_nanos(Local::now().timestamp_subsec_nanos() as u64));
+        let countdown = match Duration::from_nanos(self.timer.remaining.subsec_nanos() as u64) {
+            fraction if self.timer.is_running() && !fraction.is_zero() => fraction,
+            _ => second,
+        };
+        wall.min(countdown) + WAKE_MARGIN
+    }
+
+    /// Local wall-clock time, as shown on screen (theme of the day, end times, stats)
+    pub fn now(&self) -> DateTime<Local> {
+        self.frozen_now.unwrap_or_else(Local::now)
+    }
+
+    /// Slow-link mode on or off; `--slow-link` turns it on whatever the config says
+    pub fn set_slow_link(&mut self, on: bool) {
+        self.slow_link = on;
+        self.animation.set_detail_cap(detail_cap(&self.scaling, on));
     }
 
     /// Update terminal dimensions and recalculate scaling
     pub fn update_dimensions(&mut self, width: u16, height: u16) {
         self.scaling = ScalingContext::new(width, height);
+        self.scaling.set_cell_aspect(self.config.cell_aspect);
+        self.animation.set_detail_cap(detail_cap(&self.scaling, self.slow_link));
 
         // Auto-select font if adaptive mode is enabled
         if self.adaptive_font {
@@ -65,145 +723,2044 @@ impl App {
         }
     }
 
-    /// Toggle adaptive font mode
+    /// Next font style (`f`), remembered for the current theme
+    pub fn next_font(&mut self) {
+        self.animation.next_font();
+        self.pick_theme_font(self.animation.current_font);
+    }
+
+    /// Use `font` from now on whenever the current theme shows (saved to config)
+    fn pick_theme_font(&mut self, font: DigitFont) {
+        let theme = self.animation.current_theme;
+        self.adaptive_font = false;
+        self.animation.set_font(font);
+        self.font_theme = Some(theme);
+        self.theme_font = true;
+        self.config.theme_fonts.insert(theme.slug(), font.slug());
+        let _ = self.config.save();
+    }
+
+    /// Switch to the font picked for the theme now showing, or back to the usual
+    /// one (gallery pick or adaptive) when leaving a theme that had its own
+    fn apply_theme_font(&mut self) {
+        let theme = self.animation.current_theme;
+        if self.font_theme == Some(theme) {
+            return;
+        }
+        self.font_theme = Some(theme);
+        match self.config.theme_fonts.get(&theme.slug()).and_then(|f| DigitFont::from_name(f)) {
+            Some(font) => {
+                self.adaptive_font = false;
+                self.animation.set_font(font);
+                self.theme_font = true;
+            }
+            None if self.theme_font => {
+                self.theme_font = false;
+                self.restore_default_font();
+            }
+            None => {}
+        }
+    }
+
+    /// The font in use without theme overrides: the preset's, else the gallery
+    /// pick, else adaptive
+    fn restore_default_font(&mut self) {
+        let preset_font = self.preset.as_ref().and_then(|p| p.font.as_deref());
+        match preset_font.or(self.config.font.as_deref()).and_then(DigitFont::from_name) {
+            Some(font) => {
+                self.adaptive_font = false;
+                self.animation.set_font(font);
+            }
+            None => {
+                self.adaptive_font = true;
+                self.animation.current_font = self.scaling.recommended_font;
+            }
+        }
+    }
+
+    /// Toggle adaptive font mode (`F`); on a theme with a font of its own, forget
+    /// that font and go back to adaptive instead
     pub fn toggle_adaptive_font(&mut self) {
+        let theme = self.animation.current_theme;
+        if self.config.theme_fonts.remove(&theme.slug()).is_some() {
+            let _ = self.config.save();
+            self.theme_font = false;
+            self.adaptive_font = true;
+            self.animation.current_font = self.scaling.recommended_font;
+            self.last_font = self.animation.current_font;
+            self.show_toast(format!("Adaptive font (dropped {}'s own font)", theme.name()));
+            return;
+        }
         self.adaptive_font = !self.adaptive_font;
+        self.show_toast(format!("Adaptive font: {}", on_off(self.adaptive_font)));
         if self.adaptive_font {
             self.animation.current_font = self.scaling.recommended_font;
         }
     }
 
+    /// Menu entries in order; "Continue cycle" only when there is a cycle to continue
+    pub fn menu_items(&self) -> Vec<MenuItem> {
+        let mut items = vec![MenuItem::NewCycle, MenuItem::Custom, MenuItem::Stats, MenuItem::Fonts, MenuItem::Quit];
+        if !self.presets.is_empty() {
+            items.insert(2, MenuItem::Presets);
+        }
+        if !self.config.reminders.is_empty() {
+            let fonts = items.iter().position(|&item| item == MenuItem::Fonts).unwrap_or(items.len());
+            items.insert(fonts, MenuItem::Reminders);
+        }
+        if self.continue_from.is_some() {
+            items.insert(0, MenuItem::Continue);
+        }
+        items
+    }
+
     pub fn menu_up(&mut self) {
-        self.menu_selection = MenuItem::Start;
+        self.menu_move(false);
     }
 
     pub fn menu_down(&mut self) {
-        self.menu_selection = MenuItem::Quit;
+        self.menu_move(true);
+    }
+
+    fn menu_move(&mut self, down: bool) {
+        let items = self.menu_items();
+        let index = items.iter().position(|&item| item == self.menu_selection).unwrap_or(0);
+        let index = if down { (index + 1).min(items.len() - 1) } else { index.saturating_sub(1) };
+        self.menu_selection = items[index];
     }
 
     /// Returns false if app should quit
     pub fn menu_select(&mut self) -> bool {
         match self.menu_selection {
-            MenuItem::Start => {
-                self.screen = AppScreen::Timer;
-                self.timer.start();
-                self.animation.reset();
+            MenuItem::Continue => {
+                self.continue_cycle();
+                true
+            }
+            MenuItem::NewCycle => {
+                self.new_cycle();
+                true
+            }
+            MenuItem::Custom => {
+                self.custom_minutes = Some(String::new());
+                true
+            }
+            MenuItem::Presets => {
+                let current = self.presets.iter().position(|p| self.config.preset.as_ref() == Some(&p.name));
+                self.preset_picker = Some(current.map_or(0, |i| i + 1));
+                true
+            }
+            MenuItem::Reminders => {
+                self.open_reminders();
+                true
+            }
+            MenuItem::Fonts => {
+                self.open_font_gallery();
+                true
+            }
+            MenuItem::Stats => {
+                self.open_stats();
                 true
             }
             MenuItem::Quit => false,
         }
     }
 
-    pub fn toggle_pause(&mut self) {
-        self.timer.toggle_pause();
+    /// Start a cycle from its first work session
+    pub fn new_cycle(&mut self) {
+        self.screen = AppScreen::Timer;
+        self.seed_laps();
+        self.timer.start();
+        self.animation.reset();
+        self.team_broadcast();
+        self.prompt_intention();
     }
 
-    pub fn reset_session(&mut self) {
-        self.timer.reset_current_session();
+    /// Start where the last cycle was left: its unfinished session, or a fresh
+    /// one at the same step once that is too old to pick up
+    pub fn continue_cycle(&mut self) {
+        let Some(last) = self.continue_from.clone() else {
+            return self.new_cycle();
+        };
+        self.screen = AppScreen::Timer;
+        self.seed_laps();
         self.animation.reset();
+        self.pick_up(&last);
+        self.team_broadcast();
     }
 
-    pub fn quit_to_menu(&mut self) {
-        self.screen = AppScreen::Menu;
-        self.timer = PomodoroTimer::new();
-        self.animation.reset();
+    /// What "Continue cycle" would start: "Next: Short Break, 5:00"
+    pub fn continue_label(&self) -> Option<String> {
+        let last = self.continue_from.as_ref()?;
+        let (kind, secs) = match last.recent_session() {
+            Some(session) => (SessionKind::from_state(&session.state)?, session.remaining_secs),
+            None => {
+                let step = last.cycle_step?;
+                (SessionKind::of_step(step), self.timer.step_length(step).as_secs())
+            }
+        };
+        Some(format!("Next: {}, {}:{:02}", kind.label(), secs / 60, secs % 60))
     }
 
-    /// Skip to next interval/cycle AND change theme (Tab key)
-    pub fn skip_to_next(&mut self) {
-        self.timer.advance_state();
-        self.animation.rotate_theme();
+    /// Offer "Continue cycle" on the menu for `last`, when it left a cycle
+    fn offer_continue(&mut self, last: LastRun) {
+        let resumable = last.recent_session().is_some() || last.cycle_step.is_some();
+        self.continue_from = resumable.then_some(last);
+        if resumable {
+            self.menu_selection = MenuItem::Continue;
+        } else if self.menu_selection == MenuItem::Continue {
+            self.menu_selection = MenuItem::NewCycle;
+        }
     }
 
-    /// Toggle theme selector overlay (Shift+T)
-    pub fn toggle_theme_selector(&mut self) {
-        self.theme_selector_open = !self.theme_selector_open;
-        if self.theme_selector_open {
-            // Set selector to current theme
-            let themes = ThemeType::all();
-            self.theme_selector_index = themes
-                .iter()
-                .position(|&t| t == self.animation.current_theme)
-                .unwrap_or(0);
+    pub fn custom_minutes_push(&mut self, c: char) {
+        if let Some(minutes) = self.custom_minutes.as_mut() {
+            if c.is_ascii_digit() && minutes.len() < 3 {
+                minutes.push(c);
+            }
         }
     }
 
-    /// Navigate theme selector up
-    pub fn theme_selector_up(&mut self) {
-        let themes = ThemeType::all();
-        if self.theme_selector_index > 0 {
-            self.theme_selector_index -= 1;
-        } else {
-            self.theme_selector_index = themes.len() - 1;
+    pub fn custom_minutes_backspace(&mut self) {
+        if let Some(minutes) = self.custom_minutes.as_mut() {
+            minutes.pop();
         }
-        // Preview the theme as we navigate
-        self.animation.set_theme(themes[self.theme_selector_index]);
     }
 
-    /// Navigate theme selector down
-    pub fn theme_selector_down(&mut self) {
-        let themes = ThemeType::all();
-        self.theme_selector_index = (self.theme_selector_index + 1) % themes.len();
-        // Preview the theme as we navigate
-        self.animation.set_theme(themes[self.theme_selector_index]);
+    /// Start a new cycle with work sessions of the typed length; Esc (`start:
+    /// false`) closes the field, as does Enter on nothing
+    pub fn custom_minutes_submit(&mut self, start: bool) {
+        let minutes = self.custom_minutes.take().and_then(|m| m.parse::<u64>().ok()).filter(|&m| m > 0);
+        if let Some(minutes) = minutes.filter(|_| start) {
+            // This session only; the ones after it keep the usual length
+            self.new_cycle();
+            self.timer.set_session_length(Duration::from_secs(minutes * 60));
+            self.team_broadcast();
+        }
     }
 
-    /// Confirm theme selection
-    pub fn theme_selector_confirm(&mut self) {
-        let themes = ThemeType::all();
-        self.animation.set_theme(themes[self.theme_selector_index]);
-        self.theme_selector_open = false;
+    /// Move the preset picker's selection, wrapping around
+    pub fn preset_picker_move(&mut self, down: bool) {
+        let count = self.presets.len() + 1;
+        if let Some(index) = self.preset_picker {
+            self.preset_picker = Some(if down { (index + 1) % count } else { (index + count - 1) % count });
+        }
     }
 
-    /// Cancel theme selection (restore previous)
-    pub fn theme_selector_cancel(&mut self) {
-        self.theme_selector_open = false;
-        // Theme already set during navigation, just close
+    /// Close the picker and switch to the selected preset, or back to the
+    /// user's own settings on "No preset"; only the choice is saved
+    pub fn preset_picker_confirm(&mut self) {
+        let Some(index) = self.preset_picker.take() else {
+            return;
+        };
+        match index.checked_sub(1).and_then(|i| self.presets.get(i)).cloned() {
+            Some(preset) => {
+                self.use_preset(&preset);
+                self.config.preset = Some(preset.name.clone());
+            }
+            None => {
+                self.drop_preset();
+                self.config.preset = None;
+            }
+        }
+        let _ = self.config.save();
     }
 
-    /// Toggle auto-rotation of themes
-    pub fn toggle_auto_rotate(&mut self) {
-        self.auto_rotate = !self.auto_rotate;
+    /// Take a preset's session lengths, keep rotation to its themes and play
+    /// with its font and sounds over the config's
+    fn use_preset(&mut self, preset: &Preset) {
+        let minutes = |m: u64| Duration::from_secs(m * 60);
+        let timer = &self.timer;
+        self.own_lengths
+            .get_or_insert([timer.work_duration, timer.short_break_duration, timer.long_break_duration]);
+        self.timer.set_work_duration(minutes(preset.durations.work));
+        self.timer.set_short_break_duration(minutes(preset.durations.short_break));
+        self.timer.set_long_break_duration(minutes(preset.durations.long_break));
+        self.preset_themes = preset.theme_types();
+        if let Some(&first) = self.preset_themes.first().filter(|_| !self.preset_themes.contains(&self.animation.current_theme)) {
+            self.animation.set_theme(first);
+        }
+        self.ambient_enabled = preset.sounds.ambient.unwrap_or(self.config.ambient.enabled);
+        self.preset = Some(preset.clone());
+        if !self.theme_font {
+            self.restore_default_font();
+        }
     }
 
-    /// Toggle hints visibility
-    pub fn toggle_hints(&mut self) {
-        self.hints_visible = !self.hints_visible;
-        if !self.hints_visible {
-            // Show flash message for ~2 seconds (20 frames at 10fps)
-            self.hint_flash_frames = 20;
+    /// Back to the session lengths from before the preset, any theme, and the
+    /// config's font and sounds
+    fn drop_preset(&mut self) {
+        if let Some([work, short_break, long_break]) = self.own_lengths.take() {
+            self.timer.set_work_duration(work);
+            self.timer.set_short_break_duration(short_break);
+            self.timer.set_long_break_duration(long_break);
+        }
+        self.preset_themes.clear();
+        self.ambient_enabled = self.config.ambient.enabled;
+        self.preset = None;
+        if !self.theme_font {
+            self.restore_default_font();
         }
     }
 
-    pub fn tick(&mut self) {
-        // Always tick animation (for menu preview too)
-        self.animation.tick(&self.timer.state, self.auto_rotate);
+    /// Whether the final countdown ticks: the preset's choice, else the config's
+    fn countdown_ticks(&self) -> bool {
+        self.preset.as_ref().and_then(|p| p.sounds.countdown_ticks).unwrap_or(self.config.countdown_ticks)
+    }
 
-        // Countdown hint flash
-        if self.hint_flash_frames > 0 {
-            self.hint_flash_frames -= 1;
+    /// Show the stats screen (history is re-read so it includes this run)
+    pub fn open_stats(&mut self) {
+        self.stats_return = self.screen;
+        self.stats_records = history::load().unwrap_or_default();
+        self.screen = AppScreen::Stats;
+    }
+
+    pub fn close_stats(&mut self) {
+        self.screen = self.stats_return;
+    }
+
+    /// Show the reminders and when each comes next
+    pub fn open_reminders(&mut self) {
+        if self.screen != AppScreen::Reminders {
+            self.reminders_return = self.screen;
         }
+        self.reminder_selection = self.reminder_selection.min(self.config.reminders.len().saturating_sub(1));
+        self.screen = AppScreen::Reminders;
+    }
 
-        if self.screen == AppScreen::Timer {
-            let previous_state = self.timer.state.clone();
-            self.timer.tick();
+    pub fn close_reminders(&mut self) {
+        self.screen = self.reminders_return;
+    }
 
-            // Check for state transition to send notification
-            if !matches!(self.timer.state, TimerState::Idle)
-                && !matches!(self.timer.state, TimerState::Paused(_))
-                && std::mem::discriminant(&previous_state)
-                    != std::mem::discriminant(&self.timer.state)
-            {
-                let msg = match previous_state {
-                    TimerState::Work { .. } => Some("Work session"),
-                    TimerState::ShortBreak { .. } => Some("Short break"),
-                    TimerState::LongBreak => Some("Long break"),
-                    _ => None,
-                };
-                if let Some(session_type) = msg {
-                    notify_session_end(session_type);
+    pub fn reminders_move(&mut self, down: bool) {
+        let (index, last) = (self.reminder_selection, self.config.reminders.len().saturating_sub(1));
+        self.reminder_selection = if down { (index + 1).min(last) } else { index.saturating_sub(1) };
+    }
+
+    /// Switch the selected reminder on or off (and remember it)
+    pub fn toggle_reminder(&mut self) {
+        let i = self.reminder_selection;
+        let Some(reminder) = self.config.reminders.get_mut(i) else {
+            return;
+        };
+        reminder.enabled = !reminder.enabled;
+        self.reminders.restart(&self.config.reminders, i, self.timer.clock().now());
+        let _ = self.config.save();
+    }
+
+    /// Make the selected reminder come more or less often, counting from now
+    pub fn adjust_reminder(&mut self, minutes: i64) {
+        let i = self.reminder_selection;
+        let Some(reminder) = self.config.reminders.get_mut(i) else {
+            return;
+        };
+        let every = reminder.every_minutes.max(reminders::MIN_EVERY_MINUTES) as i64 + minutes;
+        reminder.every_minutes = every.max(reminders::MIN_EVERY_MINUTES as i64) as u64;
+        self.reminders.restart(&self.config.reminders, i, self.timer.clock().now());
+        let _ = self.config.save();
+    }
+
+    /// Count the selected reminder from now again
+    pub fn restart_reminder(&mut self) {
+        self.reminders.restart(&self.config.reminders, self.reminder_selection, self.timer.clock().now());
+    }
+
+    /// Toast and desktop notification for the reminders that are due
+    fn fire_reminders(&mut self) {
+        for i in self.reminders.due(&self.config.reminders, self.timer.clock().now()) {
+            let message = self.config.reminders[i].message.clone();
+            if !self.config.silent && self.config.notifications.desktop.enabled {
+                notification::notify_desktop(&message);
+            }
+            self.show_toast(format!("Reminder: {}", message));
+        }
+    }
+
+    /// Walkthrough on the very first start, "What's new" on the first start after an upgrade
+    pub fn show_first_run(&mut self) {
+        match self.config.last_seen_version.as_deref() {
+            None => self.onboarding_step = Some(0),
+            Some(onboarding::VERSION) => {}
+            Some(seen) => {
+                let releases = onboarding::releases_since(Some(seen));
+                if releases.is_empty() {
+                    self.mark_version_seen();
+                } else {
+                    self.open_whats_new(releases);
                 }
             }
         }
     }
+
+    pub fn onboarding_next(&mut self) {
+        match self.onboarding_step {
+            Some(step) if step + 1 < onboarding::STEPS.len() => self.onboarding_step = Some(step + 1),
+            Some(_) => self.finish_onboarding(),
+            None => {}
+        }
+    }
+
+    pub fn onboarding_back(&mut self) {
+        if let Some(step) = self.onboarding_step {
+            self.onboarding_step = Some(step.saturating_sub(1));
+        }
+    }
+
+    /// Done or skipped: either way it is not shown again
+    pub fn finish_onboarding(&mut self) {
+        self.onboarding_step = None;
+        self.mark_version_seen();
+    }
+
+    pub fn open_whats_new(&mut self, releases: Vec<Release>) {
+        self.whats_new = releases;
+        self.whats_new_scroll = 0;
+        if self.screen != AppScreen::WhatsNew {
+            self.whats_new_return = self.screen;
+        }
+        self.screen = AppScreen::WhatsNew;
+    }
+
+    pub fn whats_new_scroll_by(&mut self, delta: i16) {
+        self.whats_new_scroll = self.whats_new_scroll.saturating_add_signed(delta);
+    }
+
+    pub fn close_whats_new(&mut self) {
+        self.screen = self.whats_new_return;
+        self.mark_version_seen();
+    }
+
+    fn mark_version_seen(&mut self) {
+        if self.config.last_seen_version.as_deref() != Some(onboarding::VERSION) {
+            self.config.last_seen_version = Some(onboarding::VERSION.to_string());
+            let _ = self.config.save();
+        }
+    }
+
+    /// Ask for an intention as a work session begins, holding the timer until answered.
+    /// Replaces the "Press Space" interstitial when both would show.
+    fn prompt_intention(&mut self) {
+        if !self.config.intentions {
+            return;
+        }
+        self.current_intention = None;
+        self.task = None;
+        self.intention_input = Some(String::new());
+        self.intention_paused = self.timer.is_running() || self.awaiting_start;
+        self.awaiting_start = false;
+        if self.timer.is_running() {
+            self.timer.toggle_pause();
+        }
+    }
+
+    pub fn intention_push(&mut self, c: char) {
+        if let Some(input) = self.intention_input.as_mut() {
+            if input.chars().count() < MAX_INTENTION_CHARS {
+                input.push(c);
+            }
+        }
+    }
+
+    pub fn intention_backspace(&mut self) {
+        if let Some(input) = self.intention_input.as_mut() {
+            input.pop();
+        }
+    }
+
+    /// Close the prompt and start the session; `keep` false discards the text (Esc)
+    pub fn intention_submit(&mut self, keep: bool) {
+        let Some(input) = self.intention_input.take() else {
+            return;
+        };
+        let (text, estimate) = history::split_estimate(&input);
+        self.current_intention = (keep && !text.is_empty()).then(|| text.to_string());
+        // An estimate given once sticks to the intention in later sessions
+        self.task = self.current_intention.as_deref().and_then(|task| {
+            let mut progress = history::task_progress(&history::load().unwrap_or_default(), task);
+            progress.estimate = estimate.or(progress.estimate);
+            progress.estimate.is_some().then_some(progress)
+        });
+        if self.intention_paused && self.timer.is_paused() {
+            self.timer.toggle_pause();
+        }
+        self.intention_paused = false;
+    }
+
+    /// Rate the work session waiting for review (None skips) and write it to history
+    pub fn finish_review(&mut self, rating: Option<u8>) {
+        if let Some(mut record) = self.pending_review.take() {
+            record.focus_rating = rating;
+            self.write_record(&record);
+        }
+    }
+
+    /// Copy today's summary card to the clipboard and save it to a file
+    pub fn share_summary(&mut self) {
+        let records = history::load().unwrap_or_default();
+        let summary = DaySummary::from_records(&records, self.now().date_naive());
+        let card = summary.card();
+        let message = match (summary::copy_to_clipboard(&card), summary::save(&summary, &card)) {
+            (Ok(()), Ok(path)) => format!("Summary copied, saved to {}", path.display()),
+            (Err(_), Ok(path)) => format!("Summary saved to {} (no clipboard tool)", path.display()),
+            (Ok(()), Err(_)) => "Summary copied (couldn't save it)".to_string(),
+            (Err(e), Err(_)) => format!("Summary: {}", e),
+        };
+        self.show_toast(message);
+    }
+
+    /// Switch to the theme featured on the menu today
+    pub fn try_theme_of_the_day(&mut self) {
+        self.animation
+            .set_theme(ThemeType::of_the_day(self.now().date_naive()));
+    }
+
+    /// Show the font gallery, starting on the current font
+    pub fn open_font_gallery(&mut self) {
+        self.gallery_return = self.screen;
+        self.gallery_index = DigitFont::every()
+            .iter()
+            .position(|&f| f == self.animation.current_font)
+            .unwrap_or(0);
+        self.screen = AppScreen::FontGallery;
+    }
+
+    /// Move the gallery selection by `delta` cards (clamped to the list)
+    pub fn gallery_move(&mut self, delta: isize) {
+        let last = DigitFont::every().len() as isize - 1;
+        self.gallery_index = (self.gallery_index as isize + delta).clamp(0, last) as usize;
+    }
+
+    /// Move the gallery selection one row up or down
+    pub fn gallery_move_row(&mut self, down: bool) {
+        let columns = font_gallery::columns(self.scaling.width) as isize;
+        self.gallery_move(if down { columns } else { -columns });
+    }
+
+    /// Use the selected font from now on (and remember it across runs)
+    pub fn gallery_select(&mut self) {
+        let font = DigitFont::every()[self.gallery_index];
+        self.adaptive_font = false;
+        self.animation.set_font(font);
+        self.config.font = Some(font.slug());
+        let _ = self.config.save();
+        self.close_font_gallery();
+    }
+
+    pub fn close_font_gallery(&mut self) {
+        self.screen = self.gallery_return;
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.timer.toggle_pause();
+        self.team_broadcast();
+    }
+
+    /// Space toggles pause; held down (autorepeat) it opens the quick actions
+    /// instead, leaving the timer as it was before the first press
+    pub fn space_press(&mut self) {
+        let gap = self.last_space_press.map(|last| last.elapsed());
+        let quick = gap.is_some_and(|gap| gap <= SPACE_HOLD_GAP);
+        let in_row = gap.is_some_and(|gap| gap <= SPACE_REPEAT_DELAY);
+        self.last_space_press = Some(Instant::now());
+        if !in_row || (!quick && self.space_presses >= 2) {
+            self.space_presses = 0;
+        }
+        if self.space_presses == 0 {
+            self.space_was_paused = self.timer.is_paused();
+        }
+        self.space_presses += 1;
+
+        // A third press hard on the second is a key being held
+        if quick && self.space_presses >= 3 {
+            self.space_presses = 0;
+            if self.timer.is_paused() != self.space_was_paused {
+                self.toggle_pause();
+            }
+            self.toggle_quick_actions();
+        } else {
+            self.toggle_pause();
+        }
+    }
+
+    /// Open the quick actions popup on its first entry, or close it
+    pub fn toggle_quick_actions(&mut self) {
+        self.quick_actions = match self.quick_actions {
+            Some(_) => None,
+            None => Some(0),
+        };
+    }
+
+    /// Move the quick actions selection, wrapping around
+    pub fn quick_actions_move(&mut self, down: bool) {
+        let count = QuickAction::all().len();
+        if let Some(index) = self.quick_actions {
+            self.quick_actions = Some(if down { (index + 1) % count } else { (index + count - 1) % count });
+        }
+    }
+
+    /// Close the popup and run the selected action
+    pub fn quick_actions_confirm(&mut self) {
+        let Some(action) = self.quick_actions.take().and_then(|i| QuickAction::all().get(i).copied()) else {
+            return;
+        };
+        match action {
+            QuickAction::Pause => self.toggle_pause(),
+            QuickAction::AddFive => self.extend_session(QUICK_EXTEND),
+            QuickAction::Skip => self.skip_to_next(),
+            QuickAction::LockTheme => self.toggle_auto_rotate(),
+            QuickAction::Zen => self.toggle_hints(),
+            QuickAction::Quit => self.request_quit_to_menu(),
+        }
+    }
+
+    /// More time on the session under way, keeping its elapsed time
+    fn extend_session(&mut self, extra: Duration) {
+        if SessionKind::from_state(&self.timer.state).is_none() {
+            return;
+        }
+        self.timer.set_session_length(self.timer.session_duration() + extra);
+        self.team_broadcast();
+        let secs = self.timer.remaining.as_secs();
+        self.show_toast(format!("+{} min: {}:{:02} left", extra.as_secs() / 60, secs / 60, secs % 60));
+    }
+
+    pub fn reset_session(&mut self) {
+        self.push_undo("reset");
+        self.timer.reset_current_session();
+        self.animation.reset();
+        self.team_broadcast();
+    }
+
+    /// Leave the timer, asking first if that would abandon a work session
+    pub fn request_quit_to_menu(&mut self) {
+        if self.break_locked() {
+            self.strict_escape_press();
+            return;
+        }
+
+        // Nothing to abandon while the next session is still waiting to start
+        if self.awaiting_start || SessionKind::from_state(&self.timer.state) != Some(SessionKind::Work) {
+            self.quit_to_menu();
+            return;
+        }
+
+        // Hold the session still while the user decides
+        self.quit_confirm_paused = self.timer.is_running();
+        if self.quit_confirm_paused {
+            self.timer.toggle_pause();
+        }
+        self.quit_confirm_open = true;
+    }
+
+    /// True while strict breaks hold the user in the current break
+    pub fn break_locked(&self) -> bool {
+        self.config.strict_breaks
+            && matches!(
+                SessionKind::from_state(&self.timer.state),
+                Some(SessionKind::ShortBreak | SessionKind::LongBreak)
+            )
+    }
+
+    /// Count a `q` press during a strict break; holding it long enough leaves
+    fn strict_escape_press(&mut self) {
+        let held = self
+            .last_escape_press
+            .is_some_and(|last| last.elapsed() <= STRICT_ESCAPE_GAP);
+        self.strict_escape_presses = if held { self.strict_escape_presses + 1 } else { 1 };
+        self.last_escape_press = Some(Instant::now());
+
+        if self.strict_escape_presses >= STRICT_ESCAPE_PRESSES {
+            self.strict_escape_presses = 0;
+            if let Some(kind) = SessionKind::from_state(&self.timer.state).filter(|_| !self.awaiting_start) {
+                self.record_session(kind, SessionOutcome::Abandoned, self.timer.elapsed(), self.timer.paused_time());
+            }
+            self.quit_to_menu();
+        } else {
+            let held_pct = self.strict_escape_presses * 100 / STRICT_ESCAPE_PRESSES;
+            self.flash_command_message(format!("Strict break - hold q to leave ({}%)", held_pct));
+        }
+    }
+
+    pub fn focus_changed(&mut self, focused: bool) {
+        self.focused = focused;
+        if focused {
+            self.last_break_nag = None;
+        }
+    }
+
+    /// Confirm the dialog: record the work session as abandoned and go to the menu
+    pub fn quit_confirm_accept(&mut self) {
+        self.quit_confirm_open = false;
+        let (elapsed, paused) = (self.timer.elapsed(), self.timer.paused_time());
+        self.record_session(SessionKind::Work, SessionOutcome::Abandoned, elapsed, paused);
+        self.quit_to_menu();
+    }
+
+    /// Dismiss the dialog and carry on with the session
+    pub fn quit_confirm_cancel(&mut self) {
+        self.quit_confirm_open = false;
+        if self.quit_confirm_paused && self.timer.is_paused() {
+            self.timer.toggle_pause();
+        }
+        self.quit_confirm_paused = false;
+    }
+
+    pub fn quit_to_menu(&mut self) {
+        self.note_last_run();
+        self.finish_review(None);
+        self.expire_undo(true);
+        self.intention_input = None;
+        self.current_intention = None;
+        self.task = None;
+        self.awaiting_start = false;
+        self.screen = AppScreen::Menu;
+        // A fresh cycle, at the lengths in use (set with `:work`, or the preset's)
+        let timer = PomodoroTimer::with_clock(self.timer.clock());
+        let old = std::mem::replace(&mut self.timer, timer);
+        self.timer.set_work_duration(old.work_duration);
+        self.timer.set_short_break_duration(old.short_break_duration);
+        self.timer.set_long_break_duration(old.long_break_duration);
+        self.animation.reset();
+    }
+
+    /// Number work laps on from the pomodoros already completed today
+    fn seed_laps(&mut self) {
+        let records = history::load().unwrap_or_default();
+        let today = self.now().date_naive();
+        let done = history::completed_on(&records, today);
+        self.timer.lap_target = self.config.lap_target;
+        self.timer.set_laps_done(done.min(u8::MAX as usize) as u8);
+        if let Some(agenda) = self.agenda.as_mut() {
+            agenda.seed(&records, today);
+        }
+        self.apply_agenda();
+    }
+
+    /// Walk through a day template from config.json; false when there is no such template
+    pub fn set_template(&mut self, name: &str) -> bool {
+        let Some(blocks) = self.config.templates.get(name) else {
+            return false;
+        };
+        self.agenda = Some(Agenda::new(name, blocks.clone()));
+        self.seed_laps();
+        true
+    }
+
+    /// Take the session lengths of the template block in progress; the session
+    /// running now keeps its own
+    fn apply_agenda(&mut self) {
+        let Some(block) = self.agenda.as_ref().and_then(Agenda::current_block) else {
+            return;
+        };
+        let (work, short_break) = (block.work_minutes * 60, block.break_minutes * 60);
+        let running = SessionKind::from_state(&self.timer.state);
+        if running != Some(SessionKind::Work) {
+            self.timer.set_work_duration(Duration::from_secs(work));
+        }
+        if running != Some(SessionKind::ShortBreak) {
+            self.timer.set_short_break_duration(Duration::from_secs(short_break));
+        }
+    }
+
+    /// Show the whole agenda or just the block in progress
+    pub fn toggle_agenda(&mut self) {
+        if self.agenda.is_none() {
+            self.show_toast("No day template (pomo --template <name>)");
+            return;
+        }
+        self.agenda_collapsed = !self.agenda_collapsed;
+        self.show_toast(if self.agenda_collapsed { "Agenda: collapsed" } else { "Agenda: expanded" });
+    }
+
+    /// Remember the session lengths in use and the session being left
+    fn note_last_run(&mut self) {
+        let mut last = LastRun {
+            work_secs: Some(self.timer.work_duration.as_secs()),
+            short_break_secs: Some(self.timer.short_break_duration.as_secs()),
+            long_break_secs: Some(self.timer.long_break_duration.as_secs()),
+            category: self.timer.category.clone(),
+            cycle_step: self.timer.cycle_step(),
+            ..LastRun::default()
+        };
+        last.leave(SessionKind::from_state(&self.timer.state).map(|_| self.timer.snapshot()));
+        self.offer_continue(last.clone());
+        self.last_run = Some(last);
+    }
+
+    /// Write what `pomowise resume` picks up; a run that left no session keeps
+    /// the one the run before it left. Incognito and locked runs write nothing.
+    pub fn save_last_run(&mut self) {
+        if !history::mode().is_persistent() {
+            return;
+        }
+        if self.screen == AppScreen::Timer {
+            self.note_last_run();
+        }
+        let mut last = self.last_run.take().unwrap_or_else(LastRun::load);
+        last.theme = (!self.auto_rotate).then(|| self.animation.current_theme.slug());
+        last.font = (!self.adaptive_font).then(|| self.animation.current_font.slug());
+        let _ = last.save();
+    }
+
+    /// Straight into the timer with the previous run's theme, font and session
+    /// lengths, continuing the session it left unless `fresh`
+    pub fn resume_last(&mut self, fresh: bool) {
+        let last = LastRun::load();
+        if let Some(theme) = last.theme.as_deref().and_then(ThemeType::from_name) {
+            self.animation.set_theme(theme);
+            self.auto_rotate = false;
+            self.last_theme = theme;
+        }
+        if let Some(font) = last.font.as_deref().and_then(DigitFont::from_name) {
+            self.animation.set_font(font);
+            self.adaptive_font = false;
+            self.last_font = font;
+        }
+        let length = |secs: Option<u64>| secs.filter(|&s| s > 0).map(Duration::from_secs);
+        if let Some(duration) = length(last.work_secs) {
+            self.timer.set_work_duration(duration);
+        }
+        if let Some(duration) = length(last.short_break_secs) {
+            self.timer.set_short_break_duration(duration);
+        }
+        if let Some(duration) = length(last.long_break_secs) {
+            self.timer.set_long_break_duration(duration);
+        }
+        if let Some(name) = &last.category {
+            let minutes = self.config.categories.iter().find(|c| &c.name == name).and_then(|c| c.work_minutes);
+            self.timer.set_category(Some(name.clone()), minutes.map(|m| Duration::from_secs(m * 60)));
+        }
+
+        self.screen = AppScreen::Timer;
+        self.seed_laps();
+        self.animation.reset();
+        if fresh {
+            self.timer.start();
+            self.prompt_intention();
+        } else {
+            self.pick_up(&last);
+        }
+        self.team_broadcast();
+    }
+
+    /// Continue the session `last` left, or start a fresh one at its step in the cycle
+    fn pick_up(&mut self, last: &LastRun) {
+        match last.recent_session() {
+            Some(session) => {
+                let remaining = Duration::from_secs(session.remaining_secs);
+                self.timer.restore(session.state.clone(), remaining, session.cycle_position);
+                self.show_toast(format!(
+                    "Resumed {} · {:02}:{:02} left",
+                    self.timer.session_name(),
+                    session.remaining_secs / 60,
+                    session.remaining_secs % 60
+                ));
+            }
+            None => {
+                self.timer.start();
+                if let Some(step) = last.cycle_step {
+                    self.timer.jump_to(step);
+                }
+                if SessionKind::from_state(&self.timer.state) == Some(SessionKind::Work) {
+                    self.prompt_intention();
+                }
+            }
+        }
+    }
+
+    /// Skip to next interval/cycle AND change theme (Tab key)
+    pub fn skip_to_next(&mut self) {
+        if self.break_locked() {
+            self.flash_command_message("Strict breaks: skipping is disabled".to_string());
+            return;
+        }
+        self.push_undo("skip");
+        // A session still waiting to start was never had, so there's nothing to record
+        if let Some(kind) = SessionKind::from_state(&self.timer.state).filter(|_| !self.awaiting_start) {
+            self.record_session(kind, SessionOutcome::Skipped, self.timer.elapsed(), self.timer.paused_time());
+        }
+        self.timer.advance_state();
+        let filter = self.theme_filter();
+        self.animation.rotate_theme(&filter);
+        self.team_broadcast();
+        if SessionKind::from_state(&self.timer.state) == Some(SessionKind::Work) {
+            self.prompt_intention();
+        }
+    }
+
+    /// Remember the timer as it is before a skip or reset
+    fn push_undo(&mut self, action: &'static str) {
+        if self.undo_stack.len() == UNDO_DEPTH {
+            self.drop_undo();
+        }
+        self.undo_stack.push_back(Undo {
+            action,
+            timer: self.timer.savestate(),
+            intention: self.current_intention.clone(),
+            task: self.task.clone(),
+            record: None,
+        });
+    }
+
+    /// Take back the last skip or reset, if it was under 10 seconds ago
+    pub fn undo(&mut self) {
+        self.expire_undo(false);
+        let Some(undo) = self.undo_stack.pop_back() else {
+            self.show_toast("Nothing to undo");
+            return;
+        };
+        self.timer.load_savestate(&undo.timer);
+        self.current_intention = undo.intention;
+        self.task = undo.task;
+        self.intention_input = None;
+        self.intention_paused = false;
+        self.animation.reset();
+        self.team_broadcast();
+        self.show_toast(format!("Undid {}", undo.action));
+    }
+
+    /// Drop the undo entries past their window (all of them, when `all`)
+    fn expire_undo(&mut self, all: bool) {
+        while let Some(undo) = self.undo_stack.front() {
+            if !all && self.timer.savestate_age(&undo.timer) < UNDO_WINDOW {
+                break;
+            }
+            self.drop_undo();
+        }
+    }
+
+    /// Drop the oldest undo entry, writing the skipped session it held back
+    fn drop_undo(&mut self) {
+        if let Some(record) = self.undo_stack.pop_front().and_then(|undo| undo.record) {
+            self.write_record(&record);
+        }
+    }
+
+    /// Open the go-to menu on the current session, or close it
+    pub fn toggle_goto_menu(&mut self) {
+        self.goto_menu = match self.goto_menu {
+            Some(_) => None,
+            None => Some(self.timer.cycle_step().unwrap_or(0)),
+        };
+    }
+
+    /// Move the go-to selection, wrapping around the cycle
+    pub fn goto_menu_move(&mut self, down: bool) {
+        if let Some(step) = self.goto_menu {
+            self.goto_menu = Some(if down {
+                (step + 1) % CYCLE_STEPS
+            } else {
+                (step + CYCLE_STEPS - 1) % CYCLE_STEPS
+            });
+        }
+    }
+
+    /// Jump to the selected session; the one left behind is logged as adjusted
+    pub fn goto_menu_confirm(&mut self) {
+        let Some(step) = self.goto_menu.take() else { return };
+        if self.break_locked() {
+            self.flash_command_message("Strict breaks: skipping is disabled".to_string());
+            return;
+        }
+        if let Some(kind) = SessionKind::from_state(&self.timer.state).filter(|_| !self.awaiting_start) {
+            self.record_session(kind, SessionOutcome::Adjusted, self.timer.elapsed(), self.timer.paused_time());
+        }
+        self.timer.jump_to(step);
+        self.team_broadcast();
+        self.show_toast(format!("Jumped to {}", PomodoroTimer::step_name(step)));
+        if SessionKind::from_state(&self.timer.state) == Some(SessionKind::Work) {
+            self.prompt_intention();
+        }
+    }
+
+    /// Whether the user is at work during a running break: input anywhere on the
+    /// desktop in the last few seconds, or without an idle time, another window focused
+    fn working_through_break(&mut self) -> bool {
+        if !self.config.break_check.enabled
+            || !self.in_break()
+            || !self.timer.is_running()
+            || self.break_activity.is_some()
+            || self.timer.elapsed() < BREAK_CHECK_GRACE
+        {
+            return false;
+        }
+        match self.idle.idle(Instant::now()) {
+            Some(idle) => idle < idle::ACTIVE_WITHIN,
+            None => !self.focused,
+        }
+    }
+
+    /// Whether the desktop has been idle for a while, or all of a session `length` long
+    fn away_for(&mut self, length: Duration) -> bool {
+        self.config.break_check.enabled
+            && self.idle.idle(Instant::now()).is_some_and(|idle| idle >= idle::AWAY_FOR.min(length))
+    }
+
+    /// Take the break that ran out while the user was away again, `extend_minutes` long
+    pub fn extend_break(&mut self) {
+        let Some(step) = self.break_extension.take().filter(|_| self.awaiting_start) else { return };
+        let minutes = self.config.break_check.extend_minutes.max(1);
+        self.timer.jump_to(step);
+        self.timer.set_session_length(Duration::from_secs(minutes * 60));
+        self.timer.toggle_pause();
+        self.team_broadcast();
+        self.show_toast(format!("Break extended: {} more min", minutes));
+    }
+
+    fn in_break(&self) -> bool {
+        matches!(
+            SessionKind::from_state(&self.timer.state),
+            Some(SessionKind::ShortBreak | SessionKind::LongBreak)
+        )
+    }
+
+    /// Start (or stop) the breathing exercise for this break
+    pub fn toggle_breathing(&mut self) {
+        if matches!(self.break_activity, Some(BreakActivity::Breathing(_))) {
+            self.break_activity = None;
+        } else if self.in_break() {
+            self.break_activity = Some(BreakActivity::Breathing(Breathing::new()));
+        } else {
+            self.show_toast("Breathing exercise: during breaks");
+        }
+    }
+
+    /// Start (or stop) the snake game for this break
+    pub fn toggle_snake(&mut self) {
+        if matches!(self.break_activity, Some(BreakActivity::Snake(_))) {
+            self.break_activity = None;
+        } else if self.in_break() {
+            self.break_activity = Some(BreakActivity::Snake(Snake::new()));
+        } else {
+            self.show_toast("Snake: during breaks");
+        }
+    }
+
+    pub fn playing_snake(&self) -> bool {
+        matches!(self.break_activity, Some(BreakActivity::Snake(_)))
+    }
+
+    pub fn snake_turn(&mut self, direction: Direction) {
+        if let Some(BreakActivity::Snake(snake)) = &mut self.break_activity {
+            snake.turn(direction);
+        }
+    }
+
+    /// New game after the snake crashed
+    pub fn snake_restart(&mut self) {
+        if let Some(BreakActivity::Snake(snake)) = &mut self.break_activity {
+            if !snake.is_alive() {
+                *snake = Snake::new();
+            }
+        }
+    }
+
+    pub fn close_break_activity(&mut self) {
+        self.break_activity = None;
+    }
+
+    /// Warn once when the next pomodoro would run past the target; clear it once reached
+    fn check_meeting(&mut self) {
+        let now = self.now();
+        let Some(meeting) = &mut self.meeting else { return };
+        let target = meeting.target.format("%H:%M").to_string();
+        if now >= meeting.target {
+            self.meeting = None;
+            self.show_toast(format!("It's {}", target));
+        } else if !meeting.warned && !meeting.plan(now, &self.timer).next_fits {
+            meeting.warned = true;
+            self.show_toast(format!("Next pomodoro won't fit before {}", target));
+        }
+    }
+
+    /// Share a local timer change with the team
+    fn team_broadcast(&mut self) {
+        if let Some(team) = &self.team {
+            let timer = TeamTimer::capture(&self.timer);
+            self.team_changed_at = timer.changed_at;
+            team.broadcast(timer);
+        }
+    }
+
+    /// Apply what peers sent since the last frame
+    fn poll_team(&mut self) {
+        let Some(events) = self.team.as_ref().map(Team::poll) else {
+            return;
+        };
+        for event in events {
+            match event {
+                TeamEvent::Joined(name) => {
+                    // Bring the newcomer up to date (keeping our change time, so newer wins)
+                    if let (Some(team), true) = (&self.team, self.team_changed_at > 0) {
+                        team.broadcast(TeamTimer {
+                            changed_at: self.team_changed_at,
+                            ..TeamTimer::capture(&self.timer)
+                        });
+                    }
+                    self.show_toast(format!("{} joined", name));
+                }
+                TeamEvent::Left(name) => self.show_toast(format!("{} left", name)),
+                TeamEvent::Timer(timer) if timer.changed_at > self.team_changed_at => {
+                    self.team_changed_at = timer.changed_at;
+                    timer.apply(&mut self.timer);
+                    if self.timer.state != TimerState::Idle && self.screen != AppScreen::Timer {
+                        self.screen = AppScreen::Timer;
+                        self.animation.reset();
+                    }
+                }
+                TeamEvent::Timer(_) => {}
+            }
+        }
+    }
+
+    /// Pause work while the window manager has a distraction focused, and
+    /// resume once it doesn't (unless the timer was resumed by hand meanwhile)
+    fn poll_window_manager(&mut self) {
+        if !self.timer.is_paused() {
+            self.distraction = None;
+        }
+        let Some(focus) = self.window_manager.as_ref().and_then(WindowManager::poll) else {
+            return;
+        };
+        let found = self.config.distractions.as_ref().and_then(|d| d.matches(&focus));
+        match (found, self.distraction.is_some()) {
+            (Some(what), false) => {
+                let working = matches!(self.timer.state, TimerState::Work { .. });
+                if self.screen == AppScreen::Timer && working && !self.awaiting_start {
+                    self.toggle_pause();
+                    self.show_toast(format!("Paused: {} is a distraction", what));
+                    self.distraction = Some(what);
+                }
+            }
+            (None, true) => {
+                self.distraction = None;
+                self.toggle_pause();
+                self.show_toast("Back to work");
+            }
+            _ => {}
+        }
+    }
+
+    /// Toggle theme selector overlay (Shift+T)
+    pub fn toggle_theme_selector(&mut self) {
+        self.theme_selector_open = !self.theme_selector_open;
+        self.animation.sequences = self.theme_sequences();
+        if self.theme_selector_open {
+            // Set selector to current theme
+            let themes = ThemeType::all();
+            self.theme_selector_index = themes
+                .iter()
+                .position(|&t| t == self.animation.current_theme)
+                .unwrap_or(0);
+        }
+    }
+
+    /// Navigate theme selector up
+    pub fn theme_selector_up(&mut self) {
+        let themes = ThemeType::all();
+        if self.theme_selector_index > 0 {
+            self.theme_selector_index -= 1;
+        } else {
+            self.theme_selector_index = themes.len() - 1;
+        }
+        // Preview the theme as we navigate
+        self.animation.set_theme(themes[self.theme_selector_index]);
+    }
+
+    /// Navigate theme selector down
+    pub fn theme_selector_down(&mut self) {
+        let themes = ThemeType::all();
+        self.theme_selector_index = (self.theme_selector_index + 1) % themes.len();
+        // Preview the theme as we navigate
+        self.animation.set_theme(themes[self.theme_selector_index]);
+    }
+
+    /// Confirm theme selection
+    pub fn theme_selector_confirm(&mut self) {
+        let themes = ThemeType::all();
+        self.animation.set_theme(themes[self.theme_selector_index]);
+        self.theme_selector_open = false;
+    }
+
+    /// Cancel theme selection (restore previous)
+    pub fn theme_selector_cancel(&mut self) {
+        self.theme_selector_open = false;
+        // Theme already set during navigation, just close
+    }
+
+    /// Whether theme changes play intros and outros: not with reduced motion, nor
+    /// while the selector previews one theme after another
+    fn theme_sequences(&self) -> bool {
+        self.config.theme_sequences && !self.animation.reduced_motion() && !self.theme_selector_open
+    }
+
+    /// Toggle auto-rotation of themes
+    pub fn toggle_auto_rotate(&mut self) {
+        self.auto_rotate = !self.auto_rotate;
+        self.show_toast(format!("Auto-rotate: {}", on_off(self.auto_rotate)));
+    }
+
+    /// Show a short-lived OSD message
+    pub fn show_toast(&mut self, message: impl Into<String>) {
+        self.toast = Some(Toast::new(message));
+    }
+
+    /// Font the digits are drawn in: the current font, stepped up to the largest
+    /// that fits during the final minute when `grow_final_minute` is on
+    pub fn display_font(&self) -> DigitFont {
+        let font = self.animation.current_font;
+        if self.config.grow_final_minute && self.timer.is_running() && self.timer.remaining <= GROW_WINDOW {
+            self.scaling.grown_font(font)
+        } else {
+            font
+        }
+    }
+
+    /// Whether the current session is shown as plain digits without animation
+    pub fn digits_only(&self) -> bool {
+        self.config.digits_only.for_state(&self.timer.state)
+    }
+
+    /// Turn digits-only on/off (None toggles) for the current session type; remembered in config
+    pub fn set_digits_only(&mut self, on: Option<bool>) {
+        let Some(kind) = SessionKind::from_state(&self.timer.state) else {
+            return;
+        };
+        let enabled = on.unwrap_or(!self.config.digits_only.get(kind));
+        self.config.digits_only.set(kind, enabled);
+        let _ = self.config.save();
+        self.show_toast(format!("Digits only ({}): {}", kind.label(), on_off(enabled)));
+    }
+
+    /// Cycle how the timer digits are colored; remembered in config
+    pub fn cycle_digit_colors(&mut self) {
+        self.config.digit_colors = self.config.digit_colors.next();
+        let _ = self.config.save();
+        self.show_toast(format!("Digit colors: {}", self.config.digit_colors.name()));
+    }
+
+    /// Label work sessions with the configured category at `index` (None clears it).
+    /// A session's category is picked before it starts: one under way keeps its
+    /// own, and the pick waits for the next work session
+    pub fn set_category(&mut self, index: Option<usize>) {
+        let category = index.and_then(|i| self.config.categories.get(i));
+        let name = category.map_or("none", |c| c.name.as_str());
+        let label = match category.and_then(|c| c.work_minutes) {
+            Some(minutes) => format!("{} ({} min)", name, minutes),
+            None => name.to_string(),
+        };
+        let under_way = self.watched_step.is_some() && SessionKind::from_state(&self.timer.state) == Some(SessionKind::Work);
+        if under_way {
+            self.next_category = Some(index);
+            self.show_toast(format!("Category: {} from the next work session", label));
+        } else {
+            self.next_category = None;
+            self.apply_category(index);
+            self.show_toast(format!("Category: {}", label));
+        }
+    }
+
+    /// Label the timer's work sessions with the category at `index`; one with its
+    /// own length re-times a work session waiting to start
+    fn apply_category(&mut self, index: Option<usize>) {
+        let category = index.and_then(|i| self.config.categories.get(i));
+        let name = category.map(|c| c.name.clone());
+        let minutes = category.and_then(|c| c.work_minutes);
+        self.timer.set_category(name, minutes.map(|m| Duration::from_secs(m * 60)));
+    }
+
+    /// Next configured category, then none, then the first again
+    pub fn cycle_category(&mut self) {
+        let current = self.next_category.unwrap_or_else(|| {
+            self.timer
+                .category
+                .as_ref()
+                .and_then(|name| self.config.categories.iter().position(|c| &c.name == name))
+        });
+        let next = match current {
+            None => 0,
+            Some(i) => i + 1,
+        };
+        self.set_category((next < self.config.categories.len()).then_some(next));
+    }
+
+    /// Step the brightness up or down by `BRIGHTNESS_STEP`, within 25-100%
+    pub fn adjust_brightness(&mut self, brighter: bool) {
+        self.brightness = if brighter {
+            self.brightness.saturating_add(BRIGHTNESS_STEP)
+        } else {
+            self.brightness.saturating_sub(BRIGHTNESS_STEP)
+        }
+        .clamp(MIN_BRIGHTNESS, MAX_BRIGHTNESS);
+        self.show_toast(format!("Brightness: {}%", self.brightness));
+    }
+
+    /// Switch the color filter; remembered in config
+    pub fn set_color_filter(&mut self, filter: ColorFilter) {
+        self.config.color_filter.filter = filter;
+        let _ = self.config.save();
+        self.show_toast(format!("Filter: {}", filter.name()));
+    }
+
+    /// Colors for the boxes, dialogs and hints, borders in the session's accent if enabled
+    pub fn chrome(&self) -> Palette {
+        let mut palette = self.config.chrome.palette();
+        if let Some(accent) = self.config.session_accents.color(&self.timer.state) {
+            palette.border = Some(accent);
+        }
+        palette
+    }
+
+    /// Switch the chrome palette; remembered in config
+    pub fn set_chrome(&mut self, chrome: Chrome) {
+        self.config.chrome = chrome;
+        let _ = self.config.save();
+        self.show_toast(format!("Chrome: {}", chrome.name()));
+    }
+
+    /// Toggle the elapsed / end-time clock
+    pub fn toggle_elapsed(&mut self) {
+        self.show_elapsed = !self.show_elapsed;
+    }
+
+    /// Event log pane on/off (None toggles)
+    pub fn set_event_log(&mut self, on: Option<bool>) {
+        self.config.event_log = on.unwrap_or(!self.config.event_log);
+        let _ = self.config.save();
+        self.show_toast(if self.config.event_log { "Event log: on" } else { "Event log: off" });
+    }
+
+    /// Silent mode on/off (None toggles): no bell, sounds, music or desktop notifications
+    pub fn set_silent(&mut self, on: Option<bool>) {
+        self.config.silent = on.unwrap_or(!self.config.silent);
+        self.notifier.silent = self.config.silent;
+        self.speaker.silent = self.config.silent;
+        let _ = self.config.save();
+        self.show_toast(if self.config.silent { "Silent mode: on" } else { "Silent mode: off" });
+    }
+
+    /// Dismiss the alert banner, or the alarm screen, and stop its bell
+    pub fn acknowledge_alert(&mut self) {
+        self.alert_banner = None;
+        self.alert_frames = 0;
+        self.alert_takeover = false;
+        self.alert_bell = None;
+    }
+
+    /// Toggle hints visibility
+    pub fn toggle_hints(&mut self) {
+        self.hints_visible = !self.hints_visible;
+        if !self.hints_visible {
+            // Show flash message for ~2 seconds (20 frames at 10fps)
+            self.hint_flash_frames = 20;
+        }
+    }
+
+    /// Open the `:` command palette
+    pub fn open_command_line(&mut self) {
+        self.command_line = Some(String::new());
+        self.command_message = None;
+    }
+
+    /// Palette ready for a new length of the session under way (`d`)
+    pub fn prompt_session_length(&mut self) {
+        if self.break_locked() {
+            self.flash_command_message("Strict breaks: the break can't be shortened".to_string());
+            return;
+        }
+        self.command_line = Some("length ".to_string());
+        self.command_message = None;
+    }
+
+    pub fn command_line_push(&mut self, c: char) {
+        if let Some(line) = self.command_line.as_mut() {
+            line.push(c);
+        }
+    }
+
+    /// Delete the last character; closes the palette when already empty
+    pub fn command_line_backspace(&mut self) {
+        match self.command_line.as_mut() {
+            Some(line) if !line.is_empty() => {
+                line.pop();
+            }
+            _ => self.command_line = None,
+        }
+    }
+
+    /// Tab completion for the current palette input
+    pub fn command_line_complete(&mut self) {
+        if let Some(line) = self.command_line.as_mut() {
+            if let Some(completed) = commands::complete(line) {
+                *line = completed;
+            }
+        }
+    }
+
+    pub fn command_line_cancel(&mut self) {
+        self.command_line = None;
+    }
+
+    /// Parse and run the palette input, then close the palette
+    pub fn command_line_submit(&mut self) {
+        let Some(line) = self.command_line.take() else {
+            return;
+        };
+        if line.trim().is_empty() {
+            return;
+        }
+        match commands::parse(&line) {
+            Ok(command) => self.execute_command(command),
+            Err(err) => self.flash_command_message(err),
+        }
+    }
+
+    fn flash_command_message(&mut self, message: String) {
+        self.command_message = Some(message);
+        // ~2 seconds at 10fps
+        self.command_message_frames = 20;
+    }
+
+    /// Run a palette command
+    pub fn execute_command(&mut self, command: Command) {
+        match command {
+            Command::Theme(theme) => self.animation.set_theme(theme),
+            Command::Fonts => self.open_font_gallery(),
+            Command::Stats => self.open_stats(),
+            Command::Reminders => self.open_reminders(),
+            Command::Summary => self.share_summary(),
+            Command::Changelog => self.open_whats_new(onboarding::releases_since(None)),
+            Command::Font(font) => {
+                self.adaptive_font = false;
+                self.animation.set_font(font);
+            }
+            Command::Work(minutes) => {
+                self.timer.set_work_duration(Duration::from_secs(minutes * 60));
+                self.team_broadcast();
+                self.flash_command_message(format!("work: {} min", minutes));
+            }
+            Command::ShortBreak(_) | Command::LongBreak(_) | Command::Length(_) if self.break_locked() => {
+                self.flash_command_message("Strict breaks: the break can't be shortened".to_string());
+            }
+            Command::ShortBreak(minutes) => {
+                self.timer.set_short_break_duration(Duration::from_secs(minutes * 60));
+                self.team_broadcast();
+                self.flash_command_message(format!("break: {} min", minutes));
+            }
+            Command::LongBreak(minutes) => {
+                self.timer.set_long_break_duration(Duration::from_secs(minutes * 60));
+                self.team_broadcast();
+                self.flash_command_message(format!("long break: {} min", minutes));
+            }
+            Command::Length(_) if SessionKind::from_state(&self.timer.state).is_none() => {
+                self.flash_command_message("length: no session under way".to_string());
+            }
+            Command::Length(length) => {
+                self.timer.set_session_length(length);
+                self.team_broadcast();
+                let secs = length.as_secs();
+                self.flash_command_message(format!("this session: {}:{:02}", secs / 60, secs % 60));
+            }
+            Command::AutoBreak(on) => {
+                self.auto_continue_break = on.unwrap_or(!self.auto_continue_break);
+                let mode = if self.auto_continue_break { "auto" } else { "wait" };
+                self.flash_command_message(format!("work -> break: {}", mode));
+            }
+            Command::AutoWork(on) => {
+                self.auto_continue_work = on.unwrap_or(!self.auto_continue_work);
+                let mode = if self.auto_continue_work { "auto" } else { "wait" };
+                self.flash_command_message(format!("break -> work: {}", mode));
+            }
+            Command::DigitsOnly(on) => self.set_digits_only(on),
+            Command::Filter(filter) => self.set_color_filter(filter),
+            Command::Chrome(chrome) => self.set_chrome(chrome),
+            Command::Aspect(aspect) => {
+                self.scaling.set_cell_aspect(aspect);
+                self.animation.set_cell_aspect(self.scaling.cell_aspect);
+                self.config.cell_aspect = self.scaling.cell_aspect;
+                let _ = self.config.save();
+                self.flash_command_message(format!("cell aspect: {:.2}", self.config.cell_aspect));
+            }
+            Command::Motion(scale) => {
+                self.animation.set_motion(scale);
+                self.config.motion = self.animation.params().motion;
+                let _ = self.config.save();
+                self.flash_command_message(format!("motion: {:.0}%", self.config.motion * 100.0));
+            }
+            Command::Dim(percent) => {
+                self.config.color_filter.dim = percent;
+                let _ = self.config.save();
+                self.flash_command_message(format!("dim: {}%", percent));
+            }
+            Command::Until(Some(time)) => {
+                let now = self.now();
+                let meeting = Meeting::at(time, now);
+                let plan = meeting.plan(now, &self.timer);
+                self.flash_command_message(format!(
+                    "until {}: {} pomodoros fit",
+                    meeting.target.format("%H:%M"),
+                    plan.pomodoros
+                ));
+                self.meeting = Some(meeting);
+            }
+            Command::Until(None) => {
+                self.meeting = None;
+                self.flash_command_message("until: off".to_string());
+            }
+            Command::Transparent(on) => {
+                self.config.transparent_background = on.unwrap_or(!self.config.transparent_background);
+                background::set_transparent(self.config.transparent_background);
+                let _ = self.config.save();
+                self.flash_command_message(format!(
+                    "terminal background: {}",
+                    on_off(self.config.transparent_background)
+                ));
+            }
+            Command::Braille(on) => {
+                self.config.braille = on.unwrap_or(!self.config.braille);
+                canvas::set_high_res(self.config.braille);
+                let _ = self.config.save();
+                self.flash_command_message(format!("braille: {}", on_off(self.config.braille)));
+            }
+            Command::Diegetic(on) => {
+                self.config.diegetic_progress = on.unwrap_or(!self.config.diegetic_progress);
+                let _ = self.config.save();
+                let theme = self.animation.current_theme;
+                let message = if self.config.diegetic_progress && !theme.shows_progress() {
+                    format!("progress in the scene: on (not in {})", theme.name())
+                } else {
+                    format!("progress in the scene: {}", on_off(self.config.diegetic_progress))
+                };
+                self.flash_command_message(message);
+            }
+            Command::Ring(on) => {
+                let ring = &mut self.config.progress_ring;
+                ring.visible = on.unwrap_or(!ring.visible);
+                let _ = self.config.save();
+                self.flash_command_message(format!("progress ring: {}", on_off(self.config.progress_ring.visible)));
+            }
+            Command::Category(None) => self.set_category(None),
+            Command::Category(Some(name)) => {
+                let query = name.to_lowercase();
+                let found = self
+                    .config
+                    .categories
+                    .iter()
+                    .position(|c| c.name.to_lowercase() == query)
+                    .or_else(|| self.config.categories.iter().position(|c| c.name.to_lowercase().starts_with(&query)));
+                match found {
+                    Some(index) => self.set_category(Some(index)),
+                    None => self.flash_command_message(format!("unknown category: {}", name)),
+                }
+            }
+            Command::Music(on) => {
+                self.music_enabled = on.unwrap_or(!self.music_enabled);
+                let message = if music_config(&self.preset, &self.config).is_empty() {
+                    "music: nothing configured".to_string()
+                } else {
+                    format!("music: {}", on_off(self.music_enabled))
+                };
+                self.flash_command_message(message);
+            }
+            Command::Ambient(on) => {
+                self.ambient_enabled = on.unwrap_or(!self.ambient_enabled);
+                let theme = self.animation.current_theme;
+                let message = match self.config.ambient.for_theme(&theme.slug()) {
+                    None if self.ambient_enabled => format!("ambient: on ({} has no sound)", theme.name()),
+                    _ => format!("ambient: {}", on_off(self.ambient_enabled)),
+                };
+                self.flash_command_message(message);
+            }
+            Command::Mute(on) => {
+                self.muted = on.unwrap_or(!self.muted);
+                self.flash_command_message(format!("mute: {}", on_off(self.muted)));
+            }
+            Command::Silent(on) => self.set_silent(on),
+            Command::Zen(item, on) => {
+                let keep = on.unwrap_or(!self.config.zen.get(item));
+                self.config.zen.set(item, keep);
+                let _ = self.config.save();
+                self.flash_command_message(format!("zen {}: {}", item.name(), on_off(keep)));
+            }
+            Command::Log(on) => self.set_event_log(on),
+            Command::Skip => self.skip_to_next(),
+            Command::Pause => self.toggle_pause(),
+            Command::Reset => self.reset_session(),
+            Command::Menu => self.request_quit_to_menu(),
+            Command::Quit if self.break_locked() => {
+                self.flash_command_message("Strict breaks: hold q to leave the break".to_string());
+            }
+            Command::Quit => self.should_quit = true,
+        }
+    }
+
+    /// Rotation constraints right now: session mood plus time-of-day themes
+    fn theme_filter(&self) -> ThemeFilter {
+        ThemeFilter {
+            mood: self.config.moods.for_state(&self.timer.state),
+            // A preset's own themes win over the time of day
+            themes: if self.preset_themes.is_empty() {
+                self.config.time_of_day.themes_at(self.now().time())
+            } else {
+                self.preset_themes.clone()
+            },
+            exclude: self.animation.crashed_themes().to_vec(),
+        }
+    }
+
+    /// Append a finished session to the history file (and sync it if configured)
+    fn record_session(&mut self, kind: SessionKind, outcome: SessionOutcome, duration: Duration, paused: Duration) {
+        self.events.publish(self.now(), AppEvent::Ended(kind, outcome));
+        let mut record = SessionRecord::new(kind, outcome, duration);
+        record.paused_secs = paused.as_secs();
+        if kind == SessionKind::Work {
+            record.intention = self.current_intention.take();
+            record.estimate = self.task.take().and_then(|task| task.estimate);
+            record.category = self.timer.category.clone();
+            record.block = self.agenda.as_ref().and_then(Agenda::current_block).map(|b| b.name.clone());
+            record.theme = Some(self.animation.current_theme.slug());
+            record.project = self.project.take();
+        }
+        // The day's target reached: offer the summary card
+        if kind == SessionKind::Work && outcome == SessionOutcome::Completed && self.timer.laps_done() == self.timer.lap_target {
+            self.show_toast("Daily target reached! :summary copies your day's card");
+        }
+        if let (Some(agenda), SessionKind::Work, SessionOutcome::Completed) = (self.agenda.as_mut(), kind, outcome) {
+            agenda.complete_one();
+            if agenda.current().is_none() {
+                let message = format!("{}: all blocks done", agenda.name);
+                self.show_toast(message);
+            }
+            self.apply_agenda();
+        }
+
+        // A skip can still be undone, so its record waits out the undo window
+        if outcome == SessionOutcome::Skipped {
+            if let Some(undo) = self.undo_stack.back_mut() {
+                undo.record = Some(record);
+                return;
+            }
+        }
+
+        // A finished work session waits for its focus rating before it is written
+        self.finish_review(None);
+        if kind == SessionKind::Work && outcome == SessionOutcome::Completed && self.config.intentions {
+            self.pending_review = Some(record);
+            return;
+        }
+        self.write_record(&record);
+    }
+
+    /// Recount today's numbers for the menu from the history
+    fn refresh_day_summary(&mut self) {
+        let records = history::load().unwrap_or_default();
+        self.day_summary = records
+            .iter()
+            .any(|r| r.kind == SessionKind::Work)
+            .then(|| DaySummary::from_records(&records, self.now().date_naive()));
+    }
+
+    fn write_record(&mut self, record: &SessionRecord) {
+        let _ = history::append(record);
+        // Incognito and locked runs leave the notes alone too
+        if let (Some(journal), true) = (&self.config.journal, history::mode().is_persistent()) {
+            let _ = journal.log(record);
+        }
+        if record.kind != SessionKind::Work {
+            self.break_compliance = history::load().ok().and_then(|r| history::break_compliance(&r));
+        } else {
+            self.refresh_day_summary();
+        }
+        if let Some(sync_config) = &self.config.sync {
+            sync::sync_in_background(sync_config);
+        }
+    }
+
+    /// Publish a session starting, pausing or resuming, however it came about
+    fn watch_session(&mut self) {
+        let step = match self.screen {
+            AppScreen::Timer if !self.awaiting_start => self.timer.cycle_step(),
+            _ => None,
+        };
+        // The intention prompt holds the session without it counting as a pause
+        let paused = self.timer.is_paused() && self.intention_input.is_none();
+        if step != self.watched_step {
+            if let (Some(_), Some(kind)) = (step, SessionKind::from_state(&self.timer.state)) {
+                if kind == SessionKind::Work {
+                    self.project = self.config.auto_label.detect();
+                    if let Some(index) = self.next_category.take() {
+                        self.apply_category(index);
+                    }
+                }
+                self.events.publish(self.now(), AppEvent::Started(kind));
+            }
+            self.watched_step = step;
+        } else if step.is_some() && paused != self.watched_paused {
+            let event = match (&self.distraction, paused) {
+                (Some(what), true) => AppEvent::Interrupted(what.clone()),
+                (None, true) => AppEvent::Paused,
+                (_, false) => AppEvent::Resumed,
+            };
+            self.events.publish(self.now(), event);
+        }
+        self.watched_paused = paused;
+    }
+
+    /// Hand this tick's events to every subscriber
+    fn deliver_events(&mut self) {
+        for event in self.events.drain() {
+            self.event_log.on_event(&event);
+            self.notifier.on_event(&event);
+            self.speaker.on_event(&event);
+            if let Some(coach) = self.coach.as_mut() {
+                coach.on_event(&event);
+            }
+            if let Some(script) = self.script.as_mut() {
+                script.on_event(&event);
+            }
+            self.on_event(&event);
+        }
+        if let Some(tip) = self.coach.as_mut().and_then(Coach::take_tip) {
+            self.coach_tip = Some(Toast::lasting(format!("{} · x dismisses", tip.message()), COACH_TIP_FRAMES));
+        }
+    }
+
+    /// Give the script its once-a-second tick and carry out what its hooks asked for
+    fn run_script(&mut self) {
+        let Some(script) = self.script.as_mut() else {
+            return;
+        };
+        let running = SessionKind::from_state(&self.timer.state)
+            .filter(|_| self.screen == AppScreen::Timer && self.timer.is_running());
+        script.tick(running, self.timer.remaining.as_secs());
+        for action in script.take_actions() {
+            match action {
+                ScriptAction::Notify(message) => {
+                    if !self.config.silent {
+                        notification::notify_desktop(&message);
+                    }
+                    self.show_toast(message);
+                }
+                ScriptAction::SetTheme(name) => match ThemeType::from_name(&name) {
+                    Some(theme) => self.animation.set_theme(theme),
+                    None => self.show_toast(format!("Script: unknown theme {}", name)),
+                },
+                ScriptAction::Shell(command) => notification::spawn_quiet(&mut notification::shell_command(&command)),
+                ScriptAction::Error(e) => self.show_toast(format!("Script error in {}", e)),
+            }
+        }
+    }
+
+    pub fn tick(&mut self) {
+        // Entering a new time-of-day window swaps out a theme it doesn't list
+        let range = self.config.time_of_day.active_range(self.now().time());
+        if range != self.schedule_range {
+            self.schedule_range = range;
+            let filter = self.theme_filter();
+            if self.auto_rotate
+                && !filter.themes.is_empty()
+                && !filter.themes.contains(&self.animation.current_theme)
+            {
+                self.animation.rotate_theme(&filter);
+            }
+        }
+
+        self.poll_team();
+        self.poll_window_manager();
+        self.fire_reminders();
+        self.expire_undo(false);
+
+        // For the themes that show the session's progress in their scene
+        let in_session = self.config.diegetic_progress && self.timer.state != TimerState::Idle;
+        self.animation.set_progress(in_session.then(|| self.timer.session_progress()));
+
+        // Always tick animation (for menu preview too)
+        let filter = self.theme_filter();
+        self.animation.sequences = self.theme_sequences();
+        self.animation.tick(&self.timer.state, self.auto_rotate, &filter);
+        if let Some(failure) = self.animation.take_failure() {
+            self.theme_error = Some(failure);
+        }
+
+        // Countdown hint flash
+        if self.hint_flash_frames > 0 {
+            self.hint_flash_frames -= 1;
+        }
+        if self.celebration_frames > 0 {
+            self.celebration_frames -= 1;
+        }
+        self.banner_frames = self.banner_frames.saturating_sub(1);
+        if self.banner_frames == 0 {
+            self.banner = None;
+        }
+        // The alarm screen keeps pulsing until it is acknowledged
+        self.alert_frames = self.alert_frames.saturating_sub(1);
+        if self.alert_takeover && self.alert_frames == 0 {
+            self.alert_frames = ALERT_FRAMES;
+        }
+        let now = self.timer.clock().now();
+        if self.alert_bell.as_mut().is_some_and(|bell| bell.ring_due(now)) && !self.config.silent {
+            ring_bell();
+        }
+        self.tick_transition();
+        // Past midnight the menu's numbers start over
+        if self.day_summary.as_ref().is_some_and(|s| s.day != self.now().date_naive()) {
+            self.refresh_day_summary();
+        }
+        if self.mini {
+            // Follow the session running elsewhere until one starts here
+            self.mirrored = match self.screen {
+                AppScreen::Menu => ipc::read_live_status(MIRROR_MAX_AGE),
+                _ => None,
+            };
+        }
+        if let Some(toast) = self.toast.as_mut() {
+            if !toast.tick() {
+                self.toast = None;
+            }
+        }
+        if let Some(tip) = self.coach_tip.as_mut() {
+            if !tip.tick() {
+                self.coach_tip = None;
+            }
+        }
+
+        self.apply_theme_font();
+
+        // Announce theme/font changes from any source (keys, palette, auto-rotation);
+        // the theme selector previews live and shows its own list, so stay quiet there
+        let theme_changed = self.animation.current_theme != self.last_theme && !self.theme_selector_open;
+        let font_changed = self.animation.current_font != self.last_font;
+        if theme_changed && font_changed {
+            self.show_toast(format!(
+                "Theme: {} · Font: {}",
+                self.animation.current_theme.name(),
+                self.animation.current_font.name()
+            ));
+        } else if theme_changed {
+            self.show_toast(format!("Theme: {}", self.animation.current_theme.name()));
+        } else if font_changed {
+            self.show_toast(format!("Font: {}", self.animation.current_font.name()));
+        }
+        if !self.theme_selector_open {
+            self.last_theme = self.animation.current_theme;
+        }
+        self.last_font = self.animation.current_font;
+        if self.command_message_frames > 0 {
+            self.command_message_frames -= 1;
+            if self.command_message_frames == 0 {
+                self.command_message = None;
+            }
+        }
+
+        if self.screen != AppScreen::Timer {
+            self.digit_roll.reset();
+        }
+        if self.screen == AppScreen::Timer {
+            let previous_state = self.timer.state.clone();
+            let previous_step = self.timer.cycle_step();
+            self.timer.tick();
+            if self.config.digit_roll && !self.animation.reduced_motion() {
+                self.digit_roll.update(self.timer.remaining.as_secs());
+            } else {
+                self.digit_roll.reset();
+            }
+
+            // Break activities end with the break, however it ended
+            if !self.in_break() {
+                self.break_activity = None;
+            }
+            if let Some(BreakActivity::Snake(snake)) = &mut self.break_activity {
+                snake.tick();
+            }
+
+            // Final countdown: one tick per second in the last few seconds
+            if self.timer.in_final_countdown() {
+                let secs = self.timer.remaining.as_secs();
+                if self.last_countdown_tick != Some(secs) {
+                    self.last_countdown_tick = Some(secs);
+                    if self.countdown_ticks() && !self.config.silent {
+                        ring_bell();
+                    }
+                }
+            } else {
+                self.last_countdown_tick = None;
+            }
+            // "Five minutes remaining", spoken if configured
+            let running = SessionKind::from_state(&self.timer.state).map(|kind| (kind, self.timer.remaining.as_secs()));
+            self.speaker.countdown(running);
+
+            // A session ran out: record it, which publishes its completion
+            if !matches!(self.timer.state, TimerState::Idle)
+                && !matches!(self.timer.state, TimerState::Paused(_))
+                && std::mem::discriminant(&previous_state)
+                    != std::mem::discriminant(&self.timer.state)
+            {
+                if let Some(kind) = SessionKind::from_state(&previous_state) {
+                    // Its own length if it had one (`d`, +5 min, an extended break)
+                    let duration = self.timer.last_session_length();
+                    let paused = self.timer.last_session_paused();
+                    self.record_session(kind, SessionOutcome::Completed, duration, paused);
+                }
+                if matches!(previous_state, TimerState::Work { .. }) && self.config.celebration && !self.animation.reduced_motion() {
+                    self.celebration_frames = CELEBRATION_FRAMES;
+                }
+
+                // Manual transition: hold the new session paused behind the interstitial
+                let auto_continue = match previous_state {
+                    TimerState::Work { .. } => self.auto_continue_break,
+                    _ => self.auto_continue_work,
+                };
+                if !auto_continue {
+                    self.timer.toggle_pause();
+                    self.awaiting_start = true;
+                    // A break that ran out unnoticed can be taken again, longer
+                    let ended_break = previous_step.filter(|_| !matches!(previous_state, TimerState::Work { .. }));
+                    if let Some(step) = ended_break {
+                        if self.away_for(self.timer.step_length(step)) {
+                            self.break_extension = Some(step);
+                        }
+                    }
+                }
+                if SessionKind::from_state(&self.timer.state) == Some(SessionKind::Work) {
+                    self.prompt_intention();
+                }
+            }
+
+            // Nag while the user is off in another window (strict breaks) or still
+            // typing during the break (break checks)
+            let off_break = (self.break_locked() && !self.focused) || self.working_through_break();
+            if off_break && self.timer.is_running() {
+                let due = self
+                    .last_break_nag
+                    .is_none_or(|last| last.elapsed() >= BREAK_NAG_INTERVAL);
+                if due {
+                    self.last_break_nag = Some(Instant::now());
+                    self.notifier.dispatch(SessionEvent::BreakNag);
+                    self.show_toast(SessionEvent::BreakNag.message());
+                }
+            }
+
+            // A session left paused: remind, more and more often, before it's forgotten
+            // (waiting for the next one to start isn't a pause)
+            if self.timer.is_paused() && !self.awaiting_start && self.config.pause_reminder_minutes > 0 {
+                let now = self.timer.clock().now();
+                let since = *self.paused_at.get_or_insert(now);
+                let after = Duration::from_secs(self.config.pause_reminder_minutes * 60);
+                if now.saturating_duration_since(since) >= notification::pause_reminder_at(after, self.pause_reminders) {
+                    self.pause_reminders += 1;
+                    self.notifier.dispatch(SessionEvent::PauseReminder);
+                    self.show_toast("Still paused - Space resumes, r resets");
+                }
+            } else {
+                self.paused_at = None;
+                self.pause_reminders = 0;
+            }
+
+            // Any way of getting the timer running again (Space, Tab, r) ends the wait
+            if self.awaiting_start && !self.timer.is_paused() {
+                self.awaiting_start = false;
+                self.break_extension = None;
+                // Waiting to start isn't a pause of the session
+                self.timer.clear_paused_time();
+            }
+        }
+
+        // A fresh quote for each new session
+        let step = match self.screen {
+            AppScreen::Timer => self.timer.cycle_step(),
+            _ => None,
+        };
+        if step != self.quote_step {
+            self.quote_step = step;
+            self.quote = SessionKind::from_state(&self.timer.state)
+                .filter(|_| step.is_some())
+                .and_then(|kind| self.quotes.pick(kind, self.quote.as_deref()));
+        }
+
+        self.check_meeting();
+        self.watch_session();
+        self.deliver_events();
+        self.run_script();
+
+        // Music plays only while a session is actually running, and so does the
+        // theme's ambient sound, which follows the theme as it rotates
+        let audible = !self.config.silent && !self.muted && self.screen == AppScreen::Timer && self.timer.is_running();
+        let playing = match SessionKind::from_state(&self.timer.state) {
+            Some(kind) if self.music_enabled && audible => music_config(&self.preset, &self.config).for_kind(kind),
+            _ => None,
+        };
+        self.music.play(playing);
+        let ambient = (self.ambient_enabled && audible && SessionKind::from_state(&self.timer.state).is_some())
+            .then(|| self.config.ambient.for_theme(&self.animation.current_theme.slug()))
+            .flatten();
+        self.ambient.play(ambient.as_ref());
+
+        // A time entry spans the whole work session, pauses included
+        if let Some(tracker) = &mut self.tracker {
+            match SessionKind::from_state(&self.timer.state) {
+                Some(SessionKind::Work) if self.screen == AppScreen::Timer => {
+                    if self.timer.is_running() {
+                        tracker.start(self.current_intention.as_deref());
+                    }
+                }
+                _ => tracker.stop(),
+            }
+        }
+    }
+}
+
+/// The session banner, the stats screen and the silent-mode alert follow the event bus too
+impl Subscriber for App {
+    fn on_event(&mut self, event: &Event) {
+        match event.event {
+            AppEvent::Started(kind) => {
+                self.apply_agenda();
+                if self.config.session_banner && !self.mini {
+                    self.banner = Some(kind);
+                    self.banner_frames = BANNER_FRAMES;
+                }
+            }
+            AppEvent::Ended(kind, outcome) => {
+                let takeover = kind == SessionKind::Work && self.config.alarm.enabled && !self.mini;
+                if outcome == SessionOutcome::Completed && (takeover || self.config.silent) {
+                    self.alert_frames = ALERT_FRAMES;
+                    self.alert_banner = Some(SessionEvent::completed(kind));
+                    self.alert_takeover = takeover;
+                    let now = self.timer.clock().now();
+                    self.alert_bell = takeover.then(|| Bell::new(self.config.alarm.bell, now));
+                }
+                if self.screen == AppScreen::Stats {
+                    self.stats_records = history::load().unwrap_or_default();
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Ratings are only asked for once the session is over, so make sure an
+/// unanswered one (and a skip still open to undo) lands in the history when the app exits
+impl Drop for App {
+    fn drop(&mut self) {
+        self.finish_review(None);
+        self.expire_undo(true);
+    }
+}
+
+fn on_off(enabled: bool) -> &'static str {
+    if enabled { "on" } else { "off" }
+}
+
+/// Theme detail the terminal size allows, held low on a slow link
+fn detail_cap(scaling: &ScalingContext, slow_link: bool) -> u8 {
+    if slow_link {
+        scaling.background_detail_level.min(slow_link::DETAIL_CAP)
+    } else {
+        scaling.background_detail_level
+    }
+}
+
+/// Music per session type: the preset's when it has some, else the config's
+fn music_config<'a>(preset: &'a Option<Preset>, config: &'a Config) -> &'a MusicConfig {
+    preset.as_ref().and_then(|p| p.sounds.music.as_ref()).unwrap_or(&config.music)
 }
@@ -0,0 +1,147 @@
+//! `pomowise bench`: render every theme headlessly and time it
+//! Each theme runs for a number of frames at several terminal sizes in a ratatui
+//! `TestBackend`. Panics are caught and reported per theme/size, and the command
+//! fails if any happened, so it doubles as a smoke test in CI.
+
+use std::io;
+use std::panic::{self, AssertUnwindSafe};
+use std::time::{Duration, Instant};
+
+use ratatui::{backend::TestBackend, layout::Rect, Terminal};
+
+use crate::animation::themes::{RenderParams, ThemeState, ThemeType};
+use crate::crash::panic_message;
+
+/// Terminal sizes every theme is rendered at, from a cramped split to a big monitor
+const SIZES: [(u16, u16); 5] = [(20, 6), (40, 12), (80, 24), (120, 40), (200, 60)];
+
+const DEFAULT_FRAMES: usize = 100;
+
+/// How one theme did at one size
+enum Outcome {
+    /// Mean and worst frame time
+    Ok { mean: Duration, max: Duration },
+    /// The panic (or draw error) message and the frame it happened on
+    Failed { message: String, frame: usize },
+}
+
+/// Run the benchmark; `args` are what follows `bench` on the command line
+pub fn run(args: &[String]) -> io::Result<()> {
+    let frames = parse_frames(args)?;
+
+    // Panics are expected and reported below, keep the default hook quiet meanwhile
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    print!("{:<20}", "theme");
+    for (width, height) in SIZES {
+        print!("{:>16}", format!("{}x{}", width, height));
+    }
+    println!();
+
+    let mut failures = Vec::new();
+    for &theme in ThemeType::all() {
+        print!("{:<20}", theme.name());
+        for (width, height) in SIZES {
+            match bench_theme(theme, width, height, frames) {
+                Outcome::Ok { mean, max } => print!("{:>16}", format!("{} / {}", millis(mean), millis(max))),
+                Outcome::Failed { message, frame } => {
+                    print!("{:>16}", "FAILED");
+                    failures.push(format!("{} at {}x{}, frame {}: {}", theme.name(), width, height, frame, message));
+                }
+            }
+        }
+        println!();
+    }
+
+    panic::set_hook(default_hook);
+
+    println!("\n{} frames per run; mean / worst frame time in ms", frames);
+    if failures.is_empty() {
+        return Ok(());
+    }
+    println!();
+    for failure in &failures {
+        println!("{}", failure);
+    }
+    Err(io::Error::other(format!("{} theme renders failed", failures.len())))
+}
+
+/// `--frames N` (or a bare number); defaults to `DEFAULT_FRAMES`
+fn parse_frames(args: &[String]) -> io::Result<usize> {
+    let value = match args {
+        [] => return Ok(DEFAULT_FRAMES),
+        [flag, value] if flag == "--frames" => value,
+        [value] => value,
+        _ => return Err(io::Error::other("usage: pomowise bench [--frames N]")),
+    };
+    value
+        .parse()
+        .ok()
+        .filter(|&n| n > 0)
+        .ok_or_else(|| io::Error::other(format!("invalid frame count: {}", value)))
+}
+
+/// Render `frames` frames the way the live background does (state carried between frames)
+fn bench_theme(theme: ThemeType, width: u16, height: u16, frames: usize) -> Outcome {
+    let Ok(mut terminal) = Terminal::new(TestBackend::new(width, height)) else {
+        return Outcome::Failed {
+            message: "could not create the test terminal".to_string(),
+            frame: 0,
+        };
+    };
+    let area = Rect::new(0, 0, width, height);
+    let mut state = ThemeState::new(theme, area, 0);
+    let mut total = Duration::ZERO;
+    let mut max = Duration::ZERO;
+
+    for frame_index in 0..frames {
+        let start = Instant::now();
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            state.update(area, frame_index);
+            terminal
+                .draw(|frame| theme.render_with_state(frame, area, frame_index, &state, &RenderParams::default()))
+                .map(|_| ())
+        }));
+        let elapsed = start.elapsed();
+
+        match result {
+            Ok(Ok(())) => {}
+            Ok(Err(e)) => {
+                return Outcome::Failed {
+                    message: e.to_string(),
+                    frame: frame_index,
+                }
+            }
+            Err(payload) => {
+                return Outcome::Failed {
+                    message: panic_message(payload.as_ref()),
+                    frame: frame_index,
+                }
+            }
+        }
+        total += elapsed;
+        max = max.max(elapsed);
+    }
+
+    Outcome::Ok {
+        mean: total / frames as u32,
+        max,
+    }
+}
+
+fn millis(duration: Duration) -> String {
+    format!("{:.2}", duration.as_secs_f64() * 1000.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bench_times_a_theme() {
+        assert!(matches!(bench_theme(ThemeType::Minimal, 40, 12, 3), Outcome::Ok { .. }));
+        assert_eq!(parse_frames(&["--frames".to_string(), "25".to_string()]).unwrap(), 25);
+        assert!(parse_frames(&["0".to_string()]).is_err());
+    }
+}
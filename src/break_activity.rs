@@ -0,0 +1,266 @@
+//! Something to do during a break: a 4-7-8 breathing exercise or a tiny snake game
+//! Picked per break with `b` / `g` on the timer screen; the app drops the activity
+//! as soon as the break is over.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Snake board size in cells (each drawn two columns wide so cells look square)
+pub const SNAKE_COLS: u16 = 24;
+pub const SNAKE_ROWS: u16 = 12;
+
+/// Time between snake moves
+const SNAKE_STEP: Duration = Duration::from_millis(150);
+
+/// 4-7-8 breathing: in for 4 seconds, hold for 7, out for 8
+const INHALE_SECS: u64 = 4;
+const HOLD_SECS: u64 = 7;
+const EXHALE_SECS: u64 = 8;
+const BREATH_CYCLE_SECS: u64 = INHALE_SECS + HOLD_SECS + EXHALE_SECS;
+
+pub enum BreakActivity {
+    Breathing(Breathing),
+    Snake(Snake),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreathPhase {
+    In,
+    Hold,
+    Out,
+}
+
+impl BreathPhase {
+    pub fn prompt(&self) -> &'static str {
+        match self {
+            BreathPhase::In => "Breathe in",
+            BreathPhase::Hold => "Hold",
+            BreathPhase::Out => "Breathe out",
+        }
+    }
+}
+
+/// Where the exercise is at a point in time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BreathStep {
+    pub phase: BreathPhase,
+    /// Whole seconds left in the phase (counts down 4..1, 7..1, 8..1)
+    pub secs_left: u64,
+    /// Circle size, 0.0 (empty lungs) to 1.0 (full)
+    pub size: f32,
+    /// Completed breaths
+    pub cycles: u64,
+}
+
+pub struct Breathing {
+    started: Instant,
+}
+
+impl Breathing {
+    pub fn new() -> Self {
+        Self { started: Instant::now() }
+    }
+
+    pub fn now(&self) -> BreathStep {
+        Self::at(self.started.elapsed())
+    }
+
+    /// The exercise `elapsed` after it started
+    pub fn at(elapsed: Duration) -> BreathStep {
+        let millis = elapsed.as_millis() as u64;
+        let cycles = millis / (BREATH_CYCLE_SECS * 1000);
+        let t = millis % (BREATH_CYCLE_SECS * 1000);
+
+        let (phase, start, length) = if t < INHALE_SECS * 1000 {
+            (BreathPhase::In, 0, INHALE_SECS)
+        } else if t < (INHALE_SECS + HOLD_SECS) * 1000 {
+            (BreathPhase::Hold, INHALE_SECS, HOLD_SECS)
+        } else {
+            (BreathPhase::Out, INHALE_SECS + HOLD_SECS, EXHALE_SECS)
+        };
+        let into = t - start * 1000;
+        let progress = into as f32 / (length * 1000) as f32;
+        let size = match phase {
+            BreathPhase::In => progress,
+            BreathPhase::Hold => 1.0,
+            BreathPhase::Out => 1.0 - progress,
+        };
+
+        BreathStep {
+            phase,
+            secs_left: length - into / 1000,
+            size,
+            cycles,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl Direction {
+    fn opposite(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+        }
+    }
+}
+
+pub struct Snake {
+    /// Head first
+    body: VecDeque<(u16, u16)>,
+    direction: Direction,
+    /// Turn applied on the next move (so two quick turns can't reverse into the neck)
+    next_direction: Direction,
+    food: (u16, u16),
+    score: u32,
+    alive: bool,
+    last_step: Instant,
+    seed: u64,
+}
+
+impl Snake {
+    pub fn new() -> Self {
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos() as u64)
+            .unwrap_or(0);
+        let (x, y) = (SNAKE_COLS / 2, SNAKE_ROWS / 2);
+        let mut snake = Self {
+            body: VecDeque::from([(x, y), (x - 1, y), (x - 2, y)]),
+            direction: Direction::Right,
+            next_direction: Direction::Right,
+            food: (0, 0),
+            score: 0,
+            alive: true,
+            last_step: Instant::now(),
+            seed,
+        };
+        snake.place_food();
+        snake
+    }
+
+    pub fn body(&self) -> &VecDeque<(u16, u16)> {
+        &self.body
+    }
+
+    pub fn food(&self) -> (u16, u16) {
+        self.food
+    }
+
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    pub fn is_alive(&self) -> bool {
+        self.alive
+    }
+
+    pub fn turn(&mut self, direction: Direction) {
+        if direction != self.direction.opposite() {
+            self.next_direction = direction;
+        }
+    }
+
+    /// Move if a step is due
+    pub fn tick(&mut self) {
+        if self.alive && self.last_step.elapsed() >= SNAKE_STEP {
+            self.last_step = Instant::now();
+            self.step();
+        }
+    }
+
+    fn step(&mut self) {
+        if !self.alive {
+            return;
+        }
+        self.direction = self.next_direction;
+        let (x, y) = self.body[0];
+        let head = match self.direction {
+            Direction::Up => y.checked_sub(1).map(|y| (x, y)),
+            Direction::Down => (y + 1 < SNAKE_ROWS).then_some((x, y + 1)),
+            Direction::Left => x.checked_sub(1).map(|x| (x, y)),
+            Direction::Right => (x + 1 < SNAKE_COLS).then_some((x + 1, y)),
+        };
+
+        // The tail moves out of the way unless the snake grows this step
+        let eating = head == Some(self.food);
+        let tail = if eating { None } else { self.body.pop_back() };
+        match head {
+            Some(head) if !self.body.contains(&head) => self.body.push_front(head),
+            // Into a wall or itself: stay put so the crash is visible
+            _ => {
+                self.body.extend(tail);
+                self.alive = false;
+                return;
+            }
+        }
+        if eating {
+            self.score += 1;
+            self.place_food();
+        }
+    }
+
+    /// Food on a random free cell
+    fn place_food(&mut self) {
+        let free: Vec<(u16, u16)> = (0..SNAKE_ROWS)
+            .flat_map(|y| (0..SNAKE_COLS).map(move |x| (x, y)))
+            .filter(|cell| !self.body.contains(cell))
+            .collect();
+        if free.is_empty() {
+            return;
+        }
+        // xorshift
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        self.food = free[(self.seed % free.len() as u64) as usize];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breathing_follows_4_7_8() {
+        let at = |secs: f32| Breathing::at(Duration::from_secs_f32(secs));
+        assert_eq!(at(0.0).phase, BreathPhase::In);
+        assert_eq!(at(0.0).secs_left, 4);
+        assert_eq!(at(2.0).size, 0.5);
+        assert_eq!(at(5.0).phase, BreathPhase::Hold);
+        assert_eq!(at(10.5).secs_left, 1);
+        assert_eq!(at(11.0).phase, BreathPhase::Out);
+        assert_eq!(at(15.0).size, 0.5);
+        assert_eq!(at(19.5).cycles, 1);
+        assert_eq!(at(19.5).phase, BreathPhase::In);
+    }
+
+    #[test]
+    fn test_snake_eats_grows_and_hits_the_wall() {
+        let mut snake = Snake::new();
+        let (x, y) = snake.body[0];
+        snake.food = (x + 1, y);
+        snake.step();
+        assert_eq!(snake.score(), 1);
+        assert_eq!(snake.body().len(), 4);
+
+        // Reversing into the neck is ignored
+        snake.turn(Direction::Left);
+        snake.step();
+        assert_eq!(snake.body[0], (x + 2, y));
+
+        for _ in 0..SNAKE_COLS {
+            snake.step();
+        }
+        assert!(!snake.is_alive());
+    }
+}
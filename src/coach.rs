@@ -0,0 +1,178 @@
+//! Coach mode: pomodoro technique tips drawn from how sessions actually go
+//! The coach follows the event bus like the event log does, remembering the
+//! latest outcomes of each session type (seeded from the history) and the
+//! pauses in the work session under way. When a session ends it may offer one
+//! tip; each tip is given at most once per run, so it never nags.
+
+use std::collections::VecDeque;
+
+use crate::events::{AppEvent, Event, Subscriber};
+use crate::history::{SessionKind, SessionOutcome, SessionRecord};
+
+/// Outcomes remembered per session type
+const RECENT: usize = 8;
+
+/// Work sessions out of the recent ones cut short before suggesting shorter ones
+const CUT_SHORT_LIMIT: usize = 3;
+
+/// Pauses within one work session before suggesting to note interruptions instead
+const PAUSE_LIMIT: usize = 3;
+
+/// Breaks out of the last four skipped before a reminder to take them
+const SKIPPED_BREAK_LIMIT: usize = 2;
+
+/// Something the coach can point out
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tip {
+    /// Work sessions keep getting skipped or abandoned
+    ShorterSessions,
+    /// The last work session was paused again and again
+    Interruptions(usize),
+    /// Breaks keep getting skipped
+    TakeBreaks,
+}
+
+impl Tip {
+    pub fn message(&self) -> String {
+        match self {
+            Tip::ShorterSessions => "Tip: work often cut short? Try shorter sessions, like :work 15".to_string(),
+            Tip::Interruptions(pauses) => {
+                format!("Tip: {} pauses that session. Note interruptions for the break instead (L)", pauses)
+            }
+            Tip::TakeBreaks => "Tip: breaks skipped lately. A real break keeps the next session sharp".to_string(),
+        }
+    }
+
+    /// Same tip, whatever the numbers in it
+    fn same_kind(&self, other: &Tip) -> bool {
+        std::mem::discriminant(self) == std::mem::discriminant(other)
+    }
+}
+
+/// Recent behaviour and the tips already given this run
+#[derive(Debug, Default)]
+pub struct Coach {
+    work: VecDeque<SessionOutcome>,
+    breaks: VecDeque<SessionOutcome>,
+    /// Session type under way, from its start event
+    current: Option<SessionKind>,
+    /// Pauses and interruptions in the work session under way
+    pauses: usize,
+    given: Vec<Tip>,
+    pending: Option<Tip>,
+}
+
+impl Coach {
+    /// A coach that already knows the latest sessions in the history
+    pub fn new(records: &[SessionRecord]) -> Self {
+        let mut coach = Self::default();
+        for record in records {
+            coach.remember(record.kind, record.outcome);
+        }
+        coach
+    }
+
+    /// The tip earned at the last session boundary, if any
+    pub fn take_tip(&mut self) -> Option<Tip> {
+        self.pending.take()
+    }
+
+    fn remember(&mut self, kind: SessionKind, outcome: SessionOutcome) {
+        let outcomes = match kind {
+            SessionKind::Work => &mut self.work,
+            SessionKind::ShortBreak | SessionKind::LongBreak => &mut self.breaks,
+        };
+        if outcomes.len() == RECENT {
+            outcomes.pop_front();
+        }
+        outcomes.push_back(outcome);
+    }
+
+    /// What stands out now that a session of `kind` ended
+    fn advise(&self, kind: SessionKind) -> Option<Tip> {
+        let cut_short = |o: &&SessionOutcome| matches!(o, SessionOutcome::Skipped | SessionOutcome::Abandoned);
+        let candidates = match kind {
+            SessionKind::Work => [
+                (self.pauses >= PAUSE_LIMIT).then_some(Tip::Interruptions(self.pauses)),
+                (self.work.iter().filter(cut_short).count() >= CUT_SHORT_LIMIT).then_some(Tip::ShorterSessions),
+            ],
+            SessionKind::ShortBreak | SessionKind::LongBreak => {
+                let skipped = self.breaks.iter().rev().take(4).filter(cut_short).count();
+                [(skipped >= SKIPPED_BREAK_LIMIT).then_some(Tip::TakeBreaks), None]
+            }
+        };
+        candidates
+            .into_iter()
+            .flatten()
+            .find(|tip| !self.given.iter().any(|given| given.same_kind(tip)))
+    }
+}
+
+impl Subscriber for Coach {
+    fn on_event(&mut self, event: &Event) {
+        match event.event {
+            AppEvent::Started(kind) => {
+                self.current = Some(kind);
+                self.pauses = 0;
+            }
+            AppEvent::Paused | AppEvent::Interrupted(_) if self.current == Some(SessionKind::Work) => {
+                self.pauses += 1;
+            }
+            AppEvent::Ended(kind, outcome) => {
+                self.remember(kind, outcome);
+                if let Some(tip) = self.advise(kind) {
+                    self.given.push(tip);
+                    self.pending = Some(tip);
+                }
+                self.current = None;
+                self.pauses = 0;
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Local;
+
+    fn deliver(coach: &mut Coach, event: AppEvent) {
+        coach.on_event(&Event { at: Local::now(), event });
+    }
+
+    #[test]
+    fn test_tips_follow_behaviour_once_per_run() {
+        let mut coach = Coach::new(&[]);
+        deliver(&mut coach, AppEvent::Started(SessionKind::Work));
+        for _ in 0..PAUSE_LIMIT {
+            deliver(&mut coach, AppEvent::Paused);
+            deliver(&mut coach, AppEvent::Resumed);
+        }
+        deliver(&mut coach, AppEvent::Ended(SessionKind::Work, SessionOutcome::Completed));
+        assert_eq!(coach.take_tip(), Some(Tip::Interruptions(PAUSE_LIMIT)));
+        assert_eq!(coach.take_tip(), None);
+
+        // Pauses during a break don't count
+        deliver(&mut coach, AppEvent::Started(SessionKind::ShortBreak));
+        deliver(&mut coach, AppEvent::Paused);
+        deliver(&mut coach, AppEvent::Ended(SessionKind::ShortBreak, SessionOutcome::Completed));
+        assert_eq!(coach.take_tip(), None);
+
+        for _ in 0..CUT_SHORT_LIMIT {
+            deliver(&mut coach, AppEvent::Started(SessionKind::Work));
+            deliver(&mut coach, AppEvent::Ended(SessionKind::Work, SessionOutcome::Skipped));
+        }
+        assert_eq!(coach.take_tip(), Some(Tip::ShorterSessions));
+        deliver(&mut coach, AppEvent::Ended(SessionKind::Work, SessionOutcome::Abandoned));
+        assert_eq!(coach.take_tip(), None);
+    }
+
+    #[test]
+    fn test_history_seeds_recent_breaks() {
+        let skipped = SessionRecord::new(SessionKind::ShortBreak, SessionOutcome::Skipped, Default::default());
+        let mut coach = Coach::new(&[skipped]);
+        deliver(&mut coach, AppEvent::Ended(SessionKind::LongBreak, SessionOutcome::Skipped));
+        assert_eq!(coach.take_tip(), Some(Tip::TakeBreaks));
+    }
+}
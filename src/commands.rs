@@ -0,0 +1,303 @@
+//! Command palette (`:` commands) parsing and tab completion
+//! Commands are plain text so the same parser can later back scripting.
+
+use std::time::Duration;
+
+use chrono::NaiveTime;
+
+use crate::animation::themes::ThemeType;
+use crate::animation::DigitFont;
+use crate::config::ZenItem;
+use crate::scaling::{MAX_CELL_ASPECT, MIN_CELL_ASPECT};
+use crate::ui::chrome::Chrome;
+use crate::ui::color_filter::{ColorFilter, MAX_DIM};
+
+/// Longest session length accepted from the palette (and from team peers), in minutes
+pub const MAX_MINUTES: u64 = 999;
+
+/// A parsed palette command
+#[derive(Debug, Clone, PartialEq)]
+pub enum Command {
+    Theme(ThemeType),
+    Font(DigitFont),
+    /// Open the font gallery
+    Fonts,
+    /// Open the stats screen
+    Stats,
+    /// Open the reminders screen
+    Reminders,
+    /// Copy today's summary card and save it to a file
+    Summary,
+    /// Show the changelog
+    Changelog,
+    /// Work session length in minutes
+    Work(u64),
+    /// Short break length in minutes
+    ShortBreak(u64),
+    /// Long break length in minutes
+    LongBreak(u64),
+    /// Length of the session under way only (`15` or `12:30`)
+    Length(Duration),
+    /// Auto-continue from work into breaks (None toggles)
+    AutoBreak(Option<bool>),
+    /// Auto-continue from breaks into work (None toggles)
+    AutoWork(Option<bool>),
+    /// Digits-only display for the current session type (None toggles)
+    DigitsOnly(Option<bool>),
+    /// Color post-processing filter
+    Filter(ColorFilter),
+    /// Colors of the boxes and hints drawn over the theme
+    Chrome(Chrome),
+    /// Dim all colors by a percentage
+    Dim(u8),
+    /// Use the terminal's background instead of the themes' (None toggles)
+    Transparent(Option<bool>),
+    /// Braille dots for smooth theme curves (None toggles)
+    Braille(Option<bool>),
+    /// Session progress shown in the scene by themes that support it (None toggles)
+    Diegetic(Option<bool>),
+    /// Terminal cell height over width, to keep theme circles round
+    Aspect(f32),
+    /// Theme motion scale, 0 (still) to 1 (full)
+    Motion(f32),
+    /// Focus music for this run (None toggles)
+    Music(Option<bool>),
+    /// Theme ambient sounds for this run (None toggles)
+    Ambient(Option<bool>),
+    /// Master mute for music and ambient sounds (None toggles)
+    Mute(Option<bool>),
+    /// Keep or hide one thing in zen mode (None toggles)
+    Zen(ZenItem, Option<bool>),
+    /// Side pane of session events (None toggles)
+    Log(Option<bool>),
+    /// Progress ring around the digits (None toggles)
+    Ring(Option<bool>),
+    /// Visual-only alerts, no sound or desktop notifications (None toggles)
+    Silent(Option<bool>),
+    /// Label work sessions with a configured category (None clears it)
+    Category(Option<String>),
+    /// Plan sessions to end by a wall-clock time (None clears it)
+    Until(Option<NaiveTime>),
+    Skip,
+    Pause,
+    Reset,
+    Menu,
+    Quit,
+}
+
+/// Every command name, in the order shown by completion
+pub const COMMAND_NAMES: &[&str] = &[
+    "theme", "font", "fonts", "stats", "reminders", "summary", "changelog", "work", "break", "longbreak", "length", "autobreak",
+    "autowork", "digits", "filter", "chrome", "dim", "transparent", "braille", "diegetic", "aspect", "motion", "music", "ambient", "mute", "silent",
+    "zen",
+    "log", "ring", "category", "until", "skip", "pause", "reset", "menu", "quit",
+];
+
+/// Parse a command line (without the leading `:`)
+pub fn parse(input: &str) -> Result<Command, String> {
+    let mut parts = input.split_whitespace();
+    let name = parts.next().ok_or_else(|| "empty command".to_string())?;
+    let arg = parts.collect::<Vec<_>>().join(" ");
+
+    match name {
+        "theme" => ThemeType::from_name(&arg)
+            .map(Command::Theme)
+            .ok_or_else(|| format!("unknown theme: {}", arg)),
+        "font" => DigitFont::from_name(&arg)
+            .map(Command::Font)
+            .ok_or_else(|| format!("unknown font: {}", arg)),
+        "fonts" => Ok(Command::Fonts),
+        "stats" => Ok(Command::Stats),
+        "reminders" => Ok(Command::Reminders),
+        "summary" => Ok(Command::Summary),
+        "changelog" => Ok(Command::Changelog),
+        "work" => parse_minutes(&arg).map(Command::Work),
+        "break" => parse_minutes(&arg).map(Command::ShortBreak),
+        "longbreak" => parse_minutes(&arg).map(Command::LongBreak),
+        "length" => parse_length(&arg).map(Command::Length),
+        "autobreak" => parse_switch(&arg).map(Command::AutoBreak),
+        "autowork" => parse_switch(&arg).map(Command::AutoWork),
+        "digits" => parse_switch(&arg).map(Command::DigitsOnly),
+        "filter" => ColorFilter::from_name(&arg)
+            .map(Command::Filter)
+            .ok_or_else(|| format!("unknown filter: {}", arg)),
+        "chrome" => Chrome::from_name(&arg)
+            .map(Command::Chrome)
+            .ok_or_else(|| format!("unknown chrome: {}", arg)),
+        "dim" => match arg.trim_end_matches('%').parse::<u8>() {
+            Ok(percent) if percent <= MAX_DIM => Ok(Command::Dim(percent)),
+            _ => Err(format!("expected percent (0-{}), got: {}", MAX_DIM, arg)),
+        },
+        "transparent" => parse_switch(&arg).map(Command::Transparent),
+        "braille" => parse_switch(&arg).map(Command::Braille),
+        "diegetic" => parse_switch(&arg).map(Command::Diegetic),
+        "aspect" => match arg.parse::<f32>() {
+            Ok(aspect) if (MIN_CELL_ASPECT..=MAX_CELL_ASPECT).contains(&aspect) => Ok(Command::Aspect(aspect)),
+            _ => Err(format!("expected a ratio ({}-{}), got: {}", MIN_CELL_ASPECT, MAX_CELL_ASPECT, arg)),
+        },
+        "motion" => match arg.as_str() {
+            "off" => Ok(Command::Motion(0.0)),
+            "full" | "on" => Ok(Command::Motion(1.0)),
+            _ => match arg.parse::<f32>() {
+                Ok(scale) if (0.0..=1.0).contains(&scale) => Ok(Command::Motion(scale)),
+                _ => Err(format!("expected 0-1, off or full, got: {}", arg)),
+            },
+        },
+        "music" => parse_switch(&arg).map(Command::Music),
+        "ambient" => parse_switch(&arg).map(Command::Ambient),
+        "mute" => parse_switch(&arg).map(Command::Mute),
+        "silent" => parse_switch(&arg).map(Command::Silent),
+        "zen" => {
+            let (item, switch) = arg.split_once(' ').unwrap_or((&arg, ""));
+            let item = ZenItem::from_name(item)
+                .ok_or_else(|| format!("expected clock, session or progress, got: {}", item))?;
+            parse_switch(switch).map(|on| Command::Zen(item, on))
+        }
+        "log" => parse_switch(&arg).map(Command::Log),
+        "ring" => parse_switch(&arg).map(Command::Ring),
+        "category" if arg.is_empty() || arg == "off" => Ok(Command::Category(None)),
+        "category" => Ok(Command::Category(Some(arg))),
+        "until" if arg == "off" => Ok(Command::Until(None)),
+        "until" => NaiveTime::parse_from_str(&arg, "%H:%M")
+            .map(|time| Command::Until(Some(time)))
+            .map_err(|_| format!("expected a time like 15:00, got: {}", arg)),
+        "skip" => Ok(Command::Skip),
+        "pause" => Ok(Command::Pause),
+        "reset" => Ok(Command::Reset),
+        "menu" => Ok(Command::Menu),
+        "quit" | "q" => Ok(Command::Quit),
+        other => Err(format!("unknown command: {}", other)),
+    }
+}
+
+fn parse_minutes(arg: &str) -> Result<u64, String> {
+    match arg.parse::<u64>() {
+        Ok(m) if (1..=MAX_MINUTES).contains(&m) => Ok(m),
+        _ => Err(format!("expected minutes (1-{}), got: {}", MAX_MINUTES, arg)),
+    }
+}
+
+/// Minutes, or minutes and seconds: `15`, `12:30`
+fn parse_length(arg: &str) -> Result<Duration, String> {
+    let secs = match arg.split_once(':') {
+        Some((minutes, seconds)) => match (minutes.parse::<u64>(), seconds.parse::<u64>()) {
+            (Ok(m), Ok(s)) if s < 60 && seconds.len() == 2 => Some(m * 60 + s),
+            _ => None,
+        },
+        None => arg.parse::<u64>().ok().map(|m| m * 60),
+    };
+    match secs {
+        Some(secs) if (1..=MAX_MINUTES * 60).contains(&secs) => Ok(Duration::from_secs(secs)),
+        _ => Err(format!("expected minutes or m:ss (up to {} min), got: {}", MAX_MINUTES, arg)),
+    }
+}
+
+/// `on` / `off`, or nothing to toggle
+fn parse_switch(arg: &str) -> Result<Option<bool>, String> {
+    match arg {
+        "" => Ok(None),
+        "on" => Ok(Some(true)),
+        "off" => Ok(Some(false)),
+        other => Err(format!("expected on or off, got: {}", other)),
+    }
+}
+
+/// Complete the command line as far as it is unambiguous.
+/// Returns None when there is nothing to add.
+pub fn complete(input: &str) -> Option<String> {
+    match input.split_once(' ') {
+        None => {
+            let names: Vec<String> = COMMAND_NAMES.iter().map(|n| n.to_string()).collect();
+            let completed = complete_word(input, &names)?;
+            // A unique match gets a trailing space if it takes an argument
+            let unique = names.iter().filter(|n| n.starts_with(input)).count() == 1;
+            if unique && matches!(completed.as_str(), "theme" | "font") {
+                Some(format!("{} ", completed))
+            } else {
+                Some(completed)
+            }
+        }
+        Some((name, arg)) => {
+            let candidates: Vec<String> = match name {
+                "theme" => ThemeType::all().iter().map(|t| t.slug()).collect(),
+                "font" => DigitFont::every().iter().map(|f| f.slug()).collect(),
+                _ => return None,
+            };
+            complete_word(arg.trim_start(), &candidates).map(|arg| format!("{} {}", name, arg))
+        }
+    }
+}
+
+/// Longest common prefix of all candidates starting with `word`
+fn complete_word(word: &str, candidates: &[String]) -> Option<String> {
+    let mut matches = candidates.iter().filter(|c| c.starts_with(word));
+    let first = matches.next()?;
+    let prefix = matches.fold(first.as_str(), |prefix, candidate| {
+        let len = prefix
+            .chars()
+            .zip(candidate.chars())
+            .take_while(|(a, b)| a == b)
+            .map(|(a, _)| a.len_utf8())
+            .sum();
+        &prefix[..len]
+    });
+    (prefix.len() > word.len()).then(|| prefix.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_commands() {
+        assert_eq!(parse("theme matrix"), Ok(Command::Theme(ThemeType::Matrix)));
+        assert_eq!(parse("font lcd"), Ok(Command::Font(DigitFont::LCD)));
+        assert_eq!(parse("work 45"), Ok(Command::Work(45)));
+        assert_eq!(parse("length 15"), Ok(Command::Length(Duration::from_secs(900))));
+        assert_eq!(parse("length 12:30"), Ok(Command::Length(Duration::from_secs(750))));
+        assert!(parse("length 12:75").is_err());
+        assert_eq!(parse("skip"), Ok(Command::Skip));
+        assert_eq!(parse("summary"), Ok(Command::Summary));
+        assert_eq!(parse("autobreak off"), Ok(Command::AutoBreak(Some(false))));
+        assert_eq!(parse("autowork"), Ok(Command::AutoWork(None)));
+        assert_eq!(parse("digits on"), Ok(Command::DigitsOnly(Some(true))));
+        assert_eq!(parse("filter sepia"), Ok(Command::Filter(ColorFilter::Sepia)));
+        assert_eq!(parse("chrome high"), Ok(Command::Chrome(Chrome::HighContrast)));
+        assert_eq!(parse("dim 30%"), Ok(Command::Dim(30)));
+        assert!(parse("dim 95").is_err());
+        assert_eq!(parse("transparent"), Ok(Command::Transparent(None)));
+        assert_eq!(parse("braille off"), Ok(Command::Braille(Some(false))));
+        assert_eq!(parse("diegetic"), Ok(Command::Diegetic(None)));
+        assert_eq!(parse("aspect 2.2"), Ok(Command::Aspect(2.2)));
+        assert!(parse("aspect 9").is_err());
+        assert_eq!(parse("motion 0.3"), Ok(Command::Motion(0.3)));
+        assert_eq!(parse("motion off"), Ok(Command::Motion(0.0)));
+        assert!(parse("motion 2").is_err());
+        assert_eq!(parse("music off"), Ok(Command::Music(Some(false))));
+        assert_eq!(parse("ambient on"), Ok(Command::Ambient(Some(true))));
+        assert_eq!(parse("mute"), Ok(Command::Mute(None)));
+        assert_eq!(parse("silent"), Ok(Command::Silent(None)));
+        assert_eq!(parse("zen clock on"), Ok(Command::Zen(ZenItem::Clock, Some(true))));
+        assert_eq!(parse("zen progress"), Ok(Command::Zen(ZenItem::Progress, None)));
+        assert!(parse("zen quote").is_err());
+        assert_eq!(parse("log on"), Ok(Command::Log(Some(true))));
+        assert_eq!(parse("ring"), Ok(Command::Ring(None)));
+        assert_eq!(parse("category deep work"), Ok(Command::Category(Some("deep work".to_string()))));
+        assert_eq!(parse("category off"), Ok(Command::Category(None)));
+        assert_eq!(parse("until 15:00"), Ok(Command::Until(NaiveTime::from_hms_opt(15, 0, 0))));
+        assert!(parse("until 3pm").is_err());
+        assert!(parse("autowork maybe").is_err());
+        assert!(parse("work 0").is_err());
+        assert!(parse("theme nope").is_err());
+        assert!(parse("dance").is_err());
+    }
+
+    #[test]
+    fn test_complete() {
+        assert_eq!(complete("th").as_deref(), Some("theme "));
+        assert_eq!(complete("theme ma").as_deref(), Some("theme matrix-rain"));
+        assert_eq!(complete("theme s"), None); // snowfall, starfield, ... share only "s"
+        assert_eq!(complete("font 3").as_deref(), Some("font 3d-blocks"));
+        assert_eq!(complete("quit"), None);
+    }
+}
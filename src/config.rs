@@ -0,0 +1,440 @@
+//! User configuration stored in ~/.pomowise/config.json
+//! Every field has a default, so a partial (or missing) file is always valid.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use crate::agenda::AgendaBlock;
+use crate::history::SessionKind;
+use crate::paths;
+use crate::timer::{TimerState, DEFAULT_LAP_TARGET};
+
+use crate::animation::daylight::SkyConfig;
+use crate::animation::digits::DigitColorMode;
+use crate::animation::schedule::ThemeSchedule;
+use crate::animation::themes::Mood;
+use crate::journal::JournalConfig;
+use crate::scripting::ScriptConfig;
+use crate::speech::SpeechConfig;
+use crate::music::{AmbientConfig, MusicConfig};
+use crate::notification::NotificationConfig;
+use crate::sync::SyncConfig;
+use crate::ui::transition::TransitionStyle;
+use crate::privacy::PrivacyConfig;
+use crate::store::StoreConfig;
+use crate::quotes::QuotesConfig;
+use crate::reminders::Reminder;
+use crate::team::TeamConfig;
+use crate::wm::DistractionConfig;
+use crate::tracking::TrackingConfig;
+use crate::alarm::AlarmConfig;
+use crate::idle::BreakCheckConfig;
+use crate::project::AutoLabel;
+use crate::tmux::TmuxConfig;
+use crate::weather::WeatherConfig;
+use crate::contributions::GithubConfig;
+use crate::ui::chrome::{Chrome, SessionAccents};
+use crate::ui::color_filter::ColorFilterConfig;
+use crate::ui::glyphs::Glyphs;
+use crate::ui::slow_link::SlowLink;
+use crate::ui::widgets::overlay::OverlayConfig;
+use crate::ui::widgets::progress::ProgressBarConfig;
+use crate::ui::widgets::ring::ProgressRingConfig;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    /// Ring the terminal bell once per second during the final countdown
+    pub countdown_ticks: bool,
+    /// Mood that auto-rotation is restricted to for each session type
+    pub moods: MoodConfig,
+    /// Local time ranges mapped to the themes auto-rotation picks from
+    pub time_of_day: ThemeSchedule,
+    /// Play a short fireworks overlay when a work session completes
+    pub celebration: bool,
+    /// Spell out the session name in block letters for a moment as each session starts
+    pub session_banner: bool,
+    /// Work sessions to aim for each day, the total shown next to the session ("Lap 3/10")
+    pub lap_target: u8,
+    /// Step the digits up to the largest font that fits during a session's final minute
+    pub grow_final_minute: bool,
+    /// Show elapsed time and the session's wall-clock end under the digits
+    pub show_elapsed: bool,
+    /// Notification backends and the events each one fires for
+    pub notifications: NotificationConfig,
+    /// Phrases spoken through a text-to-speech command as sessions end and near their end
+    pub speech: SpeechConfig,
+    /// No sound or desktop notifications (`m`); transitions pulse the screen and leave a banner instead
+    pub silent: bool,
+    /// Breaks can't be skipped or left (except by holding `q`), and losing focus nags
+    pub strict_breaks: bool,
+    /// Minutes a session can stay paused before a `pause_reminder` notification, repeated
+    /// more and more often; 0 never reminds
+    pub pause_reminder_minutes: u64,
+    /// Recurring reminders ("Stand up" every 60 minutes) that come whatever the timer is doing
+    pub reminders: Vec<Reminder>,
+    /// Nudge when typing elsewhere during breaks; offer a longer break when one ends while away
+    pub break_check: BreakCheckConfig,
+    /// Look and placement of the session progress bar
+    pub progress_bar: ProgressBarConfig,
+    /// Ring around the digits that fills over the session, alongside or instead of the bar
+    pub progress_ring: ProgressRingConfig,
+    /// Whether finished sessions roll straight into the next one
+    pub auto_continue: AutoContinueConfig,
+    /// Time zones shown with the local time under the digits, up to three: `"Asia/Tokyo"` or `"Ana=Asia/Tokyo"`
+    pub world_clock: Vec<String>,
+    /// Digit font slug picked in the gallery (`lcd`, `3d-blocks`, ...); unset uses adaptive font
+    pub font: Option<String>,
+    /// Font slug per theme slug, picked with `f` while that theme shows; `F` forgets the current theme's
+    pub theme_fonts: BTreeMap<String, String>,
+    /// Where to mirror the session history; unset keeps it on this machine only
+    pub sync: Option<SyncConfig>,
+    /// Session types shown as plain digits on a solid background, with no animation
+    pub digits_only: DigitsOnlyConfig,
+    /// How the timer digits are colored: `theme`, `gradient`, `rainbow` or `heat`
+    pub digit_colors: DigitColorMode,
+    /// Roll each timer digit over to its new value, split-flap style, instead of swapping it
+    pub digit_roll: bool,
+    /// How screen changes are animated: `slide`, `fade` or `off`
+    pub transitions: TransitionStyle,
+    /// Play a theme's intro when it comes in and its outro when it is replaced
+    pub theme_sequences: bool,
+    /// Keep the terminal's own background: themes draw only their foreground effects
+    pub transparent_background: bool,
+    /// Draw smooth theme curves in braille dots instead of half blocks
+    pub braille: bool,
+    /// Show the session's progress in the scene itself, in the themes that can (Landscape, Medieval, GitHub)
+    pub diegetic_progress: bool,
+    /// Height of a terminal cell over its width; themes use it to keep circles round
+    pub cell_aspect: f32,
+    /// Save bandwidth over SSH: 2 frames a second, low-detail themes, coalesced colors (`auto`, `on`, `off`)
+    pub slow_link: SlowLink,
+    /// Characters the terminal can show: `auto`, `full`, `extended` (Windows console fonts) or `basic` (ASCII)
+    pub glyphs: Glyphs,
+    /// Theme motion from 0.0 (still) to 1.0 (full); anything less also drops flashes and strobes
+    pub motion: f32,
+    /// Landscape day and night from the local clock instead of the animated cycle
+    pub landscape_sky: SkyConfig,
+    /// What stays on the timer screen in zen mode (`h`)
+    pub zen: ZenConfig,
+    /// Where the small clock, session info, theme name and key hints go on the timer screen
+    pub overlay: OverlayConfig,
+    /// Preview the next session by the clock all the time, not only in a session's last minutes
+    pub always_show_next: bool,
+    /// Colors of the boxes, dialogs and hints, independent of the theme: `midnight`, `slate` or `high_contrast`
+    pub chrome: Chrome,
+    /// Border colors by session type, warm for work and calm for breaks, over the theme's
+    pub session_accents: SessionAccents,
+    /// Show the side pane of session events (started, paused, complete) on the timer screen
+    pub event_log: bool,
+    /// Session progress on the taskbar icon or tab badge, in terminals detected to support it
+    pub terminal_progress: bool,
+    /// Status line refresh and border colors when running inside tmux; unset leaves tmux alone
+    pub tmux: Option<TmuxConfig>,
+    /// Post-processing over the final frame colors (dimming, night shift, colorblind modes)
+    pub color_filter: ColorFilterConfig,
+    /// Share one timer with other pomowise instances on the LAN; unset keeps it solo
+    pub team: Option<TeamConfig>,
+    /// Workspaces and apps that pause work while i3, sway or Hyprland has them focused; unset never pauses
+    pub distractions: Option<DistractionConfig>,
+    /// Labels work sessions can carry (`w` cycles them), shown in stats and the journal
+    pub categories: Vec<Category>,
+    /// Label each work session with its project as it starts: `tmux`, `git`, `directory`, `auto` or `off`
+    pub auto_label: AutoLabel,
+    /// Day templates by name, blocks of pomodoros walked through in order (`--template <name>`)
+    pub templates: BTreeMap<String, Vec<AgendaBlock>>,
+    /// Installed preset picked in the menu; its session lengths, themes, font and sounds apply at every start
+    pub preset: Option<String>,
+    /// A quote under the digits as each session starts, from the bundled list and an optional file
+    pub quotes: QuotesConfig,
+    /// Ask for an intention before each work session and a 1-5 focus rating after it
+    pub intentions: bool,
+    /// Pomodoro technique tips at session boundaries, picked from how sessions have been going
+    pub coach: bool,
+    /// Today's pomodoros, focus time and streak under the menu
+    pub menu_stats: bool,
+    /// Full-screen alarm at the end of work sessions, until a key is pressed
+    pub alarm: AlarmConfig,
+    /// Encrypted history storage
+    pub privacy: PrivacyConfig,
+    /// Where the plain history is kept: `json` (history.jsonl) or `sqlite`
+    pub history_store: StoreConfig,
+    /// Music or noise to play during each session type; unset types stay silent
+    pub music: MusicConfig,
+    /// Ambient sounds that come with the themes, at a volume of their own
+    pub ambient: AmbientConfig,
+    /// Toggl or Clockify account that work sessions are logged to; unset logs nowhere
+    pub tracking: Option<TrackingConfig>,
+    /// Where the Landscape and Seasonal themes take their weather from; unset keeps them fair
+    pub weather: Option<WeatherConfig>,
+    /// GitHub user whose contribution calendar the GitHub theme shows; unset draws a made-up one
+    pub github: Option<GithubConfig>,
+    /// Rhai script with hooks at session start, end and every second; unset runs none
+    pub script: Option<ScriptConfig>,
+    /// Daily Markdown file that completed pomodoros are logged to; unset keeps no journal
+    pub journal: Option<JournalConfig>,
+    /// Version whose changes were last shown; unset until the first-run walkthrough is done
+    pub last_seen_version: Option<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            countdown_ticks: false,
+            moods: MoodConfig::default(),
+            time_of_day: ThemeSchedule::default(),
+            celebration: true,
+            session_banner: true,
+            lap_target: DEFAULT_LAP_TARGET,
+            grow_final_minute: false,
+            show_elapsed: false,
+            notifications: NotificationConfig::default(),
+            speech: SpeechConfig::default(),
+            silent: false,
+            strict_breaks: false,
+            break_check: BreakCheckConfig::default(),
+            reminders: Vec::new(),
+            pause_reminder_minutes: 15,
+            progress_bar: ProgressBarConfig::default(),
+            progress_ring: ProgressRingConfig::default(),
+            auto_continue: AutoContinueConfig::default(),
+            font: None,
+            theme_fonts: BTreeMap::new(),
+            sync: None,
+            digits_only: DigitsOnlyConfig::default(),
+            digit_colors: DigitColorMode::default(),
+            world_clock: Vec::new(),
+            digit_roll: true,
+            slow_link: SlowLink::default(),
+            glyphs: Glyphs::default(),
+            transitions: TransitionStyle::default(),
+            theme_sequences: true,
+            transparent_background: false,
+            braille: false,
+            diegetic_progress: false,
+            cell_aspect: crate::scaling::DEFAULT_CELL_ASPECT,
+            motion: 1.0,
+            landscape_sky: SkyConfig::default(),
+            zen: ZenConfig::default(),
+            overlay: OverlayConfig::default(),
+            always_show_next: false,
+            chrome: Chrome::default(),
+            session_accents: SessionAccents::default(),
+            event_log: false,
+            terminal_progress: true,
+            tmux: None,
+            color_filter: ColorFilterConfig::default(),
+            team: None,
+            distractions: None,
+            categories: ["Deep Work", "Admin", "Meetings", "Learning"]
+                .into_iter()
+                .map(Category::named)
+                .collect(),
+            auto_label: AutoLabel::default(),
+            templates: BTreeMap::new(),
+            preset: None,
+            quotes: QuotesConfig::default(),
+            intentions: false,
+            coach: false,
+            menu_stats: true,
+            alarm: AlarmConfig::default(),
+            privacy: PrivacyConfig::default(),
+            history_store: StoreConfig::default(),
+            music: MusicConfig::default(),
+            ambient: AmbientConfig::default(),
+            tracking: None,
+            weather: None,
+            github: None,
+            script: None,
+            journal: None,
+            last_seen_version: None,
+        }
+    }
+}
+
+/// A label work sessions can carry, optionally with a length of its own
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Category {
+    pub name: String,
+    /// Work session length in minutes; unset uses the normal length
+    #[serde(default)]
+    pub work_minutes: Option<u64>,
+}
+
+impl Category {
+    fn named(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            work_minutes: None,
+        }
+    }
+}
+
+/// Per-session mood mapping; unset entries rotate through every theme
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MoodConfig {
+    pub work: Option<Mood>,
+    pub short_break: Option<Mood>,
+    pub long_break: Option<Mood>,
+}
+
+impl MoodConfig {
+    /// Mood for the given timer state (paused states use their inner session)
+    pub fn for_state(&self, state: &TimerState) -> Option<Mood> {
+        match state {
+            TimerState::Work { .. } => self.work,
+            TimerState::ShortBreak { .. } => self.short_break,
+            TimerState::LongBreak => self.long_break,
+            TimerState::Paused(inner) => self.for_state(inner),
+            TimerState::Idle => None,
+        }
+    }
+}
+
+/// Digits-only display per session type (toggled with `o` on the timer screen)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DigitsOnlyConfig {
+    pub work: bool,
+    pub short_break: bool,
+    pub long_break: bool,
+}
+
+impl DigitsOnlyConfig {
+    /// Whether the given timer state is shown digits-only (paused states use their inner session)
+    pub fn for_state(&self, state: &TimerState) -> bool {
+        SessionKind::from_state(state).is_some_and(|kind| self.get(kind))
+    }
+
+    /// Setting for one session type
+    pub fn get(&self, kind: SessionKind) -> bool {
+        match kind {
+            SessionKind::Work => self.work,
+            SessionKind::ShortBreak => self.short_break,
+            SessionKind::LongBreak => self.long_break,
+        }
+    }
+
+    pub fn set(&mut self, kind: SessionKind, enabled: bool) {
+        match kind {
+            SessionKind::Work => self.work = enabled,
+            SessionKind::ShortBreak => self.short_break = enabled,
+            SessionKind::LongBreak => self.long_break = enabled,
+        }
+    }
+}
+
+/// What zen mode keeps visible; everything else is hidden
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ZenConfig {
+    /// Small countdown in the top-right corner
+    pub clock: bool,
+    /// Session name and lap in the top-left corner
+    pub session: bool,
+    /// Thin progress line along the bottom edge
+    pub progress: bool,
+}
+
+impl Default for ZenConfig {
+    fn default() -> Self {
+        Self {
+            clock: false,
+            session: false,
+            progress: true,
+        }
+    }
+}
+
+/// One of the things zen mode can keep (`:zen clock on`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZenItem {
+    Clock,
+    Session,
+    Progress,
+}
+
+impl ZenItem {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "clock" => Some(ZenItem::Clock),
+            "session" => Some(ZenItem::Session),
+            "progress" => Some(ZenItem::Progress),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ZenItem::Clock => "clock",
+            ZenItem::Session => "session",
+            ZenItem::Progress => "progress",
+        }
+    }
+}
+
+impl ZenConfig {
+    pub fn get(&self, item: ZenItem) -> bool {
+        match item {
+            ZenItem::Clock => self.clock,
+            ZenItem::Session => self.session,
+            ZenItem::Progress => self.progress,
+        }
+    }
+
+    pub fn set(&mut self, item: ZenItem, enabled: bool) {
+        match item {
+            ZenItem::Clock => self.clock = enabled,
+            ZenItem::Session => self.session = enabled,
+            ZenItem::Progress => self.progress = enabled,
+        }
+    }
+}
+
+/// Auto-continue per transition; `false` waits for Space on an interstitial screen
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AutoContinueConfig {
+    /// Work -> short/long break
+    pub to_break: bool,
+    /// Break -> work
+    pub to_work: bool,
+}
+
+impl Default for AutoContinueConfig {
+    fn default() -> Self {
+        Self {
+            to_break: true,
+            to_work: true,
+        }
+    }
+}
+
+impl Config {
+    /// Path to the config file
+    pub fn path() -> PathBuf {
+        paths::config_dir().join("config.json")
+    }
+
+    /// Load the config file, falling back to defaults if missing or invalid
+    pub fn load() -> Self {
+        std::fs::read_to_string(Self::path())
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write the config file (pretty-printed so it stays hand-editable)
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json)
+    }
+}
@@ -0,0 +1,182 @@
+//! A GitHub user's contribution calendar for the GitHub theme (the `github` key
+//! in config.json), in place of its made-up grid. A background thread asks the
+//! GitHub GraphQL API for it through curl, once a day: the answer is kept in
+//! contributions.json in the state directory and reused until the next day, so
+//! starts and offline runs show the last calendar without asking again.
+
+use std::io;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::{Mutex, Once};
+use std::time::Duration;
+
+use chrono::Local;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::curl;
+use crate::paths;
+
+/// Per-request limit, so a hanging connection doesn't hold up the next try
+const REQUEST_TIMEOUT_SECS: &str = "20";
+
+/// How often the thread checks whether the calendar is from an earlier day
+const CHECK_INTERVAL: Duration = Duration::from_secs(3600);
+
+const QUERY: &str = "query($login: String!) { user(login: $login) { contributionsCollection { \
+    contributionCalendar { weeks { contributionDays { weekday contributionLevel } } } } } }";
+
+/// Whose calendar to show (the `github` key in config.json)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GithubConfig {
+    pub user: String,
+    /// Personal access token (no scopes needed); unset reads `GITHUB_TOKEN`
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+/// A week of the calendar: contribution level 0-4 by weekday from Sunday, None
+/// for days outside the year shown
+pub type Week = [Option<u8>; 7];
+
+/// Weeks of the last calendar fetched, oldest first; empty until there is one
+static CALENDAR: Mutex<Vec<Week>> = Mutex::new(Vec::new());
+
+static WATCH: Once = Once::new();
+
+/// The weeks of the calendar, oldest first; empty while there is none
+pub fn calendar() -> Vec<Week> {
+    CALENDAR.lock().map(|weeks| weeks.clone()).unwrap_or_default()
+}
+
+fn set_calendar(weeks: &[Week]) {
+    if let Ok(mut calendar) = CALENDAR.lock() {
+        *calendar = weeks.to_vec();
+    }
+}
+
+/// Last calendar fetched, kept for the rest of the day
+#[derive(Debug, Serialize, Deserialize)]
+struct Cached {
+    user: String,
+    /// Local date of the fetch, `YYYY-MM-DD`
+    fetched_on: String,
+    weeks: Vec<Week>,
+}
+
+impl Cached {
+    fn path() -> PathBuf {
+        paths::state_dir().join("contributions.json")
+    }
+
+    fn load() -> Option<Self> {
+        let json = std::fs::read_to_string(Self::path()).ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string(self)?)
+    }
+}
+
+/// Start following the calendar; later calls (another embedded app) do nothing
+pub fn watch(config: &GithubConfig) {
+    let config = config.clone();
+    WATCH.call_once(move || {
+        let mut known = Cached::load().filter(|c| c.user == config.user);
+        if let Some(cached) = &known {
+            set_calendar(&cached.weeks);
+        }
+        std::thread::spawn(move || loop {
+            let today = Local::now().date_naive().to_string();
+            if known.as_ref().is_none_or(|c| c.fetched_on != today) {
+                // Offline or refused: keep showing the last calendar and try again later
+                if let Ok(weeks) = fetch(&config) {
+                    let cached = Cached { user: config.user.clone(), fetched_on: today, weeks };
+                    let _ = cached.save();
+                    set_calendar(&cached.weeks);
+                    known = Some(cached);
+                }
+            }
+            std::thread::sleep(CHECK_INTERVAL);
+        });
+    });
+}
+
+/// The user's calendar for the last year, from the GraphQL API
+fn fetch(config: &GithubConfig) -> io::Result<Vec<Week>> {
+    let token = config
+        .token
+        .clone()
+        .or_else(|| std::env::var("GITHUB_TOKEN").ok())
+        .ok_or_else(|| io::Error::other("github: no token"))?;
+    let body = json!({ "query": QUERY, "variables": { "login": config.user } });
+    // The token goes to curl on stdin, not where `ps` shows it
+    let output = curl::output(
+        Command::new("curl")
+            .args(["-sSf", "--max-time", REQUEST_TIMEOUT_SECS, "-X", "POST"])
+            .args(["-H", "User-Agent: pomowise"])
+            .arg("--data-binary")
+            .arg(body.to_string())
+            .arg("https://api.github.com/graphql"),
+        &curl::option("header", &format!("Authorization: bearer {}", token)),
+    )?;
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::other(format!("github: {}", error.trim())));
+    }
+    parse_calendar(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// Weeks out of a GraphQL response
+fn parse_calendar(body: &str) -> io::Result<Vec<Week>> {
+    let response: Value = serde_json::from_str(body)?;
+    let weeks = response["data"]["user"]["contributionsCollection"]["contributionCalendar"]["weeks"]
+        .as_array()
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "github: no contribution calendar"))?;
+    Ok(weeks
+        .iter()
+        .map(|week| {
+            let mut days = [None; 7];
+            for day in week["contributionDays"].as_array().into_iter().flatten() {
+                let Some(weekday) = day["weekday"].as_u64().filter(|&d| d < 7) else { continue };
+                days[weekday as usize] = Some(level(day["contributionLevel"].as_str().unwrap_or_default()));
+            }
+            days
+        })
+        .collect())
+}
+
+/// Level 0-4 from the API's quartile name
+fn level(name: &str) -> u8 {
+    match name {
+        "FIRST_QUARTILE" => 1,
+        "SECOND_QUARTILE" => 2,
+        "THIRD_QUARTILE" => 3,
+        "FOURTH_QUARTILE" => 4,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_calendar() {
+        let body = r#"{"data":{"user":{"contributionsCollection":{"contributionCalendar":{"weeks":[
+            {"contributionDays":[{"weekday":5,"contributionLevel":"NONE"},{"weekday":6,"contributionLevel":"FOURTH_QUARTILE"}]},
+            {"contributionDays":[{"weekday":0,"contributionLevel":"FIRST_QUARTILE"},{"weekday":1,"contributionLevel":"THIRD_QUARTILE"}]}
+        ]}}}}}"#;
+        let weeks = parse_calendar(body).unwrap();
+        assert_eq!(weeks[0], [None, None, None, None, None, Some(0), Some(4)]);
+        assert_eq!(weeks[1], [Some(1), Some(3), None, None, None, None, None]);
+
+        let missing = r#"{"data":{"user":null},"errors":[{"message":"Could not resolve to a User"}]}"#;
+        assert!(parse_calendar(missing).is_err());
+    }
+}
@@ -0,0 +1,71 @@
+//! Panics. `install_hook` gives the terminal back (raw mode off, main screen,
+//! cursor shown) before the main thread's panic message prints, so it lands where it can be
+//! read instead of in a wiped alternate screen. `catch` runs a piece of drawing
+//! that may panic, such as a theme's index math, and turns the panic into an
+//! error the app can report and recover from.
+
+use std::cell::{Cell, RefCell};
+use std::panic::{self, AssertUnwindSafe};
+
+thread_local! {
+    /// Inside `catch`: the panic is expected, keep the hook quiet
+    static CATCHING: Cell<bool> = const { Cell::new(false) };
+    /// Where the panic being caught happened ("src/animation/themes/fire.rs:80:21")
+    static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Restore the terminal with `restore` before an uncaught panic on the main
+/// thread is reported. Panics on other threads (team, tracking, sync, ...) go to
+/// the default hook as they are, since the UI is still running and keeps the terminal.
+pub fn install_hook(restore: fn()) {
+    let default_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if CATCHING.get() {
+            LOCATION.set(info.location().map(|l| l.to_string()));
+            return;
+        }
+        if std::thread::current().name() == Some("main") {
+            restore();
+        }
+        default_hook(info);
+    }));
+}
+
+/// Run `f`, or the panic message (with where it happened, when the hook is
+/// installed) if it panics
+pub(crate) fn catch<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+    let catching = CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    CATCHING.set(catching);
+    result.map_err(|payload| {
+        let message = panic_message(payload.as_ref());
+        match LOCATION.take() {
+            Some(location) => format!("{} at {}", message, location),
+            None => message,
+        }
+    })
+}
+
+pub(crate) fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catch_turns_a_panic_into_an_error() {
+        assert_eq!(catch(|| 2 + 2), Ok(4));
+        let index = 7;
+        let error = catch(|| [1, 2, 3][index]).unwrap_err();
+        assert!(error.contains("index out of bounds"), "{}", error);
+        // Nested catches leave the outer one catching
+        assert!(catch(|| catch(|| panic!("inner")).is_err() && CATCHING.get()).unwrap());
+        assert!(!CATCHING.get());
+    }
+}
@@ -0,0 +1,49 @@
+//! Running `curl` with secrets (API tokens, passwords, auth headers) kept off
+//! its command line, where `ps` shows them to every user of the machine: they
+//! go in a curl config read from standard input (`-K -`).
+
+use std::io::{self, Write};
+use std::process::{Command, Output, Stdio};
+
+/// One line of a curl config, `name = "value"`, the value quoted and escaped
+pub fn option(name: &str, value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            c => quoted.push(c),
+        }
+    }
+    format!("{} = \"{}\"\n", name, quoted)
+}
+
+/// Run `cmd` (a curl command) with `config` as its config on standard input,
+/// capturing its output
+pub fn output(cmd: &mut Command, config: &str) -> io::Result<Output> {
+    let mut child = cmd
+        .args(["-K", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    // A few lines, well within the pipe buffer; closed so curl starts
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(config.as_bytes())?;
+    }
+    child.wait_with_output()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_config_option_quoting() {
+        assert_eq!(option("user", "me:pa\"ss\\word"), "user = \"me:pa\\\"ss\\\\word\"\n");
+        assert_eq!(option("header", "X-Api-Key: a\nb"), "header = \"X-Api-Key: a\\nb\"\n");
+    }
+}
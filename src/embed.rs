@@ -0,0 +1,310 @@
+//! Embedding entry point
+//! `PomowiseApp` wraps the whole application so other ratatui programs can run it
+//! full screen (`run_app`) or render it inside a pane of their own layout.
+
+use std::io::{self, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+use crossterm::event::{self, Event};
+use ratatui::backend::TestBackend;
+use ratatui::prelude::*;
+use tokio::sync::mpsc;
+
+use crate::animation::themes::ThemeType;
+use crate::app::{App, AppScreen};
+use crate::clock::{self, SharedClock};
+use crate::config::Config;
+use crate::idle::IdleMonitor;
+use crate::taskbar::Taskbar;
+use crate::timer::{PomodoroTimer, TimerState};
+use crate::tmux::Tmux;
+use crate::{input, ipc, ui};
+
+/// The pomodoro timer, its themes and every screen, ready to embed.
+///
+/// Drive it like any ratatui component: feed it events with
+/// [`handle_event`](Self::handle_event), advance it with [`tick`](Self::tick)
+/// after each event or once [`next_wake`](Self::next_wake) has passed, and draw it either full frame with
+/// [`draw`](Self::draw) or into a pane by rendering `&mut PomowiseApp` as a widget.
+pub struct PomowiseApp {
+    app: App,
+    /// Offscreen terminal used when rendering into a pane
+    pane: Option<Terminal<TestBackend>>,
+}
+
+impl PomowiseApp {
+    /// App with the user's config from `config.json` (see [`crate::paths`]); opens the
+    /// first-run walkthrough or the "What's new" screen when they are due
+    pub fn new() -> Self {
+        let mut pomowise = Self::with_config(Config::load());
+        pomowise.app.show_first_run();
+        pomowise
+    }
+
+    /// App with an explicit config (nothing is read from disk)
+    pub fn with_config(config: Config) -> Self {
+        Self::with_clock(config, clock::system())
+    }
+
+    /// App with an explicit config whose timer and animation read `clock`,
+    /// e.g. a [`MockClock`](crate::clock::MockClock) for tests
+    pub fn with_clock(config: Config, clock: SharedClock) -> Self {
+        Self {
+            app: App::with_clock(config, clock),
+            pane: None,
+        }
+    }
+
+    /// Apply a terminal event (keys, resize, focus)
+    pub fn handle_event(&mut self, event: &Event) {
+        input::handle_event(&mut self.app, event);
+    }
+
+    /// False when the last frame drawn is still accurate (a static scene within
+    /// the same second, no input since); the host may skip drawing then
+    pub fn needs_redraw(&mut self) -> bool {
+        self.app.take_redraw()
+    }
+
+    /// How long the host may wait for input before calling [`tick`](Self::tick):
+    /// a frame (100 ms) while anything animates, up to a second for static scenes
+    pub fn next_wake(&self) -> Duration {
+        self.app.next_wake()
+    }
+
+    /// Start in the timer where the last run left off (`pomowise resume`); with
+    /// `fresh`, only its theme, font and session lengths are picked up
+    pub fn resume_last(&mut self, fresh: bool) {
+        self.app.resume_last(fresh);
+    }
+
+    /// Advance timer and animation; call once per frame
+    pub fn tick(&mut self) {
+        self.app.tick();
+    }
+
+    /// True once the user asked to quit (`q` on the menu, `:quit`)
+    pub fn should_quit(&self) -> bool {
+        self.app.should_quit
+    }
+
+    /// True while the timer screen is showing
+    pub fn in_session(&self) -> bool {
+        self.app.screen == AppScreen::Timer
+    }
+
+    pub fn timer(&self) -> &PomodoroTimer {
+        &self.app.timer
+    }
+
+    /// Name of the digit font in use (`lcd`, `3d-blocks`, ...)
+    pub fn font(&self) -> String {
+        self.app.animation.current_font.slug()
+    }
+
+    /// Switch to a theme by name (`matrix`, `ocean`, ...) and stop auto-rotation;
+    /// false when there is no such theme
+    pub fn set_theme(&mut self, name: &str) -> bool {
+        let Some(theme) = ThemeType::from_name(name) else {
+            return false;
+        };
+        self.app.animation.set_theme(theme);
+        self.app.auto_rotate = false;
+        true
+    }
+
+    /// Jump the theme animation to a given frame
+    pub fn set_frame_index(&mut self, frame_index: usize) {
+        self.app.animation.jump_to_frame(frame_index);
+    }
+
+    /// Pin the wall clock shown on screen (theme of the day, end times, stats);
+    /// with the frame index this makes renders reproducible. `None` follows the system clock.
+    pub fn freeze_clock(&mut self, now: Option<DateTime<Local>>) {
+        self.app.frozen_now = now;
+    }
+
+    /// Walk through a day template from the config (`--template`); false when
+    /// the config has no template by that name
+    pub fn set_template(&mut self, name: &str) -> bool {
+        self.app.set_template(name)
+    }
+
+    /// Use the `--mini` layout for a tiny floating pane
+    pub fn set_mini(&mut self, mini: bool) {
+        self.app.mini = mini;
+    }
+
+    /// Save bandwidth over a slow connection (see `--slow-link`)
+    pub fn set_slow_link(&mut self, on: bool) {
+        self.app.set_slow_link(on);
+    }
+
+    /// Read the desktop idle time (break checks) from `source` instead of
+    /// `xprintidle`/`ioreg`; `None` from it means the desktop doesn't say
+    pub fn set_idle_source(&mut self, source: impl Fn() -> Option<Duration> + Send + Sync + 'static) {
+        self.app.idle = IdleMonitor::with_source(Arc::new(source));
+    }
+
+    /// Draw over the whole frame
+    pub fn draw(&mut self, frame: &mut Frame) {
+        let area = frame.area();
+        self.fit(area);
+        ui::draw(frame, &self.app);
+    }
+
+    /// Keep layout and adaptive font in step with the space we are given
+    fn fit(&mut self, area: Rect) {
+        if (self.app.scaling.width, self.app.scaling.height) != (area.width, area.height) {
+            self.app.update_dimensions(area.width, area.height);
+        }
+    }
+}
+
+impl Default for PomowiseApp {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Render into a pane: the UI is laid out for the pane's size offscreen, then
+/// copied into the host buffer at the pane's position.
+impl Widget for &mut PomowiseApp {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = area.intersection(buf.area);
+        if area.is_empty() {
+            return;
+        }
+        self.fit(area);
+
+        let stale = self
+            .pane
+            .as_ref()
+            .is_none_or(|t| t.backend().buffer().area.as_size() != area.as_size());
+        if stale {
+            match Terminal::new(TestBackend::new(area.width, area.height)) {
+                Ok(terminal) => self.pane = Some(terminal),
+                Err(_) => return,
+            }
+        }
+        let Some(pane) = self.pane.as_mut() else {
+            return;
+        };
+
+        let app = &self.app;
+        if pane.draw(|frame| ui::draw(frame, app)).is_err() {
+            return;
+        }
+        let offscreen = pane.backend().buffer();
+        for y in 0..area.height {
+            for x in 0..area.width {
+                buf[(area.x + x, area.y + y)] = offscreen[(x, y)].clone();
+            }
+        }
+    }
+}
+
+/// How often the input thread checks whether the run loop still listens
+const INPUT_POLL: Duration = Duration::from_millis(250);
+
+/// Terminal events read on a thread of their own, so the run loop can await
+/// input and its next deadline together; the thread stops when this is dropped
+struct InputReader {
+    events: mpsc::UnboundedReceiver<io::Result<Event>>,
+    stop: Arc<AtomicBool>,
+    thread: Option<JoinHandle<()>>,
+}
+
+impl InputReader {
+    fn spawn() -> Self {
+        let (sender, events) = mpsc::unbounded_channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = Arc::clone(&stop);
+        let thread = thread::spawn(move || {
+            while !stopped.load(Ordering::Relaxed) {
+                let event = match event::poll(INPUT_POLL) {
+                    Ok(false) => continue,
+                    Ok(true) => event::read(),
+                    Err(e) => Err(e),
+                };
+                let failed = event.is_err();
+                if sender.send(event).is_err() || failed {
+                    break;
+                }
+            }
+        });
+        Self { events, stop, thread: Some(thread) }
+    }
+
+    /// The next terminal event, or the error that stopped the reader
+    async fn next(&mut self) -> io::Result<Event> {
+        self.events.recv().await.unwrap_or_else(|| Err(io::Error::other("terminal input closed")))
+    }
+}
+
+impl Drop for InputReader {
+    fn drop(&mut self) {
+        // Joined so that no key pressed after the loop ends goes to this thread
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+/// Run the app full screen on `terminal` until the user quits.
+/// Sleeps until input arrives or the scene next changes: ten frames a second
+/// while animating, once a second for a static scene (digits-only, `--mini`).
+/// Also publishes timer state for the tray icon while a session is running, its
+/// progress to the taskbar or tab badge of terminals that can show it, and to
+/// tmux when configured. The taskbar escapes go through the backend's own writer
+/// (a `CrosstermBackend` writes wherever it draws), between frames.
+pub async fn run_app<B: Backend + Write>(terminal: &mut Terminal<B>, app: &mut PomowiseApp) -> io::Result<()> {
+    let mut input = InputReader::spawn();
+    let mut published = false;
+    let mut taskbar = Taskbar::detect(app.app.config.terminal_progress);
+    let mut tmux = Tmux::detect(app.app.config.tmux.as_ref());
+
+    loop {
+        // Draw UI; static scenes (digits-only, --mini) skip frames where nothing changed
+        if app.needs_redraw() {
+            terminal.draw(|f| app.draw(f))?;
+        }
+
+        // Wait for input, or for the next frame or second to be due
+        tokio::select! {
+            event = input.next() => app.handle_event(&event?),
+            _ = tokio::time::sleep(app.next_wake()) => {}
+        }
+
+        // Update timer and animation
+        app.tick();
+
+        // Write timer state for tray to read
+        if app.in_session() {
+            published = ipc::write_status(&app.timer().snapshot()).is_ok() || published;
+        }
+        let session = (app.in_session() && app.timer().state != TimerState::Idle).then(|| app.timer().snapshot());
+        let _ = taskbar.update(terminal.backend_mut(), session.as_ref());
+        if let Some(tmux) = tmux.as_mut() {
+            tmux.update(session.as_ref());
+        }
+
+        // Only our own status file: a `--mini` mirror must not remove the one it follows
+        if app.should_quit() {
+            app.app.save_last_run();
+            let _ = taskbar.clear(terminal.backend_mut());
+            if let Some(tmux) = tmux.as_mut() {
+                tmux.clear();
+            }
+            if published {
+                ipc::cleanup();
+            }
+            return Ok(());
+        }
+    }
+}
@@ -0,0 +1,124 @@
+//! In-memory event bus for what happens to the running session
+//! The app publishes lifecycle events as they happen (a session starting,
+//! pausing, resuming, ending) and delivers them once per tick to every
+//! subscriber: the event log pane (`L`), the notification backends and the
+//! stats screen. Nothing here is written to disk; the history file stays the
+//! record of finished sessions.
+
+use std::collections::VecDeque;
+
+use chrono::{DateTime, Local};
+
+use crate::history::{SessionKind, SessionOutcome};
+
+/// Entries the event log keeps for this run
+const LOG_CAPACITY: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AppEvent {
+    Started(SessionKind),
+    Paused,
+    /// Paused because the window manager focused a distraction ("discord")
+    Interrupted(String),
+    Resumed,
+    Ended(SessionKind, SessionOutcome),
+}
+
+/// An event and when it happened
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    pub at: DateTime<Local>,
+    pub event: AppEvent,
+}
+
+impl Event {
+    /// One line of the event log, without the time ("Work complete")
+    pub fn describe(&self) -> String {
+        match &self.event {
+            AppEvent::Started(kind) => format!("{} started", kind.label()),
+            AppEvent::Paused => "Paused".to_string(),
+            AppEvent::Interrupted(what) => format!("Interrupted: {}", what),
+            AppEvent::Resumed => "Resumed".to_string(),
+            AppEvent::Ended(kind, outcome) => {
+                let how = match outcome {
+                    SessionOutcome::Completed => "complete",
+                    SessionOutcome::Skipped => "skipped",
+                    SessionOutcome::Abandoned => "abandoned",
+                    SessionOutcome::Adjusted => "left early",
+                };
+                format!("{} {}", kind.label(), how)
+            }
+        }
+    }
+}
+
+/// Something that reacts to published events
+pub trait Subscriber {
+    fn on_event(&mut self, event: &Event);
+}
+
+/// Events published since the last delivery
+#[derive(Debug, Default)]
+pub struct EventBus {
+    pending: Vec<Event>,
+}
+
+impl EventBus {
+    pub fn publish(&mut self, at: DateTime<Local>, event: AppEvent) {
+        self.pending.push(Event { at, event });
+    }
+
+    /// Take the pending events, oldest first, for delivery
+    pub fn drain(&mut self) -> Vec<Event> {
+        std::mem::take(&mut self.pending)
+    }
+}
+
+/// The most recent events of this run, for the side pane
+#[derive(Debug, Default)]
+pub struct EventLog {
+    entries: VecDeque<Event>,
+}
+
+impl EventLog {
+    /// Oldest first
+    pub fn entries(&self) -> impl DoubleEndedIterator<Item = &Event> + ExactSizeIterator {
+        self.entries.iter()
+    }
+}
+
+impl Subscriber for EventLog {
+    fn on_event(&mut self, event: &Event) {
+        if self.entries.len() == LOG_CAPACITY {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(event.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_log_keeps_recent_events_in_order() {
+        let mut bus = EventBus::default();
+        let mut log = EventLog::default();
+        let now = Local::now();
+        bus.publish(now, AppEvent::Started(SessionKind::Work));
+        bus.publish(now, AppEvent::Ended(SessionKind::Work, SessionOutcome::Completed));
+        for event in bus.drain() {
+            log.on_event(&event);
+        }
+        assert!(bus.drain().is_empty());
+
+        let lines: Vec<String> = log.entries().map(Event::describe).collect();
+        assert_eq!(lines, ["Work started", "Work complete"]);
+
+        for _ in 0..LOG_CAPACITY {
+            log.on_event(&Event { at: now, event: AppEvent::Paused });
+        }
+        assert_eq!(log.entries().len(), LOG_CAPACITY);
+        assert_eq!(log.entries().next().map(Event::describe).as_deref(), Some("Paused"));
+    }
+}
@@ -0,0 +1,609 @@
+//! Session history stored in ~/.pomowise/history.jsonl
+//! One JSON record per line, appended whenever a session ends (completed or abandoned),
+//! or a row in SQLite with `history_store` (see `store`). With encryption on it is
+//! kept in history.enc instead, and a locked or incognito run keeps its records in
+//! memory only (see `privacy`).
+
+use std::collections::HashSet;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use chrono::{DateTime, Local, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::paths;
+use crate::privacy;
+use crate::store::StoreConfig;
+use crate::timer::{TimerState, CYCLE_STEPS};
+
+/// Serializes access to the history file (sync rewrites it from a background thread)
+static FILE_LOCK: Mutex<()> = Mutex::new(());
+
+/// How this run stores the history, set once at startup
+static MODE: Mutex<HistoryMode> = Mutex::new(HistoryMode::Plain);
+
+/// Where a plain history is kept, set once at startup
+static STORE: Mutex<StoreConfig> = Mutex::new(StoreConfig::Json);
+
+/// Records of this run when nothing goes to disk (locked or incognito)
+static MEMORY: Mutex<Vec<SessionRecord>> = Mutex::new(Vec::new());
+
+/// First line of the decrypted history, so a wrong passphrase that happens to
+/// decrypt without an error is still caught before anything is overwritten
+const ENCRYPTED_HEADER: &str = "pomowise-history-v1";
+
+/// Where the history goes for this run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HistoryMode {
+    /// Plain JSON lines in history.jsonl
+    Plain,
+    /// history.enc, encrypted with this passphrase
+    Encrypted(String),
+    /// Encryption is on but no passphrase was given: this run's sessions stay in memory
+    Locked,
+    /// `--incognito`: this run's sessions stay in memory
+    Incognito,
+}
+
+impl HistoryMode {
+    /// Whether records survive the run
+    pub fn is_persistent(&self) -> bool {
+        matches!(self, HistoryMode::Plain | HistoryMode::Encrypted(_))
+    }
+}
+
+pub fn set_mode(mode: HistoryMode) {
+    *MODE.lock().unwrap_or_else(|e| e.into_inner()) = mode;
+}
+
+pub fn mode() -> HistoryMode {
+    MODE.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Keep the plain history in this store (the `history_store` config key)
+pub fn set_store(store: StoreConfig) {
+    *STORE.lock().unwrap_or_else(|e| e.into_inner()) = store;
+}
+
+pub fn store() -> StoreConfig {
+    STORE.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Kind of session a record describes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionKind {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+impl SessionKind {
+    /// Session kind of a timer state (paused states use their inner session)
+    pub fn from_state(state: &TimerState) -> Option<Self> {
+        match state {
+            TimerState::Work { .. } => Some(SessionKind::Work),
+            TimerState::ShortBreak { .. } => Some(SessionKind::ShortBreak),
+            TimerState::LongBreak => Some(SessionKind::LongBreak),
+            TimerState::Paused(inner) => Self::from_state(inner),
+            TimerState::Idle => None,
+        }
+    }
+
+    /// Session kind at a position in the cycle (see `PomodoroTimer::cycle_step`)
+    pub fn of_step(step: usize) -> Self {
+        if step >= CYCLE_STEPS - 1 {
+            SessionKind::LongBreak
+        } else if step.is_multiple_of(2) {
+            SessionKind::Work
+        } else {
+            SessionKind::ShortBreak
+        }
+    }
+
+    /// Display name
+    pub fn label(&self) -> &'static str {
+        match self {
+            SessionKind::Work => "Work",
+            SessionKind::ShortBreak => "Short Break",
+            SessionKind::LongBreak => "Long Break",
+        }
+    }
+}
+
+/// How a session ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SessionOutcome {
+    Completed,
+    /// Left before the timer ran out (confirmed quit to menu)
+    Abandoned,
+    /// Skipped ahead to the next session with Tab
+    Skipped,
+    /// Left for another point in the cycle from the go-to menu
+    Adjusted,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SessionRecord {
+    pub kind: SessionKind,
+    pub outcome: SessionOutcome,
+    /// Unix timestamp (seconds) of when the session ended
+    pub ended_at: u64,
+    /// Time actually spent in the session
+    pub duration_secs: u64,
+    /// What the user meant to do in this work session
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub intention: Option<String>,
+    /// Self-rated focus, 1-5, given when the work session ended
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub focus_rating: Option<u8>,
+    /// Category the work session was labelled with (deep work, admin, ...)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category: Option<String>,
+    /// Pomodoros the intention was estimated to take ("3🍅" at the prompt)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate: Option<u8>,
+    /// Time the session spent paused, on top of `duration_secs`
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub paused_secs: u64,
+    /// Day template block the work session belonged to ("Morning")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub block: Option<String>,
+    /// Theme on screen as the work session ended (slug)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    /// Project the work session ran in, from tmux, git or the directory (`auto_label`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+}
+
+fn is_zero(secs: &u64) -> bool {
+    *secs == 0
+}
+
+impl SessionRecord {
+    /// A record for a session ending now
+    pub fn new(kind: SessionKind, outcome: SessionOutcome, duration: Duration) -> Self {
+        let ended_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Self {
+            kind,
+            outcome,
+            ended_at,
+            duration_secs: duration.as_secs(),
+            intention: None,
+            focus_rating: None,
+            category: None,
+            estimate: None,
+            paused_secs: 0,
+            block: None,
+            theme: None,
+            project: None,
+        }
+    }
+}
+
+/// Share of breaks taken in full (None until a break has ended)
+pub fn break_compliance(records: &[SessionRecord]) -> Option<f64> {
+    let breaks: Vec<_> = records
+        .iter()
+        .filter(|r| matches!(r.kind, SessionKind::ShortBreak | SessionKind::LongBreak))
+        .collect();
+    if breaks.is_empty() {
+        return None;
+    }
+    let completed = breaks
+        .iter()
+        .filter(|r| r.outcome == SessionOutcome::Completed)
+        .count();
+    Some(completed as f64 / breaks.len() as f64)
+}
+
+/// Mean focus rating over the rated sessions (None if none were rated)
+pub fn average_rating<'a>(records: impl IntoIterator<Item = &'a SessionRecord>) -> Option<f64> {
+    let ratings: Vec<u8> = records.into_iter().filter_map(|r| r.focus_rating).collect();
+    if ratings.is_empty() {
+        return None;
+    }
+    Some(ratings.iter().map(|&r| r as f64).sum::<f64>() / ratings.len() as f64)
+}
+
+/// Work sessions grouped by category, most time first: (category, records)
+pub fn by_category<'a>(records: impl IntoIterator<Item = &'a SessionRecord>) -> Vec<(String, Vec<&'a SessionRecord>)> {
+    grouped(records, |record| record.category.as_ref())
+}
+
+/// Work sessions grouped by project, most time first: (project, records)
+pub fn by_project<'a>(records: impl IntoIterator<Item = &'a SessionRecord>) -> Vec<(String, Vec<&'a SessionRecord>)> {
+    grouped(records, |record| record.project.as_ref())
+}
+
+/// Work sessions grouped by a label, most time first, leaving out unlabelled ones
+fn grouped<'a>(
+    records: impl IntoIterator<Item = &'a SessionRecord>,
+    label: impl Fn(&SessionRecord) -> Option<&String>,
+) -> Vec<(String, Vec<&'a SessionRecord>)> {
+    let mut groups: Vec<(String, Vec<&SessionRecord>)> = Vec::new();
+    for record in records.into_iter().filter(|r| r.kind == SessionKind::Work) {
+        let Some(label) = label(record) else { continue };
+        match groups.iter_mut().find(|(name, _)| name == label) {
+            Some((_, group)) => group.push(record),
+            None => groups.push((label.clone(), vec![record])),
+        }
+    }
+    let total = |group: &[&SessionRecord]| group.iter().map(|r| r.duration_secs).sum::<u64>();
+    groups.sort_by_key(|(_, group)| std::cmp::Reverse(total(group)));
+    groups
+}
+
+/// Work sessions completed on the given local day
+pub fn completed_on(records: &[SessionRecord], day: NaiveDate) -> usize {
+    completed_work_on(records, day).count()
+}
+
+/// Work sessions completed on the given local day in a day template block
+pub fn completed_in_block(records: &[SessionRecord], day: NaiveDate, block: &str) -> usize {
+    completed_work_on(records, day)
+        .filter(|r| r.block.as_deref() == Some(block))
+        .count()
+}
+
+fn completed_work_on(records: &[SessionRecord], day: NaiveDate) -> impl Iterator<Item = &SessionRecord> {
+    records
+        .iter()
+        .filter(|r| r.kind == SessionKind::Work && r.outcome == SessionOutcome::Completed)
+        .filter(move |r| {
+            DateTime::from_timestamp(r.ended_at as i64, 0)
+                .is_some_and(|end| end.with_timezone(&Local).date_naive() == day)
+        })
+}
+
+/// Completed pomodoros on one intention against its estimate
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaskProgress {
+    pub task: String,
+    pub done: usize,
+    pub estimate: Option<u8>,
+}
+
+impl TaskProgress {
+    /// Pomodoros beyond the estimate, once there are any
+    pub fn overrun(&self) -> Option<usize> {
+        let estimate = self.estimate? as usize;
+        (self.done > estimate).then(|| self.done - estimate)
+    }
+}
+
+/// Split a trailing estimate off an intention: "fix parser 3🍅" or "fix parser ~3"
+pub fn split_estimate(text: &str) -> (&str, Option<u8>) {
+    let text = text.trim();
+    let Some((task, last)) = text.rsplit_once(' ') else {
+        return (text, None);
+    };
+    let count = last
+        .strip_suffix('🍅')
+        .or_else(|| last.strip_prefix('~'))
+        .and_then(|n| n.parse::<u8>().ok())
+        .filter(|&n| n > 0);
+    match count {
+        Some(count) => (task.trim_end(), Some(count)),
+        None => (text, None),
+    }
+}
+
+/// Progress on `task` so far; the estimate is the latest one it was given
+pub fn task_progress(records: &[SessionRecord], task: &str) -> TaskProgress {
+    let sessions = records
+        .iter()
+        .filter(|r| r.kind == SessionKind::Work && r.intention.as_deref() == Some(task));
+    let mut progress = TaskProgress { task: task.to_string(), done: 0, estimate: None };
+    for record in sessions {
+        if record.outcome == SessionOutcome::Completed {
+            progress.done += 1;
+        }
+        progress.estimate = record.estimate.or(progress.estimate);
+    }
+    progress
+}
+
+/// Every estimated task, most recently worked on first
+pub fn estimated_tasks(records: &[SessionRecord]) -> Vec<TaskProgress> {
+    let mut tasks: Vec<TaskProgress> = Vec::new();
+    for record in records.iter().rev().filter(|r| r.estimate.is_some()) {
+        let Some(task) = &record.intention else { continue };
+        if !tasks.iter().any(|t| &t.task == task) {
+            tasks.push(task_progress(records, task));
+        }
+    }
+    tasks
+}
+
+/// Path to the history file
+pub fn history_path() -> PathBuf {
+    paths::data_dir().join("history.jsonl")
+}
+
+/// Path to the encrypted history file
+pub fn encrypted_path() -> PathBuf {
+    paths::data_dir().join("history.enc")
+}
+
+/// Whether `passphrase` opens the encrypted history (any passphrase does before it exists).
+/// Called once at startup, so it also folds the records appended by earlier runs
+/// into one block, keeping later loads to a single decryption or a few.
+pub fn check_passphrase(passphrase: &str) -> bool {
+    let _lock = FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let Ok((records, blocks)) = read_encrypted(passphrase) else {
+        return false;
+    };
+    if blocks > 1 {
+        let _ = save_encrypted(&records, passphrase);
+    }
+    true
+}
+
+/// Append one record to the history
+pub fn append(record: &SessionRecord) -> io::Result<()> {
+    let _lock = FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    match mode() {
+        HistoryMode::Plain => store().store().append(record),
+        HistoryMode::Encrypted(passphrase) => {
+            // A plain history still waiting goes in first, as its own block
+            let plain = load_plain()?;
+            if !plain.is_empty() {
+                append_encrypted(&plain, &passphrase)?;
+                store().store().remove()?;
+            }
+            append_encrypted(std::slice::from_ref(record), &passphrase)
+        }
+        HistoryMode::Locked | HistoryMode::Incognito => {
+            MEMORY.lock().unwrap_or_else(|e| e.into_inner()).push(record.clone());
+            Ok(())
+        }
+    }
+}
+
+/// Read every record; a missing file is an empty history
+pub fn load() -> io::Result<Vec<SessionRecord>> {
+    match mode() {
+        HistoryMode::Plain => load_plain(),
+        HistoryMode::Encrypted(passphrase) => load_encrypted(&passphrase),
+        HistoryMode::Locked | HistoryMode::Incognito => {
+            Ok(MEMORY.lock().unwrap_or_else(|e| e.into_inner()).clone())
+        }
+    }
+}
+
+fn load_plain() -> io::Result<Vec<SessionRecord>> {
+    store().store().load()
+}
+
+/// The encrypted history, plus a plain history not yet moved into it
+fn load_encrypted(passphrase: &str) -> io::Result<Vec<SessionRecord>> {
+    let (records, _) = read_encrypted(passphrase)?;
+    Ok(merge(&records, &load_plain()?))
+}
+
+/// Records of history.enc and how many blocks they came in. Each line of the
+/// file is one block: the records of one append, encrypted on their own so a
+/// session's end doesn't re-encrypt the whole history.
+fn read_encrypted(passphrase: &str) -> io::Result<(Vec<SessionRecord>, usize)> {
+    let cipher = match std::fs::read_to_string(encrypted_path()) {
+        Ok(cipher) => cipher,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok((Vec::new(), 0)),
+        Err(err) => return Err(err),
+    };
+    let mut records = Vec::new();
+    let mut blocks = 0;
+    for line in cipher.lines().map(str::trim).filter(|line| !line.is_empty()) {
+        let plain = privacy::decrypt(line, passphrase)?;
+        let Some(body) = plain.strip_prefix(ENCRYPTED_HEADER) else {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "wrong passphrase"));
+        };
+        records.extend(parse(body));
+        blocks += 1;
+    }
+    Ok((records, blocks))
+}
+
+fn encrypt_block(records: &[SessionRecord], passphrase: &str) -> io::Result<String> {
+    privacy::encrypt(&format!("{}\n{}", ENCRYPTED_HEADER, to_jsonl(records)), passphrase)
+}
+
+/// Add a block to history.enc
+fn append_encrypted(records: &[SessionRecord], passphrase: &str) -> io::Result<()> {
+    let block = encrypt_block(records, passphrase)?;
+    let path = encrypted_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", block)
+}
+
+/// Write the encrypted history as a single block and drop the plain history it replaces
+fn save_encrypted(records: &[SessionRecord], passphrase: &str) -> io::Result<()> {
+    let cipher = encrypt_block(&merge(records, &load_plain()?), passphrase)?;
+    let path = encrypted_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp = path.with_extension("enc.tmp");
+    std::fs::write(&tmp, cipher + "\n")?;
+    std::fs::rename(&tmp, &path)?;
+    store().store().remove()
+}
+
+/// Merge records from elsewhere into the local history; returns the merged history.
+/// Only plain histories sync: the remote copy would otherwise leak what is encrypted here.
+pub fn merge_into_local(other: &[SessionRecord]) -> io::Result<Vec<SessionRecord>> {
+    let _lock = FILE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    if mode() != HistoryMode::Plain {
+        return Err(io::Error::other("history sync is off for encrypted and incognito histories"));
+    }
+    let local = store().store();
+    let merged = merge(&local.load()?, other);
+    local.replace(&merged)?;
+    Ok(merged)
+}
+
+/// Union of two histories without duplicates, oldest first.
+/// Order-independent, so two machines merging each other's files agree.
+pub fn merge(a: &[SessionRecord], b: &[SessionRecord]) -> Vec<SessionRecord> {
+    let mut seen = HashSet::new();
+    let mut merged: Vec<SessionRecord> = a
+        .iter()
+        .chain(b)
+        .filter(|record| seen.insert(*record))
+        .cloned()
+        .collect();
+    merged.sort_by_key(|record| record.ended_at);
+    merged
+}
+
+/// Serialize records in the history file format
+pub fn to_jsonl(records: &[SessionRecord]) -> String {
+    records
+        .iter()
+        .filter_map(|record| serde_json::to_string(record).ok())
+        .map(|json| json + "\n")
+        .collect()
+}
+
+/// Parse history lines, skipping any that are malformed (e.g. a torn last write)
+pub fn parse(contents: &str) -> Vec<SessionRecord> {
+    contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_skips_malformed_lines() {
+        let contents = concat!(
+            r#"{"kind":"work","outcome":"completed","ended_at":100,"duration_secs":1500}"#,
+            "\n{\"kind\":\"wo\n",
+            r#"{"kind":"work","outcome":"abandoned","ended_at":200,"duration_secs":300}"#,
+            "\n",
+        );
+        let records = parse(contents);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].outcome, SessionOutcome::Abandoned);
+        assert_eq!(records[1].duration_secs, 300);
+    }
+
+    #[test]
+    fn test_merge_is_order_independent_union() {
+        let record = |ended_at| SessionRecord {
+            kind: SessionKind::Work,
+            outcome: SessionOutcome::Completed,
+            ended_at,
+            duration_secs: 1500,
+            intention: None,
+            focus_rating: None,
+            category: None,
+            estimate: None,
+            paused_secs: 0,
+            block: None,
+            theme: None,
+            project: None,
+        };
+        let here = vec![record(1), record(3)];
+        let there = vec![record(2), record(3)];
+
+        let merged = merge(&here, &there);
+        assert_eq!(merged, vec![record(1), record(2), record(3)]);
+        assert_eq!(merge(&there, &here), merged);
+        assert_eq!(parse(&to_jsonl(&merged)), merged);
+    }
+
+    #[test]
+    fn test_break_compliance() {
+        let record = |kind, outcome| SessionRecord {
+            kind,
+            outcome,
+            ended_at: 0,
+            duration_secs: 300,
+            intention: None,
+            focus_rating: None,
+            category: None,
+            estimate: None,
+            paused_secs: 0,
+            block: None,
+            theme: None,
+            project: None,
+        };
+        let records = vec![
+            record(SessionKind::Work, SessionOutcome::Abandoned),
+            record(SessionKind::ShortBreak, SessionOutcome::Completed),
+            record(SessionKind::LongBreak, SessionOutcome::Completed),
+            record(SessionKind::ShortBreak, SessionOutcome::Completed),
+            record(SessionKind::ShortBreak, SessionOutcome::Skipped),
+        ];
+        assert_eq!(break_compliance(&records), Some(0.75));
+        assert_eq!(break_compliance(&records[..1]), None);
+    }
+
+    #[test]
+    fn test_by_category() {
+        let mut admin = SessionRecord::new(SessionKind::Work, SessionOutcome::Completed, Duration::from_secs(300));
+        let unlabelled = admin.clone();
+        admin.category = Some("admin".to_string());
+        let mut deep = admin.clone();
+        deep.category = Some("deep work".to_string());
+        deep.duration_secs = 3000;
+
+        // Most time first; unlabelled sessions are left out
+        let groups = by_category([&admin, &deep, &admin, &unlabelled]);
+        let names: Vec<&str> = groups.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["deep work", "admin"]);
+        assert_eq!(groups[1].1.len(), 2);
+        admin.project = Some("pomowise".to_string());
+        assert_eq!(by_project([&admin, &deep]).len(), 1);
+    }
+
+    #[test]
+    fn test_intention_and_rating_are_optional() {
+        let old = r#"{"kind":"work","outcome":"completed","ended_at":100,"duration_secs":1500}"#;
+        let rated = r#"{"kind":"work","outcome":"completed","ended_at":200,"duration_secs":1500,"intention":"Write docs","focus_rating":4}"#;
+        let records = parse(&format!("{}\n{}\n", old, rated));
+        assert_eq!(records[0].intention, None);
+        assert_eq!(records[1].intention.as_deref(), Some("Write docs"));
+        assert_eq!(average_rating(&records), Some(4.0));
+        assert!(!to_jsonl(&records[..1]).contains("intention"));
+    }
+
+    #[test]
+    fn test_task_estimates() {
+        assert_eq!(split_estimate("fix parser 3🍅"), ("fix parser", Some(3)));
+        assert_eq!(split_estimate(" fix parser ~2 "), ("fix parser", Some(2)));
+        assert_eq!(split_estimate("read chapter 3"), ("read chapter 3", None));
+        assert_eq!(split_estimate("3🍅"), ("3🍅", None));
+
+        let session = |outcome, estimate| SessionRecord {
+            intention: Some("fix parser".to_string()),
+            estimate,
+            ..SessionRecord::new(SessionKind::Work, outcome, Duration::from_secs(1500))
+        };
+        let mut records = vec![
+            session(SessionOutcome::Completed, Some(2)),
+            session(SessionOutcome::Abandoned, None),
+            session(SessionOutcome::Completed, None),
+        ];
+        let progress = task_progress(&records, "fix parser");
+        assert_eq!((progress.done, progress.estimate, progress.overrun()), (2, Some(2), None));
+
+        records.push(session(SessionOutcome::Completed, None));
+        assert_eq!(estimated_tasks(&records)[0].overrun(), Some(1));
+        assert_eq!(task_progress(&records, "other").done, 0);
+    }
+}
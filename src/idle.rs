@@ -0,0 +1,135 @@
+//! Break checks (`break_check` in config.json): whether the user is still at the
+//! keyboard during a break, and whether they were away when it ended.
+//! The desktop's idle time comes from `xprintidle` on X11 and `ioreg` on macOS,
+//! asked on a thread of its own so a slow answer never holds up a frame.
+//! Where neither answers, the checks fall back on terminal focus: a break spent
+//! in another window gets the nudge, and no extension is offered.
+
+use std::process::{Command, Stdio};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// How often the idle time is asked for; in between it is counted up from the last answer
+const POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Input this recent during a break counts as still working
+pub const ACTIVE_WITHIN: Duration = Duration::from_secs(10);
+
+/// Idle at least this long as a break ends (or the whole break, if shorter) counts as away
+pub const AWAY_FOR: Duration = Duration::from_secs(120);
+
+/// Break check settings (the `break_check` key in config.json)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BreakCheckConfig {
+    /// Nudge when there's typing elsewhere during a break, and offer a longer one when it ends unnoticed
+    pub enabled: bool,
+    /// Minutes `e` adds to a break that ended while away
+    pub extend_minutes: u64,
+}
+
+impl Default for BreakCheckConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            extend_minutes: 5,
+        }
+    }
+}
+
+/// Where the idle time comes from instead of the desktop (tests, embedding hosts);
+/// it is asked on every check, so it should answer at once
+pub type IdleSource = Arc<dyn Fn() -> Option<Duration> + Send + Sync>;
+
+/// The desktop's idle time, asked for every few seconds
+#[derive(Default)]
+pub struct IdleMonitor {
+    /// Idle time at the last answer, and when it was read; None once the desktop gave no answer
+    last: Option<(Duration, Instant)>,
+    polled: Option<Instant>,
+    /// The desktop's answer to the question in flight
+    pending: Option<Receiver<Option<(Duration, Instant)>>>,
+    source: Option<IdleSource>,
+}
+
+impl IdleMonitor {
+    /// Read the idle time from `source` rather than the desktop
+    pub fn with_source(source: IdleSource) -> Self {
+        Self {
+            source: Some(source),
+            ..Self::default()
+        }
+    }
+
+    /// Time since the last keyboard or mouse input anywhere, if the desktop says
+    pub fn idle(&mut self, now: Instant) -> Option<Duration> {
+        if let Some(source) = &self.source {
+            return source();
+        }
+        if let Some(pending) = &self.pending {
+            match pending.try_recv() {
+                Ok(answer) => {
+                    self.last = answer;
+                    self.pending = None;
+                }
+                Err(TryRecvError::Disconnected) => self.pending = None,
+                Err(TryRecvError::Empty) => {}
+            }
+        }
+        let due = self.polled.is_none_or(|polled| now.saturating_duration_since(polled) >= POLL_INTERVAL);
+        if due && self.pending.is_none() {
+            self.polled = Some(now);
+            let (sender, receiver) = mpsc::channel();
+            std::thread::spawn(move || {
+                let _ = sender.send(system_idle().map(|idle| (idle, Instant::now())));
+            });
+            self.pending = Some(receiver);
+        }
+        self.last.map(|(idle, at)| idle + now.saturating_duration_since(at))
+    }
+}
+
+/// Ask the desktop how long it has been idle
+fn system_idle() -> Option<Duration> {
+    if cfg!(target_os = "macos") {
+        let text = run("ioreg", &["-c", "IOHIDSystem", "-d", "4"])?;
+        parse_ioreg(&text)
+    } else if std::env::var_os("DISPLAY").is_some() {
+        let millis = run("xprintidle", &[])?.trim().parse().ok()?;
+        Some(Duration::from_millis(millis))
+    } else {
+        None
+    }
+}
+
+fn run(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// `"HIDIdleTime" = 1234567890` (nanoseconds) out of the ioreg listing
+fn parse_ioreg(text: &str) -> Option<Duration> {
+    let line = text.lines().find(|line| line.contains("\"HIDIdleTime\""))?;
+    let nanos = line.split('=').nth(1)?.trim().parse().ok()?;
+    Some(Duration::from_nanos(nanos))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ioreg() {
+        let listing = "    | |   \"HIDIdleTime\" = 4500000000\n    | |   \"HIDKeyboardModifierMappingPairs\" = ()";
+        assert_eq!(parse_ioreg(listing), Some(Duration::from_millis(4500)));
+        assert_eq!(parse_ioreg("no such key"), None);
+    }
+}
@@ -0,0 +1,297 @@
+//! Keyboard and terminal event handling
+//! Maps crossterm events to `App` actions for every screen and overlay.
+
+use crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers};
+
+use crate::app::{App, AppScreen};
+use crate::break_activity::Direction;
+
+/// Apply one terminal event to the app
+pub fn handle_event(app: &mut App, event: &Event) {
+    app.redraw_requested = true;
+    match event {
+        // Handle terminal resize
+        Event::Resize(width, height) => {
+            app.update_dimensions(*width, *height);
+        }
+
+        // Focus tracking (strict breaks nag when the terminal loses focus)
+        Event::FocusGained => app.focus_changed(true),
+        Event::FocusLost => app.focus_changed(false),
+
+        // Handle key events
+        // The first-run walkthrough sits over every screen
+        Event::Key(key) if key.kind == KeyEventKind::Press && app.onboarding_step.is_some() => match key.code {
+            KeyCode::Enter | KeyCode::Right | KeyCode::Char(' ') | KeyCode::Char('l') => app.onboarding_next(),
+            KeyCode::Left | KeyCode::Backspace | KeyCode::Char('h') => app.onboarding_back(),
+            KeyCode::Esc | KeyCode::Char('q') => app.finish_onboarding(),
+            _ => {}
+        },
+
+        // A crashed theme's report goes away with any key
+        Event::Key(key) if key.kind == KeyEventKind::Press && app.theme_error.is_some() => app.theme_error = None,
+
+        // So does the alarm screen, and the key does nothing else
+        Event::Key(key) if key.kind == KeyEventKind::Press && app.alert_takeover => app.acknowledge_alert(),
+
+        // Brightness works on every screen, unless a text prompt is taking the keys
+        Event::Key(key)
+            if key.kind == KeyEventKind::Press
+                && matches!(key.code, KeyCode::Char('[') | KeyCode::Char(']'))
+                && app.command_line.is_none()
+                && app.intention_input.is_none() =>
+        {
+            app.adjust_brightness(key.code == KeyCode::Char(']'));
+        }
+
+        Event::Key(key) if key.kind == KeyEventKind::Press => {
+            match app.screen {
+                // Custom timer field - typed minutes, Enter starts the cycle
+                AppScreen::Menu if app.custom_minutes.is_some() => match key.code {
+                    KeyCode::Enter => app.custom_minutes_submit(true),
+                    KeyCode::Esc => app.custom_minutes_submit(false),
+                    KeyCode::Backspace => app.custom_minutes_backspace(),
+                    KeyCode::Char(c) => app.custom_minutes_push(c),
+                    _ => {}
+                },
+                AppScreen::Menu if app.preset_picker.is_some() => match key.code {
+                    KeyCode::Up | KeyCode::Char('k') => app.preset_picker_move(false),
+                    KeyCode::Down | KeyCode::Char('j') => app.preset_picker_move(true),
+                    KeyCode::Enter => app.preset_picker_confirm(),
+                    KeyCode::Esc | KeyCode::Char('q') => app.preset_picker = None,
+                    _ => {}
+                },
+                AppScreen::Menu => match key.code {
+                    KeyCode::Up | KeyCode::Char('k') => app.menu_up(),
+                    KeyCode::Down | KeyCode::Char('j') => app.menu_down(),
+                    KeyCode::Enter => app.should_quit = !app.menu_select(),
+                    KeyCode::Char('t') => app.try_theme_of_the_day(),
+                    KeyCode::Char('q') => app.should_quit = true,
+                    _ => {}
+                },
+                AppScreen::Stats => match key.code {
+                    KeyCode::Esc | KeyCode::Char('q') => app.close_stats(),
+                    KeyCode::Char('s') => app.share_summary(),
+                    _ => {}
+                },
+                AppScreen::Reminders => match key.code {
+                    KeyCode::Up | KeyCode::Char('k') => app.reminders_move(false),
+                    KeyCode::Down | KeyCode::Char('j') => app.reminders_move(true),
+                    KeyCode::Char(' ') | KeyCode::Enter => app.toggle_reminder(),
+                    KeyCode::Char('+') | KeyCode::Char('=') => app.adjust_reminder(5),
+                    KeyCode::Char('-') => app.adjust_reminder(-5),
+                    KeyCode::Char('r') => app.restart_reminder(),
+                    KeyCode::Esc | KeyCode::Char('q') => app.close_reminders(),
+                    _ => {}
+                },
+                AppScreen::WhatsNew => match key.code {
+                    KeyCode::Up | KeyCode::Char('k') => app.whats_new_scroll_by(-1),
+                    KeyCode::Down | KeyCode::Char('j') => app.whats_new_scroll_by(1),
+                    KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => app.close_whats_new(),
+                    _ => {}
+                },
+                AppScreen::FontGallery => match key.code {
+                    KeyCode::Left | KeyCode::Char('h') => app.gallery_move(-1),
+                    KeyCode::Right | KeyCode::Char('l') => app.gallery_move(1),
+                    KeyCode::Up | KeyCode::Char('k') => app.gallery_move_row(false),
+                    KeyCode::Down | KeyCode::Char('j') => app.gallery_move_row(true),
+                    KeyCode::Enter => app.gallery_select(),
+                    KeyCode::Esc | KeyCode::Char('q') => app.close_font_gallery(),
+                    _ => {}
+                },
+                AppScreen::Timer => {
+                    // Quit confirmation is open - only y/n matter
+                    if app.quit_confirm_open {
+                        match key.code {
+                            KeyCode::Char('y') | KeyCode::Char('Y') => app.quit_confirm_accept(),
+                            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                                app.quit_confirm_cancel();
+                            }
+                            _ => {}
+                        }
+                    } else if app.intention_input.is_some() {
+                        // Intention prompt - typed text, Enter starts the session
+                        match key.code {
+                            KeyCode::Enter => app.intention_submit(true),
+                            KeyCode::Esc => app.intention_submit(false),
+                            KeyCode::Backspace => app.intention_backspace(),
+                            KeyCode::Char(c) => app.intention_push(c),
+                            _ => {}
+                        }
+                    } else if app.command_line.is_some() {
+                        // Command palette is open - it captures all input
+                        match key.code {
+                            KeyCode::Enter => app.command_line_submit(),
+                            KeyCode::Esc => app.command_line_cancel(),
+                            KeyCode::Tab => app.command_line_complete(),
+                            KeyCode::Backspace => app.command_line_backspace(),
+                            KeyCode::Char(c) => app.command_line_push(c),
+                            _ => {}
+                        }
+                    } else if app.theme_selector_open {
+                        // Theme selector is open - handle its input
+                        match key.code {
+                            KeyCode::Up | KeyCode::Char('k') => app.theme_selector_up(),
+                            KeyCode::Down | KeyCode::Char('j') => app.theme_selector_down(),
+                            KeyCode::Enter => app.theme_selector_confirm(),
+                            KeyCode::Esc | KeyCode::Char('T') => app.theme_selector_cancel(),
+                            _ => {}
+                        }
+                    } else if app.goto_menu.is_some() {
+                        // Go-to menu - pick a point in the cycle
+                        match key.code {
+                            KeyCode::Up | KeyCode::Char('k') => app.goto_menu_move(false),
+                            KeyCode::Down | KeyCode::Char('j') => app.goto_menu_move(true),
+                            KeyCode::Char(c @ '1'..='8') => {
+                                app.goto_menu = c.to_digit(10).map(|d| d as usize - 1);
+                                app.goto_menu_confirm();
+                            }
+                            KeyCode::Enter => app.goto_menu_confirm(),
+                            KeyCode::Esc | KeyCode::Char('G') => app.toggle_goto_menu(),
+                            _ => {}
+                        }
+                    } else if app.quick_actions.is_some() {
+                        // Quick actions popup - pick one of a few common actions
+                        match key.code {
+                            KeyCode::Up | KeyCode::Char('k') => app.quick_actions_move(false),
+                            KeyCode::Down | KeyCode::Char('j') => app.quick_actions_move(true),
+                            KeyCode::Char(c @ '1'..='6') => {
+                                app.quick_actions = c.to_digit(10).map(|d| d as usize - 1);
+                                app.quick_actions_confirm();
+                            }
+                            KeyCode::Enter => app.quick_actions_confirm(),
+                            KeyCode::Esc | KeyCode::Char('.') => app.toggle_quick_actions(),
+                            _ => {}
+                        }
+                    } else if app.playing_snake() {
+                        // Snake game during a break - steer, restart, leave
+                        match key.code {
+                            KeyCode::Up | KeyCode::Char('k') | KeyCode::Char('w') => {
+                                app.snake_turn(Direction::Up);
+                            }
+                            KeyCode::Down | KeyCode::Char('j') | KeyCode::Char('s') => {
+                                app.snake_turn(Direction::Down);
+                            }
+                            KeyCode::Left | KeyCode::Char('h') | KeyCode::Char('a') => {
+                                app.snake_turn(Direction::Left);
+                            }
+                            KeyCode::Right | KeyCode::Char('l') | KeyCode::Char('d') => {
+                                app.snake_turn(Direction::Right);
+                            }
+                            KeyCode::Enter | KeyCode::Char(' ') => app.snake_restart(),
+                            KeyCode::Esc | KeyCode::Char('g') | KeyCode::Char('q') => {
+                                app.close_break_activity();
+                            }
+                            _ => {}
+                        }
+                    } else {
+                        // Silent mode: any key clears the banner, and still does its own job
+                        if app.alert_banner.is_some() {
+                            app.acknowledge_alert();
+                        }
+                        // Normal timer controls
+                        match key.code {
+                            // Focus rating for the work session that just ended
+                            KeyCode::Char(c @ '1'..='5') if app.pending_review.is_some() => {
+                                app.finish_review(c.to_digit(10).map(|d| d as u8));
+                            }
+                            KeyCode::Esc if app.pending_review.is_some() => app.finish_review(None),
+                            KeyCode::Char('x') if app.coach_tip.is_some() => app.coach_tip = None,
+                            KeyCode::Char('e') if app.break_extension.is_some() && app.awaiting_start => {
+                                app.extend_break();
+                            }
+                            KeyCode::Esc if app.break_activity.is_some() => app.close_break_activity(),
+                            KeyCode::Char(' ') => app.space_press(),
+                            KeyCode::Char('r') => app.reset_session(),
+                            KeyCode::Char('q') => app.request_quit_to_menu(),
+                            KeyCode::Tab => app.skip_to_next(),
+                            KeyCode::Char('u') => app.undo(),
+                            KeyCode::Char('T') if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                                app.toggle_theme_selector();
+                            }
+                            KeyCode::Char('t') => {
+                                // Also allow lowercase 't' for convenience
+                                app.toggle_theme_selector();
+                            }
+                            KeyCode::Char('f') => {
+                                // Cycle through font styles, kept for this theme (disables adaptive mode)
+                                app.next_font();
+                            }
+                            KeyCode::Char('F') if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                                // Toggle adaptive font mode
+                                app.toggle_adaptive_font();
+                            }
+                            KeyCode::Char('a') => {
+                                // Toggle auto-rotation
+                                app.toggle_auto_rotate();
+                            }
+                            KeyCode::Char('h') => {
+                                // Toggle hints visibility
+                                app.toggle_hints();
+                            }
+                            KeyCode::Char('m') => {
+                                // Toggle silent mode
+                                app.set_silent(None);
+                            }
+                            KeyCode::Char('e') => {
+                                // Toggle elapsed / end-time clock
+                                app.toggle_elapsed();
+                            }
+                            KeyCode::Char('L') if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                                // Toggle the event log pane
+                                app.set_event_log(None);
+                            }
+                            KeyCode::Char('A') if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                                // Collapse or expand the day template agenda
+                                app.toggle_agenda();
+                            }
+                            KeyCode::Char('C') if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                                // Cycle digit color modes
+                                app.cycle_digit_colors();
+                            }
+                            KeyCode::Char('c') => {
+                                // Cycle color filters
+                                app.set_color_filter(app.config.color_filter.filter.next());
+                            }
+                            KeyCode::Char('w') => {
+                                // Cycle work session categories
+                                app.cycle_category();
+                            }
+                            KeyCode::Char('o') => {
+                                // Toggle digits-only display for this session type
+                                app.set_digits_only(None);
+                            }
+                            KeyCode::Char('b') => {
+                                // Breathing exercise (breaks only)
+                                app.toggle_breathing();
+                            }
+                            KeyCode::Char('G') if key.modifiers.contains(KeyModifiers::SHIFT) => {
+                                // Jump to another point in the cycle
+                                app.toggle_goto_menu();
+                            }
+                            KeyCode::Char('g') => {
+                                // Snake game (breaks only)
+                                app.toggle_snake();
+                            }
+                            KeyCode::Char('.') => {
+                                // Quick actions popup, for when the keys don't come to mind
+                                app.toggle_quick_actions();
+                            }
+                            KeyCode::Char(':') => {
+                                // Open the command palette
+                                app.open_command_line();
+                            }
+                            KeyCode::Char('d') => {
+                                // Type a new length for this session
+                                app.prompt_session_length();
+                            }
+                            _ => {}
+                        }
+                    }
+                }
+            }
+        }
+
+        _ => {} // Ignore other events (mouse, paste, etc.)
+    }
+}
@@ -1,15 +1,13 @@
 use std::io;
 use std::path::PathBuf;
+use std::time::Duration;
 
+use crate::paths;
 use crate::timer::TimerSnapshot;
 
 /// Path to the IPC status file
 pub fn status_path() -> PathBuf {
-    let home = std::env::var("HOME")
-        .or_else(|_| std::env::var("USERPROFILE"))
-        .map(PathBuf::from)
-        .unwrap_or_else(|_| PathBuf::from("."));
-    home.join(".pomowise").join("status.json")
+    paths::state_dir().join("status.json")
 }
 
 /// Write a snapshot to the status file as JSON
@@ -31,6 +29,16 @@ pub fn read_status() -> io::Result<TimerSnapshot> {
     Ok(snapshot)
 }
 
+/// The status of another running instance, if it wrote one within `max_age`
+/// (sessions in progress rewrite it every frame, so an older file was left by a crash)
+pub fn read_live_status(max_age: Duration) -> Option<TimerSnapshot> {
+    let modified = std::fs::metadata(status_path()).and_then(|m| m.modified()).ok()?;
+    if modified.elapsed().unwrap_or(Duration::MAX) > max_age {
+        return None;
+    }
+    read_status().ok()
+}
+
 /// Remove the status file on exit
 pub fn cleanup() {
     let path = status_path();
@@ -0,0 +1,178 @@
+//! Focus journal: one Markdown line per completed pomodoro in a daily notes file
+//! The file path is a strftime pattern (`~/notes/%Y-%m-%d.md`) evaluated on the
+//! session's start, and each line comes from a small template:
+//! `- {start}–{end} {kind}[: {intention}][ (focus {rating}/5)]`. A `[...]` section
+//! is left out when a placeholder inside it has no value.
+
+use std::io::{self, Write};
+
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::history::{SessionKind, SessionOutcome, SessionRecord};
+use crate::sync::expand_home;
+
+/// Where and how sessions are logged (the `journal` key in config.json)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct JournalConfig {
+    /// Daily file, as a strftime pattern; `~/` is the home directory
+    pub path: String,
+    /// Line appended per session; see the module docs for placeholders
+    pub template: String,
+}
+
+impl Default for JournalConfig {
+    fn default() -> Self {
+        Self {
+            path: "~/notes/%Y-%m-%d.md".to_string(),
+            template: "- {start}–{end} {kind}[: {intention}][ (focus {rating}/5)]".to_string(),
+        }
+    }
+}
+
+impl JournalConfig {
+    /// Append the line for a finished session; only completed work sessions are logged
+    pub fn log(&self, record: &SessionRecord) -> io::Result<()> {
+        if record.kind != SessionKind::Work || record.outcome != SessionOutcome::Completed {
+            return Ok(());
+        }
+        let Some((start, end)) = session_times(record) else {
+            return Ok(());
+        };
+
+        // An unknown `%` specifier would panic once formatted
+        let items: Vec<Item> = StrftimeItems::new(&self.path).collect();
+        if items.contains(&Item::Error) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, format!("bad journal path: {}", self.path)));
+        }
+        let path = expand_home(&start.format_with_items(items.into_iter()).to_string());
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        let mut file = std::fs::OpenOptions::new().create(true).append(true).open(&path)?;
+        writeln!(file, "{}", render(&self.template, record, start, end))
+    }
+}
+
+fn session_times(record: &SessionRecord) -> Option<(DateTime<Local>, DateTime<Local>)> {
+    let end = DateTime::from_timestamp(record.ended_at as i64, 0)?.with_timezone(&Local);
+    let start = end - chrono::Duration::seconds(record.duration_secs as i64);
+    Some((start, end))
+}
+
+/// Fill in the template, dropping `[...]` sections with an empty placeholder
+fn render(template: &str, record: &SessionRecord, start: DateTime<Local>, end: DateTime<Local>) -> String {
+    let value = |name: &str| -> Option<String> {
+        match name {
+            "start" => Some(start.format("%H:%M").to_string()),
+            "end" => Some(end.format("%H:%M").to_string()),
+            "date" => Some(start.format("%Y-%m-%d").to_string()),
+            "kind" => Some(record.kind.label().to_string()),
+            "minutes" => Some((record.duration_secs / 60).to_string()),
+            "intention" => record.intention.clone().filter(|i| !i.trim().is_empty()),
+            "rating" => record.focus_rating.map(|r| r.to_string()),
+            "category" => record.category.clone(),
+            "project" => record.project.clone(),
+            // Unknown placeholders are kept as written
+            other => Some(format!("{{{}}}", other)),
+        }
+    };
+
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(open) = rest.find('[') {
+        out.push_str(&fill(&rest[..open], &value).0);
+        let Some(close) = rest[open..].find(']') else {
+            rest = &rest[open..];
+            break;
+        };
+        if let (section, true) = fill(&rest[open + 1..open + close], &value) {
+            out.push_str(&section);
+        }
+        rest = &rest[open + close + 1..];
+    }
+    out.push_str(&fill(rest, &value).0);
+    out
+}
+
+/// Substitute `{name}` placeholders; false along with the text if any of them was empty
+fn fill(text: &str, value: &impl Fn(&str) -> Option<String>) -> (String, bool) {
+    let mut out = String::new();
+    let mut complete = true;
+    let mut rest = text;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let Some(close) = rest[open..].find('}') else {
+            rest = &rest[open..];
+            break;
+        };
+        match value(&rest[open + 1..open + close]) {
+            Some(v) => out.push_str(&v),
+            None => complete = false,
+        }
+        rest = &rest[open + close + 1..];
+    }
+    out.push_str(rest);
+    (out, complete)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_render_drops_empty_sections() {
+        let end = Local.with_ymd_and_hms(2025, 1, 15, 9, 25, 0).unwrap();
+        let start = end - chrono::Duration::minutes(25);
+        let mut record = SessionRecord {
+            kind: SessionKind::Work,
+            outcome: SessionOutcome::Completed,
+            ended_at: end.timestamp() as u64,
+            duration_secs: 25 * 60,
+            intention: Some("fix parser".to_string()),
+            focus_rating: Some(4),
+            category: Some("Deep Work".to_string()),
+            estimate: None,
+            paused_secs: 0,
+            block: None,
+            theme: None,
+            project: None,
+        };
+        let template = JournalConfig::default().template;
+
+        assert_eq!(render(&template, &record, start, end), "- 09:00–09:25 Work: fix parser (focus 4/5)");
+
+        record.intention = None;
+        record.focus_rating = None;
+        assert_eq!(render(&template, &record, start, end), "- 09:00–09:25 Work");
+        assert_eq!(render("{date} {minutes}m {nope}", &record, start, end), "2025-01-15 25m {nope}");
+        assert_eq!(render("{kind}: {intention}", &record, start, end), "Work: ");
+        assert_eq!(render("{kind}[ ({category})]", &record, start, end), "Work (Deep Work)");
+        assert_eq!(render("a{b", &record, start, end), "a{b");
+        assert_eq!(render("{kind} {kind", &record, start, end), "Work {kind");
+    }
+
+    #[test]
+    fn test_bad_path_pattern_is_an_error() {
+        let journal = JournalConfig { path: "notes/%Q.md".to_string(), ..JournalConfig::default() };
+        let record = SessionRecord {
+            kind: SessionKind::Work,
+            outcome: SessionOutcome::Completed,
+            ended_at: 1_736_930_700,
+            duration_secs: 25 * 60,
+            intention: None,
+            focus_rating: None,
+            category: None,
+            estimate: None,
+            paused_secs: 0,
+            block: None,
+            theme: None,
+            project: None,
+        };
+        let err = journal.log(&record).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,76 @@
+//! What `pomowise resume` (or `pomowise last`) picks up
+//! The theme, font and session lengths of the previous run, and the session it
+//! left unfinished, kept in `last.json` in the state directory. Written when the
+//! app quits; leaving a session early for the menu is remembered until then.
+
+use std::io;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::paths;
+use crate::timer::TimerSnapshot;
+
+/// An unfinished session older than this is not picked up again
+const SESSION_MAX_AGE: Duration = Duration::from_secs(12 * 3600);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LastRun {
+    /// Theme slug; unset while themes were rotating
+    pub theme: Option<String>,
+    /// Font slug; unset while the font followed the terminal size
+    pub font: Option<String>,
+    pub work_secs: Option<u64>,
+    pub short_break_secs: Option<u64>,
+    pub long_break_secs: Option<u64>,
+    pub category: Option<String>,
+    /// Where the cycle was when the timer was left (see `PomodoroTimer::cycle_step`)
+    pub cycle_step: Option<usize>,
+    /// The session that was left before it ended
+    pub session: Option<TimerSnapshot>,
+    /// Unix timestamp (seconds) of when `session` was left
+    pub left_at: u64,
+}
+
+impl LastRun {
+    pub fn path() -> PathBuf {
+        paths::state_dir().join("last.json")
+    }
+
+    /// The last run, or nothing remembered when there is no readable file
+    pub fn load() -> Self {
+        std::fs::read_to_string(Self::path())
+            .ok()
+            .and_then(|json| serde_json::from_str(&json).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)?)
+    }
+
+    /// Remember `session` as left just now
+    pub fn leave(&mut self, session: Option<TimerSnapshot>) {
+        self.session = session;
+        self.left_at = now_secs();
+    }
+
+    /// The unfinished session, unless it was left too long ago to pick up
+    pub fn recent_session(&self) -> Option<&TimerSnapshot> {
+        let age = now_secs().saturating_sub(self.left_at);
+        self.session.as_ref().filter(|_| age <= SESSION_MAX_AGE.as_secs())
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
@@ -1,2 +1,73 @@
+//! pomowise: an animated Pomodoro timer for the terminal.
+//!
+//! Besides the `pomowise` binary, the crate can be embedded in other ratatui
+//! applications through [`PomowiseApp`]:
+//!
+//! ```no_run
+//! use pomowise::PomowiseApp;
+//! use ratatui::{backend::TestBackend, layout::Rect, Terminal};
+//!
+//! let mut app = PomowiseApp::new();
+//! let mut terminal = Terminal::new(TestBackend::new(120, 40)).unwrap();
+//! terminal
+//!     .draw(|frame| {
+//!         // Give the timer the right half of the screen
+//!         frame.render_widget(&mut app, Rect::new(60, 0, 60, 40));
+//!     })
+//!     .unwrap();
+//! app.tick();
+//! ```
+
 pub mod timer;
+pub mod clock;
 pub mod ipc;
+pub mod paths;
+pub mod history;
+pub mod config;
+pub mod bench;
+pub mod bundle;
+pub mod preset;
+pub mod crash;
+pub mod privacy;
+pub mod screenshot;
+pub mod scaffold;
+pub mod service;
+pub mod store;
+pub mod tmux;
+
+mod agenda;
+mod alarm;
+mod animation;
+mod app;
+mod break_activity;
+mod coach;
+mod commands;
+mod contributions;
+mod curl;
+mod embed;
+mod events;
+mod idle;
+mod input;
+mod journal;
+mod last_run;
+mod meeting;
+mod music;
+mod notification;
+mod onboarding;
+mod project;
+mod quotes;
+mod reminders;
+mod scaling;
+mod scripting;
+mod speech;
+mod summary;
+mod sync;
+mod taskbar;
+mod team;
+mod tracking;
+mod ui;
+mod weather;
+mod wm;
+mod world_clock;
+
+pub use embed::{run_app, PomowiseApp};
@@ -1,147 +1,97 @@
-mod app;
-mod notification;
-mod ui;
-mod animation;
-mod scaling;
-
-use pomowise::ipc;
-
 use std::io;
-use std::time::Duration;
 
 use crossterm::{
     cursor::Show,
-    event::{self, Event, KeyCode, KeyEventKind, KeyModifiers},
+    event::{DisableFocusChange, EnableFocusChange},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::prelude::*;
 
-use app::{App, AppScreen};
+use pomowise::config::Config;
+use pomowise::{history, paths, privacy, run_app, PomowiseApp};
 
 struct TerminalGuard;
 
 impl Drop for TerminalGuard {
     fn drop(&mut self) {
-        let _ = disable_raw_mode();
-        let _ = execute!(io::stdout(), LeaveAlternateScreen);
-        let _ = execute!(io::stdout(), Show);
+        restore_terminal();
     }
 }
 
+/// Back to a normal shell: raw mode off, main screen, cursor shown
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stdout(), DisableFocusChange);
+    let _ = execute!(io::stdout(), LeaveAlternateScreen);
+    let _ = execute!(io::stdout(), Show);
+}
+
 #[tokio::main(flavor = "current_thread")]
 async fn main() -> io::Result<()> {
-    // Setup terminal
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    paths::apply_override(&mut args);
+    if args.first().is_some_and(|a| a == "bench") {
+        return pomowise::bench::run(&args[1..]);
+    }
+    if args.first().is_some_and(|a| a == "screenshot") {
+        return pomowise::screenshot::run(&args[1..]);
+    }
+    if args.first().is_some_and(|a| a == "new-theme") {
+        return pomowise::scaffold::run(&args[1..]);
+    }
+    if args.first().is_some_and(|a| a == "install-service") {
+        return pomowise::service::run(&args[1..]);
+    }
+    if args.first().is_some_and(|a| a == "migrate-history") {
+        return pomowise::store::run(&args[1..]);
+    }
+    if args.first().is_some_and(|a| a == "config") {
+        return pomowise::bundle::run(&args[1..]);
+    }
+    if args.first().is_some_and(|a| a == "preset") {
+        return pomowise::preset::run(&args[1..]);
+    }
+    if args.first().is_some_and(|a| a == "tmux-status") {
+        return pomowise::tmux::run(&args[1..]);
+    }
+    let config = Config::load();
+    let template = match args.iter().position(|a| a == "--template") {
+        Some(i) => {
+            let name = args.get(i + 1).ok_or_else(|| io::Error::other("usage: pomowise --template <name>"))?;
+            if !config.templates.contains_key(name) {
+                return Err(io::Error::other(format!("no template named '{}' in config.json", name)));
+            }
+            Some(name.clone())
+        }
+        None => None,
+    };
+    history::set_store(config.history_store.clone());
+    // Before the alternate screen: unlocking an encrypted history may prompt
+    let incognito = args.iter().any(|a| a == "--incognito");
+    privacy::init(&config.privacy, incognito)?;
+
+    // Setup terminal; a panic gives it back before its message prints
+    pomowise::crash::install_hook(restore_terminal);
     enable_raw_mode()?;
     let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen)?;
+    execute!(stdout, EnterAlternateScreen, EnableFocusChange)?;
     let _guard = TerminalGuard; // RAII: ensures cleanup on drop, even on panic
 
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
     // Create app and run
-    let mut app = App::new();
-    run_app(&mut terminal, &mut app).await
-}
-
-async fn run_app<B: Backend>(terminal: &mut Terminal<B>, app: &mut App) -> io::Result<()> {
-    let tick_rate = Duration::from_millis(100); // 10 FPS
-
-    loop {
-        // Draw UI
-        terminal.draw(|f| ui::draw(f, app))?;
-
-        // Handle events with timeout for animation
-        if event::poll(tick_rate)? {
-            match event::read()? {
-                // Handle terminal resize
-                Event::Resize(width, height) => {
-                    app.update_dimensions(width, height);
-                }
-
-                // Handle key events
-                Event::Key(key) if key.kind == KeyEventKind::Press => {
-                    match app.screen {
-                        AppScreen::Menu => match key.code {
-                            KeyCode::Up | KeyCode::Char('k') => app.menu_up(),
-                            KeyCode::Down | KeyCode::Char('j') => app.menu_down(),
-                            KeyCode::Enter => {
-                                if !app.menu_select() {
-                                    ipc::cleanup();
-                                    return Ok(());
-                                }
-                            }
-                            KeyCode::Char('q') => {
-                                ipc::cleanup();
-                                return Ok(());
-                            }
-                            _ => {}
-                        },
-                        AppScreen::Timer => {
-                            // Theme selector is open - handle its input
-                            if app.theme_selector_open {
-                                match key.code {
-                                    KeyCode::Up | KeyCode::Char('k') => app.theme_selector_up(),
-                                    KeyCode::Down | KeyCode::Char('j') => app.theme_selector_down(),
-                                    KeyCode::Enter => app.theme_selector_confirm(),
-                                    KeyCode::Esc | KeyCode::Char('T') => app.theme_selector_cancel(),
-                                    _ => {}
-                                }
-                            } else {
-                                // Normal timer controls
-                                match key.code {
-                                    KeyCode::Char(' ') => app.toggle_pause(),
-                                    KeyCode::Char('r') => app.reset_session(),
-                                    KeyCode::Char('q') => app.quit_to_menu(),
-                                    KeyCode::Tab => app.skip_to_next(),
-                                    KeyCode::Char('T') if key.modifiers.contains(KeyModifiers::SHIFT) => {
-                                        app.toggle_theme_selector();
-                                    }
-                                    KeyCode::Char('t') => {
-                                        // Also allow lowercase 't' for convenience
-                                        app.toggle_theme_selector();
-                                    }
-                                    KeyCode::Char('f') => {
-                                        // Cycle through font styles (disables adaptive mode)
-                                        app.adaptive_font = false;
-                                        app.animation.next_font();
-                                    }
-                                    KeyCode::Char('F') if key.modifiers.contains(KeyModifiers::SHIFT) => {
-                                        // Toggle adaptive font mode
-                                        app.toggle_adaptive_font();
-                                    }
-                                    KeyCode::Char('a') => {
-                                        // Toggle auto-rotation
-                                        app.toggle_auto_rotate();
-                                    }
-                                    KeyCode::Char('h') => {
-                                        // Toggle hints visibility
-                                        app.toggle_hints();
-                                    }
-                                    _ => {}
-                                }
-                            }
-                        }
-                    }
-                }
-
-                _ => {} // Ignore other events (mouse, focus, etc.)
-            }
-        }
-
-        // Update timer and animation
-        app.tick();
-
-        // Write timer state for tray to read
-        if app.screen == AppScreen::Timer {
-            let snapshot = app.timer.snapshot();
-            let _ = ipc::write_status(&snapshot);
-        }
-
-        if app.should_quit {
-            ipc::cleanup();
-            return Ok(());
-        }
+    let mut app = PomowiseApp::new();
+    app.set_mini(args.iter().any(|a| a == "--mini"));
+    if args.iter().any(|a| a == "--slow-link") {
+        app.set_slow_link(true);
     }
+    if let Some(name) = &template {
+        app.set_template(name);
+    }
+    if args.first().is_some_and(|a| a == "resume" || a == "last") {
+        app.resume_last(args.iter().any(|a| a == "--fresh"));
+    }
+    run_app(&mut terminal, &mut app).await
 }
@@ -0,0 +1,135 @@
+//! Meeting mode: plan pomodoros around a hard stop (`:until 15:00`)
+//! The plan walks the usual work/break cycle forward from the current session
+//! and counts the work sessions that finish before the target.
+
+use std::time::Duration;
+
+use chrono::{DateTime, Local, NaiveTime};
+
+use crate::timer::{PomodoroTimer, TimerState};
+
+/// A wall-clock time to be done by
+#[derive(Debug, Clone)]
+pub struct Meeting {
+    pub target: DateTime<Local>,
+    /// Whether the "won't fit" warning was shown already
+    pub warned: bool,
+}
+
+/// What still fits before the target
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Plan {
+    /// Work sessions (the current one included) that end in time
+    pub pomodoros: u32,
+    /// Whether the next work session to start ends in time
+    pub next_fits: bool,
+}
+
+impl Meeting {
+    /// The next occurrence of `time`: today, or tomorrow if it already passed
+    pub fn at(time: NaiveTime, now: DateTime<Local>) -> Self {
+        let mut day = now.date_naive();
+        if time <= now.time() {
+            day = day.succ_opt().unwrap_or(day);
+        }
+        let target = day
+            .and_time(time)
+            .and_local_timezone(Local)
+            .earliest()
+            .unwrap_or(now);
+        Self { target, warned: false }
+    }
+
+    pub fn time_left(&self, now: DateTime<Local>) -> Duration {
+        (self.target - now).to_std().unwrap_or(Duration::ZERO)
+    }
+
+    pub fn plan(&self, now: DateTime<Local>, timer: &PomodoroTimer) -> Plan {
+        plan(self.time_left(now), timer)
+    }
+}
+
+/// Walk the cycle forward through `available` time
+fn plan(available: Duration, timer: &PomodoroTimer) -> Plan {
+    let state = match &timer.state {
+        TimerState::Paused(inner) => inner.as_ref().clone(),
+        state => state.clone(),
+    };
+    // Idle starts a fresh cycle
+    let (mut state, mut cycle, mut ends) = match state {
+        TimerState::Idle => (TimerState::Work { lap: 1 }, 0, timer.work_length()),
+        state => (state, timer.cycle_position, timer.remaining),
+    };
+    // A session already under way isn't "next"
+    let mut under_way = !matches!(timer.state, TimerState::Idle);
+
+    let mut pomodoros = 0;
+    let mut next_fits = None;
+    while ends <= available {
+        if matches!(state, TimerState::Work { .. }) {
+            pomodoros += 1;
+            if !under_way {
+                next_fits.get_or_insert(true);
+            }
+        }
+        // Same transitions as `PomodoroTimer::advance_state`
+        (state, ends) = match state {
+            TimerState::Work { .. } => {
+                cycle += 1;
+                if cycle >= 4 {
+                    (TimerState::LongBreak, ends + timer.long_break_duration)
+                } else {
+                    (TimerState::ShortBreak { lap: 1 }, ends + timer.short_break_duration)
+                }
+            }
+            TimerState::LongBreak => {
+                cycle = 0;
+                (TimerState::Work { lap: 1 }, ends + timer.work_length())
+            }
+            _ => (TimerState::Work { lap: 1 }, ends + timer.work_length()),
+        };
+        under_way = false;
+    }
+
+    Plan {
+        pomodoros,
+        next_fits: next_fits.unwrap_or(false),
+    }
+}
+
+/// "1:05:09" / "12:30"
+pub fn format_left(left: Duration) -> String {
+    let secs = left.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plan_counts_pomodoros_that_fit() {
+        let mut timer = PomodoroTimer::new();
+        let mins = |m: u64| Duration::from_secs(m * 60);
+
+        // Idle with 60 minutes: work 25, break 5, work 25 = 55
+        assert_eq!(plan(mins(60), &timer), Plan { pomodoros: 2, next_fits: true });
+
+        // A 5 minute break with 20 minutes to go: the next pomodoro can't finish
+        timer.start();
+        timer.advance_state();
+        assert_eq!(plan(mins(20), &timer), Plan { pomodoros: 0, next_fits: false });
+
+        // Working with 10 minutes to go and 45 available: this one, then 5 + 25 more
+        timer.advance_state();
+        timer.remaining = mins(10);
+        assert_eq!(plan(mins(45), &timer), Plan { pomodoros: 2, next_fits: true });
+
+        assert_eq!(format_left(Duration::from_secs(3909)), "1:05:09");
+        assert_eq!(format_left(Duration::from_secs(750)), "12:30");
+    }
+}
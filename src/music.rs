@@ -0,0 +1,438 @@
+//! Focus music: a player per session type, started and stopped with the timer
+//! A profile is either an external command (`mpv --no-video <url>`, a playlist
+//! script, ...) or the built-in noise generator, which pipes raw samples into
+//! whichever of paplay / aplay / sox `play` is installed. Pausing stops playback.
+//! Themes can bring ambient sounds of their own (`ambient`): rain, a crackling
+//! fire or waves, synthesized the same way, on a second player next to the music.
+
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+
+use crate::history::SessionKind;
+
+const SAMPLE_RATE: u32 = 22050;
+
+/// Raw-PCM players tried in order for the noise generator (16-bit mono at `SAMPLE_RATE`)
+const RAW_PLAYERS: &[&[&str]] = &[
+    &["paplay", "--raw", "--rate=22050", "--format=s16le", "--channels=1"],
+    &["aplay", "-q", "-t", "raw", "-f", "S16_LE", "-r", "22050", "-c", "1"],
+    &["play", "-q", "-t", "raw", "-r", "22050", "-e", "signed", "-b", "16", "-c", "1", "-"],
+];
+
+/// What to play during one session type
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "player", rename_all = "lowercase")]
+pub enum MusicProfile {
+    /// Shell command, stopped by killing it (e.g. "mpv --no-video https://...")
+    Command { command: String },
+    /// Built-in noise generator
+    Noise {
+        #[serde(default)]
+        color: NoiseColor,
+        /// 0.0-1.0
+        #[serde(default = "default_volume")]
+        volume: f32,
+    },
+    /// Built-in ambient loop
+    Ambient {
+        sound: AmbientSound,
+        /// 0.0-1.0
+        #[serde(default = "default_volume")]
+        volume: f32,
+    },
+}
+
+impl MusicProfile {
+    /// The profile at `scale` times its volume; commands play as they are
+    fn scaled(&self, scale: f32) -> Self {
+        match self.clone() {
+            MusicProfile::Noise { color, volume } => MusicProfile::Noise { color, volume: volume * scale },
+            MusicProfile::Ambient { sound, volume } => MusicProfile::Ambient { sound, volume: volume * scale },
+            command => command,
+        }
+    }
+}
+
+fn default_volume() -> f32 {
+    0.3
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NoiseColor {
+    White,
+    Pink,
+    /// Deep rumble, the usual pick for focus
+    #[default]
+    Brown,
+}
+
+/// Synthesized ambient loops
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AmbientSound {
+    /// Steady hiss with droplets
+    Rain,
+    /// Low rumble with crackles
+    Fire,
+    /// Surf swelling and falling back
+    Waves,
+}
+
+/// Themes that come with a sound unless `ambient.themes` says otherwise
+const THEME_SOUNDS: &[(&str, AmbientSound)] = &[
+    ("rain-drops", AmbientSound::Rain),
+    ("fire", AmbientSound::Fire),
+    ("ocean-waves", AmbientSound::Waves),
+];
+
+/// Ambient sounds per theme (the `ambient` key in config.json)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AmbientConfig {
+    /// Play the current theme's sound while a session runs
+    pub enabled: bool,
+    /// 0.0-1.0, applied to every ambient sound and separate from the music's
+    pub volume: f32,
+    /// Profile per theme slug, over the built-in pairings; null silences a theme
+    pub themes: BTreeMap<String, Option<MusicProfile>>,
+}
+
+impl Default for AmbientConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            volume: 0.5,
+            themes: BTreeMap::new(),
+        }
+    }
+}
+
+impl AmbientConfig {
+    /// What to play with the theme `slug`, at the ambient volume
+    pub fn for_theme(&self, slug: &str) -> Option<MusicProfile> {
+        let profile = match self.themes.get(slug) {
+            Some(profile) => profile.clone(),
+            None => THEME_SOUNDS
+                .iter()
+                .find(|(theme, _)| *theme == slug)
+                .map(|&(_, sound)| MusicProfile::Ambient { sound, volume: 1.0 }),
+        };
+        profile.map(|p| p.scaled(self.volume.clamp(0.0, 1.0)))
+    }
+}
+
+/// Profiles per session type (the `music` key in config.json); unset is silence
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MusicConfig {
+    pub work: Option<MusicProfile>,
+    pub short_break: Option<MusicProfile>,
+    pub long_break: Option<MusicProfile>,
+}
+
+impl MusicConfig {
+    pub fn for_kind(&self, kind: SessionKind) -> Option<&MusicProfile> {
+        match kind {
+            SessionKind::Work => self.work.as_ref(),
+            SessionKind::ShortBreak => self.short_break.as_ref(),
+            SessionKind::LongBreak => self.long_break.as_ref(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.work.is_none() && self.short_break.is_none() && self.long_break.is_none()
+    }
+}
+
+/// A running player
+struct Playing {
+    profile: MusicProfile,
+    child: Child,
+    /// Tells the noise thread to stop writing
+    stop: Arc<AtomicBool>,
+}
+
+/// Keeps at most one player running, matching the profile asked for
+#[derive(Default)]
+pub struct MusicPlayer {
+    playing: Option<Playing>,
+}
+
+impl MusicPlayer {
+    /// Make `profile` the one playing (None stops); unchanged profiles keep playing.
+    /// A player that fails to start is not retried until the profile changes.
+    pub fn play(&mut self, profile: Option<&MusicProfile>) {
+        if self.playing.as_ref().map(|p| &p.profile) == profile {
+            return;
+        }
+        self.stop();
+        if let Some(profile) = profile {
+            self.playing = start(profile).ok();
+        }
+    }
+
+    pub fn stop(&mut self) {
+        if let Some(mut playing) = self.playing.take() {
+            playing.stop.store(true, Ordering::Relaxed);
+            let _ = playing.child.kill();
+            let _ = playing.child.wait();
+        }
+    }
+}
+
+impl Drop for MusicPlayer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+fn start(profile: &MusicProfile) -> io::Result<Playing> {
+    let stop = Arc::new(AtomicBool::new(false));
+    let child = match profile {
+        MusicProfile::Command { command } => shell(command)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()?,
+        MusicProfile::Noise { color, volume } => {
+            let mut noise = Noise::new(*color, volume.clamp(0.0, 1.0));
+            play_samples(move || noise.next_sample(), &stop)?
+        }
+        MusicProfile::Ambient { sound, volume } => {
+            let mut soundscape = Soundscape::new(*sound, volume.clamp(0.0, 1.0));
+            play_samples(move || soundscape.next_sample(), &stop)?
+        }
+    };
+    Ok(Playing {
+        profile: profile.clone(),
+        child,
+        stop,
+    })
+}
+
+/// Run through the shell so quoting and pipes work; `exec` makes the player
+/// itself the child, so stopping kills the player and not just the shell
+fn shell(command: &str) -> Command {
+    if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        cmd.args(["/C", command]);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.args(["-c", &format!("exec {}", command)]);
+        cmd
+    }
+}
+
+/// Start a raw player fed from `next_sample` on a thread of its own, until `stop`
+fn play_samples(mut next_sample: impl FnMut() -> i16 + Send + 'static, stop: &Arc<AtomicBool>) -> io::Result<Child> {
+    let mut child = spawn_raw_player()?;
+    let mut stdin = child.stdin.take().ok_or_else(|| io::Error::other("player has no stdin"))?;
+    let stop = Arc::clone(stop);
+    std::thread::spawn(move || {
+        // The player blocks writes at its playback rate; a broken pipe ends the thread
+        let mut chunk = vec![0u8; SAMPLE_RATE as usize / 10 * 2];
+        while !stop.load(Ordering::Relaxed) {
+            for bytes in chunk.chunks_exact_mut(2) {
+                bytes.copy_from_slice(&next_sample().to_le_bytes());
+            }
+            if stdin.write_all(&chunk).is_err() {
+                break;
+            }
+        }
+    });
+    Ok(child)
+}
+
+fn spawn_raw_player() -> io::Result<Child> {
+    for args in RAW_PLAYERS {
+        let spawned = Command::new(args[0])
+            .args(&args[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        if let Ok(child) = spawned {
+            return Ok(child);
+        }
+    }
+    Err(io::Error::new(io::ErrorKind::NotFound, "no raw audio player (paplay, aplay or sox) found"))
+}
+
+/// Noise sample generator
+struct Noise {
+    color: NoiseColor,
+    volume: f32,
+    seed: u32,
+    /// Pink: Paul Kellet's filter state; brown: the running sum in `[0]`
+    state: [f32; 7],
+}
+
+impl Noise {
+    fn new(color: NoiseColor, volume: f32) -> Self {
+        Self {
+            color,
+            volume,
+            seed: 0x2545_f491,
+            state: [0.0; 7],
+        }
+    }
+
+    /// Uniform in -1.0..1.0 (xorshift)
+    fn white(&mut self) -> f32 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 17;
+        self.seed ^= self.seed << 5;
+        self.seed as f32 / u32::MAX as f32 * 2.0 - 1.0
+    }
+
+    fn next_sample(&mut self) -> i16 {
+        (self.next_value().clamp(-1.0, 1.0) * self.volume * i16::MAX as f32) as i16
+    }
+
+    /// Next sample at full volume, roughly within -1.0..1.0
+    fn next_value(&mut self) -> f32 {
+        let white = self.white();
+        match self.color {
+            NoiseColor::White => white,
+            NoiseColor::Pink => {
+                let b = &mut self.state;
+                b[0] = 0.99886 * b[0] + white * 0.0555179;
+                b[1] = 0.99332 * b[1] + white * 0.0750759;
+                b[2] = 0.969 * b[2] + white * 0.153852;
+                b[3] = 0.8665 * b[3] + white * 0.3104856;
+                b[4] = 0.55 * b[4] + white * 0.5329522;
+                b[5] = -0.7616 * b[5] - white * 0.016898;
+                let pink = b[0] + b[1] + b[2] + b[3] + b[4] + b[5] + b[6] + white * 0.5362;
+                b[6] = white * 0.115926;
+                pink * 0.11
+            }
+            NoiseColor::Brown => {
+                let sum = &mut self.state[0];
+                *sum = (*sum + white * 0.02) / 1.02;
+                *sum * 3.5
+            }
+        }
+    }
+}
+
+/// Seconds from one wave to the next
+const WAVE_PERIOD: f32 = 8.0;
+
+/// Ambient loop generator: colored noise shaped into rain, fire or surf
+struct Soundscape {
+    sound: AmbientSound,
+    volume: f32,
+    bed: Noise,
+    /// Source of droplets, crackles and their timing
+    spark: Noise,
+    /// Loudness of the droplet or crackle sounding now, decaying to 0
+    burst: f32,
+    samples: u64,
+}
+
+impl Soundscape {
+    fn new(sound: AmbientSound, volume: f32) -> Self {
+        let color = match sound {
+            AmbientSound::Fire => NoiseColor::Brown,
+            AmbientSound::Rain | AmbientSound::Waves => NoiseColor::Pink,
+        };
+        Self {
+            sound,
+            volume,
+            bed: Noise::new(color, 1.0),
+            spark: Noise::new(NoiseColor::White, 1.0),
+            burst: 0.0,
+            samples: 0,
+        }
+    }
+
+    /// A burst up to `loudness` starts on average once in `every` samples,
+    /// and fades by `decay` a sample
+    fn bursts(&mut self, every: f32, loudness: f32, decay: f32) -> f32 {
+        let chance = (self.spark.white() + 1.0) / 2.0;
+        if chance < 1.0 / every {
+            let strength = (self.spark.white() + 1.0) / 2.0;
+            self.burst = loudness * (0.4 + 0.6 * strength);
+        }
+        self.burst *= decay;
+        self.spark.white() * self.burst
+    }
+
+    fn next_sample(&mut self) -> i16 {
+        self.samples += 1;
+        let bed = self.bed.next_value();
+        let value = match self.sound {
+            AmbientSound::Rain => bed * 0.5 + self.bursts(2500.0, 0.6, 0.995),
+            AmbientSound::Fire => bed * 0.6 + self.bursts(6000.0, 0.9, 0.99),
+            AmbientSound::Waves => {
+                let t = self.samples as f32 / SAMPLE_RATE as f32;
+                let swell = 0.5 - 0.5 * (t / WAVE_PERIOD * std::f32::consts::TAU).cos();
+                bed * (0.15 + 0.85 * swell * swell)
+            }
+        };
+        (value.clamp(-1.0, 1.0) * self.volume * i16::MAX as f32) as i16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profiles_parse_from_config() {
+        let json = r#"{
+            "work": { "player": "command", "command": "mpv --no-video lofi.m3u" },
+            "long_break": { "player": "noise", "color": "pink" }
+        }"#;
+        let config: MusicConfig = serde_json::from_str(json).unwrap();
+        assert!(matches!(config.for_kind(SessionKind::Work), Some(MusicProfile::Command { .. })));
+        assert_eq!(config.for_kind(SessionKind::ShortBreak), None);
+        assert_eq!(
+            config.for_kind(SessionKind::LongBreak),
+            Some(&MusicProfile::Noise { color: NoiseColor::Pink, volume: 0.3 })
+        );
+
+        // Every color stays within the requested volume
+        for color in [NoiseColor::White, NoiseColor::Pink, NoiseColor::Brown] {
+            let mut noise = Noise::new(color, 0.5);
+            let peak = (0..10_000).map(|_| noise.next_sample().unsigned_abs()).max().unwrap();
+            assert!(peak > 0 && peak <= i16::MAX as u16 / 2 + 1, "{:?} peak {}", color, peak);
+        }
+    }
+
+    #[test]
+    fn test_themes_pick_ambient_sounds() {
+        let json = r#"{
+            "enabled": true,
+            "volume": 0.5,
+            "themes": { "fire": null, "aurora": { "player": "ambient", "sound": "waves", "volume": 0.6 } }
+        }"#;
+        let config: AmbientConfig = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            config.for_theme("rain-drops"),
+            Some(MusicProfile::Ambient { sound: AmbientSound::Rain, volume: 0.5 })
+        );
+        assert_eq!(config.for_theme("fire"), None);
+        assert_eq!(config.for_theme("matrix-rain"), None);
+        assert_eq!(
+            config.for_theme("aurora"),
+            Some(MusicProfile::Ambient { sound: AmbientSound::Waves, volume: 0.3 })
+        );
+
+        let mut waves = Soundscape::new(AmbientSound::Waves, 1.0);
+        let loudness = |w: &mut Soundscape| (0..2205).map(|_| w.next_sample().unsigned_abs() as u64).sum::<u64>();
+        let trough = loudness(&mut waves);
+        for _ in 0..(SAMPLE_RATE as usize * 4 - 4410) {
+            waves.next_sample();
+        }
+        // Half a period on, the wave is in
+        assert!(loudness(&mut waves) > trough * 4);
+    }
+}
@@ -1,9 +1,356 @@
+//! Session notifications
+//! Each backend implements `Notifier`; the dispatcher fans events out to every
+//! enabled backend that is configured for that event. Completions arrive from
+//! the event bus like any other subscriber's.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
 use notify_rust::Notification;
+use serde::{Deserialize, Serialize};
+
+use crate::events::{AppEvent, Event, Subscriber};
+use crate::history::{SessionKind, SessionOutcome};
+
+/// Per-request limit for webhooks, so a dead endpoint doesn't leave curl hanging
+const WEBHOOK_TIMEOUT_SECS: &str = "10";
+
+/// Events that can trigger notifications
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+#[allow(clippy::enum_variant_names)]
+pub enum SessionEvent {
+    WorkComplete,
+    ShortBreakComplete,
+    LongBreakComplete,
+    /// Strict breaks: the terminal lost focus during a break
+    BreakNag,
+    /// The timer has been paused for `pause_reminder_minutes` (and again, more often, after)
+    PauseReminder,
+}
+
+impl SessionEvent {
+    /// Completion of a session of `kind`
+    pub fn completed(kind: SessionKind) -> Self {
+        match kind {
+            SessionKind::Work => SessionEvent::WorkComplete,
+            SessionKind::ShortBreak => SessionEvent::ShortBreakComplete,
+            SessionKind::LongBreak => SessionEvent::LongBreakComplete,
+        }
+    }
+
+    /// Stable identifier passed to webhooks and commands
+    pub fn key(&self) -> &'static str {
+        match self {
+            SessionEvent::WorkComplete => "work_complete",
+            SessionEvent::ShortBreakComplete => "short_break_complete",
+            SessionEvent::LongBreakComplete => "long_break_complete",
+            SessionEvent::BreakNag => "break_nag",
+            SessionEvent::PauseReminder => "pause_reminder",
+        }
+    }
+
+    /// Human readable message
+    pub fn message(&self) -> &'static str {
+        match self {
+            SessionEvent::WorkComplete => "Work session complete!",
+            SessionEvent::ShortBreakComplete => "Short break complete!",
+            SessionEvent::LongBreakComplete => "Long break complete!",
+            SessionEvent::BreakNag => "It's break time - step away from the screen",
+            SessionEvent::PauseReminder => "pomowise is still paused - resume or reset?",
+        }
+    }
+}
+
+/// A notification backend
+pub trait Notifier {
+    fn notify(&self, event: SessionEvent);
+
+    /// Whether it makes a sound or pops up on the desktop (held back in silent mode)
+    fn is_intrusive(&self) -> bool {
+        true
+    }
+}
+
+/// Desktop notification via the system notification daemon
+pub struct DesktopNotifier;
+
+impl Notifier for DesktopNotifier {
+    fn notify(&self, event: SessionEvent) {
+        notify_desktop(event.message());
+    }
+}
+
+/// Terminal bell
+pub struct BellNotifier;
+
+impl Notifier for BellNotifier {
+    fn notify(&self, _event: SessionEvent) {
+        ring_bell();
+    }
+}
+
+/// Plays a sound file with the platform's command-line player
+pub struct SoundNotifier {
+    pub file: String,
+}
+
+impl Notifier for SoundNotifier {
+    fn notify(&self, _event: SessionEvent) {
+        #[cfg(target_os = "linux")]
+        let mut cmd = {
+            let mut cmd = Command::new("paplay");
+            cmd.arg(&self.file);
+            cmd
+        };
+
+        #[cfg(target_os = "macos")]
+        let mut cmd = {
+            let mut cmd = Command::new("afplay");
+            cmd.arg(&self.file);
+            cmd
+        };
+
+        #[cfg(target_os = "windows")]
+        let mut cmd = {
+            let mut cmd = Command::new("powershell");
+            cmd.arg("-c").arg(format!(
+                "(New-Object Media.SoundPlayer '{}').PlaySync()",
+                self.file.replace('\'', "''")
+            ));
+            cmd
+        };
+
+        spawn_quiet(&mut cmd);
+    }
+}
+
+/// POSTs a small JSON payload to a URL (via curl, so no HTTP stack is linked in)
+pub struct WebhookNotifier {
+    pub url: String,
+}
+
+impl Notifier for WebhookNotifier {
+    fn notify(&self, event: SessionEvent) {
+        let payload = serde_json::json!({
+            "event": event.key(),
+            "message": event.message(),
+        });
+        spawn_quiet(
+            Command::new("curl")
+                .args(["-s", "--max-time", WEBHOOK_TIMEOUT_SECS, "-X", "POST"])
+                .args(["-H", "Content-Type: application/json", "-d"])
+                .arg(payload.to_string())
+                .arg(&self.url),
+        );
+    }
+
+    fn is_intrusive(&self) -> bool {
+        false
+    }
+}
+
+/// Runs a user shell command with the event in its environment
+pub struct CommandNotifier {
+    pub command: String,
+}
+
+impl Notifier for CommandNotifier {
+    fn notify(&self, event: SessionEvent) {
+        spawn_quiet(
+            shell_command(&self.command)
+                .env("POMOWISE_EVENT", event.key())
+                .env("POMOWISE_MESSAGE", event.message()),
+        );
+    }
+
+    fn is_intrusive(&self) -> bool {
+        false
+    }
+}
+
+/// Pop up a desktop notification with any message (user scripts use it too)
+pub fn notify_desktop(message: &str) {
+    Notification::new().summary("Pomodoro").body(message).show().ok();
+}
+
+/// Build a platform shell invocation for a command string
+pub fn shell_command(command: &str) -> Command {
+    if cfg!(target_os = "windows") {
+        let mut cmd = Command::new("cmd");
+        cmd.arg("/C").arg(command);
+        cmd
+    } else {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(command);
+        cmd
+    }
+}
+
+/// Spawn without waiting and without letting output clobber the TUI; the
+/// child is reaped on a thread of its own, so it doesn't linger as a zombie
+pub(crate) fn spawn_quiet(cmd: &mut Command) {
+    let child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn();
+    if let Ok(mut child) = child {
+        std::thread::spawn(move || child.wait());
+    }
+}
+
+/// Settings shared by every backend
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct BackendConfig {
+    pub enabled: bool,
+    /// Events this backend fires for; empty means all events
+    pub events: Vec<SessionEvent>,
+}
+
+impl BackendConfig {
+    fn enabled_by_default() -> Self {
+        Self {
+            enabled: true,
+            ..Self::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SoundConfig {
+    #[serde(flatten)]
+    pub backend: BackendConfig,
+    /// Sound file to play
+    pub file: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WebhookConfig {
+    #[serde(flatten)]
+    pub backend: BackendConfig,
+    /// URL that receives a JSON POST
+    pub url: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CommandConfig {
+    #[serde(flatten)]
+    pub backend: BackendConfig,
+    /// Shell command; gets POMOWISE_EVENT and POMOWISE_MESSAGE in its environment
+    pub command: String,
+}
+
+/// Notification settings (the `notifications` key in config.json)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationConfig {
+    pub desktop: BackendConfig,
+    pub bell: BackendConfig,
+    pub sound: SoundConfig,
+    pub webhook: WebhookConfig,
+    pub command: CommandConfig,
+}
+
+impl Default for NotificationConfig {
+    fn default() -> Self {
+        Self {
+            desktop: BackendConfig::enabled_by_default(),
+            bell: BackendConfig::default(),
+            sound: SoundConfig::default(),
+            webhook: WebhookConfig::default(),
+            command: CommandConfig::default(),
+        }
+    }
+}
+
+/// Fans session events out to all enabled backends
+pub struct NotificationDispatcher {
+    backends: Vec<(Box<dyn Notifier>, Vec<SessionEvent>)>,
+    /// Silent mode: only webhooks and commands fire
+    pub silent: bool,
+}
+
+impl NotificationDispatcher {
+    /// Build the enabled backends from config
+    pub fn from_config(config: &NotificationConfig) -> Self {
+        let mut backends: Vec<(Box<dyn Notifier>, Vec<SessionEvent>)> = Vec::new();
+
+        if config.desktop.enabled {
+            backends.push((Box::new(DesktopNotifier), config.desktop.events.clone()));
+        }
+        if config.bell.enabled {
+            backends.push((Box::new(BellNotifier), config.bell.events.clone()));
+        }
+        if config.sound.backend.enabled && !config.sound.file.is_empty() {
+            let notifier = SoundNotifier { file: config.sound.file.clone() };
+            backends.push((Box::new(notifier), config.sound.backend.events.clone()));
+        }
+        if config.webhook.backend.enabled && !config.webhook.url.is_empty() {
+            let notifier = WebhookNotifier { url: config.webhook.url.clone() };
+            backends.push((Box::new(notifier), config.webhook.backend.events.clone()));
+        }
+        if config.command.backend.enabled && !config.command.command.is_empty() {
+            let notifier = CommandNotifier { command: config.command.command.clone() };
+            backends.push((Box::new(notifier), config.command.backend.events.clone()));
+        }
+
+        Self { backends, silent: false }
+    }
+
+    /// Send an event to every backend configured for it
+    pub fn dispatch(&self, event: SessionEvent) {
+        for (backend, events) in &self.backends {
+            if self.silent && backend.is_intrusive() {
+                continue;
+            }
+            if events.is_empty() || events.contains(&event) {
+                backend.notify(event);
+            }
+        }
+    }
+}
+
+/// Sessions that ran out notify; skipped or abandoned ones don't
+impl Subscriber for NotificationDispatcher {
+    fn on_event(&mut self, event: &Event) {
+        if let AppEvent::Ended(kind, SessionOutcome::Completed) = event.event {
+            self.dispatch(SessionEvent::completed(kind));
+        }
+    }
+}
+
+/// Shortest gap between pause reminders, however long they have been going
+const MIN_PAUSE_REMINDER_GAP: Duration = Duration::from_secs(120);
+
+/// How long into a pause reminder `n` (from 0) is due: the first after `after`,
+/// then each gap half the one before, down to `MIN_PAUSE_REMINDER_GAP`
+pub fn pause_reminder_at(after: Duration, n: u32) -> Duration {
+    (0..=n)
+        .map(|i| (after / 2u32.saturating_pow(i)).max(MIN_PAUSE_REMINDER_GAP.min(after)))
+        .sum()
+}
+
+/// Ring the terminal bell (used for countdown ticks)
+pub fn ring_bell() {
+    let mut stdout = std::io::stdout();
+    let _ = stdout.write_all(b"\x07");
+    let _ = stdout.flush();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-pub fn notify_session_end(session_type: &str) {
-    Notification::new()
-        .summary("Pomodoro")
-        .body(&format!("{} complete!", session_type))
-        .show()
-        .ok();
+    #[test]
+    fn test_pause_reminders_come_more_often() {
+        let minutes = |n| pause_reminder_at(Duration::from_secs(16 * 60), n).as_secs() / 60;
+        assert_eq!((0..6).map(minutes).collect::<Vec<_>>(), [16, 24, 28, 30, 32, 34]);
+        assert_eq!(SessionEvent::PauseReminder.key(), "pause_reminder");
+    }
 }
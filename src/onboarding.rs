@@ -0,0 +1,128 @@
+//! First-run walkthrough and the "What's new" screen
+//! The walkthrough runs on the very first start; after an upgrade the changelog
+//! entries since the last version seen are shown once instead. The version seen
+//! is kept in config.json (`last_seen_version`).
+
+/// Embedded so the notes always match the binary
+const CHANGELOG: &str = include_str!("../CHANGELOG.md");
+
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// One page of the walkthrough: a title and (key, what it does) rows;
+/// rows with an empty key are plain text
+pub struct Step {
+    pub title: &'static str,
+    pub rows: &'static [(&'static str, &'static str)],
+}
+
+pub const STEPS: &[Step] = &[
+    Step {
+        title: "Welcome to pomowise",
+        rows: &[
+            ("", "Work for 25 minutes, rest for 5, and take a long break"),
+            ("", "after every fourth session. Each session gets an animated theme."),
+            ("", ""),
+            ("Enter", "start a session from the menu"),
+        ],
+    },
+    Step {
+        title: "The timer",
+        rows: &[
+            ("Space", "pause / resume"),
+            ("Tab", "skip to the next session"),
+            ("r", "restart the current session"),
+            ("q", "back to the menu"),
+        ],
+    },
+    Step {
+        title: "Make it yours",
+        rows: &[
+            ("t", "pick a theme (a: stop auto-rotation)"),
+            ("f", "cycle digit fonts, or browse them all from the menu"),
+            ("c", "color filters such as night shift"),
+            ("h", "zen mode: hide everything but the time"),
+        ],
+    },
+    Step {
+        title: "Commands",
+        rows: &[
+            (":", "open the command palette, Tab completes"),
+            (":work 50", "change the session length"),
+            (":theme matrix", "switch theme by name"),
+            (":stats", "your focus history"),
+        ],
+    },
+    Step {
+        title: "Breaks",
+        rows: &[
+            ("b", "a guided breathing exercise"),
+            ("g", "a round of snake"),
+            ("", ""),
+            ("", "Everything else lives in config.json, see the README."),
+        ],
+    },
+];
+
+/// Notes for one version from the changelog
+#[derive(Debug, Clone, PartialEq)]
+pub struct Release {
+    pub version: String,
+    pub notes: Vec<String>,
+}
+
+/// Releases newer than `seen`, newest first (all of them when `seen` is None)
+pub fn releases_since(seen: Option<&str>) -> Vec<Release> {
+    let seen = seen.and_then(parse_version);
+    parse_changelog(CHANGELOG)
+        .into_iter()
+        .filter(|release| {
+            parse_version(&release.version).is_some_and(|v| seen.is_none_or(|seen| v > seen))
+        })
+        .collect()
+}
+
+/// `## <version>` headings with `- ` bullets; other sections (Unreleased) are skipped
+fn parse_changelog(text: &str) -> Vec<Release> {
+    let mut releases: Vec<Release> = Vec::new();
+    let mut in_release = false;
+    for line in text.lines() {
+        if let Some(heading) = line.strip_prefix("## ") {
+            in_release = parse_version(heading).is_some();
+            if in_release {
+                releases.push(Release {
+                    version: heading.trim().to_string(),
+                    notes: Vec::new(),
+                });
+            }
+        } else if let (true, Some(note)) = (in_release, line.strip_prefix("- ")) {
+            if let Some(release) = releases.last_mut() {
+                release.notes.push(note.trim().to_string());
+            }
+        }
+    }
+    releases
+}
+
+/// "0.2.0" -> (0, 2, 0)
+fn parse_version(text: &str) -> Option<(u64, u64, u64)> {
+    let mut parts = text.trim().splitn(3, '.').map(|part| part.parse::<u64>().ok());
+    Some((parts.next()??, parts.next()??, parts.next()??))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_releases_since_last_seen() {
+        let text = "# Changelog\n\n## Unreleased\n\n- soon\n\n## 0.3.0\n\n- new\n- shiny\n\n## 0.2.1\n\n- fix\n\n## 0.2.0\n\n- old\n";
+        let releases = parse_changelog(text);
+        assert_eq!(releases.len(), 3);
+        assert_eq!(releases[0].notes, vec!["new", "shiny"]);
+        assert!(parse_version("0.2.1") < parse_version("0.10.0"));
+
+        // The embedded changelog has an entry for this very version
+        assert!(releases_since(None).iter().any(|release| release.version == VERSION));
+        assert!(releases_since(Some(VERSION)).is_empty());
+    }
+}
@@ -0,0 +1,143 @@
+//! Where pomowise keeps its files
+//! `--config-dir <dir>` or `POMOWISE_HOME` puts everything in one directory (a USB
+//! stick, a dotfiles checkout). Otherwise an existing `~/.pomowise` install (one
+//! with settings or a history in it) keeps being used, and new installs follow the platform conventions: the XDG base
+//! directories on Linux, `~/Library/Application Support/pomowise` on macOS and
+//! `%APPDATA%\pomowise` on Windows.
+
+use std::path::{Path, PathBuf};
+
+/// Environment variable naming a single directory for all pomowise files
+pub const HOME_VAR: &str = "POMOWISE_HOME";
+
+/// Settings: `config.json`
+pub fn config_dir() -> PathBuf {
+    resolve(Kind::Config)
+}
+
+/// Long-lived records: the session history
+pub fn data_dir() -> PathBuf {
+    resolve(Kind::Data)
+}
+
+/// Throwaway state: the IPC status file, queued tracker requests
+pub fn state_dir() -> PathBuf {
+    resolve(Kind::State)
+}
+
+/// Take `--config-dir <dir>` (or `--config-dir=<dir>`) out of the arguments and
+/// export it as `POMOWISE_HOME`, so the tray and hooks started from here agree
+pub fn apply_override(args: &mut Vec<String>) {
+    if let Some(dir) = take_config_dir(args) {
+        std::env::set_var(HOME_VAR, crate::sync::expand_home(&dir));
+    }
+}
+
+fn take_config_dir(args: &mut Vec<String>) -> Option<String> {
+    let index = args.iter().position(|a| a == "--config-dir" || a.starts_with("--config-dir="))?;
+    let flag = args.remove(index);
+    match flag.strip_prefix("--config-dir=") {
+        Some(dir) => Some(dir.to_string()),
+        None if index < args.len() => Some(args.remove(index)),
+        None => None,
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Kind {
+    Config,
+    Data,
+    State,
+}
+
+fn resolve(kind: Kind) -> PathBuf {
+    resolve_with(kind, &env_dir)
+}
+
+/// `resolve` with the environment looked up through `env`
+fn resolve_with(kind: Kind, env: &dyn Fn(&str) -> Option<PathBuf>) -> PathBuf {
+    if let Some(dir) = env(HOME_VAR) {
+        return dir;
+    }
+    let Some(home) = env("HOME").or_else(|| env("USERPROFILE")) else {
+        return PathBuf::from(".pomowise");
+    };
+    let legacy = home.join(".pomowise");
+    if is_install(&legacy) {
+        return legacy;
+    }
+    platform_dir(kind, &home, env)
+}
+
+/// A directory pomowise itself has written to; the npm package puts only its
+/// `bin` in `~/.pomowise`
+fn is_install(dir: &Path) -> bool {
+    ["config.json", "history.jsonl", "history.enc"].iter().any(|file| dir.join(file).is_file())
+}
+
+#[cfg(target_os = "macos")]
+fn platform_dir(_kind: Kind, home: &Path, _env: &dyn Fn(&str) -> Option<PathBuf>) -> PathBuf {
+    home.join("Library").join("Application Support").join("pomowise")
+}
+
+#[cfg(windows)]
+fn platform_dir(_kind: Kind, home: &Path, env: &dyn Fn(&str) -> Option<PathBuf>) -> PathBuf {
+    env("APPDATA")
+        .unwrap_or_else(|| home.join("AppData").join("Roaming"))
+        .join("pomowise")
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn platform_dir(kind: Kind, home: &Path, env: &dyn Fn(&str) -> Option<PathBuf>) -> PathBuf {
+    let (var, fallback) = match kind {
+        Kind::Config => ("XDG_CONFIG_HOME", home.join(".config")),
+        Kind::Data => ("XDG_DATA_HOME", home.join(".local").join("share")),
+        Kind::State => ("XDG_STATE_HOME", home.join(".local").join("state")),
+    };
+    // The spec ignores relative paths
+    env(var).filter(|dir| dir.is_absolute()).unwrap_or(fallback).join("pomowise")
+}
+
+fn env_dir(var: &str) -> Option<PathBuf> {
+    std::env::var_os(var).filter(|v| !v.is_empty()).map(PathBuf::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn test_take_config_dir() {
+        let mut list = args(&["--config-dir", "/mnt/usb/pomo", "--incognito"]);
+        assert_eq!(take_config_dir(&mut list).as_deref(), Some("/mnt/usb/pomo"));
+        assert_eq!(list, args(&["--incognito"]));
+
+        let mut list = args(&["--incognito", "--config-dir=~/dotfiles/pomowise"]);
+        assert_eq!(take_config_dir(&mut list).as_deref(), Some("~/dotfiles/pomowise"));
+        assert_eq!(list, args(&["--incognito"]));
+
+        let mut list = args(&["--incognito"]);
+        assert_eq!(take_config_dir(&mut list), None);
+    }
+
+    #[test]
+    fn test_legacy_dir_needs_pomowise_files() {
+        let home = std::env::temp_dir().join(format!("pomowise-paths-{}", std::process::id()));
+        std::fs::create_dir_all(home.join(".pomowise").join("bin")).unwrap();
+        let env = |var: &str| (var == "HOME").then(|| home.clone());
+
+        // Only the npm launcher: a new install
+        assert_ne!(resolve_with(Kind::Config, &env), home.join(".pomowise"));
+        std::fs::write(home.join(".pomowise").join("history.jsonl"), "").unwrap();
+        assert_eq!(resolve_with(Kind::Config, &env), home.join(".pomowise"));
+        // The override wins over both
+        let env = |var: &str| (var == HOME_VAR).then(|| PathBuf::from("/mnt/usb/pomo"));
+        assert_eq!(resolve_with(Kind::Data, &env), PathBuf::from("/mnt/usb/pomo"));
+
+        std::fs::remove_dir_all(&home).unwrap();
+    }
+}
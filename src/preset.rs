@@ -0,0 +1,245 @@
+//! Presets: a whole focus setup in one file to share (session lengths, the
+//! themes to rotate through, a digit font and sounds). `pomowise preset install
+//! <file|url>` checks one and copies it into the presets directory next to
+//! config.json; the Presets entry in the menu switches between installed ones.
+//!
+//! ```toml
+//! format = 1
+//! name = "Deep Work"
+//! description = "Long sessions in calm scenes"
+//! themes = ["ocean", "aurora", "nature"]
+//! font = "lcd"
+//!
+//! [durations]
+//! work = 50
+//! short_break = 10
+//! long_break = 30
+//!
+//! [sounds]
+//! ambient = true
+//! music.work = { player = "noise", color = "brown", volume = 0.4 }
+//! ```
+//!
+//! Presets come from strangers, so their music can only be the built-in noise
+//! and ambient players, never a command.
+
+use std::io;
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use serde::{Deserialize, Serialize};
+
+use crate::animation::digit_fonts::DigitFont;
+use crate::animation::themes::ThemeType;
+use crate::music::{MusicConfig, MusicProfile};
+use crate::paths;
+
+const USAGE: &str = "usage: pomowise preset <install <file|url> | list>";
+
+/// Version of the schema, raised if it ever changes incompatibly
+pub const FORMAT: u32 = 1;
+
+/// Longest session a preset can set, in minutes
+const MAX_MINUTES: u64 = 240;
+
+/// Limit on downloading a preset
+const REQUEST_TIMEOUT_SECS: &str = "20";
+
+/// A shareable focus setup; unset parts leave the user's own settings alone
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Preset {
+    pub format: u32,
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    #[serde(default)]
+    pub author: Option<String>,
+    /// Themes auto-rotation picks from, as accepted by `:theme`; empty allows any
+    #[serde(default)]
+    pub themes: Vec<String>,
+    /// Digit font slug (`lcd`, `3d-blocks`, ...)
+    #[serde(default)]
+    pub font: Option<String>,
+    pub durations: Durations,
+    #[serde(default)]
+    pub sounds: Sounds,
+}
+
+/// Session lengths in minutes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Durations {
+    pub work: u64,
+    pub short_break: u64,
+    pub long_break: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Sounds {
+    /// Noise or ambient loop per session type, replacing the `music` setting
+    pub music: Option<MusicConfig>,
+    /// Turn the themes' ambient sounds on or off
+    pub ambient: Option<bool>,
+    /// Turn the final countdown's ticks on or off
+    pub countdown_ticks: Option<bool>,
+}
+
+impl Preset {
+    /// Read a preset file and check everything in it is something this version knows
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let preset: Preset = toml::from_str(text).map_err(|e| e.to_string())?;
+        if preset.format > FORMAT {
+            return Err(format!("format {} is newer than this pomowise reads ({})", preset.format, FORMAT));
+        }
+        if preset.name.trim().is_empty() {
+            return Err("no name".to_string());
+        }
+        let Durations { work, short_break, long_break } = preset.durations;
+        if [work, short_break, long_break].iter().any(|&m| m == 0 || m > MAX_MINUTES) {
+            return Err(format!("durations must be 1-{} minutes", MAX_MINUTES));
+        }
+        if let Some(theme) = preset.themes.iter().find(|t| ThemeType::from_name(t).is_none()) {
+            return Err(format!("unknown theme \"{}\"", theme));
+        }
+        if let Some(font) = preset.font.as_deref().filter(|f| DigitFont::from_name(f).is_none()) {
+            return Err(format!("unknown font \"{}\"", font));
+        }
+        let music = preset.sounds.music.iter().flat_map(|m| [&m.work, &m.short_break, &m.long_break]);
+        if music.flatten().any(|profile| matches!(profile, MusicProfile::Command { .. })) {
+            return Err("music can only be noise or ambient in a preset, not a command".to_string());
+        }
+        Ok(preset)
+    }
+
+    pub fn theme_types(&self) -> Vec<ThemeType> {
+        self.themes.iter().filter_map(|name| ThemeType::from_name(name)).collect()
+    }
+
+    /// Work, short and long break minutes: "50/10/30"
+    pub fn lengths(&self) -> String {
+        let Durations { work, short_break, long_break } = self.durations;
+        format!("{}/{}/{}", work, short_break, long_break)
+    }
+
+    /// File it is installed as: the name in lowercase, dashes for anything else
+    fn file_name(&self) -> String {
+        let slug: String = self
+            .name
+            .trim()
+            .to_lowercase()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect();
+        format!("{}.toml", slug)
+    }
+}
+
+/// Where installed presets are kept
+pub fn dir() -> PathBuf {
+    paths::config_dir().join("presets")
+}
+
+/// File name and text of the installed preset called `name`
+pub fn installed_file(name: &str) -> Option<(String, String)> {
+    std::fs::read_dir(dir()).ok()?.flatten().find_map(|entry| {
+        let text = std::fs::read_to_string(entry.path()).ok()?;
+        let preset = Preset::parse(&text).ok()?;
+        (preset.name == name).then(|| (entry.file_name().to_string_lossy().into_owned(), text))
+    })
+}
+
+/// The installed presets by name; files that no longer check out are left out
+pub fn installed() -> Vec<Preset> {
+    let Ok(entries) = std::fs::read_dir(dir()) else {
+        return Vec::new();
+    };
+    let mut presets: Vec<Preset> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "toml"))
+        .filter_map(|path| Preset::parse(&std::fs::read_to_string(path).ok()?).ok())
+        .collect();
+    presets.sort_by(|a, b| a.name.cmp(&b.name));
+    presets
+}
+
+/// Run the command; `args` are what follows `preset` on the command line
+pub fn run(args: &[String]) -> io::Result<()> {
+    match (args.first().map(String::as_str), args.get(1)) {
+        (Some("install"), Some(source)) => {
+            let text = read_source(source)?;
+            let preset = Preset::parse(&text).map_err(|e| io::Error::other(format!("{}: {}", source, e)))?;
+            std::fs::create_dir_all(dir())?;
+            let path = dir().join(preset.file_name());
+            // The file as published, comments and all
+            std::fs::write(&path, text)?;
+            println!("Installed preset '{}' ({} min) to {}", preset.name, preset.lengths(), path.display());
+            println!("Pick it from Presets in the menu");
+            Ok(())
+        }
+        (Some("list"), None) => {
+            let presets = installed();
+            if presets.is_empty() {
+                println!("No presets installed (pomowise preset install <file|url>)");
+            }
+            for preset in presets {
+                println!("{:<24} {:<10} {}", preset.name, preset.lengths(), preset.description);
+            }
+            Ok(())
+        }
+        _ => Err(io::Error::other(USAGE)),
+    }
+}
+
+/// A preset's text from a file, or from an http(s) URL through curl
+fn read_source(source: &str) -> io::Result<String> {
+    if !(source.starts_with("https://") || source.starts_with("http://")) {
+        return std::fs::read_to_string(source);
+    }
+    let output = Command::new("curl")
+        .args(["-sSfL", "--max-time", REQUEST_TIMEOUT_SECS])
+        .arg(source)
+        .stdin(Stdio::null())
+        .output()?;
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::other(format!("{}: {}", source, error.trim())));
+    }
+    String::from_utf8(output.stdout).map_err(|_| io::Error::other(format!("{}: not a text file", source)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DEEP_WORK: &str = r#"
+format = 1
+name = "Deep Work"
+themes = ["ocean", "aurora"]
+font = "lcd"
+
+[durations]
+work = 50
+short_break = 10
+long_break = 30
+
+[sounds]
+ambient = true
+music.work = { player = "noise", color = "brown" }
+"#;
+
+    #[test]
+    fn test_parse_preset() {
+        let preset = Preset::parse(DEEP_WORK).unwrap();
+        assert_eq!(preset.lengths(), "50/10/30");
+        assert_eq!(preset.theme_types(), [ThemeType::Ocean, ThemeType::Aurora]);
+        assert_eq!(preset.file_name(), "deep-work.toml");
+        assert!(preset.sounds.music.is_some_and(|m| m.work.is_some()));
+
+        let command = DEEP_WORK.replace(r#"player = "noise", color = "brown""#, r#"player = "command", command = "rm -rf ~""#);
+        assert!(Preset::parse(&command).unwrap_err().contains("not a command"));
+        let theme = DEEP_WORK.replace("\"aurora\"", "\"vaporwave\"");
+        assert_eq!(Preset::parse(&theme).unwrap_err(), "unknown theme \"vaporwave\"");
+        assert!(Preset::parse(&DEEP_WORK.replace("format = 1", "format = 2")).is_err());
+    }
+}
@@ -0,0 +1,198 @@
+//! Privacy for the session history on shared machines
+//! `--incognito` keeps nothing on disk for the run. With `privacy.encrypt_history`
+//! the history lives in history.enc, encrypted with a passphrase taken from the OS
+//! keyring or asked for at startup. Encryption shells out to `openssl` (AES-256,
+//! PBKDF2-derived key), like sync does with git and curl.
+
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode};
+use serde::{Deserialize, Serialize};
+
+use crate::history::{self, HistoryMode};
+
+/// Keyring entry holding the passphrase (service / account)
+const KEYRING_SERVICE: &str = "pomowise";
+const KEYRING_ACCOUNT: &str = "history";
+
+/// Wrong passphrases allowed at the prompt before the history stays locked
+const PROMPT_ATTEMPTS: usize = 3;
+
+/// Privacy settings (the `privacy` key in config.json)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PrivacyConfig {
+    /// Store the history encrypted instead of as plain JSON lines
+    pub encrypt_history: bool,
+    /// Look the passphrase up in the OS keyring before prompting
+    pub keyring: bool,
+}
+
+/// Decide how this run stores its history; call before the terminal UI starts,
+/// since it may prompt for the passphrase on stdin
+pub fn init(config: &PrivacyConfig, incognito: bool) -> io::Result<()> {
+    let mode = if incognito {
+        HistoryMode::Incognito
+    } else if !config.encrypt_history {
+        HistoryMode::Plain
+    } else if !openssl_available() {
+        eprintln!("pomowise: openssl not found, the encrypted history stays locked for this run");
+        HistoryMode::Locked
+    } else {
+        match unlock(config)? {
+            Some(passphrase) => HistoryMode::Encrypted(passphrase),
+            None => HistoryMode::Locked,
+        }
+    };
+    history::set_mode(mode);
+    Ok(())
+}
+
+/// Passphrase from the keyring or the prompt; None leaves the history locked
+fn unlock(config: &PrivacyConfig) -> io::Result<Option<String>> {
+    if config.keyring {
+        if let Some(passphrase) = keyring_lookup() {
+            if history::check_passphrase(&passphrase) {
+                return Ok(Some(passphrase));
+            }
+            eprintln!("pomowise: the keyring passphrase does not open the history");
+        }
+    }
+
+    let exists = history::encrypted_path().exists();
+    for _ in 0..PROMPT_ATTEMPTS {
+        let passphrase = prompt("History passphrase (Enter to skip): ")?;
+        if passphrase.is_empty() {
+            return Ok(None);
+        }
+        if exists {
+            if history::check_passphrase(&passphrase) {
+                return Ok(Some(passphrase));
+            }
+            eprintln!("Wrong passphrase");
+        } else if prompt("Repeat it to create the encrypted history: ")? == passphrase {
+            return Ok(Some(passphrase));
+        } else {
+            eprintln!("The passphrases differ");
+        }
+    }
+    Ok(None)
+}
+
+/// Read a line without echoing it
+fn prompt(message: &str) -> io::Result<String> {
+    eprint!("{}", message);
+    io::stderr().flush()?;
+    enable_raw_mode()?;
+    let line = read_hidden_line();
+    disable_raw_mode()?;
+    eprintln!();
+    line
+}
+
+/// Esc and Ctrl+C give up (an empty answer)
+fn read_hidden_line() -> io::Result<String> {
+    let mut line = String::new();
+    loop {
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Enter => return Ok(line),
+            KeyCode::Esc => return Ok(String::new()),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => return Ok(String::new()),
+            KeyCode::Backspace => {
+                line.pop();
+            }
+            KeyCode::Char(c) => line.push(c),
+            _ => {}
+        }
+    }
+}
+
+/// Passphrase stored with `secret-tool` (Linux) or `security` (macOS)
+fn keyring_lookup() -> Option<String> {
+    let output = if cfg!(target_os = "macos") {
+        Command::new("security")
+            .args(["find-generic-password", "-s", KEYRING_SERVICE, "-a", KEYRING_ACCOUNT, "-w"])
+            .output()
+    } else {
+        Command::new("secret-tool")
+            .args(["lookup", "service", KEYRING_SERVICE, "account", KEYRING_ACCOUNT])
+            .output()
+    }
+    .ok()?;
+    let passphrase = String::from_utf8(output.stdout).ok()?.trim_end_matches('\n').to_string();
+    (output.status.success() && !passphrase.is_empty()).then_some(passphrase)
+}
+
+fn openssl_available() -> bool {
+    Command::new("openssl")
+        .arg("version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+/// Encrypt to base64 text
+pub fn encrypt(plain: &str, passphrase: &str) -> io::Result<String> {
+    let output = openssl(&[], plain.as_bytes(), passphrase)?;
+    String::from_utf8(output).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Decrypt what `encrypt` wrote; a wrong passphrase is an `InvalidData` error
+pub fn decrypt(cipher: &str, passphrase: &str) -> io::Result<String> {
+    let output = openssl(&["-d"], cipher.as_bytes(), passphrase)?;
+    String::from_utf8(output).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+/// Run `openssl enc`; the passphrase goes through the environment, never argv
+fn openssl(extra: &[&str], input: &[u8], passphrase: &str) -> io::Result<Vec<u8>> {
+    let mut child = Command::new("openssl")
+        .args(["enc", "-aes-256-cbc", "-pbkdf2", "-iter", "200000", "-salt", "-a", "-A"])
+        .args(extra)
+        .args(["-pass", "env:POMOWISE_PASSPHRASE"])
+        .env("POMOWISE_PASSPHRASE", passphrase)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+    // Fed from another thread while the output is read here: with both pipes
+    // full, writing all of a large history first would deadlock with openssl
+    let writer = child.stdin.take().map(|mut stdin| {
+        let input = input.to_vec();
+        std::thread::spawn(move || stdin.write_all(&input))
+    });
+    let output = child.wait_with_output()?;
+    if let Some(writer) = writer {
+        writer.join().map_err(|_| io::Error::other("openssl input thread panicked"))??;
+    }
+    if output.status.success() {
+        Ok(output.stdout)
+    } else {
+        Err(io::Error::new(io::ErrorKind::InvalidData, "openssl could not decrypt or encrypt"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encrypt_round_trip_beyond_pipe_buffer() {
+        if !openssl_available() {
+            return;
+        }
+        let plain = "{\"kind\":\"work\"}\n".repeat(20_000);
+        let cipher = encrypt(&plain, "correct horse").unwrap();
+        assert!(!cipher.contains('\n'));
+        assert_eq!(decrypt(&cipher, "correct horse").unwrap(), plain);
+        assert!(!decrypt(&cipher, "battery staple").is_ok_and(|text| text == plain));
+    }
+}
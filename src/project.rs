@@ -0,0 +1,92 @@
+//! Project labels for work sessions (`auto_label` in config.json)
+//! Each work session is labelled, as it starts, with where pomowise runs: the
+//! tmux window, the git repository or the working directory. The label goes into
+//! the history, so the stats group sessions by project without any typing.
+
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use serde::{Deserialize, Serialize};
+
+/// Where work session labels come from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AutoLabel {
+    /// No labels
+    #[default]
+    Off,
+    /// Name of the tmux window pomowise runs in
+    Tmux,
+    /// Name of the git repository the working directory is in
+    Git,
+    /// Name of the working directory
+    Directory,
+    /// A tmux window named by hand, else the git repository, else the directory
+    Auto,
+}
+
+impl AutoLabel {
+    /// The label for a session starting now, if there is one
+    pub fn detect(&self) -> Option<String> {
+        let cwd = std::env::current_dir().ok();
+        let cwd = cwd.as_deref();
+        match self {
+            AutoLabel::Off => None,
+            AutoLabel::Tmux => tmux_window("#W"),
+            AutoLabel::Git => cwd.and_then(git_repo),
+            AutoLabel::Directory => cwd.and_then(directory),
+            // An automatically renamed window is just the command running in it
+            AutoLabel::Auto => tmux_window("#{?automatic-rename,,#W}")
+                .or_else(|| cwd.and_then(git_repo))
+                .or_else(|| cwd.and_then(directory)),
+        }
+    }
+}
+
+/// Window name from tmux, in the `format` given, when running inside it
+fn tmux_window(format: &str) -> Option<String> {
+    std::env::var_os("TMUX")?;
+    let mut command = Command::new("tmux");
+    command.args(["display-message", "-p"]);
+    if let Ok(pane) = std::env::var("TMUX_PANE") {
+        command.args(["-t", &pane]);
+    }
+    let output = command.arg(format).stdin(Stdio::null()).stderr(Stdio::null()).output().ok()?;
+    let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && !name.is_empty()).then_some(name)
+}
+
+/// Name of the repository `dir` is in: the folder holding `.git`
+fn git_repo(dir: &Path) -> Option<String> {
+    dir.ancestors().find(|d| d.join(".git").exists()).and_then(file_name)
+}
+
+/// Name of `dir` itself; the home directory says nothing about a project
+fn directory(dir: &Path) -> Option<String> {
+    if std::env::var_os("HOME").is_some_and(|home| Path::new(&home) == dir) {
+        return None;
+    }
+    file_name(dir)
+}
+
+fn file_name(dir: &Path) -> Option<String> {
+    dir.file_name().map(|name| name.to_string_lossy().into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_labels_come_from_the_repository_and_directory() {
+        let root = std::env::temp_dir().join("pomowise-project-test").join("parser");
+        let nested = root.join("src").join("lexer");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::create_dir_all(root.join(".git")).unwrap();
+
+        assert_eq!(git_repo(&nested).as_deref(), Some("parser"));
+        assert_eq!(directory(&nested).as_deref(), Some("lexer"));
+        assert_eq!(git_repo(Path::new("/")), None);
+        assert_eq!(AutoLabel::Off.detect(), None);
+    }
+}
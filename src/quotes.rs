@@ -0,0 +1,135 @@
+//! Quote of the session
+//! A motivational or stoic line when a work session starts and a rest-minded one
+//! for breaks, shown under the digits for the first seconds of the session. The
+//! bundled lists can be extended from a plain text file: one quote per line,
+//! `#` starts a comment and a `break:` prefix files the quote under breaks.
+
+use serde::{Deserialize, Serialize};
+
+use crate::history::SessionKind;
+use crate::sync::expand_home;
+
+/// How long a quote stays up at the start of a session, the last part fading out
+pub const QUOTE_SECS: f32 = 15.0;
+pub const QUOTE_FADE_SECS: f32 = 4.0;
+
+const WORK_QUOTES: &[&str] = &[
+    "The impediment to action advances action. What stands in the way becomes the way. — Marcus Aurelius",
+    "You could leave life right now. Let that determine what you do and say and think. — Marcus Aurelius",
+    "It is not that we have a short time to live, but that we waste a lot of it. — Seneca",
+    "No man is free who is not master of himself. — Epictetus",
+    "First say to yourself what you would be; then do what you have to do. — Epictetus",
+    "Well begun is half done. — Aristotle",
+    "Concentrate every minute on doing what's in front of you. — Marcus Aurelius",
+    "The secret of getting ahead is getting started. — Mark Twain",
+    "Do the hard jobs first. The easy jobs will take care of themselves. — Dale Carnegie",
+    "Action is the foundational key to all success. — Pablo Picasso",
+    "Focus is a matter of deciding what things you're not going to do. — John Carmack",
+    "Amateurs sit and wait for inspiration, the rest of us just get up and go to work. — Stephen King",
+];
+
+const BREAK_QUOTES: &[&str] = &[
+    "Almost everything will work again if you unplug it for a few minutes, including you. — Anne Lamott",
+    "The mind should be allowed some relaxation, that it may return to its work all the better. — Seneca",
+    "Rest is not idleness. — John Lubbock",
+    "Nature does not hurry, yet everything is accomplished. — Lao Tzu",
+    "Take rest; a field that has rested gives a bountiful crop. — Ovid",
+    "Breathe. Let go. And remind yourself that this very moment is the only one you know you have for sure. — Oprah Winfrey",
+    "Sometimes the most productive thing you can do is relax. — Mark Black",
+    "Stand up, stretch, look at something far away.",
+    "Drink some water. Your future self says thanks.",
+    "Within you there is a stillness and a sanctuary to which you can retreat at any time. — Hermann Hesse",
+];
+
+/// The `quotes` key in config.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct QuotesConfig {
+    pub enabled: bool,
+    /// Extra quotes, one per line; `~/` is the home directory
+    pub file: Option<String>,
+}
+
+impl Default for QuotesConfig {
+    fn default() -> Self {
+        Self { enabled: true, file: None }
+    }
+}
+
+/// Quotes to pick from, bundled plus the user's
+#[derive(Debug, Clone, Default)]
+pub struct Quotes {
+    work: Vec<String>,
+    rest: Vec<String>,
+}
+
+impl Quotes {
+    /// The bundled quotes and those in the configured file; none when disabled
+    pub fn load(config: &QuotesConfig) -> Self {
+        if !config.enabled {
+            return Self::default();
+        }
+        let mut quotes = Self {
+            work: WORK_QUOTES.iter().map(|q| q.to_string()).collect(),
+            rest: BREAK_QUOTES.iter().map(|q| q.to_string()).collect(),
+        };
+        if let Some(text) = config.file.as_deref().and_then(|f| std::fs::read_to_string(expand_home(f)).ok()) {
+            quotes.extend(&text);
+        }
+        quotes
+    }
+
+    fn extend(&mut self, text: &str) {
+        for line in text.lines().map(str::trim).filter(|l| !l.is_empty() && !l.starts_with('#')) {
+            match line.strip_prefix("break:") {
+                Some(quote) => self.rest.push(quote.trim().to_string()),
+                None => self.work.push(line.to_string()),
+            }
+        }
+    }
+
+    /// A quote for a session of this kind, different from `last` when there is a choice
+    pub fn pick(&self, kind: SessionKind, last: Option<&str>) -> Option<String> {
+        use std::time::{SystemTime, UNIX_EPOCH};
+        let seed = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos() as usize;
+
+        let list = match kind {
+            SessionKind::Work => &self.work,
+            SessionKind::ShortBreak | SessionKind::LongBreak => &self.rest,
+        };
+        let candidates: Vec<&String> = list.iter().filter(|q| Some(q.as_str()) != last).collect();
+        match candidates.len() {
+            0 => list.first().cloned(),
+            n => Some(candidates[seed % n].clone()),
+        }
+    }
+}
+
+/// Opacity of the quote `elapsed_secs` into the session: full, then fading to nothing
+pub fn opacity(elapsed_secs: f32) -> f32 {
+    ((QUOTE_SECS - elapsed_secs) / QUOTE_FADE_SECS).clamp(0.0, 1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_user_quotes_and_fade() {
+        let mut quotes = Quotes::default();
+        quotes.extend("# mine\nShip it.\n\nbreak: Walk around the block.\n");
+        assert_eq!(quotes.pick(SessionKind::Work, None).as_deref(), Some("Ship it."));
+        assert_eq!(quotes.pick(SessionKind::LongBreak, None).as_deref(), Some("Walk around the block."));
+        // A single quote repeats rather than leaving the session without one
+        assert_eq!(quotes.pick(SessionKind::Work, Some("Ship it.")).as_deref(), Some("Ship it."));
+
+        assert!(Quotes::load(&QuotesConfig { enabled: false, file: None }).pick(SessionKind::Work, None).is_none());
+
+        assert_eq!(opacity(0.0), 1.0);
+        assert_eq!(opacity(QUOTE_SECS - QUOTE_FADE_SECS), 1.0);
+        assert_eq!(opacity(QUOTE_SECS), 0.0);
+    }
+}
@@ -0,0 +1,96 @@
+//! Recurring reminders (`reminders` in config.json): "Stand up" every 60
+//! minutes, "Drink water" every 30, whatever the timer is doing. Each counts
+//! from the start of the run (or from when it was last switched on or
+//! restarted) and shows a toast and a desktop notification when due.
+
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+/// Shortest interval, so a typo doesn't turn into a notification a minute
+pub const MIN_EVERY_MINUTES: u64 = 5;
+
+/// A reminder and how often it comes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Reminder {
+    pub message: String,
+    pub every_minutes: u64,
+    /// Switched off from the reminders screen (`:reminders`), kept for later
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+impl Reminder {
+    fn interval(&self) -> Duration {
+        Duration::from_secs(self.every_minutes.max(MIN_EVERY_MINUTES) * 60)
+    }
+}
+
+/// When each configured reminder is next due; None for the ones switched off
+#[derive(Debug, Default)]
+pub struct Scheduler {
+    next: Vec<Option<Instant>>,
+}
+
+impl Scheduler {
+    pub fn new(reminders: &[Reminder], now: Instant) -> Self {
+        let mut scheduler = Self::default();
+        for i in 0..reminders.len() {
+            scheduler.restart(reminders, i, now);
+        }
+        scheduler
+    }
+
+    /// Count reminder `i` from `now` again (or stop it, if it's off)
+    pub fn restart(&mut self, reminders: &[Reminder], i: usize, now: Instant) {
+        if self.next.len() < reminders.len() {
+            self.next.resize(reminders.len(), None);
+        }
+        if let Some(reminder) = reminders.get(i) {
+            self.next[i] = reminder.enabled.then(|| now + reminder.interval());
+        }
+    }
+
+    /// Reminders due at `now`, each put a full interval on; after a suspend
+    /// that skipped several, a reminder comes once
+    pub fn due(&mut self, reminders: &[Reminder], now: Instant) -> Vec<usize> {
+        let due: Vec<usize> = (0..reminders.len().min(self.next.len()))
+            .filter(|&i| self.next[i].is_some_and(|next| next <= now))
+            .collect();
+        for &i in &due {
+            self.restart(reminders, i, now);
+        }
+        due
+    }
+
+    /// Time until reminder `i` is due, None when it's off
+    pub fn due_in(&self, i: usize, now: Instant) -> Option<Duration> {
+        self.next.get(i).copied().flatten().map(|next| next.saturating_duration_since(now))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reminders_come_round_again() {
+        let reminder = |message: &str, every_minutes, enabled| Reminder { message: message.to_string(), every_minutes, enabled };
+        let reminders = [reminder("Stand up", 60, true), reminder("Water", 30, true), reminder("Eyes", 20, false)];
+        let start = Instant::now();
+        let mut scheduler = Scheduler::new(&reminders, start);
+        let minutes = |m: u64| start + Duration::from_secs(m * 60);
+
+        assert!(scheduler.due(&reminders, minutes(29)).is_empty());
+        assert_eq!(scheduler.due(&reminders, minutes(30)), [1]);
+        assert_eq!(scheduler.due(&reminders, minutes(60)), [0, 1]);
+        // An hour asleep: each comes once, then counts from the wake-up
+        assert_eq!(scheduler.due(&reminders, minutes(200)), [0, 1]);
+        assert_eq!(scheduler.due_in(1, minutes(200)), Some(Duration::from_secs(30 * 60)));
+        assert_eq!(scheduler.due_in(2, minutes(200)), None);
+    }
+}
@@ -0,0 +1,181 @@
+//! `pomowise new-theme <name>`: start a theme module from a working skeleton
+//! The generated file follows docs/THEME_TEMPLATE.md: a `render_background`
+//! function with a background fill, a sample particle system and the theme's
+//! colors as constants, ready to be registered in `src/animation/themes/mod.rs`.
+//! Run it from a checkout to write straight into the themes directory.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+const USAGE: &str = "usage: pomowise new-theme <name> [--out <dir>]";
+
+/// Where theme modules live, relative to a checkout
+const THEMES_DIR: &str = "src/animation/themes";
+
+const TEMPLATE: &str = r#"//! __NAME__: drifting motes over a dark backdrop (generated by `pomowise new-theme`)
+
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+
+use crate::animation::background;
+use crate::animation::math::fast_sin;
+
+/// Colors to register in `primary_color()`, `secondary_color()` and `background_color()`
+pub const PRIMARY: Color = Color::Rgb(230, 200, 120);
+pub const SECONDARY: Color = Color::Rgb(110, 90, 50);
+pub const BACKGROUND: Color = Color::Rgb(12, 10, 20);
+
+const NUM_MOTES: usize = 40;
+
+fn simple_hash(seed: usize, salt: usize) -> usize {
+    let mut h = seed.wrapping_mul(2654435761);
+    h ^= salt.wrapping_mul(1597334677);
+    h = h.wrapping_mul(2654435761);
+    h ^ (h >> 16)
+}
+
+/// One particle, derived from its seed and the frame alone, so the theme needs no
+/// state between frames and looks the same at any terminal size
+struct Mote {
+    x: f32,
+    y: f32,
+    brightness: f32,
+}
+
+impl Mote {
+    fn at(seed: usize, area: Rect, frame_index: usize) -> Self {
+        let (width, height) = (area.width.max(1) as f32, area.height.max(1) as f32);
+        let speed = 0.05 + (simple_hash(seed, 1) % 100) as f32 / 400.0;
+        let t = frame_index as f32;
+        // Rise slowly, sway sideways, wrap around the top
+        let start_x = (simple_hash(seed, 2) % 1000) as f32 / 1000.0 * width;
+        let start_y = (simple_hash(seed, 3) % 1000) as f32 / 1000.0 * height;
+        let sway = fast_sin(t * 0.03 + seed as f32) * 2.0;
+        Self {
+            x: (start_x + sway).rem_euclid(width),
+            y: (start_y - t * speed).rem_euclid(height),
+            brightness: (fast_sin(t * 0.1 + seed as f32 * 0.7) + 1.0) / 2.0,
+        }
+    }
+}
+
+pub fn render_background(frame: &mut Frame, area: Rect, frame_index: usize) {
+    // 1. Backdrop (left alone with `transparent_background`)
+    background::fill(frame, area, BACKGROUND);
+
+    // 2. Animated elements
+    for i in 0..NUM_MOTES {
+        let mote = Mote::at(i, area, frame_index);
+        let (ch, color) = if mote.brightness > 0.7 {
+            ("•", PRIMARY)
+        } else {
+            ("·", SECONDARY)
+        };
+        frame.render_widget(
+            Paragraph::new(ch).style(Style::default().fg(color)),
+            Rect::new(area.x + mote.x as u16, area.y + mote.y as u16, 1, 1),
+        );
+    }
+}
+"#;
+
+/// Run the command; `args` are what follows `new-theme` on the command line
+pub fn run(args: &[String]) -> io::Result<()> {
+    let (name, out) = parse_args(args).map_err(|e| io::Error::other(format!("{}\n{}", e, USAGE)))?;
+    let module = module_name(&name);
+    if module.is_empty() || module.starts_with(|c: char| c.is_ascii_digit()) {
+        return Err(io::Error::other(format!("'{}' doesn't make a module name\n{}", name, USAGE)));
+    }
+
+    let dir = out.unwrap_or_else(|| {
+        let checkout = Path::new(THEMES_DIR);
+        if checkout.is_dir() { checkout.to_path_buf() } else { PathBuf::from(".") }
+    });
+    let path = dir.join(format!("{}.rs", module));
+    if path.exists() {
+        return Err(io::Error::other(format!("{} already exists", path.display())));
+    }
+    std::fs::create_dir_all(&dir)?;
+    std::fs::write(&path, TEMPLATE.replace("__NAME__", &name))?;
+
+    println!("Created {}", path.display());
+    println!();
+    print!("{}", registration_steps(&name, &module));
+    Ok(())
+}
+
+fn parse_args(args: &[String]) -> Result<(String, Option<PathBuf>), String> {
+    let mut name = None;
+    let mut out = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--out" => out = Some(PathBuf::from(args.next().ok_or("--out needs a directory")?)),
+            flag if flag.starts_with("--") => return Err(format!("unknown option {}", flag)),
+            word => {
+                // Unquoted multi-word names: `new-theme Ocean Sunset`
+                name = Some(match name {
+                    Some(name) => format!("{} {}", name, word),
+                    None => word.to_string(),
+                })
+            }
+        }
+    }
+    name.map(|n| (n, out)).ok_or_else(|| "missing theme name".to_string())
+}
+
+/// "Ocean Sunset" -> "ocean_sunset"
+fn module_name(name: &str) -> String {
+    name.split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_ascii_lowercase())
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// "ocean_sunset" -> "OceanSunset"
+fn type_name(module: &str) -> String {
+    module
+        .split('_')
+        .map(|w| {
+            let mut chars = w.chars();
+            chars.next().map_or(String::new(), |c| c.to_ascii_uppercase().to_string() + chars.as_str())
+        })
+        .collect()
+}
+
+/// What to add to `src/animation/themes/mod.rs` to make the theme selectable
+fn registration_steps(name: &str, module: &str) -> String {
+    let ty = type_name(module);
+    format!(
+        "Register it in {dir}/mod.rs (see docs/THEME_TEMPLATE.md):\n\
+         \n  1. pub mod {module};\
+         \n  2. ThemeType::{ty} in the enum and in all()\
+         \n  3. name():              ThemeType::{ty} => \"{name}\",\
+         \n  4. moods():             ThemeType::{ty} => &[Mood::Calm],\
+         \n  5. render_with_state(): (ThemeType::{ty}, _) => {module}::render_background(frame, area, frame_index),\
+         \n  6. primary_color():     ThemeType::{ty} => {module}::PRIMARY,\
+         \n     secondary_color():   ThemeType::{ty} => {module}::SECONDARY,\
+         \n     background_color():  ThemeType::{ty} => {module}::BACKGROUND,\
+         \n\nThen try it with `cargo run -- screenshot --theme {slug}` and `cargo run -- bench`.\n",
+        dir = THEMES_DIR,
+        slug = name.to_lowercase().replace(' ', "-"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_theme_names() {
+        let args: Vec<String> = ["Ocean", "Sunset", "--out", "/tmp/themes"].iter().map(|a| a.to_string()).collect();
+        let (name, out) = parse_args(&args).unwrap();
+        assert_eq!(name, "Ocean Sunset");
+        assert_eq!(out, Some(PathBuf::from("/tmp/themes")));
+        assert_eq!(module_name(&name), "ocean_sunset");
+        assert_eq!(type_name("ocean_sunset"), "OceanSunset");
+        assert_eq!(module_name("Neon-City 2"), "neon_city_2");
+        assert!(parse_args(&[]).is_err());
+    }
+}
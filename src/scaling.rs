@@ -7,10 +7,29 @@ use crate::animation::DigitFont;
 pub const MIN_WIDTH: u16 = 40;
 pub const MIN_HEIGHT: u16 = 15;
 
+/// Narrowest terminal that can still show the single-line bar mode
+pub const BAR_MIN_WIDTH: u16 = 20;
+
+/// Height of a terminal cell over its width, as most fonts draw them
+pub const DEFAULT_CELL_ASPECT: f32 = 2.0;
+
+/// Range `cell_aspect` is clamped to
+pub const MIN_CELL_ASPECT: f32 = 1.0;
+pub const MAX_CELL_ASPECT: f32 = 4.0;
+
+/// Fonts the adaptive selection steps through, smallest to largest
+const GROW_FONTS: [DigitFont; 4] = [
+    DigitFont::Classic,
+    DigitFont::Terminal,
+    DigitFont::Block3D,
+    DigitFont::Outlined,
+];
+
 /// Terminal size categories
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TerminalSize {
     TooSmall,    // Below minimum - show warning
+    Bar,         // Too short for digits but wide enough - single-line bar mode
     Compact,     // 40-60 width - use smallest fonts
     Medium,      // 60-100 width - use medium fonts
     Large,       // 100-150 width - use standard fonts
@@ -20,7 +39,9 @@ pub enum TerminalSize {
 impl TerminalSize {
     /// Determine terminal size category from dimensions
     pub fn from_dimensions(width: u16, height: u16) -> Self {
-        if width < MIN_WIDTH || height < MIN_HEIGHT {
+        if height < MIN_HEIGHT && width >= BAR_MIN_WIDTH {
+            TerminalSize::Bar
+        } else if width < MIN_WIDTH || height < MIN_HEIGHT {
             TerminalSize::TooSmall
         } else if width < 60 || height < 20 {
             TerminalSize::Compact
@@ -46,6 +67,9 @@ pub struct ScalingContext {
     pub show_hints: bool,
     pub show_session_info: bool,
     pub background_detail_level: u8, // 0-3, affects theme complexity
+    /// How many cell widths one cell is tall (the `cell_aspect` key in
+    /// config.json); themes and the ring around the digits draw with it
+    pub cell_aspect: f32,
 }
 
 impl ScalingContext {
@@ -55,7 +79,7 @@ impl ScalingContext {
 
         let (recommended_font, background_detail_level, show_progress_bar, show_hints, show_session_info) =
             match size_category {
-                TerminalSize::TooSmall => (
+                TerminalSize::TooSmall | TerminalSize::Bar => (
                     DigitFont::Classic, // Smallest font
                     0,    // Minimal background
                     false,
@@ -105,20 +129,51 @@ impl ScalingContext {
             show_hints,
             show_session_info,
             background_detail_level,
+            cell_aspect: DEFAULT_CELL_ASPECT,
         }
     }
 
+    /// Draw with cells `aspect` times as tall as wide, clamped to the supported range
+    pub fn set_cell_aspect(&mut self, aspect: f32) {
+        self.cell_aspect = if aspect.is_finite() {
+            aspect.clamp(MIN_CELL_ASPECT, MAX_CELL_ASPECT)
+        } else {
+            DEFAULT_CELL_ASPECT
+        };
+    }
+
     /// Check if terminal is too small to render
     pub fn is_too_small(&self) -> bool {
         self.size_category == TerminalSize::TooSmall
     }
 
+    /// Check if only the single-line bar fits
+    pub fn is_bar_mode(&self) -> bool {
+        self.size_category == TerminalSize::Bar
+    }
+
     /// Get the timer display width for current font
     pub fn timer_width(&self) -> u16 {
         // MM:SS format = 4 digits + colon
         self.recommended_font.width() * 4 + self.recommended_font.colon_width() + 4
     }
 
+    /// Font for the final-minute "grow" effect: the largest of the adaptive fonts
+    /// that still fits between the overlay and the hint line, if it is taller than
+    /// `current`; otherwise `current`
+    pub fn grown_font(&self, current: DigitFont) -> DigitFont {
+        GROW_FONTS
+            .iter()
+            .rev()
+            .copied()
+            .find(|font| {
+                let timer_width = font.width() * 4 + font.colon_width() + 4;
+                timer_width + 4 <= self.width && self.timer_y() + font.height() + 2 <= self.hints_y()
+            })
+            .filter(|font| font.height() > current.height())
+            .unwrap_or(current)
+    }
+
     /// Calculate centered X position for an element of given width
     pub fn center_x(&self, element_width: u16) -> u16 {
         if element_width >= self.width {
@@ -219,13 +274,25 @@ mod tests {
 
     #[test]
     fn test_size_categories() {
-        assert_eq!(TerminalSize::from_dimensions(30, 10), TerminalSize::TooSmall);
+        assert_eq!(TerminalSize::from_dimensions(30, 10), TerminalSize::Bar);
+        assert_eq!(TerminalSize::from_dimensions(15, 10), TerminalSize::TooSmall);
+        assert_eq!(TerminalSize::from_dimensions(30, 20), TerminalSize::TooSmall);
         assert_eq!(TerminalSize::from_dimensions(50, 18), TerminalSize::Compact);
         assert_eq!(TerminalSize::from_dimensions(80, 24), TerminalSize::Medium);
         assert_eq!(TerminalSize::from_dimensions(120, 40), TerminalSize::Large);
         assert_eq!(TerminalSize::from_dimensions(200, 50), TerminalSize::ExtraLarge);
     }
 
+    #[test]
+    fn test_grown_font() {
+        // Roomy terminal: straight to the largest
+        let large = ScalingContext::new(120, 40);
+        assert_eq!(large.grown_font(DigitFont::Classic), DigitFont::Outlined);
+        // Never shrinks
+        let compact = ScalingContext::new(50, 18);
+        assert_eq!(compact.grown_font(DigitFont::Block3D), DigitFont::Block3D);
+    }
+
     #[test]
     fn test_font_selection() {
         // Small terminal should get small font
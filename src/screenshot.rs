@@ -0,0 +1,305 @@
+//! `pomowise screenshot`: one frame of the timer screen, saved as text or PNG
+//! The frame is drawn by the normal UI code into a ratatui `TestBackend`, with
+//! the default config and a fresh work session. PNGs are rasterized cell by cell
+//! with the bundled DejaVu Sans Mono; block elements are drawn as exact
+//! rectangles so the big digits and shaded backgrounds tile without seams.
+
+use std::io::{self, BufWriter};
+use std::path::Path;
+
+use fontdue::{Font, FontSettings};
+use ratatui::backend::TestBackend;
+use ratatui::buffer::{Buffer, Cell};
+use ratatui::style::{Color, Modifier};
+use ratatui::Terminal;
+
+use crate::animation::themes::ThemeType;
+use crate::app::{App, AppScreen};
+use crate::config::Config;
+use crate::history::{self, HistoryMode};
+use crate::ui;
+
+const FONT: &[u8] = include_bytes!("../assets/fonts/DejaVuSansMono.ttf");
+
+/// Pixel size of one terminal cell in PNGs
+const CELL_WIDTH: usize = 10;
+const CELL_HEIGHT: usize = 20;
+const FONT_PX: f32 = 16.0;
+
+const DEFAULT_SIZE: (u16, u16) = (120, 40);
+/// Animation frames run before the capture, so particles have spread out
+const DEFAULT_FRAMES: usize = 40;
+
+/// What `Color::Reset` stands for
+const DEFAULT_FG: [u8; 3] = [220, 220, 220];
+const DEFAULT_BG: [u8; 3] = [0, 0, 0];
+
+const USAGE: &str = "usage: pomowise screenshot --theme <name> [--size 120x40] [--frames N] [--out file.txt|file.png]";
+
+struct Options {
+    theme: ThemeType,
+    size: (u16, u16),
+    frames: usize,
+    out: Option<String>,
+}
+
+/// Run the command; `args` are what follows `screenshot` on the command line
+pub fn run(args: &[String]) -> io::Result<()> {
+    let options = parse_args(args).map_err(|e| io::Error::other(format!("{}\n{}", e, USAGE)))?;
+    // Nothing from the user's history is read or written
+    history::set_mode(HistoryMode::Incognito);
+    let buffer = capture(options.theme, options.size, options.frames)?;
+
+    match options.out.as_deref() {
+        None => print!("{}", to_text(&buffer)),
+        Some(path) if path.ends_with(".png") => write_png(&buffer, Path::new(path))?,
+        Some(path) => std::fs::write(path, to_text(&buffer))?,
+    }
+    Ok(())
+}
+
+fn parse_args(args: &[String]) -> Result<Options, String> {
+    let mut theme = None;
+    let mut size = DEFAULT_SIZE;
+    let mut frames = DEFAULT_FRAMES;
+    let mut out = None;
+
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        let value = args.next().ok_or_else(|| format!("{} needs a value", flag))?;
+        match flag.as_str() {
+            "--theme" => theme = Some(ThemeType::from_name(value).ok_or_else(|| format!("unknown theme: {}", value))?),
+            "--size" => size = parse_size(value).ok_or_else(|| format!("invalid size: {}", value))?,
+            "--frames" => frames = value.parse().map_err(|_| format!("invalid frame count: {}", value))?,
+            "--out" => out = Some(value.clone()),
+            other => return Err(format!("unknown option: {}", other)),
+        }
+    }
+
+    Ok(Options {
+        theme: theme.ok_or("--theme is required")?,
+        size,
+        frames,
+        out,
+    })
+}
+
+/// "120x40"
+fn parse_size(value: &str) -> Option<(u16, u16)> {
+    let (width, height) = value.split_once('x')?;
+    let size = (width.parse().ok()?, height.parse().ok()?);
+    (size.0 > 0 && size.1 > 0).then_some(size)
+}
+
+/// Draw the timer screen with `theme` after `frames` animation frames
+fn capture(theme: ThemeType, (width, height): (u16, u16), frames: usize) -> io::Result<Buffer> {
+    let mut app = App::with_config(Config::default());
+    app.update_dimensions(width, height);
+    app.auto_rotate = false;
+    app.animation.set_theme(theme);
+    app.screen = AppScreen::Timer;
+    app.timer.start();
+
+    let mut terminal = Terminal::new(TestBackend::new(width, height))?;
+    // The first draw seeds the theme's particles, the second moves them on
+    terminal.draw(|frame| ui::draw(frame, &app))?;
+    app.animation.jump_to_frame(frames);
+    terminal.draw(|frame| ui::draw(frame, &app))?;
+    Ok(terminal.backend().buffer().clone())
+}
+
+/// Plain text, one line per row, trailing blanks trimmed
+fn to_text(buffer: &Buffer) -> String {
+    let area = buffer.area;
+    let mut text = String::new();
+    for y in 0..area.height {
+        let line: String = (0..area.width).map(|x| buffer[(x, y)].symbol()).collect();
+        text.push_str(line.trim_end());
+        text.push('\n');
+    }
+    text
+}
+
+fn write_png(buffer: &Buffer, path: &Path) -> io::Result<()> {
+    let font = Font::from_bytes(FONT, FontSettings::default()).map_err(io::Error::other)?;
+    let (width, height) = (buffer.area.width as usize * CELL_WIDTH, buffer.area.height as usize * CELL_HEIGHT);
+    let mut canvas = Canvas {
+        width,
+        pixels: vec![0; width * height * 3],
+    };
+    let ascent = font
+        .horizontal_line_metrics(FONT_PX)
+        .map_or(FONT_PX * 0.8, |metrics| metrics.ascent);
+
+    for y in 0..buffer.area.height {
+        for x in 0..buffer.area.width {
+            let cell = &buffer[(x, y)];
+            canvas.draw_cell(&font, ascent, x as usize * CELL_WIDTH, y as usize * CELL_HEIGHT, cell);
+        }
+    }
+
+    let file = std::fs::File::create(path)?;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(io::Error::other)?;
+    writer.write_image_data(&canvas.pixels).map_err(io::Error::other)
+}
+
+struct Canvas {
+    width: usize,
+    /// RGB rows
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn fill(&mut self, x: usize, y: usize, width: usize, height: usize, color: [u8; 3]) {
+        for py in y..y + height {
+            for px in x..x + width {
+                let i = (py * self.width + px) * 3;
+                self.pixels[i..i + 3].copy_from_slice(&color);
+            }
+        }
+    }
+
+    /// Blend `color` over the pixel with `alpha` coverage (0-255)
+    fn blend(&mut self, x: usize, y: usize, color: [u8; 3], alpha: u8) {
+        let i = (y * self.width + x) * 3;
+        for (channel, &value) in color.iter().enumerate() {
+            let old = self.pixels[i + channel] as u32;
+            self.pixels[i + channel] = ((value as u32 * alpha as u32 + old * (255 - alpha as u32)) / 255) as u8;
+        }
+    }
+
+    fn draw_cell(&mut self, font: &Font, ascent: f32, x: usize, y: usize, cell: &Cell) {
+        let mut fg = rgb(cell.fg, DEFAULT_FG);
+        let mut bg = rgb(cell.bg, DEFAULT_BG);
+        if cell.modifier.contains(Modifier::REVERSED) {
+            std::mem::swap(&mut fg, &mut bg);
+        }
+        if cell.modifier.contains(Modifier::DIM) {
+            fg = fg.map(|c| c / 2);
+        }
+        self.fill(x, y, CELL_WIDTH, CELL_HEIGHT, bg);
+
+        let Some(ch) = cell.symbol().chars().next() else { return };
+        if ch == ' ' {
+            return;
+        }
+        if let Some((left, top, right, bottom, alpha)) = block_element(ch) {
+            let (x0, x1) = (x + CELL_WIDTH * left / 8, x + CELL_WIDTH * right / 8);
+            let (y0, y1) = (y + CELL_HEIGHT * top / 8, y + CELL_HEIGHT * bottom / 8);
+            for py in y0..y1 {
+                for px in x0..x1 {
+                    self.blend(px, py, fg, alpha);
+                }
+            }
+            return;
+        }
+
+        // Glyphs sit on a common baseline; anything outside the cell is clipped
+        let (metrics, coverage) = font.rasterize(ch, FONT_PX);
+        let baseline = y as i32 + ((CELL_HEIGHT as f32 - FONT_PX) / 2.0 + ascent) as i32;
+        let left = x as i32 + metrics.xmin;
+        let top = baseline - metrics.ymin - metrics.height as i32;
+        for gy in 0..metrics.height {
+            for gx in 0..metrics.width {
+                let (px, py) = (left + gx as i32, top + gy as i32);
+                let inside = (x as i32..(x + CELL_WIDTH) as i32).contains(&px)
+                    && (y as i32..(y + CELL_HEIGHT) as i32).contains(&py);
+                let alpha = coverage[gy * metrics.width + gx];
+                if inside && alpha > 0 {
+                    self.blend(px as usize, py as usize, fg, alpha);
+                }
+            }
+        }
+    }
+}
+
+/// Block elements as (left, top, right, bottom) in eighths of the cell plus opacity
+fn block_element(ch: char) -> Option<(usize, usize, usize, usize, u8)> {
+    Some(match ch {
+        '█' => (0, 0, 8, 8, 255),
+        '▀' => (0, 0, 8, 4, 255),
+        '▔' => (0, 0, 8, 1, 255),
+        '▌' => (0, 0, 4, 8, 255),
+        '▐' => (4, 0, 8, 8, 255),
+        '▕' => (7, 0, 8, 8, 255),
+        '░' => (0, 0, 8, 8, 64),
+        '▒' => (0, 0, 8, 8, 128),
+        '▓' => (0, 0, 8, 8, 192),
+        // Lower eighths ▁..▇, left eighths ▉..▏
+        '▁'..='▇' => (0, 8 - (ch as usize - '▁' as usize + 1), 8, 8, 255),
+        '▉'..='▏' => (0, 0, 8 - (ch as usize - '▉' as usize + 1), 8, 255),
+        _ => return None,
+    })
+}
+
+fn rgb(color: Color, reset: [u8; 3]) -> [u8; 3] {
+    match color {
+        Color::Reset => reset,
+        Color::Rgb(r, g, b) => [r, g, b],
+        Color::Indexed(i) => indexed(i),
+        Color::Black => indexed(0),
+        Color::Red => indexed(1),
+        Color::Green => indexed(2),
+        Color::Yellow => indexed(3),
+        Color::Blue => indexed(4),
+        Color::Magenta => indexed(5),
+        Color::Cyan => indexed(6),
+        Color::Gray => indexed(7),
+        Color::DarkGray => indexed(8),
+        Color::LightRed => indexed(9),
+        Color::LightGreen => indexed(10),
+        Color::LightYellow => indexed(11),
+        Color::LightBlue => indexed(12),
+        Color::LightMagenta => indexed(13),
+        Color::LightCyan => indexed(14),
+        Color::White => indexed(15),
+    }
+}
+
+/// The xterm 256-color palette
+fn indexed(i: u8) -> [u8; 3] {
+    const BASIC: [[u8; 3]; 16] = [
+        [0, 0, 0], [205, 0, 0], [0, 205, 0], [205, 205, 0],
+        [0, 0, 238], [205, 0, 205], [0, 205, 205], [229, 229, 229],
+        [127, 127, 127], [255, 0, 0], [0, 255, 0], [255, 255, 0],
+        [92, 92, 255], [255, 0, 255], [0, 255, 255], [255, 255, 255],
+    ];
+    match i {
+        0..=15 => BASIC[i as usize],
+        16..=231 => {
+            let level = |v: u8| if v == 0 { 0 } else { 55 + v * 40 };
+            let i = i - 16;
+            [level(i / 36), level(i / 6 % 6), level(i % 6)]
+        }
+        _ => {
+            let gray = 8 + (i - 232) * 10;
+            [gray, gray, gray]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_screenshot_renders_the_timer_screen() {
+        let buffer = capture(ThemeType::Minimal, (80, 24), 5).unwrap();
+        let text = to_text(&buffer);
+        assert_eq!(text.lines().count(), 24);
+        assert!(text.contains("Work"));
+
+        let path = std::env::temp_dir().join("pomowise-screenshot-test.png");
+        write_png(&buffer, &path).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        let _ = std::fs::remove_file(&path);
+        assert_eq!(&bytes[1..4], b"PNG");
+
+        assert_eq!(parse_size("120x40"), Some((120, 40)));
+        assert_eq!(parse_size("0x40"), None);
+        assert_eq!(indexed(196), [255, 0, 0]);
+    }
+}
@@ -0,0 +1,240 @@
+//! User scripts (the `script` key in config.json), run by an embedded Rhai engine
+//! A script defines any of these hooks, all optional:
+//!
+//! ```rhai
+//! fn on_session_start(kind) { }          // "work", "short_break", "long_break"
+//! fn on_tick(kind, remaining_secs) { }   // once a second while a session runs
+//! fn on_session_end(kind, outcome) { }   // "completed", "skipped", "abandoned", "adjusted"
+//! ```
+//!
+//! and calls `notify(text)`, `set_theme(name)`, `log(text)`, or `shell(command)`
+//! when `allow_shell` is on. Calls are queued as `ScriptAction`s for the app to
+//! apply once the hook returns: scripts get no file or network access of their
+//! own, and a hook that runs too long is stopped instead of hanging the timer.
+
+use std::cell::RefCell;
+use std::io::{self, Write};
+use std::rc::Rc;
+
+use chrono::Local;
+use rhai::module_resolvers::DummyModuleResolver;
+use rhai::{Dynamic, Engine, EvalAltResult, FuncArgs, Scope, AST};
+use serde::{Deserialize, Serialize};
+
+use crate::events::{AppEvent, Event, Subscriber};
+use crate::history::{SessionKind, SessionOutcome};
+use crate::paths;
+use crate::sync::expand_home;
+
+/// Operations one hook may run before it is stopped
+const MAX_OPERATIONS: u64 = 200_000;
+
+/// Where the script is and what it may do
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScriptConfig {
+    /// Rhai file with the hooks; `~/` is the home directory
+    pub path: String,
+    /// Let the script run shell commands with `shell(...)`
+    pub allow_shell: bool,
+}
+
+/// Something a hook asked the app to do
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScriptAction {
+    /// Toast, and a desktop notification unless silent
+    Notify(String),
+    /// Theme by name, as `:theme` takes it
+    SetTheme(String),
+    /// Shell command, only queued with `allow_shell`
+    Shell(String),
+    /// A hook failed (or called what it isn't allowed to)
+    Error(String),
+}
+
+/// A loaded script, its engine and the actions its hooks queued
+pub struct Script {
+    engine: Engine,
+    ast: AST,
+    scope: Scope<'static>,
+    actions: Rc<RefCell<Vec<ScriptAction>>>,
+    /// Seconds left at the last `on_tick`, so it runs once a second
+    last_tick: Option<u64>,
+}
+
+impl Script {
+    /// Read and compile the configured script file
+    pub fn load(config: &ScriptConfig) -> Result<Self, String> {
+        let path = expand_home(&config.path);
+        let source = std::fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        Self::compile(&source, config.allow_shell)
+    }
+
+    /// Compile a script and run its top level once
+    pub fn compile(source: &str, allow_shell: bool) -> Result<Self, String> {
+        let actions: Rc<RefCell<Vec<ScriptAction>>> = Rc::default();
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+        // `import` would read files; print and debug would write over the screen
+        engine.set_module_resolver(DummyModuleResolver);
+        engine.on_print(|text| {
+            let _ = append_log(text);
+        });
+        engine.on_debug(|text, _, position| {
+            let _ = append_log(&format!("debug @ {}: {}", position, text));
+        });
+
+        let queue = Rc::clone(&actions);
+        engine.register_fn("notify", move |text: &str| {
+            queue.borrow_mut().push(ScriptAction::Notify(text.to_string()));
+        });
+        let queue = Rc::clone(&actions);
+        engine.register_fn("set_theme", move |name: &str| {
+            queue.borrow_mut().push(ScriptAction::SetTheme(name.to_string()));
+        });
+        engine.register_fn("log", |text: &str| {
+            let _ = append_log(text);
+        });
+        let queue = Rc::clone(&actions);
+        engine.register_fn("shell", move |command: &str| -> Result<(), Box<EvalAltResult>> {
+            if !allow_shell {
+                return Err("shell() needs \"allow_shell\": true in the script config".into());
+            }
+            queue.borrow_mut().push(ScriptAction::Shell(command.to_string()));
+            Ok(())
+        });
+
+        let ast = engine.compile(source).map_err(|e| format!("script: {}", e))?;
+        let mut scope = Scope::new();
+        engine.run_ast_with_scope(&mut scope, &ast).map_err(|e| format!("script: {}", e))?;
+        Ok(Self {
+            engine,
+            ast,
+            scope,
+            actions,
+            last_tick: None,
+        })
+    }
+
+    /// Call `on_tick` when the countdown reaches a new second (`kind` is None
+    /// while no session runs)
+    pub fn tick(&mut self, kind: Option<SessionKind>, remaining_secs: u64) {
+        let Some(kind) = kind else {
+            self.last_tick = None;
+            return;
+        };
+        if self.last_tick == Some(remaining_secs) {
+            return;
+        }
+        self.last_tick = Some(remaining_secs);
+        self.call("on_tick", (kind_name(kind), remaining_secs as i64));
+    }
+
+    /// Actions queued by the hooks since the last call, oldest first
+    pub fn take_actions(&mut self) -> Vec<ScriptAction> {
+        std::mem::take(&mut self.actions.borrow_mut())
+    }
+
+    /// Run a hook if the script defines it with that many parameters
+    fn call(&mut self, hook: &str, args: impl FuncArgs) {
+        let mut values = Vec::new();
+        args.parse(&mut values);
+        let defined = self.ast.iter_functions().any(|f| f.name == hook && f.params.len() == values.len());
+        if !defined {
+            return;
+        }
+        let result = self.engine.call_fn::<Dynamic>(&mut self.scope, &self.ast, hook, values);
+        if let Err(e) = result {
+            self.actions.borrow_mut().push(ScriptAction::Error(format!("{}: {}", hook, e)));
+        }
+    }
+}
+
+impl Subscriber for Script {
+    fn on_event(&mut self, event: &Event) {
+        match event.event {
+            AppEvent::Started(kind) => self.call("on_session_start", (kind_name(kind),)),
+            AppEvent::Ended(kind, outcome) => {
+                self.call("on_session_end", (kind_name(kind), outcome_name(outcome)));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn kind_name(kind: SessionKind) -> &'static str {
+    match kind {
+        SessionKind::Work => "work",
+        SessionKind::ShortBreak => "short_break",
+        SessionKind::LongBreak => "long_break",
+    }
+}
+
+fn outcome_name(outcome: SessionOutcome) -> &'static str {
+    match outcome {
+        SessionOutcome::Completed => "completed",
+        SessionOutcome::Skipped => "skipped",
+        SessionOutcome::Abandoned => "abandoned",
+        SessionOutcome::Adjusted => "adjusted",
+    }
+}
+
+/// Add a timestamped line to `script.log` in the data directory
+fn append_log(text: &str) -> io::Result<()> {
+    let dir = paths::data_dir();
+    std::fs::create_dir_all(&dir)?;
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(dir.join("script.log"))?;
+    writeln!(file, "{} {}", Local::now().format("%Y-%m-%d %H:%M:%S"), text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deliver(script: &mut Script, event: AppEvent) {
+        script.on_event(&Event { at: Local::now(), event });
+    }
+
+    #[test]
+    fn test_hooks_queue_actions() {
+        let source = r#"
+            fn on_tick(kind, left) {
+                if kind == "work" && left == 300 { set_theme("fire"); }
+            }
+            fn on_session_end(kind, outcome) {
+                notify(kind + " " + outcome);
+                shell("echo done");
+            }
+        "#;
+        let mut script = Script::compile(source, false).unwrap();
+        script.tick(Some(SessionKind::Work), 301);
+        script.tick(Some(SessionKind::Work), 300);
+        script.tick(Some(SessionKind::Work), 300);
+        assert_eq!(script.take_actions(), vec![ScriptAction::SetTheme("fire".to_string())]);
+
+        // No start hook: nothing happens
+        deliver(&mut script, AppEvent::Started(SessionKind::Work));
+        deliver(&mut script, AppEvent::Ended(SessionKind::Work, SessionOutcome::Completed));
+        let actions = script.take_actions();
+        assert_eq!(actions[0], ScriptAction::Notify("work completed".to_string()));
+        assert!(matches!(&actions[1], ScriptAction::Error(e) if e.contains("allow_shell")));
+    }
+
+    #[test]
+    fn test_runaway_hooks_are_stopped() {
+        assert!(Script::compile("fn on_session_start(kind) {", false).is_err());
+        let mut script = Script::compile("fn on_session_start(kind) { loop { } }", true).unwrap();
+        deliver(&mut script, AppEvent::Started(SessionKind::Work));
+        assert!(matches!(script.take_actions().as_slice(), [ScriptAction::Error(_)]));
+    }
+
+    #[test]
+    fn test_scripts_cannot_import_files() {
+        let module = std::env::temp_dir().join(format!("pomowise-script-module-{}.rhai", std::process::id()));
+        std::fs::write(&module, "fn hello() { 1 }").unwrap();
+        let source = format!("import {:?} as m;", module.with_extension("").display().to_string());
+        let result = Script::compile(&source, false);
+        let _ = std::fs::remove_file(&module);
+        assert!(result.is_err_and(|e| e.contains("not found")));
+    }
+}
@@ -0,0 +1,253 @@
+//! `pomowise install-service`: start the tray at login
+//! The always-on part of pomowise is `pomowise-tray`: it follows the running
+//! timer through the status file and opens the TUI when clicked. This registers
+//! it as a systemd user unit on Linux, a launchd agent on macOS or a logon task
+//! in the Windows Task Scheduler; `--uninstall` takes it out again and `--print`
+//! shows what would be installed without touching anything. A `--config-dir`
+//! (or `POMOWISE_HOME`) in effect is passed on to the service.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::paths;
+
+const USAGE: &str = "usage: pomowise install-service [--uninstall] [--print]";
+
+/// Name of the unit, agent label or task
+#[cfg(not(target_os = "macos"))]
+const SERVICE_NAME: &str = "pomowise-tray";
+#[cfg(target_os = "macos")]
+const SERVICE_NAME: &str = "dev.pomowise.tray";
+
+/// What installing (or removing) the service takes on this platform
+struct Plan {
+    /// File to write, with its contents; removed again on uninstall
+    file: Option<(PathBuf, String)>,
+    /// Commands run after writing (or before removing) the file
+    commands: Vec<Vec<String>>,
+}
+
+/// Run the command; `args` are what follows `install-service` on the command line
+pub fn run(args: &[String]) -> io::Result<()> {
+    let (mut uninstall, mut print) = (false, false);
+    for arg in args {
+        match arg.as_str() {
+            "--uninstall" => uninstall = true,
+            "--print" => print = true,
+            other => return Err(io::Error::other(format!("unknown option {}\n{}", other, USAGE))),
+        }
+    }
+
+    let home = std::env::var(paths::HOME_VAR).ok().filter(|dir| !dir.is_empty());
+    let plan = if uninstall { uninstall_plan()? } else { install_plan(&tray_binary()?, home.as_deref())? };
+
+    if print {
+        if let Some((path, contents)) = &plan.file {
+            println!("# {}\n{}", path.display(), contents);
+        }
+        for command in &plan.commands {
+            println!("{}", command.join(" "));
+        }
+        return Ok(());
+    }
+
+    if uninstall {
+        run_commands(&plan.commands);
+        if let Some((path, _)) = &plan.file {
+            if path.exists() {
+                std::fs::remove_file(path)?;
+            }
+        }
+        println!("Removed {}; the tray no longer starts at login", SERVICE_NAME);
+    } else {
+        if let Some((path, contents)) = &plan.file {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(path, contents)?;
+            println!("Wrote {}", path.display());
+        }
+        run_commands(&plan.commands);
+        println!("{} now starts at login", SERVICE_NAME);
+    }
+    Ok(())
+}
+
+/// `pomowise-tray` next to this binary, where both are installed
+fn tray_binary() -> io::Result<PathBuf> {
+    let name = format!("pomowise-tray{}", std::env::consts::EXE_SUFFIX);
+    let tray = std::env::current_exe()?.with_file_name(name);
+    if tray.is_file() {
+        Ok(tray)
+    } else {
+        Err(io::Error::other(format!("{} not found; install the tray binary next to pomowise", tray.display())))
+    }
+}
+
+/// Run each command, reporting failures without stopping: a service manager
+/// that isn't running shouldn't keep the file from being written or removed
+fn run_commands(commands: &[Vec<String>]) {
+    for command in commands {
+        let Some((program, args)) = command.split_first() else { continue };
+        match Command::new(program).args(args).status() {
+            Ok(status) if status.success() => {}
+            Ok(status) => eprintln!("{} exited with {}", command.join(" "), status),
+            Err(e) => eprintln!("couldn't run {}: {}", program, e),
+        }
+    }
+}
+
+#[cfg(not(windows))]
+fn user_home() -> io::Result<PathBuf> {
+    std::env::var_os("HOME")
+        .or_else(|| std::env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+        .ok_or_else(|| io::Error::other("no home directory"))
+}
+
+fn args(list: &[&str]) -> Vec<String> {
+    list.iter().map(|a| a.to_string()).collect()
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn unit_path() -> io::Result<PathBuf> {
+    let config = match std::env::var_os("XDG_CONFIG_HOME").map(PathBuf::from) {
+        Some(dir) if dir.is_absolute() => dir,
+        _ => user_home()?.join(".config"),
+    };
+    Ok(config.join("systemd").join("user").join(format!("{}.service", SERVICE_NAME)))
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn systemd_unit(tray: &Path, home: Option<&str>) -> String {
+    let environment = home.map(|dir| format!("Environment=\"{}={}\"\n", paths::HOME_VAR, dir)).unwrap_or_default();
+    format!(
+        "[Unit]\n\
+         Description=pomowise tray timer\n\
+         PartOf=graphical-session.target\n\
+         After=graphical-session.target\n\
+         \n\
+         [Service]\n\
+         ExecStart=\"{}\"\n\
+         {}Restart=on-failure\n\
+         \n\
+         [Install]\n\
+         WantedBy=graphical-session.target\n",
+        tray.display(),
+        environment
+    )
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn install_plan(tray: &Path, home: Option<&str>) -> io::Result<Plan> {
+    let unit = format!("{}.service", SERVICE_NAME);
+    Ok(Plan {
+        file: Some((unit_path()?, systemd_unit(tray, home))),
+        commands: vec![
+            args(&["systemctl", "--user", "daemon-reload"]),
+            args(&["systemctl", "--user", "enable", "--now", &unit]),
+        ],
+    })
+}
+
+#[cfg(not(any(target_os = "macos", windows)))]
+fn uninstall_plan() -> io::Result<Plan> {
+    let unit = format!("{}.service", SERVICE_NAME);
+    Ok(Plan {
+        file: Some((unit_path()?, String::new())),
+        commands: vec![args(&["systemctl", "--user", "disable", "--now", &unit])],
+    })
+}
+
+#[cfg(target_os = "macos")]
+fn plist_path() -> io::Result<PathBuf> {
+    Ok(user_home()?.join("Library").join("LaunchAgents").join(format!("{}.plist", SERVICE_NAME)))
+}
+
+#[cfg(target_os = "macos")]
+fn launchd_plist(tray: &Path, home: Option<&str>) -> String {
+    let environment = home
+        .map(|dir| {
+            format!(
+                "    <key>EnvironmentVariables</key>\n    <dict>\n        \
+                 <key>{}</key>\n        <string>{}</string>\n    </dict>\n",
+                paths::HOME_VAR,
+                dir
+            )
+        })
+        .unwrap_or_default();
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n\
+         <plist version=\"1.0\">\n\
+         <dict>\n\
+         \x20   <key>Label</key>\n\
+         \x20   <string>{}</string>\n\
+         \x20   <key>ProgramArguments</key>\n\
+         \x20   <array>\n\
+         \x20       <string>{}</string>\n\
+         \x20   </array>\n\
+         {}\
+         \x20   <key>RunAtLoad</key>\n\
+         \x20   <true/>\n\
+         </dict>\n\
+         </plist>\n",
+        SERVICE_NAME,
+        tray.display(),
+        environment
+    )
+}
+
+#[cfg(target_os = "macos")]
+fn install_plan(tray: &Path, home: Option<&str>) -> io::Result<Plan> {
+    let path = plist_path()?;
+    let load = args(&["launchctl", "load", "-w", &path.display().to_string()]);
+    Ok(Plan {
+        file: Some((path, launchd_plist(tray, home))),
+        commands: vec![load],
+    })
+}
+
+#[cfg(target_os = "macos")]
+fn uninstall_plan() -> io::Result<Plan> {
+    let path = plist_path()?;
+    let unload = args(&["launchctl", "unload", "-w", &path.display().to_string()]);
+    Ok(Plan {
+        file: Some((path, String::new())),
+        commands: vec![unload],
+    })
+}
+
+#[cfg(windows)]
+fn install_plan(tray: &Path, home: Option<&str>) -> io::Result<Plan> {
+    // Task Scheduler has no per-task environment, so set it in a wrapping shell
+    let action = match home {
+        Some(dir) => format!("cmd /c set \"{}={}\" && \"{}\"", paths::HOME_VAR, dir, tray.display()),
+        None => format!("\"{}\"", tray.display()),
+    };
+    Ok(Plan {
+        file: None,
+        commands: vec![args(&["schtasks", "/Create", "/TN", SERVICE_NAME, "/TR", &action, "/SC", "ONLOGON", "/F"])],
+    })
+}
+
+#[cfg(windows)]
+fn uninstall_plan() -> io::Result<Plan> {
+    Ok(Plan {
+        file: None,
+        commands: vec![args(&["schtasks", "/Delete", "/TN", SERVICE_NAME, "/F"])],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(not(any(target_os = "macos", windows)))]
+    #[test]
+    fn test_systemd_unit() {
+        let unit = super::systemd_unit(std::path::Path::new("/usr/bin/pomowise-tray"), Some("/media/usb/pomowise"));
+        assert!(unit.contains("ExecStart=\"/usr/bin/pomowise-tray\"\n"));
+        assert!(unit.contains("Environment=\"POMOWISE_HOME=/media/usb/pomowise\"\n"));
+        assert!(unit.ends_with("WantedBy=graphical-session.target\n"));
+    }
+}
@@ -0,0 +1,176 @@
+//! Spoken announcements (`speech` in config.json): phrases like "Break time" or
+//! "Five minutes remaining" piped to a text-to-speech command at key moments,
+//! to keep track of the session without looking at the screen. The phrase goes
+//! to the command's standard input, and is in `POMOWISE_PHRASE` for commands
+//! that want it as an argument (`say "$POMOWISE_PHRASE"`).
+
+use std::collections::BTreeMap;
+use std::io::Write;
+use std::process::Stdio;
+
+use serde::{Deserialize, Serialize};
+
+use crate::events::{AppEvent, Event, Subscriber};
+use crate::history::{SessionKind, SessionOutcome};
+use crate::notification::shell_command;
+
+/// Moments that can be announced
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Announcement {
+    WorkStart,
+    WorkComplete,
+    BreakComplete,
+    FiveMinutesLeft,
+    OneMinuteLeft,
+}
+
+impl Announcement {
+    /// Seconds left in a session when a countdown announcement is due
+    fn at_secs(self) -> Option<u64> {
+        match self {
+            Announcement::FiveMinutesLeft => Some(300),
+            Announcement::OneMinuteLeft => Some(60),
+            _ => None,
+        }
+    }
+}
+
+/// Text-to-speech settings (the `speech` key in config.json)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpeechConfig {
+    pub enabled: bool,
+    /// Shell command that speaks its standard input; unset uses `say` on macOS,
+    /// `espeak` elsewhere and the built-in synthesizer on Windows
+    pub command: Option<String>,
+    /// What to say for each moment; moments left out (or given "") stay quiet
+    pub phrases: BTreeMap<Announcement, String>,
+}
+
+impl Default for SpeechConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            command: None,
+            phrases: BTreeMap::from([
+                (Announcement::WorkComplete, "Break time".to_string()),
+                (Announcement::BreakComplete, "Back to work".to_string()),
+                (Announcement::FiveMinutesLeft, "Five minutes remaining".to_string()),
+                (Announcement::OneMinuteLeft, "One minute remaining".to_string()),
+            ]),
+        }
+    }
+}
+
+/// Speaks the configured phrases as their moments come
+pub struct Speaker {
+    config: SpeechConfig,
+    /// The session and its seconds left at the last countdown check
+    last: Option<(SessionKind, u64)>,
+    /// Silent mode: nothing is said
+    pub silent: bool,
+}
+
+impl Speaker {
+    pub fn new(config: &SpeechConfig) -> Self {
+        Self {
+            config: config.clone(),
+            last: None,
+            silent: false,
+        }
+    }
+
+    /// Follow the countdown of the running session (None when there is none),
+    /// announcing the thresholds it crosses
+    pub fn countdown(&mut self, running: Option<(SessionKind, u64)>) {
+        let last = std::mem::replace(&mut self.last, running);
+        let (Some((kind, secs)), Some((last_kind, last_secs))) = (running, last) else {
+            return;
+        };
+        if kind != last_kind {
+            return;
+        }
+        if let Some(due) = due_between(last_secs, secs) {
+            self.say(due);
+        }
+    }
+
+    fn say(&self, announcement: Announcement) {
+        if !self.config.enabled || self.silent {
+            return;
+        }
+        let Some(phrase) = self.config.phrases.get(&announcement).filter(|p| !p.is_empty()) else {
+            return;
+        };
+        let mut cmd = match &self.config.command {
+            Some(command) => shell_command(command),
+            None => shell_command(default_command()),
+        };
+        let child = cmd
+            .env("POMOWISE_PHRASE", phrase)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        let Ok(mut child) = child else { return };
+        // Written and waited on off the UI thread; a slow voice mustn't hold up frames
+        let phrase = phrase.clone();
+        std::thread::spawn(move || {
+            if let Some(mut stdin) = child.stdin.take() {
+                let _ = writeln!(stdin, "{}", phrase);
+            }
+            let _ = child.wait();
+        });
+    }
+}
+
+/// Completions and work starts come from the event bus
+impl Subscriber for Speaker {
+    fn on_event(&mut self, event: &Event) {
+        match event.event {
+            AppEvent::Started(SessionKind::Work) => self.say(Announcement::WorkStart),
+            AppEvent::Ended(SessionKind::Work, SessionOutcome::Completed) => self.say(Announcement::WorkComplete),
+            AppEvent::Ended(_, SessionOutcome::Completed) => self.say(Announcement::BreakComplete),
+            _ => {}
+        }
+    }
+}
+
+/// The countdown announcement whose threshold lies between two readings of the
+/// seconds left (crossed going down, so starting a session under it says nothing)
+fn due_between(before: u64, now: u64) -> Option<Announcement> {
+    [Announcement::OneMinuteLeft, Announcement::FiveMinutesLeft]
+        .into_iter()
+        .find(|a| a.at_secs().is_some_and(|at| before > at && now <= at))
+}
+
+fn default_command() -> &'static str {
+    if cfg!(target_os = "macos") {
+        "say"
+    } else if cfg!(target_os = "windows") {
+        "powershell -c \"Add-Type -AssemblyName System.Speech; \
+         (New-Object System.Speech.Synthesis.SpeechSynthesizer).Speak([Console]::In.ReadToEnd())\""
+    } else {
+        "espeak"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_countdown_thresholds() {
+        assert_eq!(due_between(301, 300), Some(Announcement::FiveMinutesLeft));
+        assert_eq!(due_between(300, 299), None);
+        assert_eq!(due_between(61, 59), Some(Announcement::OneMinuteLeft));
+        // A jump over both (an edit to the length) says the closer one
+        assert_eq!(due_between(400, 30), Some(Announcement::OneMinuteLeft));
+        assert_eq!(due_between(50, 400), None);
+
+        let config: SpeechConfig = serde_json::from_str(r#"{"phrases": {"one_minute_left": "Wrap up"}}"#).unwrap();
+        assert_eq!(config.phrases.get(&Announcement::OneMinuteLeft).map(String::as_str), Some("Wrap up"));
+        assert!(!config.phrases.contains_key(&Announcement::WorkComplete));
+    }
+}
@@ -0,0 +1,285 @@
+//! Where a plain (unencrypted) history is kept
+//! Every backend implements `HistoryStore`: JSON lines in history.jsonl (the
+//! default) or a SQLite database through the `sqlite3` command line tool. A
+//! remote store only has to implement the same four calls. `pomowise
+//! migrate-history --to sqlite` copies the history across and switches the
+//! config over; the encrypted history (history.enc) is unaffected by the choice.
+
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+use crate::history::{self, SessionRecord};
+use crate::paths;
+use crate::sync::expand_home;
+
+const USAGE: &str = "usage: pomowise migrate-history --to <json|sqlite> [--from <json|sqlite>]";
+
+/// Table of the SQLite store; the full record is kept as JSON so new fields need no migration
+const SQLITE_SCHEMA: &str = "CREATE TABLE IF NOT EXISTS sessions (\
+    ended_at INTEGER NOT NULL, kind TEXT NOT NULL, outcome TEXT NOT NULL, \
+    duration_secs INTEGER NOT NULL, record TEXT NOT NULL);\n\
+    CREATE INDEX IF NOT EXISTS sessions_ended_at ON sessions (ended_at);\n";
+
+/// Storage for the session history
+pub trait HistoryStore {
+    /// Add one record at the end
+    fn append(&self, record: &SessionRecord) -> io::Result<()>;
+    /// Every record, oldest first (empty when nothing was stored yet)
+    fn load(&self) -> io::Result<Vec<SessionRecord>>;
+    /// Replace the whole history (sync merges, migration)
+    fn replace(&self, records: &[SessionRecord]) -> io::Result<()>;
+    /// Delete the stored history once it has moved elsewhere (into history.enc)
+    fn remove(&self) -> io::Result<()>;
+}
+
+/// Store settings (the `history_store` key in config.json)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "backend", rename_all = "lowercase")]
+pub enum StoreConfig {
+    /// history.jsonl in the data directory
+    #[default]
+    Json,
+    /// A SQLite database (uses sqlite3); history.db in the data directory unless `path` is set
+    Sqlite {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        path: Option<String>,
+    },
+}
+
+impl StoreConfig {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "json" => Some(StoreConfig::Json),
+            "sqlite" => Some(StoreConfig::Sqlite { path: None }),
+            _ => None,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            StoreConfig::Json => "json",
+            StoreConfig::Sqlite { .. } => "sqlite",
+        }
+    }
+
+    pub fn store(&self) -> Box<dyn HistoryStore> {
+        match self {
+            StoreConfig::Json => Box::new(JsonStore { path: history::history_path() }),
+            StoreConfig::Sqlite { path } => Box::new(SqliteStore {
+                path: path
+                    .as_deref()
+                    .map_or_else(|| paths::data_dir().join("history.db"), expand_home),
+            }),
+        }
+    }
+}
+
+/// One JSON record per line
+struct JsonStore {
+    path: PathBuf,
+}
+
+impl HistoryStore for JsonStore {
+    fn append(&self, record: &SessionRecord) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        let json = serde_json::to_string(record)?;
+        writeln!(file, "{}", json)
+    }
+
+    fn load(&self) -> io::Result<Vec<SessionRecord>> {
+        match std::fs::read_to_string(&self.path) {
+            Ok(contents) => Ok(history::parse(&contents)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn replace(&self, records: &[SessionRecord]) -> io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        // Write beside the file and rename so a crash never leaves half a history
+        let tmp = self.path.with_extension("jsonl.tmp");
+        std::fs::write(&tmp, history::to_jsonl(records))?;
+        std::fs::rename(&tmp, &self.path)
+    }
+
+    fn remove(&self) -> io::Result<()> {
+        match std::fs::remove_file(&self.path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// A `sessions` table, one row per record
+struct SqliteStore {
+    path: PathBuf,
+}
+
+impl SqliteStore {
+    /// Run a script (after the schema) and return what it printed
+    fn run(&self, script: &str) -> io::Result<String> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut child = Command::new("sqlite3")
+            .args(["-batch", "-bail"])
+            .arg(&self.path)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| match e.kind() {
+                io::ErrorKind::NotFound => io::Error::new(e.kind(), "sqlite3 not found; install it or use the json store"),
+                _ => e,
+            })?;
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(SQLITE_SCHEMA.as_bytes())?;
+            stdin.write_all(script.as_bytes())?;
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(io::Error::other(format!("sqlite3: {}", error.trim())));
+        }
+        String::from_utf8(output.stdout).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+impl HistoryStore for SqliteStore {
+    fn append(&self, record: &SessionRecord) -> io::Result<()> {
+        self.run(&insert_statement(record)?).map(drop)
+    }
+
+    fn load(&self) -> io::Result<Vec<SessionRecord>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+        let rows = self.run("SELECT record FROM sessions ORDER BY ended_at, rowid;\n")?;
+        Ok(history::parse(&rows))
+    }
+
+    fn replace(&self, records: &[SessionRecord]) -> io::Result<()> {
+        let mut script = String::from("BEGIN;\nDELETE FROM sessions;\n");
+        for record in records {
+            script += &insert_statement(record)?;
+        }
+        script += "COMMIT;\n";
+        self.run(&script).map(drop)
+    }
+
+    fn remove(&self) -> io::Result<()> {
+        match std::fs::remove_file(&self.path) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn insert_statement(record: &SessionRecord) -> io::Result<String> {
+    let text = |value: serde_json::Value| value.as_str().map(quote).unwrap_or_default();
+    Ok(format!(
+        "INSERT INTO sessions VALUES ({}, {}, {}, {}, {});\n",
+        record.ended_at,
+        text(serde_json::to_value(record.kind)?),
+        text(serde_json::to_value(record.outcome)?),
+        record.duration_secs,
+        quote(&serde_json::to_string(record)?),
+    ))
+}
+
+/// SQL string literal
+fn quote(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+/// Run the command; `args` are what follows `migrate-history` on the command line
+pub fn run(args: &[String]) -> io::Result<()> {
+    let mut config = Config::load();
+    let (from, to) = parse_args(args, &config.history_store).map_err(|e| io::Error::other(format!("{}\n{}", e, USAGE)))?;
+    if from.name() == to.name() {
+        return Err(io::Error::other(format!("both sides are the {} store\n{}", to.name(), USAGE)));
+    }
+    if config.privacy.encrypt_history {
+        return Err(io::Error::other("the history is encrypted (history.enc); turn encrypt_history off to migrate it"));
+    }
+
+    let records = from.store().load()?;
+    let target = to.store();
+    let merged = history::merge(&target.load()?, &records);
+    target.replace(&merged)?;
+    println!("Copied {} sessions from the {} store to {} ({} there now)", records.len(), from.name(), to.name(), merged.len());
+
+    if config.history_store != to {
+        config.history_store = to.clone();
+        config.save()?;
+        println!("config.json now uses the {} store; the {} copy is left as it was", to.name(), from.name());
+    }
+    Ok(())
+}
+
+/// `--from` defaults to the store in use
+fn parse_args(args: &[String], current: &StoreConfig) -> Result<(StoreConfig, StoreConfig), String> {
+    let mut from = None;
+    let mut to = None;
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        let slot = match arg.as_str() {
+            "--from" => &mut from,
+            "--to" => &mut to,
+            other => return Err(format!("unknown option {}", other)),
+        };
+        let name = args.next().ok_or_else(|| format!("{} needs a store", arg))?;
+        let store = StoreConfig::from_name(name).ok_or_else(|| format!("unknown store {}", name))?;
+        // Keep a configured database path when migrating to or from the store in use
+        *slot = Some(if store.name() == current.name() { current.clone() } else { store });
+    }
+    let to = to.ok_or("missing --to")?;
+    Ok((from.unwrap_or_else(|| current.clone()), to))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::{SessionKind, SessionOutcome};
+    use std::time::Duration;
+
+    #[test]
+    fn test_stores_round_trip() {
+        let dir = std::env::temp_dir().join(format!("pomowise-store-{}", std::process::id()));
+        let mut record = SessionRecord::new(SessionKind::Work, SessionOutcome::Completed, Duration::from_secs(1500));
+        record.intention = Some("fix Bob's parser".to_string());
+        let mut stores: Vec<Box<dyn HistoryStore>> = vec![Box::new(JsonStore { path: dir.join("history.jsonl") })];
+        // The SQLite store needs the sqlite3 tool
+        if Command::new("sqlite3").arg("-version").output().is_ok() {
+            stores.push(Box::new(SqliteStore { path: dir.join("history.db") }));
+        }
+
+        for store in stores {
+            assert_eq!(store.load().unwrap(), vec![]);
+            store.append(&record).unwrap();
+            store.append(&record).unwrap();
+            assert_eq!(store.load().unwrap(), vec![record.clone(), record.clone()]);
+            store.replace(&[record.clone()]).unwrap();
+            assert_eq!(store.load().unwrap(), vec![record.clone()]);
+            store.remove().unwrap();
+            assert_eq!(store.load().unwrap(), vec![]);
+        }
+        let _ = std::fs::remove_dir_all(dir);
+
+        let current = StoreConfig::Sqlite { path: Some("~/pomo.db".to_string()) };
+        let args = ["--to".to_string(), "json".to_string()];
+        assert_eq!(parse_args(&args, &current), Ok((current.clone(), StoreConfig::Json)));
+    }
+}
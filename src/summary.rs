@@ -0,0 +1,209 @@
+//! Shareable day summary: a plain ASCII card with the day's focus time,
+//! pomodoros, best streak and most-used theme, for pasting into a team channel.
+//! `:summary` (or `s` on the stats screen) copies it to the clipboard with
+//! whichever of wl-copy / xclip / xsel / pbcopy / clip is installed, and saves
+//! it under the data directory as `summaries/<date>.txt`.
+
+use std::collections::{BTreeSet, HashMap};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::{Command, Stdio};
+
+use chrono::{DateTime, Local, NaiveDate};
+
+use crate::animation::themes::ThemeType;
+use crate::history::{SessionKind, SessionOutcome, SessionRecord};
+use crate::paths;
+
+/// Inner width of the card, between the borders
+const CARD_WIDTH: usize = 40;
+
+const TOMATO: [&str; 5] = ["    \\|/    ", " .-'```'-. ", "/         \\", "\\         /", " '-.___.-' "];
+
+/// Clipboard tools to try, in order
+const CLIPBOARD_TOOLS: &[&[&str]] = &[
+    &["wl-copy"],
+    &["xclip", "-selection", "clipboard"],
+    &["xsel", "--clipboard", "--input"],
+    &["pbcopy"],
+    &["clip"],
+];
+
+/// What the card shows for one day
+#[derive(Debug, Clone, PartialEq)]
+pub struct DaySummary {
+    pub day: NaiveDate,
+    pub focus_secs: u64,
+    pub pomodoros: usize,
+    /// Longest run of consecutive days with a completed pomodoro, up to this day
+    pub best_streak: usize,
+    /// Consecutive days with a completed pomodoro up to this day, or up to the day
+    /// before while this one has none yet
+    pub streak: usize,
+    /// Theme on screen for most of the day's work sessions
+    pub top_theme: Option<String>,
+}
+
+impl DaySummary {
+    pub fn from_records(records: &[SessionRecord], day: NaiveDate) -> Self {
+        let work: Vec<&SessionRecord> = records
+            .iter()
+            .filter(|r| r.kind == SessionKind::Work && local_date(r).is_some_and(|d| d <= day))
+            .collect();
+        let today: Vec<&SessionRecord> = work.iter().copied().filter(|r| local_date(r) == Some(day)).collect();
+
+        let mut themes: HashMap<&str, usize> = HashMap::new();
+        for theme in today.iter().filter_map(|r| r.theme.as_deref()) {
+            *themes.entry(theme).or_default() += 1;
+        }
+        // Ties go to the alphabetically first, so the card is the same every time
+        let top_theme = themes
+            .into_iter()
+            .max_by(|a, b| a.1.cmp(&b.1).then(b.0.cmp(a.0)))
+            .map(|(slug, _)| ThemeType::from_name(slug).map_or(slug.to_string(), |t| t.name().to_string()));
+
+        let days: BTreeSet<NaiveDate> = work
+            .iter()
+            .filter(|r| r.outcome == SessionOutcome::Completed)
+            .filter_map(|r| local_date(r))
+            .collect();
+
+        Self {
+            day,
+            focus_secs: today.iter().map(|r| r.duration_secs).sum(),
+            pomodoros: today.iter().filter(|r| r.outcome == SessionOutcome::Completed).count(),
+            best_streak: longest_run(&days),
+            streak: run_up_to(&days, day),
+            top_theme,
+        }
+    }
+
+    /// The card, one line per row, ASCII only so it survives any chat client
+    pub fn card(&self) -> String {
+        let border = format!("+{}+", "-".repeat(CARD_WIDTH));
+        let heading = ["POMOWISE", "Focus summary", &self.day.format("%a %d %b %Y").to_string(), "", ""];
+        let mut rows: Vec<String> = TOMATO
+            .iter()
+            .zip(heading)
+            .map(|(art, text)| format!("   {}   {}", art, text))
+            .collect();
+        rows.push(String::new());
+        let streak = match self.best_streak {
+            1 => "1 day".to_string(),
+            days => format!("{} days", days),
+        };
+        let stats = [
+            ("Focus time", format!("{}h {:02}m", self.focus_secs / 3600, self.focus_secs % 3600 / 60)),
+            ("Pomodoros", self.pomodoros.to_string()),
+            ("Best streak", streak),
+            ("Top theme", self.top_theme.clone().unwrap_or_else(|| "-".to_string())),
+        ];
+        for (label, value) in stats {
+            rows.push(format!("   {} {} {}", label, ".".repeat(18 - label.len()), value));
+        }
+
+        let mut card = vec![border.clone()];
+        card.extend(rows.iter().map(|row| {
+            let row: String = row.chars().take(CARD_WIDTH).collect();
+            format!("|{:<width$}|", row, width = CARD_WIDTH)
+        }));
+        card.push(border);
+        card.join("\n") + "\n"
+    }
+}
+
+/// Write the card to `summaries/<date>.txt` in the data directory
+pub fn save(summary: &DaySummary, card: &str) -> io::Result<PathBuf> {
+    let dir = paths::data_dir().join("summaries");
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{}.txt", summary.day.format("%Y-%m-%d")));
+    std::fs::write(&path, card)?;
+    Ok(path)
+}
+
+/// Hand the card to the first clipboard tool that runs
+pub fn copy_to_clipboard(text: &str) -> io::Result<()> {
+    for args in CLIPBOARD_TOOLS {
+        let spawned = Command::new(args[0])
+            .args(&args[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+        let Ok(mut child) = spawned else { continue };
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())?;
+        }
+        if child.wait()?.success() {
+            return Ok(());
+        }
+    }
+    Err(io::Error::new(io::ErrorKind::NotFound, "no clipboard tool (wl-copy, xclip, xsel, pbcopy or clip) found"))
+}
+
+fn local_date(record: &SessionRecord) -> Option<NaiveDate> {
+    DateTime::from_timestamp(record.ended_at as i64, 0).map(|t| t.with_timezone(&Local).date_naive())
+}
+
+/// Consecutive days in the set ending on `day`, or on the day before it
+fn run_up_to(days: &BTreeSet<NaiveDate>, day: NaiveDate) -> usize {
+    let mut current = if days.contains(&day) { Some(day) } else { day.pred_opt() };
+    let mut run = 0;
+    while let Some(d) = current.filter(|d| days.contains(d)) {
+        run += 1;
+        current = d.pred_opt();
+    }
+    run
+}
+
+/// Longest stretch of consecutive days in the set
+fn longest_run(days: &BTreeSet<NaiveDate>) -> usize {
+    let (mut best, mut run) = (0, 0);
+    let mut previous: Option<NaiveDate> = None;
+    for &day in days {
+        run = if previous.and_then(|p| p.succ_opt()) == Some(day) { run + 1 } else { 1 };
+        best = best.max(run);
+        previous = Some(day);
+    }
+    best
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn work(day: NaiveDate, theme: &str) -> SessionRecord {
+        let noon = Local.from_local_datetime(&day.and_hms_opt(12, 0, 0).unwrap()).unwrap();
+        let mut record = SessionRecord::new(SessionKind::Work, SessionOutcome::Completed, std::time::Duration::from_secs(1500));
+        record.ended_at = noon.timestamp() as u64;
+        record.theme = Some(theme.to_string());
+        record
+    }
+
+    #[test]
+    fn test_summary_card() {
+        let day = NaiveDate::from_ymd_opt(2025, 3, 12).unwrap();
+        let records = vec![
+            work(day - chrono::Days::new(5), "ocean"),
+            work(day - chrono::Days::new(2), "ocean"),
+            work(day - chrono::Days::new(1), "ocean"),
+            work(day, "matrix"),
+            work(day, "matrix"),
+            work(day, "ocean"),
+        ];
+        let summary = DaySummary::from_records(&records, day);
+        assert_eq!((summary.pomodoros, summary.focus_secs, summary.best_streak), (3, 4500, 3));
+        assert_eq!(summary.top_theme.as_deref(), Some("Matrix Rain"));
+        assert_eq!(summary.streak, 3);
+        // Still alive the next morning, gone the day after
+        assert_eq!(DaySummary::from_records(&records, day + chrono::Days::new(1)).streak, 3);
+        assert_eq!(DaySummary::from_records(&records, day + chrono::Days::new(2)).streak, 0);
+
+        let card = summary.card();
+        assert!(card.contains("Wed 12 Mar 2025"));
+        assert!(card.contains("Focus time ........ 1h 15m"));
+        assert!(card.is_ascii());
+        assert!(card.lines().all(|line| line.len() == CARD_WIDTH + 2));
+    }
+}
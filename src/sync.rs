@@ -0,0 +1,244 @@
+//! History sync across machines
+//! A backend pulls the remote history, it is union-merged into the local file,
+//! and the merged result is pushed back. Merging is order-independent, so two
+//! machines syncing at the same time converge instead of clobbering each other.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output, Stdio};
+use std::sync::Mutex;
+
+use serde::{Deserialize, Serialize};
+
+use crate::curl;
+use crate::history::{self, SessionRecord};
+use crate::paths;
+
+/// File name used inside folders and git repos
+const REMOTE_FILE: &str = "history.jsonl";
+
+/// A place the history file is mirrored to
+pub trait SyncBackend {
+    /// Records stored remotely (empty when nothing was pushed yet)
+    fn pull(&self) -> io::Result<Vec<SessionRecord>>;
+    /// Replace the remote copy with the merged history
+    fn push(&self, records: &[SessionRecord]) -> io::Result<()>;
+}
+
+/// Sync settings (the `sync` key in config.json)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "backend", rename_all = "lowercase")]
+pub enum SyncConfig {
+    /// A folder kept in sync by another tool (Dropbox, Syncthing, ...)
+    Folder { path: String },
+    /// A local clone of a git repository; pulled and pushed on every sync
+    Git { repo: String },
+    /// A WebDAV file URL (uses curl)
+    Webdav {
+        url: String,
+        #[serde(default)]
+        username: Option<String>,
+        #[serde(default)]
+        password: Option<String>,
+    },
+}
+
+impl SyncConfig {
+    fn backend(&self) -> Box<dyn SyncBackend> {
+        match self {
+            SyncConfig::Folder { path } => Box::new(FolderSync { dir: expand_home(path) }),
+            SyncConfig::Git { repo } => Box::new(GitSync { repo: expand_home(repo) }),
+            SyncConfig::Webdav { url, username, password } => Box::new(WebDavSync {
+                url: url.clone(),
+                credentials: username
+                    .as_ref()
+                    .map(|user| format!("{}:{}", user, password.as_deref().unwrap_or(""))),
+            }),
+        }
+    }
+}
+
+/// Pull, merge into the local history, push. Returns the merged record count.
+pub fn sync(config: &SyncConfig) -> io::Result<usize> {
+    let backend = config.backend();
+    let remote = backend.pull()?;
+    let merged = history::merge_into_local(&remote)?;
+    backend.push(&merged)?;
+    Ok(merged.len())
+}
+
+/// Background sync in flight, and the one asked for meanwhile
+struct InFlight {
+    running: bool,
+    pending: Option<SyncConfig>,
+}
+
+static IN_FLIGHT: Mutex<InFlight> = Mutex::new(InFlight { running: false, pending: None });
+
+/// Run a sync without blocking the UI; failures are retried on the next sync.
+/// One runs at a time (a git clone or upload file can't take two): asking
+/// while one is under way queues a single follow-up that covers every ask.
+pub fn sync_in_background(config: &SyncConfig) {
+    {
+        let mut in_flight = IN_FLIGHT.lock().unwrap_or_else(|e| e.into_inner());
+        if in_flight.running {
+            in_flight.pending = Some(config.clone());
+            return;
+        }
+        in_flight.running = true;
+    }
+    let mut config = config.clone();
+    std::thread::spawn(move || loop {
+        let _ = sync(&config);
+        let mut in_flight = IN_FLIGHT.lock().unwrap_or_else(|e| e.into_inner());
+        match in_flight.pending.take() {
+            Some(next) => config = next,
+            None => {
+                in_flight.running = false;
+                return;
+            }
+        }
+    });
+}
+
+/// Synced folder. Reads every `history*.jsonl` so conflict copies made by the
+/// sync tool ("history (conflicted copy).jsonl") are folded back in.
+struct FolderSync {
+    dir: PathBuf,
+}
+
+impl SyncBackend for FolderSync {
+    fn pull(&self) -> io::Result<Vec<SessionRecord>> {
+        let entries = match std::fs::read_dir(&self.dir) {
+            Ok(entries) => entries,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err),
+        };
+
+        let mut records = Vec::new();
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with("history") && name.ends_with(".jsonl") {
+                let contents = std::fs::read_to_string(entry.path())?;
+                records = history::merge(&records, &history::parse(&contents));
+            }
+        }
+        Ok(records)
+    }
+
+    fn push(&self, records: &[SessionRecord]) -> io::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        write_atomic(&self.dir.join(REMOTE_FILE), &history::to_jsonl(records))
+    }
+}
+
+/// Local clone of a git repository
+struct GitSync {
+    repo: PathBuf,
+}
+
+impl GitSync {
+    fn git(&self, args: &[&str]) -> io::Result<bool> {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(&self.repo)
+            .args(args)
+            .env("GIT_TERMINAL_PROMPT", "0")
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()?;
+        Ok(status.success())
+    }
+
+    fn run(&self, args: &[&str]) -> io::Result<()> {
+        if self.git(args)? {
+            Ok(())
+        } else {
+            Err(io::Error::other(format!("git {} failed", args.join(" "))))
+        }
+    }
+}
+
+impl SyncBackend for GitSync {
+    fn pull(&self) -> io::Result<Vec<SessionRecord>> {
+        self.run(&["pull", "--rebase", "--quiet"])?;
+        match std::fs::read_to_string(self.repo.join(REMOTE_FILE)) {
+            Ok(contents) => Ok(history::parse(&contents)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn push(&self, records: &[SessionRecord]) -> io::Result<()> {
+        write_atomic(&self.repo.join(REMOTE_FILE), &history::to_jsonl(records))?;
+        self.run(&["add", REMOTE_FILE])?;
+        // Nothing staged means the remote already has everything
+        if self.git(&["diff", "--cached", "--quiet"])? {
+            return Ok(());
+        }
+        self.run(&["commit", "--quiet", "-m", "Update pomowise history"])?;
+        self.run(&["push", "--quiet"])
+    }
+}
+
+/// WebDAV file, fetched with GET and replaced with PUT
+struct WebDavSync {
+    url: String,
+    credentials: Option<String>,
+}
+
+impl WebDavSync {
+    /// Run curl with `args` and the URL; the credentials go to it on stdin,
+    /// not where `ps` shows them
+    fn curl(&self, args: &[&str]) -> io::Result<Output> {
+        let config = self.credentials.as_deref().map(|c| curl::option("user", c)).unwrap_or_default();
+        curl::output(Command::new("curl").arg("-s").args(args).arg(&self.url), &config)
+    }
+}
+
+impl SyncBackend for WebDavSync {
+    fn pull(&self) -> io::Result<Vec<SessionRecord>> {
+        // Append the status code so a missing file can be told apart from an error
+        let output = self.curl(&["-w", "\n%{http_code}"])?;
+        let body = String::from_utf8_lossy(&output.stdout);
+        let (contents, status) = body.rsplit_once('\n').unwrap_or(("", &body));
+
+        match status.trim() {
+            "404" => Ok(Vec::new()),
+            code if code.starts_with('2') => Ok(history::parse(contents)),
+            code => Err(io::Error::other(format!("WebDAV GET returned {}", code))),
+        }
+    }
+
+    fn push(&self, records: &[SessionRecord]) -> io::Result<()> {
+        // Uploaded from a file, since curl's stdin carries the credentials
+        let upload = paths::state_dir().join(format!("webdav-upload-{}.jsonl", std::process::id()));
+        if let Some(parent) = upload.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&upload, history::to_jsonl(records))?;
+        let output = self.curl(&["-f", "-T", &upload.to_string_lossy()]);
+        let _ = std::fs::remove_file(&upload);
+        if output?.status.success() {
+            Ok(())
+        } else {
+            Err(io::Error::other("WebDAV PUT failed"))
+        }
+    }
+}
+
+/// Write via a temp file and rename, so sync tools never pick up a partial file
+fn write_atomic(path: &Path, contents: &str) -> io::Result<()> {
+    let tmp = path.with_extension("jsonl.tmp");
+    std::fs::write(&tmp, contents)?;
+    std::fs::rename(&tmp, path)
+}
+
+/// Expand a leading `~/` to the home directory
+pub(crate) fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), std::env::var("HOME")) {
+        (Some(rest), Ok(home)) => Path::new(&home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
@@ -0,0 +1,158 @@
+//! Session progress outside the window: taskbar progress in Windows Terminal,
+//! ConEmu and Ghostty (OSC 9;4), or an iTerm2 tab badge with the time left (OSC
+//! 1337 SetBadgeFormat), so a minimized or background window still shows where
+//! the session is. Only sent to terminals that announce themselves in the
+//! environment; `terminal_progress: false` in config.json turns it off.
+
+use std::io::{self, Write};
+
+use crate::timer::TimerSnapshot;
+
+/// Escape sequences a terminal understands for showing progress
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    /// `ESC ] 9 ; 4 ; state ; percent BEL`: a progress bar on the taskbar icon or tab
+    ConEmu,
+    /// `ESC ] 1337 ; SetBadgeFormat=<base64> BEL`: text drawn over the session
+    ItermBadge,
+}
+
+impl Protocol {
+    /// The protocol of the terminal described by `var` (an environment lookup), if any
+    fn detect(var: impl Fn(&str) -> Option<String>) -> Option<Self> {
+        // tmux swallows unknown OSC sequences, so inside it there is nothing to talk to
+        if var("TMUX").is_some() {
+            return None;
+        }
+        let term_program = var("TERM_PROGRAM").unwrap_or_default();
+        if var("WT_SESSION").is_some() || var("ConEmuANSI").as_deref() == Some("ON") || term_program == "ghostty" {
+            return Some(Protocol::ConEmu);
+        }
+        if term_program == "iTerm.app" || var("LC_TERMINAL").as_deref() == Some("iTerm2") {
+            return Some(Protocol::ItermBadge);
+        }
+        None
+    }
+
+    /// Sequence showing a session's progress
+    fn report(self, snapshot: &TimerSnapshot) -> String {
+        match self {
+            Protocol::ConEmu => {
+                // 1: normal, 4: paused (drawn yellow)
+                let state = if snapshot.is_paused { 4 } else { 1 };
+                let percent = (snapshot.session_progress.clamp(0.0, 1.0) * 100.0) as u8;
+                format!("\x1b]9;4;{};{}\x07", state, percent)
+            }
+            Protocol::ItermBadge => {
+                let secs = snapshot.remaining_secs;
+                let paused = if snapshot.is_paused { " ⏸" } else { "" };
+                let badge = format!("{:02}:{:02}{}", secs / 60, secs % 60, paused);
+                format!("\x1b]1337;SetBadgeFormat={}\x07", base64(badge.as_bytes()))
+            }
+        }
+    }
+
+    /// Sequence taking the progress away again
+    fn clear(self) -> &'static str {
+        match self {
+            Protocol::ConEmu => "\x1b]9;4;0;0\x07",
+            Protocol::ItermBadge => "\x1b]1337;SetBadgeFormat=\x07",
+        }
+    }
+}
+
+/// Progress reporter for the terminal pomowise runs in
+pub struct Taskbar {
+    protocol: Option<Protocol>,
+    /// Last sequence written, so unchanged progress isn't sent again
+    shown: Option<String>,
+}
+
+impl Taskbar {
+    /// Detect the terminal from the environment; `enabled: false` never reports
+    pub fn detect(enabled: bool) -> Self {
+        Self {
+            protocol: enabled.then(|| Protocol::detect(|name| std::env::var(name).ok())).flatten(),
+            shown: None,
+        }
+    }
+
+    /// Show the running session's progress, or clear it when none is running
+    pub fn update(&mut self, out: &mut impl Write, session: Option<&TimerSnapshot>) -> io::Result<()> {
+        let Some(protocol) = self.protocol else {
+            return Ok(());
+        };
+        let Some(snapshot) = session else {
+            return self.clear(out);
+        };
+        let sequence = protocol.report(snapshot);
+        if self.shown.as_ref() != Some(&sequence) {
+            out.write_all(sequence.as_bytes())?;
+            out.flush()?;
+            self.shown = Some(sequence);
+        }
+        Ok(())
+    }
+
+    /// Take the progress away (leaving the timer, quitting)
+    pub fn clear(&mut self, out: &mut impl Write) -> io::Result<()> {
+        if let (Some(protocol), Some(_)) = (self.protocol, self.shown.take()) {
+            out.write_all(protocol.clear().as_bytes())?;
+            out.flush()?;
+        }
+        Ok(())
+    }
+}
+
+/// Standard base64 with padding
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::new();
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().enumerate().fold(0u32, |n, (i, &b)| n | (b as u32) << (16 - 8 * i));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timer::PomodoroTimer;
+
+    #[test]
+    fn test_terminal_progress_sequences() {
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| vars.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string())
+        };
+        assert_eq!(Protocol::detect(env(&[("WT_SESSION", "abc")])), Some(Protocol::ConEmu));
+        assert_eq!(Protocol::detect(env(&[("LC_TERMINAL", "iTerm2")])), Some(Protocol::ItermBadge));
+        assert_eq!(Protocol::detect(env(&[("WT_SESSION", "abc"), ("TMUX", "/tmp/tmux")])), None);
+        assert_eq!(Protocol::detect(env(&[("TERM_PROGRAM", "Apple_Terminal")])), None);
+
+        let mut timer = PomodoroTimer::new();
+        timer.start();
+        let mut snapshot = timer.snapshot();
+        snapshot.remaining_secs = 754;
+        snapshot.session_progress = 0.5;
+        assert_eq!(Protocol::ConEmu.report(&snapshot), "\x1b]9;4;1;50\x07");
+        // "12:34"
+        assert_eq!(Protocol::ItermBadge.report(&snapshot), "\x1b]1337;SetBadgeFormat=MTI6MzQ=\x07");
+
+        let mut taskbar = Taskbar {
+            protocol: Some(Protocol::ConEmu),
+            shown: None,
+        };
+        let mut out = Vec::new();
+        taskbar.update(&mut out, Some(&snapshot)).unwrap();
+        taskbar.update(&mut out, Some(&snapshot)).unwrap();
+        taskbar.update(&mut out, None).unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), "\x1b]9;4;1;50\x07\x1b]9;4;0;0\x07");
+    }
+}
@@ -0,0 +1,433 @@
+//! Team mode: pomowise instances on the same LAN share one timer
+//! Peers find each other with mDNS (`_pomowise._tcp`) and exchange newline-delimited
+//! JSON over TCP. Every start/pause/skip/reset is broadcast and the newest change wins,
+//! so a pair or study group starts and pauses together.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use mdns_sd::{ServiceDaemon, ServiceEvent, ServiceInfo};
+use serde::{Deserialize, Serialize};
+
+use crate::commands::MAX_MINUTES;
+use crate::timer::{PomodoroTimer, TimerState};
+
+/// mDNS service type every instance advertises and browses
+const SERVICE_TYPE: &str = "_pomowise._tcp.local.";
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// Longest line read from a peer; anything longer drops the connection
+const MAX_LINE: u64 = 64 * 1024;
+
+/// Work sessions in a cycle, the most `cycle_position` can count
+const MAX_CYCLE_POSITION: u8 = 4;
+
+/// Team settings (the `team` key in config.json)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TeamConfig {
+    /// Only peers in the same room share a timer
+    pub room: String,
+    /// Name shown to the others; unset uses $USER
+    pub name: Option<String>,
+}
+
+impl Default for TeamConfig {
+    fn default() -> Self {
+        Self {
+            room: "default".to_string(),
+            name: None,
+        }
+    }
+}
+
+/// Shared timer state, as broadcast on every local change
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamTimer {
+    pub state: TimerState,
+    pub remaining_secs: u64,
+    pub cycle_position: u8,
+    /// Work, short break and long break lengths
+    pub durations_secs: [u64; 3],
+    /// Length given to the current session alone (`d`, +5 min)
+    #[serde(default)]
+    pub session_secs: Option<u64>,
+    /// Unix time (ms) of the change; the newest change wins
+    pub changed_at: u64,
+}
+
+impl TeamTimer {
+    /// Capture the local timer after a change made here
+    pub fn capture(timer: &PomodoroTimer) -> Self {
+        Self {
+            state: timer.state.clone(),
+            remaining_secs: timer.remaining.as_secs(),
+            cycle_position: timer.cycle_position,
+            durations_secs: [
+                timer.work_duration.as_secs(),
+                timer.short_break_duration.as_secs(),
+                timer.long_break_duration.as_secs(),
+            ],
+            session_secs: timer.session_length().map(|length| length.as_secs()),
+            changed_at: now_millis(),
+        }
+    }
+
+    /// Whether the timer is one we could have sent: lengths of 1 to `MAX_MINUTES`,
+    /// a position inside the cycle, and a pause of a session (not of a pause)
+    pub fn is_valid(&self) -> bool {
+        let length = |secs: u64| (1..=MAX_MINUTES * 60).contains(&secs);
+        let state_ok = match &self.state {
+            TimerState::Paused(inner) => !matches!(**inner, TimerState::Paused(_)),
+            _ => true,
+        };
+        self.durations_secs.iter().all(|&secs| length(secs))
+            && self.session_secs.is_none_or(length)
+            && self.remaining_secs <= MAX_MINUTES * 60
+            && self.cycle_position <= MAX_CYCLE_POSITION
+            && state_ok
+    }
+
+    /// Make the local timer match
+    pub fn apply(&self, timer: &mut PomodoroTimer) {
+        let [work, short_break, long_break] = self.durations_secs.map(Duration::from_secs);
+        timer.work_duration = work;
+        timer.short_break_duration = short_break;
+        timer.long_break_duration = long_break;
+        timer.restore(
+            self.state.clone(),
+            Duration::from_secs(self.remaining_secs),
+            self.cycle_position,
+        );
+        timer.share_session_length(self.session_secs.map(Duration::from_secs));
+    }
+}
+
+/// Something that happened on the team since the last poll
+#[derive(Debug, Clone)]
+pub enum TeamEvent {
+    Joined(String),
+    Left(String),
+    Timer(TeamTimer),
+}
+
+/// Wire format, one JSON object per line
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Message {
+    Hello { id: u64, name: String, room: String },
+    Timer(TeamTimer),
+}
+
+/// An open connection to another instance
+struct Peer {
+    /// Instance id and name, known once its Hello arrives
+    id: Option<u64>,
+    name: Option<String>,
+    stream: TcpStream,
+}
+
+/// State shared between the app and the network threads
+struct Shared {
+    id: u64,
+    name: String,
+    room: String,
+    peers: Mutex<HashMap<u64, Peer>>,
+    next_connection: AtomicU64,
+    events: Mutex<Sender<TeamEvent>>,
+}
+
+/// A running team session; dropping it leaves the team
+pub struct Team {
+    shared: Arc<Shared>,
+    events: Receiver<TeamEvent>,
+    room: String,
+    mdns: ServiceDaemon,
+}
+
+impl Team {
+    /// Listen for peers, advertise on the LAN and connect to the room's members
+    pub fn start(config: &TeamConfig) -> io::Result<Self> {
+        let id = instance_id();
+        let name = config
+            .name
+            .clone()
+            .or_else(|| std::env::var("USER").ok())
+            .unwrap_or_else(|| "anonymous".to_string());
+
+        let listener = TcpListener::bind(("0.0.0.0", 0))?;
+        let port = listener.local_addr()?.port();
+
+        let (sender, events) = mpsc::channel();
+        let shared = Arc::new(Shared {
+            id,
+            name,
+            room: config.room.clone(),
+            peers: Mutex::new(HashMap::new()),
+            next_connection: AtomicU64::new(0),
+            events: Mutex::new(sender),
+        });
+
+        let mdns = ServiceDaemon::new().map_err(io::Error::other)?;
+        let id_text = id.to_string();
+        let properties = [("room", config.room.as_str()), ("id", id_text.as_str())];
+        let service = ServiceInfo::new(
+            SERVICE_TYPE,
+            &format!("pomowise-{:x}", id),
+            &format!("pomowise-{:x}.local.", id),
+            (),
+            port,
+            &properties[..],
+        )
+        .map_err(io::Error::other)?
+        .enable_addr_auto();
+        mdns.register(service).map_err(io::Error::other)?;
+        let browse = mdns.browse(SERVICE_TYPE).map_err(io::Error::other)?;
+
+        // Accept connections from peers with a lower id
+        let accept_shared = Arc::clone(&shared);
+        std::thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                add_peer(&accept_shared, stream);
+            }
+        });
+
+        // Dial peers with a higher id, so each pair ends up with one connection
+        let dial_shared = Arc::clone(&shared);
+        let room = config.room.clone();
+        std::thread::spawn(move || {
+            while let Ok(event) = browse.recv() {
+                let ServiceEvent::ServiceResolved(info) = event else {
+                    continue;
+                };
+                if info.get_property_val_str("room") != Some(room.as_str()) {
+                    continue;
+                }
+                let Some(peer_id) = info.get_property_val_str("id").and_then(|s| s.parse().ok())
+                else {
+                    continue;
+                };
+                if peer_id <= dial_shared.id || dial_shared.is_connected(peer_id) {
+                    continue;
+                }
+                let port = info.get_port();
+                for ip in info.get_addresses() {
+                    let addr = SocketAddr::new(*ip, port);
+                    if let Ok(stream) = TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT) {
+                        add_peer(&dial_shared, stream);
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(Self {
+            shared,
+            events,
+            room: config.room.clone(),
+            mdns,
+        })
+    }
+
+    pub fn room(&self) -> &str {
+        &self.room
+    }
+
+    /// Our own display name
+    pub fn name(&self) -> &str {
+        &self.shared.name
+    }
+
+    /// Names of the connected peers, sorted
+    pub fn participants(&self) -> Vec<String> {
+        let peers = self.shared.peers.lock().unwrap_or_else(|e| e.into_inner());
+        let mut names: Vec<String> = peers.values().filter_map(|p| p.name.clone()).collect();
+        names.sort();
+        names
+    }
+
+    /// Events received since the last call
+    pub fn poll(&self) -> Vec<TeamEvent> {
+        self.events.try_iter().collect()
+    }
+
+    /// Send a local timer change to every peer
+    pub fn broadcast(&self, timer: TeamTimer) {
+        let Some(line) = encode(&Message::Timer(timer)) else {
+            return;
+        };
+        let mut peers = self.shared.peers.lock().unwrap_or_else(|e| e.into_inner());
+        // A failed write means the peer is gone; its reader thread reports it
+        peers.retain(|_, peer| peer.stream.write_all(line.as_bytes()).is_ok());
+    }
+}
+
+impl Drop for Team {
+    fn drop(&mut self) {
+        let _ = self.mdns.shutdown();
+        let peers = self.shared.peers.lock().unwrap_or_else(|e| e.into_inner());
+        for peer in peers.values() {
+            let _ = peer.stream.shutdown(std::net::Shutdown::Both);
+        }
+    }
+}
+
+impl Shared {
+    fn is_connected(&self, id: u64) -> bool {
+        let peers = self.peers.lock().unwrap_or_else(|e| e.into_inner());
+        peers.values().any(|p| p.id == Some(id))
+    }
+
+    fn emit(&self, event: TeamEvent) {
+        let sender = self.events.lock().unwrap_or_else(|e| e.into_inner());
+        let _ = sender.send(event);
+    }
+}
+
+/// Register a new connection, say hello and read its messages on a thread
+fn add_peer(shared: &Arc<Shared>, stream: TcpStream) {
+    let Ok(mut writer) = stream.try_clone() else {
+        return;
+    };
+    let hello = Message::Hello {
+        id: shared.id,
+        name: shared.name.clone(),
+        room: shared.room.clone(),
+    };
+    if encode(&hello).is_none_or(|line| writer.write_all(line.as_bytes()).is_err()) {
+        return;
+    }
+
+    let connection = shared.next_connection.fetch_add(1, Ordering::Relaxed);
+    {
+        let mut peers = shared.peers.lock().unwrap_or_else(|e| e.into_inner());
+        peers.insert(
+            connection,
+            Peer {
+                id: None,
+                name: None,
+                stream: writer,
+            },
+        );
+    }
+
+    let shared = Arc::clone(shared);
+    std::thread::spawn(move || {
+        let mut reader = BufReader::new(stream);
+        let mut greeted = false;
+        while let Some(line) = read_line(&mut reader) {
+            match serde_json::from_str(&line) {
+                Ok(Message::Hello { id, name, room }) => {
+                    // Another room found our port, or a second connection to the
+                    // same instance (both sides dialed)
+                    if room != shared.room || shared.is_connected(id) {
+                        break;
+                    }
+                    greeted = true;
+                    let mut peers = shared.peers.lock().unwrap_or_else(|e| e.into_inner());
+                    if let Some(peer) = peers.get_mut(&connection) {
+                        peer.id = Some(id);
+                        peer.name = Some(name.clone());
+                    }
+                    drop(peers);
+                    shared.emit(TeamEvent::Joined(name));
+                }
+                Ok(Message::Timer(timer)) if greeted && timer.is_valid() => shared.emit(TeamEvent::Timer(timer)),
+                // Timers from strangers, impossible timers, and unknown or
+                // malformed lines (e.g. a newer version) are skipped
+                _ => {}
+            }
+        }
+
+        let mut peers = shared.peers.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(Peer { name: Some(name), .. }) = peers.remove(&connection) {
+            drop(peers);
+            shared.emit(TeamEvent::Left(name));
+        }
+    });
+}
+
+/// The next line from a peer, without its newline; None once the connection
+/// closes, fails or sends a line longer than `MAX_LINE`
+fn read_line(reader: &mut impl BufRead) -> Option<String> {
+    let mut line = String::new();
+    match reader.by_ref().take(MAX_LINE).read_line(&mut line) {
+        Ok(0) | Err(_) => None,
+        Ok(_) if !line.ends_with('\n') => None,
+        Ok(_) => {
+            line.pop();
+            Some(line)
+        }
+    }
+}
+
+fn encode(message: &Message) -> Option<String> {
+    serde_json::to_string(message).ok().map(|json| json + "\n")
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Random-enough id for this instance (clock and process id)
+fn instance_id() -> u64 {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0);
+    nanos ^ ((std::process::id() as u64) << 32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_timer_round_trips_through_the_wire_format() {
+        let mut timer = PomodoroTimer::new();
+        timer.set_work_duration(Duration::from_secs(50 * 60));
+        timer.start();
+        timer.set_session_length(Duration::from_secs(25 * 60));
+        timer.toggle_pause();
+
+        let line = encode(&Message::Timer(TeamTimer::capture(&timer))).unwrap();
+        let Ok(Message::Timer(received)) = serde_json::from_str(line.trim_end()) else {
+            panic!("not a timer message: {}", line);
+        };
+
+        let mut other = PomodoroTimer::new();
+        received.apply(&mut other);
+        assert_eq!(other.state, timer.state);
+        assert_eq!(other.remaining, Duration::from_secs(25 * 60));
+        assert_eq!(other.work_duration, timer.work_duration);
+        assert_eq!(other.session_duration(), Duration::from_secs(25 * 60));
+        assert!(other.is_paused());
+    }
+
+    #[test]
+    fn test_impossible_timers_are_not_valid() {
+        let sent = TeamTimer::capture(&PomodoroTimer::new());
+        assert!(sent.is_valid());
+        assert!(!TeamTimer { durations_secs: [0, 0, 0], ..sent.clone() }.is_valid());
+        assert!(!TeamTimer { session_secs: Some(0), ..sent.clone() }.is_valid());
+        assert!(!TeamTimer { cycle_position: 5, ..sent.clone() }.is_valid());
+        let paused_twice = TimerState::Paused(Box::new(TimerState::Paused(Box::new(TimerState::LongBreak))));
+        assert!(!TeamTimer { state: paused_twice, ..sent.clone() }.is_valid());
+    }
+
+    #[test]
+    fn test_overlong_lines_end_the_connection() {
+        let mut input = io::Cursor::new(format!("short\n{}\nafter\n", "x".repeat(MAX_LINE as usize)));
+        assert_eq!(read_line(&mut input).as_deref(), Some("short"));
+        assert_eq!(read_line(&mut input), None);
+    }
+}
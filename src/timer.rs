@@ -1,17 +1,30 @@
 use std::time::{Duration, Instant};
 use serde::{Serialize, Deserialize};
 
+use crate::clock::{self, SharedClock};
+
 const WORK_DURATION: Duration = Duration::from_secs(25 * 60);
 const SHORT_BREAK_DURATION: Duration = Duration::from_secs(5 * 60);
 const LONG_BREAK_DURATION: Duration = Duration::from_secs(15 * 60);
 
-const WORK_LAPS: u8 = 10;
-const SHORT_BREAK_LAPS: u8 = 3;
+/// Length of the final countdown announcement at the end of each session
+pub const FINAL_COUNTDOWN: Duration = Duration::from_secs(5);
+
+/// Sessions in one cycle: four work sessions, each followed by a break
+pub const CYCLE_STEPS: usize = 8;
+
+/// Work sessions to aim for in a day, unless configured otherwise
+pub const DEFAULT_LAP_TARGET: u8 = 10;
+
+/// Short breaks in one cycle, the last work session being followed by the long break
+const SHORT_BREAK_LAPS: u8 = (CYCLE_STEPS / 2 - 1) as u8;
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum TimerState {
     Idle,
+    /// `lap` counts the day's work sessions, this one included
     Work { lap: u8 },
+    /// `lap` counts the cycle's short breaks, this one included
     ShortBreak { lap: u8 },
     LongBreak,
     Paused(Box<TimerState>),
@@ -21,7 +34,28 @@ pub struct PomodoroTimer {
     pub state: TimerState,
     pub remaining: Duration,
     pub cycle_position: u8, // 0-4 for the 5-phase cycle
+    pub work_duration: Duration,
+    pub short_break_duration: Duration,
+    pub long_break_duration: Duration,
+    /// Label for work sessions (deep work, admin, ...), carried into the history
+    pub category: Option<String>,
+    /// The category's own work length, used instead of `work_duration` while set
+    category_work_duration: Option<Duration>,
+    /// The current session's own length (`d` on the timer screen), dropped when it ends
+    session_length: Option<Duration>,
+    /// Work sessions to aim for in a day, the total in "Lap 3/10"
+    pub lap_target: u8,
+    /// Work sessions completed today before the current one
+    laps_done: u8,
     last_tick: Option<Instant>,
+    /// Time the current session spent paused before the pause in progress, if any
+    paused_before: Duration,
+    paused_since: Option<Instant>,
+    /// Paused time of the session before this one, for recording it once it ended
+    last_paused: Duration,
+    /// Full length of the session before this one, its own length if it had one
+    last_length: Duration,
+    clock: SharedClock,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -32,54 +66,140 @@ pub struct TimerSnapshot {
     pub session_progress: f64,
     pub is_paused: bool,
     pub cycle_position: u8,
+    #[serde(default)]
+    pub category: Option<String>,
+    /// Time the session has spent paused so far
+    #[serde(default)]
+    pub paused_secs: u64,
+}
+
+/// Everything the countdown needs to pick up exactly where it was (undo)
+#[derive(Debug, Clone)]
+pub struct Savestate {
+    state: TimerState,
+    remaining: Duration,
+    cycle_position: u8,
+    laps_done: u8,
+    session_length: Option<Duration>,
+    paused: Duration,
+    last_paused: Duration,
+    last_length: Duration,
+    taken_at: Instant,
 }
 
 impl PomodoroTimer {
     pub fn new() -> Self {
+        Self::with_clock(clock::system())
+    }
+
+    /// Timer reading time from `clock` (a `MockClock` in tests)
+    pub fn with_clock(clock: SharedClock) -> Self {
         Self {
             state: TimerState::Idle,
             remaining: Duration::ZERO,
             cycle_position: 0,
+            work_duration: WORK_DURATION,
+            short_break_duration: SHORT_BREAK_DURATION,
+            long_break_duration: LONG_BREAK_DURATION,
+            category: None,
+            category_work_duration: None,
+            session_length: None,
+            lap_target: DEFAULT_LAP_TARGET,
+            laps_done: 0,
             last_tick: None,
+            paused_before: Duration::ZERO,
+            paused_since: None,
+            last_paused: Duration::ZERO,
+            last_length: Duration::ZERO,
+            clock,
         }
     }
 
+    /// The clock this timer reads, to share with a replacement timer
+    pub fn clock(&self) -> SharedClock {
+        self.clock.clone()
+    }
+
     pub fn start(&mut self) {
-        self.state = TimerState::Work { lap: 1 };
-        self.remaining = WORK_DURATION;
+        self.state = self.work_state();
+        self.remaining = self.work_length();
         self.cycle_position = 0;
-        self.last_tick = Some(Instant::now());
+        self.last_tick = Some(self.clock.now());
+        self.begin_session(Duration::ZERO);
     }
 
     pub fn toggle_pause(&mut self) {
         match &self.state {
             TimerState::Paused(inner) => {
                 self.state = *inner.clone();
-                self.last_tick = Some(Instant::now());
+                let now = self.clock.now();
+                self.last_tick = Some(now);
+                if let Some(since) = self.paused_since.take() {
+                    self.paused_before += now.saturating_duration_since(since);
+                }
             }
             TimerState::Idle => {}
             state => {
                 self.state = TimerState::Paused(Box::new(state.clone()));
                 self.last_tick = None;
+                self.paused_since = Some(self.clock.now());
             }
         }
     }
 
+    /// Time the current session has spent paused, including a pause in progress
+    pub fn paused_time(&self) -> Duration {
+        let ongoing = self
+            .paused_since
+            .map_or(Duration::ZERO, |since| self.clock.now().saturating_duration_since(since));
+        self.paused_before + ongoing
+    }
+
+    /// Paused time of the previous session, once the timer has moved on from it
+    pub fn last_session_paused(&self) -> Duration {
+        self.last_paused
+    }
+
+    /// Full length of the previous session, once the timer has moved on from it:
+    /// a length given to that session alone (`d`, +5 min) rather than the usual one
+    pub fn last_session_length(&self) -> Duration {
+        self.last_length
+    }
+
+    /// Forget the paused time so far, e.g. a wait before the session really began
+    pub fn clear_paused_time(&mut self) {
+        self.paused_before = Duration::ZERO;
+        self.paused_since = self.paused_since.map(|_| self.clock.now());
+    }
+
+    /// Start counting paused time afresh, remembering the `ended` session's length;
+    /// a timer that stays paused keeps counting
+    fn begin_session(&mut self, ended: Duration) {
+        self.last_length = ended;
+        self.session_length = None;
+        self.last_paused = self.paused_time();
+        self.paused_before = Duration::ZERO;
+        self.paused_since = self.is_paused().then(|| self.clock.now());
+    }
+
     pub fn reset_current_session(&mut self) {
         let inner_state = match &self.state {
             TimerState::Paused(inner) => inner.as_ref(),
             other => other,
         };
 
-        let (new_state, duration) = match inner_state {
-            TimerState::Work { .. } => (TimerState::Work { lap: 1 }, WORK_DURATION),
-            TimerState::ShortBreak { .. } => (TimerState::ShortBreak { lap: 1 }, SHORT_BREAK_DURATION),
-            TimerState::LongBreak => (TimerState::LongBreak, LONG_BREAK_DURATION),
+        let duration = match inner_state {
+            TimerState::Work { .. } => self.work_length(),
+            TimerState::ShortBreak { .. } => self.short_break_duration,
+            TimerState::LongBreak => self.long_break_duration,
             TimerState::Idle | TimerState::Paused(_) => return,
         };
-        self.state = new_state;
+        // Same session, same lap
+        let ended = self.session_duration();
+        self.state = inner_state.clone();
         self.remaining = duration;
-        self.last_tick = Some(Instant::now());
+        self.last_tick = Some(self.clock.now());
+        self.begin_session(ended);
     }
 
     pub fn tick(&mut self) {
@@ -88,11 +208,16 @@ impl PomodoroTimer {
         }
 
         if let Some(last) = self.last_tick {
-            let elapsed = last.elapsed();
-            self.last_tick = Some(Instant::now());
+            // One reading for both, so no time slips between measuring and restarting
+            let now = self.clock.now();
+            let elapsed = now.saturating_duration_since(last);
+            self.last_tick = Some(now);
 
             if elapsed >= self.remaining {
                 self.remaining = Duration::ZERO;
+                if matches!(self.state, TimerState::Work { .. }) {
+                    self.laps_done = self.laps_done.saturating_add(1);
+                }
                 self.advance_state();
             } else {
                 self.remaining -= elapsed;
@@ -103,6 +228,7 @@ impl PomodoroTimer {
     /// Advance to the next session (used when timer completes or Tab is pressed)
     /// Cycle: Work -> Short Break -> Work -> Short Break -> Work -> Short Break -> Work -> Long Break -> repeat
     pub fn advance_state(&mut self) {
+        let ended = self.session_duration();
         // Handle paused state - advance the inner state
         let current_state = match &self.state {
             TimerState::Paused(inner) => inner.as_ref().clone(),
@@ -116,22 +242,22 @@ impl PomodoroTimer {
                 if self.cycle_position >= 4 {
                     // After 4 work sessions, long break
                     self.state = TimerState::LongBreak;
-                    self.remaining = LONG_BREAK_DURATION;
+                    self.remaining = self.long_break_duration;
                 } else {
-                    self.state = TimerState::ShortBreak { lap: 1 };
-                    self.remaining = SHORT_BREAK_DURATION;
+                    self.state = TimerState::ShortBreak { lap: self.cycle_position };
+                    self.remaining = self.short_break_duration;
                 }
             }
             TimerState::ShortBreak { .. } => {
                 // Short break complete, back to work
-                self.state = TimerState::Work { lap: 1 };
-                self.remaining = WORK_DURATION;
+                self.state = self.work_state();
+                self.remaining = self.work_length();
             }
             TimerState::LongBreak => {
                 // Long break complete, reset cycle
                 self.cycle_position = 0;
-                self.state = TimerState::Work { lap: 1 };
-                self.remaining = WORK_DURATION;
+                self.state = self.work_state();
+                self.remaining = self.work_length();
             }
             TimerState::Idle => {
                 // Start fresh
@@ -140,39 +266,133 @@ impl PomodoroTimer {
             }
             TimerState::Paused(_) => unreachable!(),
         }
-        self.last_tick = Some(Instant::now());
+        self.last_tick = Some(self.clock.now());
+        self.begin_session(ended);
+    }
+
+    /// Position in the cycle: 0 is the first work session, 1 the break after it,
+    /// ... 7 the long break (None when idle)
+    pub fn cycle_step(&self) -> Option<usize> {
+        let position = self.cycle_position as usize;
+        match self.inner_state() {
+            TimerState::Work { .. } => Some(position * 2),
+            TimerState::ShortBreak { .. } => Some((position * 2).saturating_sub(1)),
+            TimerState::LongBreak => Some(CYCLE_STEPS - 1),
+            _ => None,
+        }
+    }
+
+    /// Move to a position in the cycle (see `cycle_step`) with a fresh session;
+    /// a paused timer stays paused
+    pub fn jump_to(&mut self, step: usize) {
+        let step = step.min(CYCLE_STEPS - 1);
+        let paused = self.is_paused();
+        let ended = self.session_duration();
+        let state = if step == CYCLE_STEPS - 1 {
+            TimerState::LongBreak
+        } else if step.is_multiple_of(2) {
+            self.work_state()
+        } else {
+            TimerState::ShortBreak { lap: step.div_ceil(2) as u8 }
+        };
+        self.cycle_position = step.div_ceil(2) as u8;
+        self.remaining = self.step_length(step);
+        if paused {
+            self.state = TimerState::Paused(Box::new(state));
+            self.last_tick = None;
+        } else {
+            self.state = state;
+            self.last_tick = Some(self.clock.now());
+        }
+        self.begin_session(ended);
+    }
+
+    /// Length of a fresh session at a position in the cycle
+    pub fn step_length(&self, step: usize) -> Duration {
+        if step >= CYCLE_STEPS - 1 {
+            self.long_break_duration
+        } else if step.is_multiple_of(2) {
+            self.work_length()
+        } else {
+            self.short_break_duration
+        }
+    }
+
+    /// "Work #3", "Short Break #2", "Long Break"
+    pub fn step_name(step: usize) -> String {
+        if step >= CYCLE_STEPS - 1 {
+            "Long Break".to_string()
+        } else if step.is_multiple_of(2) {
+            format!("Work #{}", step / 2 + 1)
+        } else {
+            format!("Short Break #{}", step.div_ceil(2))
+        }
+    }
+
+    /// Position in the cycle of the session that follows this one, the long break
+    /// leading back to the first work session (None when idle)
+    pub fn next_step(&self) -> Option<usize> {
+        self.cycle_step().map(|step| (step + 1) % CYCLE_STEPS)
+    }
+
+    /// Time until the long break starts: what is left of this session and the
+    /// fresh ones after it (None when idle or in the long break already)
+    pub fn cycle_remaining(&self) -> Option<Duration> {
+        let step = self.cycle_step().filter(|&step| step < CYCLE_STEPS - 1)?;
+        let later: Duration = (step + 1..CYCLE_STEPS - 1).map(|s| self.step_length(s)).sum();
+        Some(self.remaining + later)
     }
 
+    /// Work session of the day or short break of the cycle under way (0 otherwise)
     pub fn current_lap(&self) -> u8 {
-        match &self.state {
-            TimerState::Work { lap } => *lap,
-            TimerState::ShortBreak { lap } => *lap,
-            TimerState::Paused(inner) => match inner.as_ref() {
-                TimerState::Work { lap } => *lap,
-                TimerState::ShortBreak { lap } => *lap,
-                _ => 0,
-            },
+        match self.inner_state() {
+            TimerState::Work { lap } | TimerState::ShortBreak { lap } => *lap,
             _ => 0,
         }
     }
 
+    /// What `current_lap` counts towards: the daily target or the cycle's short
+    /// breaks (0 when there are no laps)
     pub fn total_laps(&self) -> u8 {
-        match &self.state {
-            TimerState::Work { .. } => WORK_LAPS,
+        match self.inner_state() {
+            TimerState::Work { .. } => self.lap_target,
             TimerState::ShortBreak { .. } => SHORT_BREAK_LAPS,
-            TimerState::Paused(inner) => match inner.as_ref() {
-                TimerState::Work { .. } => WORK_LAPS,
-                TimerState::ShortBreak { .. } => SHORT_BREAK_LAPS,
-                _ => 0,
-            },
             _ => 0,
         }
     }
 
+    /// Work sessions completed today, this one included once it has run out
+    pub fn laps_done(&self) -> u8 {
+        self.laps_done
+    }
+
+    /// Count laps on from the work sessions already completed today (from the history)
+    pub fn set_laps_done(&mut self, done: u8) {
+        self.laps_done = done;
+        if let TimerState::Work { lap } = self.inner_state_mut() {
+            *lap = done.saturating_add(1);
+        }
+    }
+
+    /// A fresh work session, numbered after the ones completed today
+    fn work_state(&self) -> TimerState {
+        TimerState::Work { lap: self.laps_done.saturating_add(1) }
+    }
+
     pub fn is_paused(&self) -> bool {
         matches!(self.state, TimerState::Paused(_))
     }
 
+    /// True while a session is actively counting down
+    pub fn is_running(&self) -> bool {
+        !matches!(self.state, TimerState::Idle | TimerState::Paused(_))
+    }
+
+    /// True during the last few seconds of a running session
+    pub fn in_final_countdown(&self) -> bool {
+        self.is_running() && self.remaining <= FINAL_COUNTDOWN
+    }
+
     pub fn session_name(&self) -> &'static str {
         match &self.state {
             TimerState::Idle => "Idle",
@@ -188,20 +408,184 @@ impl PomodoroTimer {
         }
     }
 
-    /// Progress within current session (0.0 to 1.0)
-    pub fn session_progress(&self) -> f64 {
-        let total = match &self.state {
-            TimerState::Work { .. } => WORK_DURATION,
-            TimerState::ShortBreak { .. } => SHORT_BREAK_DURATION,
-            TimerState::LongBreak => LONG_BREAK_DURATION,
+    /// Full length of the current session (zero when idle)
+    pub fn session_duration(&self) -> Duration {
+        if let Some(length) = self.session_length.filter(|_| self.state != TimerState::Idle) {
+            return length;
+        }
+        match &self.state {
+            TimerState::Work { .. } => self.work_length(),
+            TimerState::ShortBreak { .. } => self.short_break_duration,
+            TimerState::LongBreak => self.long_break_duration,
             TimerState::Paused(inner) => match inner.as_ref() {
-                TimerState::Work { .. } => WORK_DURATION,
-                TimerState::ShortBreak { .. } => SHORT_BREAK_DURATION,
-                TimerState::LongBreak => LONG_BREAK_DURATION,
-                _ => return 0.0,
+                TimerState::Work { .. } => self.work_length(),
+                TimerState::ShortBreak { .. } => self.short_break_duration,
+                TimerState::LongBreak => self.long_break_duration,
+                _ => Duration::ZERO,
             },
-            TimerState::Idle => return 0.0,
+            TimerState::Idle => Duration::ZERO,
+        }
+    }
+
+    /// Length of a work session: the category's own length while one is set
+    pub fn work_length(&self) -> Duration {
+        self.category_work_duration.unwrap_or(self.work_duration)
+    }
+
+    /// Label the work sessions, optionally with their own length; a running work
+    /// session keeps its elapsed time
+    pub fn set_category(&mut self, category: Option<String>, work_duration: Option<Duration>) {
+        let active = matches!(self.inner_state(), TimerState::Work { .. });
+        self.apply_duration(active, work_duration.unwrap_or(self.work_duration));
+        self.category = category;
+        self.category_work_duration = work_duration;
+    }
+
+    /// Change the work length; a running work session keeps its elapsed time
+    /// (unless its category sets a length of its own)
+    pub fn set_work_duration(&mut self, duration: Duration) {
+        let active = matches!(self.inner_state(), TimerState::Work { .. }) && self.category_work_duration.is_none();
+        self.apply_duration(active, duration);
+        self.work_duration = duration;
+    }
+
+    /// Change the short break length; a running short break keeps its elapsed time
+    pub fn set_short_break_duration(&mut self, duration: Duration) {
+        let active = matches!(self.inner_state(), TimerState::ShortBreak { .. });
+        self.apply_duration(active, duration);
+        self.short_break_duration = duration;
+    }
+
+    /// Change the long break length; a running long break keeps its elapsed time
+    pub fn set_long_break_duration(&mut self, duration: Duration) {
+        let active = matches!(self.inner_state(), TimerState::LongBreak);
+        self.apply_duration(active, duration);
+        self.long_break_duration = duration;
+    }
+
+    /// Give the session under way a length of its own, keeping its elapsed time;
+    /// the next session is back to the usual length
+    pub fn set_session_length(&mut self, length: Duration) {
+        if self.state == TimerState::Idle {
+            return;
+        }
+        let elapsed = self.elapsed();
+        self.session_length = Some(length);
+        self.remaining = length.saturating_sub(elapsed);
+    }
+
+    /// Jump straight to a state (used to follow a shared team timer)
+    pub fn restore(&mut self, state: TimerState, remaining: Duration, cycle_position: u8) {
+        self.last_tick = match state {
+            TimerState::Idle | TimerState::Paused(_) => None,
+            _ => Some(self.clock.now()),
         };
+        // A length of its own belongs to the session it was given to
+        let same_session = match &state {
+            TimerState::Paused(inner) => inner.as_ref() == self.inner_state(),
+            other => other == self.inner_state(),
+        };
+        if !same_session {
+            self.session_length = None;
+        }
+        self.state = state;
+        self.remaining = remaining;
+        self.cycle_position = cycle_position;
+
+        // The pause clock follows the shared state too
+        let now = self.clock.now();
+        match (self.is_paused(), self.paused_since) {
+            (true, None) => self.paused_since = Some(now),
+            (false, Some(since)) => {
+                self.paused_before += now.saturating_duration_since(since);
+                self.paused_since = None;
+            }
+            _ => {}
+        }
+    }
+
+    /// The current session's own length, if it was given one
+    pub fn session_length(&self) -> Option<Duration> {
+        self.session_length
+    }
+
+    /// Take the current session's own length as it is on another timer (team
+    /// mode), leaving the remaining time as it was restored
+    pub fn share_session_length(&mut self, length: Option<Duration>) {
+        if self.state != TimerState::Idle {
+            self.session_length = length;
+        }
+    }
+
+    /// Capture the countdown as it stands now
+    pub fn savestate(&self) -> Savestate {
+        Savestate {
+            state: self.state.clone(),
+            remaining: self.remaining,
+            cycle_position: self.cycle_position,
+            laps_done: self.laps_done,
+            session_length: self.session_length,
+            paused: self.paused_time(),
+            last_paused: self.last_paused,
+            last_length: self.last_length,
+            taken_at: self.clock.now(),
+        }
+    }
+
+    /// How long ago a savestate was taken, by this timer's clock
+    pub fn savestate_age(&self, savestate: &Savestate) -> Duration {
+        self.clock.now().saturating_duration_since(savestate.taken_at)
+    }
+
+    /// Go back to a savestate; the time since it was taken doesn't count
+    pub fn load_savestate(&mut self, savestate: &Savestate) {
+        let now = self.clock.now();
+        self.state = savestate.state.clone();
+        self.remaining = savestate.remaining;
+        self.cycle_position = savestate.cycle_position;
+        self.laps_done = savestate.laps_done;
+        self.session_length = savestate.session_length;
+        self.last_paused = savestate.last_paused;
+        self.last_length = savestate.last_length;
+        self.paused_before = savestate.paused;
+        self.paused_since = self.is_paused().then_some(now);
+        self.last_tick = self.is_running().then_some(now);
+    }
+
+    /// Recompute remaining time for a new session length (call before updating the field)
+    fn apply_duration(&mut self, active: bool, duration: Duration) {
+        // A session given its own length keeps it
+        if active && self.session_length.is_none() {
+            self.remaining = duration.saturating_sub(self.elapsed());
+        }
+    }
+
+    /// The current state with any pause wrapper removed
+    fn inner_state(&self) -> &TimerState {
+        match &self.state {
+            TimerState::Paused(inner) => inner.as_ref(),
+            other => other,
+        }
+    }
+
+    fn inner_state_mut(&mut self) -> &mut TimerState {
+        match &mut self.state {
+            TimerState::Paused(inner) => inner.as_mut(),
+            other => other,
+        }
+    }
+
+    /// Time spent in the current session so far
+    pub fn elapsed(&self) -> Duration {
+        self.session_duration().saturating_sub(self.remaining)
+    }
+
+    /// Progress within current session (0.0 to 1.0)
+    pub fn session_progress(&self) -> f64 {
+        let total = self.session_duration();
+        if total.is_zero() {
+            return 0.0;
+        }
 
         1.0 - (self.remaining.as_secs_f64() / total.as_secs_f64())
     }
@@ -214,6 +598,257 @@ impl PomodoroTimer {
             session_progress: self.session_progress(),
             is_paused: self.is_paused(),
             cycle_position: self.cycle_position,
+            category: self.category.clone(),
+            paused_secs: self.paused_time().as_secs(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::MockClock;
+
+    fn timer() -> (PomodoroTimer, MockClock) {
+        let clock = MockClock::new();
+        (PomodoroTimer::with_clock(clock.shared()), clock)
+    }
+
+    #[test]
+    fn test_pause_stops_the_countdown() {
+        let (mut timer, clock) = timer();
+        timer.start();
+        clock.advance(Duration::from_secs(60));
+        timer.tick();
+        assert_eq!(timer.remaining, WORK_DURATION - Duration::from_secs(60));
+
+        timer.toggle_pause();
+        clock.advance(Duration::from_secs(600));
+        timer.tick();
+        assert_eq!(timer.remaining, WORK_DURATION - Duration::from_secs(60));
+
+        // Time spent paused doesn't count after resuming either
+        timer.toggle_pause();
+        clock.advance(Duration::from_secs(30));
+        timer.tick();
+        assert_eq!(timer.remaining, WORK_DURATION - Duration::from_secs(90));
+    }
+
+    #[test]
+    fn test_paused_time_is_counted_per_session() {
+        let (mut timer, clock) = timer();
+        timer.start();
+        timer.toggle_pause();
+        clock.advance(Duration::from_secs(90));
+        assert_eq!(timer.snapshot().paused_secs, 90);
+        timer.toggle_pause();
+        timer.toggle_pause();
+        clock.advance(Duration::from_secs(30));
+        timer.toggle_pause();
+        assert_eq!(timer.paused_time(), Duration::from_secs(120));
+
+        // The next session starts from zero; the finished one's stays readable
+        clock.advance(WORK_DURATION);
+        timer.tick();
+        assert!(matches!(timer.state, TimerState::ShortBreak { .. }));
+        assert_eq!(timer.paused_time(), Duration::ZERO);
+        assert_eq!(timer.last_session_paused(), Duration::from_secs(120));
+    }
+
+    #[test]
+    fn test_session_end_advances_to_break() {
+        let (mut timer, clock) = timer();
+        timer.start();
+        clock.advance(WORK_DURATION);
+        timer.tick();
+        assert_eq!(timer.state, TimerState::ShortBreak { lap: 1 });
+        assert_eq!(timer.remaining, SHORT_BREAK_DURATION);
+        assert_eq!(timer.cycle_position, 1);
+    }
+
+    #[test]
+    fn test_fourth_work_session_earns_a_long_break() {
+        let (mut timer, clock) = timer();
+        timer.start();
+        let mut states = Vec::new();
+        for _ in 0..9 {
+            clock.advance(timer.remaining);
+            timer.tick();
+            states.push(timer.state.clone());
+        }
+        let names: Vec<&str> = states
+            .iter()
+            .map(|state| match state {
+                TimerState::Work { .. } => "work",
+                TimerState::ShortBreak { .. } => "short",
+                TimerState::LongBreak => "long",
+                _ => "other",
+            })
+            .collect();
+        assert_eq!(names, ["short", "work", "short", "work", "short", "work", "long", "work", "short"]);
+        assert_eq!(timer.cycle_position, 1);
+    }
+
+    #[test]
+    fn test_laps_count_the_days_work_and_the_cycles_breaks() {
+        let (mut timer, clock) = timer();
+        timer.set_laps_done(2);
+        timer.start();
+        assert_eq!((timer.current_lap(), timer.total_laps()), (3, DEFAULT_LAP_TARGET));
+
+        clock.advance(timer.remaining);
+        timer.tick();
+        assert_eq!((timer.current_lap(), timer.total_laps()), (1, 3));
+
+        // A skipped work session isn't a completed lap
+        clock.advance(timer.remaining);
+        timer.tick();
+        assert_eq!(timer.state, TimerState::Work { lap: 4 });
+        timer.advance_state();
+        assert_eq!(timer.state, TimerState::ShortBreak { lap: 2 });
+        timer.advance_state();
+        assert_eq!(timer.state, TimerState::Work { lap: 4 });
+
+        timer.jump_to(5);
+        assert_eq!(timer.state, TimerState::ShortBreak { lap: 3 });
+        timer.reset_current_session();
+        assert_eq!(timer.current_lap(), 3);
+    }
+
+    #[test]
+    fn test_savestate_restores_the_countdown_exactly() {
+        let (mut timer, clock) = timer();
+        timer.start();
+        clock.advance(Duration::from_secs(90));
+        timer.tick();
+        timer.toggle_pause();
+        clock.advance(Duration::from_secs(20));
+        let saved = timer.savestate();
+
+        timer.advance_state();
+        clock.advance(Duration::from_secs(5));
+        timer.tick();
+        assert_eq!(timer.savestate_age(&saved), Duration::from_secs(5));
+
+        timer.load_savestate(&saved);
+        assert_eq!(timer.state, TimerState::Paused(Box::new(TimerState::Work { lap: 1 })));
+        assert_eq!((timer.remaining, timer.cycle_position), (WORK_DURATION - Duration::from_secs(90), 0));
+        assert_eq!(timer.paused_time(), Duration::from_secs(20));
+        timer.toggle_pause();
+        clock.advance(Duration::from_secs(10));
+        timer.tick();
+        assert_eq!(timer.remaining, WORK_DURATION - Duration::from_secs(100));
+    }
+
+    #[test]
+    fn test_session_length_applies_to_this_session_only() {
+        let (mut timer, clock) = timer();
+        timer.start();
+        clock.advance(Duration::from_secs(5 * 60));
+        timer.tick();
+
+        timer.set_session_length(Duration::from_secs(12 * 60 + 30));
+        assert_eq!(timer.remaining, Duration::from_secs(7 * 60 + 30));
+        assert!((timer.session_progress() - 0.4).abs() < 1e-9);
+        // The usual length changing doesn't touch it
+        timer.set_work_duration(Duration::from_secs(50 * 60));
+        assert_eq!(timer.remaining, Duration::from_secs(7 * 60 + 30));
+
+        clock.advance(timer.remaining);
+        timer.tick();
+        assert_eq!(timer.last_session_length(), Duration::from_secs(12 * 60 + 30));
+        timer.advance_state();
+        assert_eq!(timer.last_session_length(), SHORT_BREAK_DURATION);
+        assert_eq!(timer.remaining, Duration::from_secs(50 * 60));
+    }
+
+    #[test]
+    fn test_category_length_keeps_elapsed_time() {
+        let (mut timer, clock) = timer();
+        timer.start();
+        clock.advance(Duration::from_secs(10 * 60));
+        timer.tick();
+
+        timer.set_category(Some("Deep Work".to_string()), Some(Duration::from_secs(50 * 60)));
+        assert_eq!(timer.remaining, Duration::from_secs(40 * 60));
+        // The base length changes underneath without touching the category's
+        timer.set_work_duration(Duration::from_secs(30 * 60));
+        assert_eq!(timer.remaining, Duration::from_secs(40 * 60));
+
+        timer.set_category(None, None);
+        assert_eq!(timer.remaining, Duration::from_secs(20 * 60));
+    }
+
+    #[test]
+    fn test_jump_keeps_the_cycle_coherent() {
+        let (mut timer, clock) = timer();
+        timer.start();
+        timer.jump_to(4);
+        assert_eq!(timer.cycle_step(), Some(4));
+        assert_eq!(PomodoroTimer::step_name(4), "Work #3");
+
+        // Finishing the fourth work session after a jump still earns the long break
+        timer.jump_to(6);
+        clock.advance(timer.remaining);
+        timer.tick();
+        assert_eq!(timer.state, TimerState::LongBreak);
+
+        timer.toggle_pause();
+        timer.jump_to(3);
+        assert_eq!(timer.state, TimerState::Paused(Box::new(TimerState::ShortBreak { lap: 2 })));
+        assert_eq!(timer.cycle_step(), Some(3));
+        assert_eq!(timer.remaining, SHORT_BREAK_DURATION);
+    }
+
+    #[test]
+    fn test_cycle_remaining_runs_to_the_long_break() {
+        let (mut timer, clock) = timer();
+        assert_eq!(timer.cycle_remaining(), None);
+        timer.start();
+        // Four work sessions and three short breaks
+        assert_eq!(timer.cycle_remaining(), Some(WORK_DURATION * 4 + SHORT_BREAK_DURATION * 3));
+
+        // Counts down with the session, paused or not
+        clock.advance(Duration::from_secs(60));
+        timer.tick();
+        timer.toggle_pause();
+        assert_eq!(timer.cycle_remaining(), Some(WORK_DURATION * 4 + SHORT_BREAK_DURATION * 3 - Duration::from_secs(60)));
+
+        timer.jump_to(5);
+        assert_eq!(timer.cycle_remaining(), Some(SHORT_BREAK_DURATION + WORK_DURATION));
+        timer.jump_to(7);
+        assert_eq!(timer.cycle_remaining(), None);
+    }
+
+    #[test]
+    fn test_next_step_follows_the_cycle() {
+        let (mut timer, _) = timer();
+        assert_eq!(timer.next_step(), None);
+        timer.start();
+        assert_eq!(timer.next_step(), Some(1));
+        assert_eq!(PomodoroTimer::step_name(1), "Short Break #1");
+        timer.jump_to(6);
+        assert_eq!(timer.next_step(), Some(7));
+        timer.toggle_pause();
+        timer.advance_state();
+        assert_eq!(timer.next_step(), Some(0));
+        assert_eq!(timer.step_length(0), WORK_DURATION);
+    }
+
+    #[test]
+    fn test_many_small_ticks_do_not_drift() {
+        let (mut timer, clock) = timer();
+        timer.start();
+        for _ in 0..600 {
+            clock.advance(Duration::from_millis(100));
+            timer.tick();
+        }
+        assert_eq!(timer.remaining, WORK_DURATION - Duration::from_secs(60));
+
+        // A long gap (say, a suspended laptop) ends the session without skipping the break
+        clock.advance(Duration::from_secs(3 * 3600));
+        timer.tick();
+        assert_eq!(timer.state, TimerState::ShortBreak { lap: 1 });
+        assert_eq!(timer.remaining, SHORT_BREAK_DURATION);
+    }
+}
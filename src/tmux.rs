@@ -0,0 +1,165 @@
+//! tmux integration: while pomowise runs inside tmux, ask tmux to redraw its
+//! status line (`refresh-client -S`) whenever the time shown changes, so
+//! `#(pomowise tmux-status)` in `status-right` keeps up to the second instead of
+//! waiting for `status-interval`, and optionally color the window's pane borders
+//! by session type. Set up with the `tmux` key in config.json.
+
+use std::io;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::history::SessionKind;
+use crate::ipc;
+use crate::timer::{TimerSnapshot, TimerState};
+
+/// A status file older than this was left by an instance that is gone
+const STATUS_MAX_AGE: Duration = Duration::from_secs(2);
+
+/// tmux settings (the `tmux` key in config.json)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TmuxConfig {
+    /// Redraw the status line whenever the time shown changes
+    pub refresh_status: bool,
+    /// Color the pane borders of the window pomowise runs in by session type
+    pub border: bool,
+    /// Border color during work (any tmux color: `red`, `colour196`, `#ff5555`)
+    pub work_color: String,
+    /// Border color during breaks
+    pub break_color: String,
+}
+
+impl Default for TmuxConfig {
+    fn default() -> Self {
+        Self {
+            refresh_status: true,
+            border: false,
+            work_color: "red".to_string(),
+            break_color: "green".to_string(),
+        }
+    }
+}
+
+/// Keeps the tmux server pomowise runs under in step with the timer
+pub struct Tmux {
+    config: TmuxConfig,
+    /// The pane pomowise runs in ($TMUX_PANE), so the border lands on its window
+    pane: String,
+    /// Session and time last shown in the status line
+    shown: Option<(TimerState, u64)>,
+    /// Border color set, if any
+    border: Option<String>,
+}
+
+impl Tmux {
+    /// Only inside tmux, and only when configured
+    pub fn detect(config: Option<&TmuxConfig>) -> Option<Self> {
+        std::env::var_os("TMUX")?;
+        Some(Self {
+            config: config?.clone(),
+            pane: std::env::var("TMUX_PANE").unwrap_or_default(),
+            shown: None,
+            border: None,
+        })
+    }
+
+    /// Follow the running session, or none
+    pub fn update(&mut self, session: Option<&TimerSnapshot>) {
+        for args in self.changes(session) {
+            let _ = tmux(&args);
+        }
+    }
+
+    /// Give the borders back to the tmux config (quitting)
+    pub fn clear(&mut self) {
+        self.update(None);
+    }
+
+    /// tmux commands bringing it up to date with `session`
+    fn changes(&mut self, session: Option<&TimerSnapshot>) -> Vec<Vec<String>> {
+        let mut commands = Vec::new();
+
+        let shown = session.map(|s| (s.state.clone(), s.remaining_secs));
+        if self.config.refresh_status && shown != self.shown {
+            commands.push(vec!["refresh-client".to_string(), "-S".to_string()]);
+        }
+        self.shown = shown;
+
+        let kind = session.and_then(|s| SessionKind::from_state(&s.state)).filter(|_| self.config.border);
+        let border = kind.map(|kind| match kind {
+            SessionKind::Work => self.config.work_color.clone(),
+            _ => self.config.break_color.clone(),
+        });
+        if border != self.border {
+            let mut args = vec!["set-option".to_string(), "-w".to_string()];
+            if !self.pane.is_empty() {
+                args.extend(["-t".to_string(), self.pane.clone()]);
+            }
+            for option in ["pane-border-style", "pane-active-border-style"] {
+                let mut set = args.clone();
+                match &border {
+                    Some(color) => set.extend([option.to_string(), format!("fg={}", color)]),
+                    None => set.extend(["-u".to_string(), option.to_string()]),
+                }
+                commands.push(set);
+            }
+            self.border = border;
+        }
+        commands
+    }
+}
+
+fn tmux(args: &[String]) -> io::Result<()> {
+    Command::new("tmux")
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(drop)
+}
+
+/// Print the running session for `status-right`: `▶ Work 17:42`, nothing when
+/// no session is running
+pub fn run(_args: &[String]) -> io::Result<()> {
+    if let Some(snapshot) = ipc::read_live_status(STATUS_MAX_AGE).filter(|s| s.state != TimerState::Idle) {
+        let icon = if snapshot.is_paused { "⏸" } else { "▶" };
+        let name = snapshot.session_name.trim_end_matches(" (Paused)");
+        let secs = snapshot.remaining_secs;
+        println!("{} {} {:02}:{:02}", icon, name, secs / 60, secs % 60);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timer::PomodoroTimer;
+
+    #[test]
+    fn test_tmux_follows_the_session() {
+        let mut tmux = Tmux {
+            config: TmuxConfig { border: true, ..TmuxConfig::default() },
+            pane: "%3".to_string(),
+            shown: None,
+            border: None,
+        };
+        let mut timer = PomodoroTimer::new();
+        timer.start();
+        let snapshot = timer.snapshot();
+
+        let commands = tmux.changes(Some(&snapshot));
+        assert_eq!(commands[0], ["refresh-client", "-S"]);
+        assert_eq!(commands[2], ["set-option", "-w", "-t", "%3", "pane-active-border-style", "fg=red"]);
+        // Nothing changed, nothing sent
+        assert!(tmux.changes(Some(&snapshot)).is_empty());
+
+        timer.advance_state();
+        let commands = tmux.changes(Some(&timer.snapshot()));
+        assert_eq!(commands[1], ["set-option", "-w", "-t", "%3", "pane-border-style", "fg=green"]);
+        let commands = tmux.changes(None);
+        assert_eq!(commands[2], ["set-option", "-w", "-t", "%3", "-u", "pane-active-border-style"]);
+    }
+}
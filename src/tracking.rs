@@ -0,0 +1,437 @@
+//! Time tracking: mirror work sessions as Toggl or Clockify time entries
+//! An entry starts when a work session starts running and stops when it ends,
+//! described by the session's intention. Requests go through `curl` on a worker
+//! thread; whatever can't be sent (offline, API down) is queued in
+//! tracking-queue.jsonl and retried before the next request. What the service
+//! refuses outright (a 4xx, say for an entry deleted there) is dropped and noted
+//! in tracking.log, so it can't hold up the queue.
+
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+use chrono::{DateTime, Local, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::curl;
+use crate::paths;
+
+/// Entry description when the session has no intention
+const DEFAULT_DESCRIPTION: &str = "Pomodoro";
+
+/// Per-request limit, so an unreachable API can't hold up quitting for long
+const REQUEST_TIMEOUT_SECS: &str = "10";
+
+/// Longest quitting waits for the last requests; after that the worker is left
+/// behind, and an entry it didn't stop runs on at the service
+const QUIT_WAIT: Duration = Duration::from_secs(2);
+
+/// Time tracking service (the `tracking` key in config.json)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "service", rename_all = "lowercase")]
+pub enum TrackingConfig {
+    Toggl {
+        api_token: String,
+        workspace_id: u64,
+        #[serde(default)]
+        project_id: Option<u64>,
+    },
+    Clockify {
+        api_key: String,
+        workspace_id: String,
+        #[serde(default)]
+        project_id: Option<String>,
+    },
+}
+
+/// Why a request didn't go through
+#[derive(Debug)]
+enum SendError {
+    /// The service refused it (HTTP 4xx); sending it again won't help
+    Rejected,
+    /// Offline, timed out or a server error; worth another try later
+    Failed,
+}
+
+/// One API call, stored as-is in the offline queue
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Request {
+    method: String,
+    url: String,
+    body: Value,
+}
+
+impl Request {
+    fn new(method: &str, url: String, body: Value) -> Self {
+        Self {
+            method: method.to_string(),
+            url,
+            body,
+        }
+    }
+}
+
+fn timestamp(at: DateTime<Utc>) -> String {
+    at.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+impl TrackingConfig {
+    /// Create a running entry
+    fn start_request(&self, description: &str, start: DateTime<Utc>) -> Request {
+        match self {
+            TrackingConfig::Toggl {
+                workspace_id,
+                project_id,
+                ..
+            } => Request::new(
+                "POST",
+                format!("https://api.track.toggl.com/api/v9/workspaces/{}/time_entries", workspace_id),
+                json!({
+                    "created_with": "pomowise",
+                    "workspace_id": workspace_id,
+                    "project_id": project_id,
+                    "description": description,
+                    "start": timestamp(start),
+                    "duration": -1,
+                }),
+            ),
+            TrackingConfig::Clockify {
+                workspace_id,
+                project_id,
+                ..
+            } => Request::new(
+                "POST",
+                format!("https://api.clockify.me/api/v1/workspaces/{}/time-entries", workspace_id),
+                json!({
+                    "projectId": project_id,
+                    "description": description,
+                    "start": timestamp(start),
+                }),
+            ),
+        }
+    }
+
+    /// Stop the running entry `id`
+    fn stop_request(&self, id: &str, entry: &Entry, stop: DateTime<Utc>) -> Request {
+        match self {
+            TrackingConfig::Toggl { workspace_id, .. } => Request::new(
+                "PATCH",
+                format!(
+                    "https://api.track.toggl.com/api/v9/workspaces/{}/time_entries/{}/stop",
+                    workspace_id, id
+                ),
+                Value::Null,
+            ),
+            // Clockify's stop endpoint needs the user id; a full update doesn't
+            TrackingConfig::Clockify {
+                workspace_id,
+                project_id,
+                ..
+            } => Request::new(
+                "PUT",
+                format!("https://api.clockify.me/api/v1/workspaces/{}/time-entries/{}", workspace_id, id),
+                json!({
+                    "projectId": project_id,
+                    "description": entry.description,
+                    "start": timestamp(entry.start),
+                    "end": timestamp(stop),
+                }),
+            ),
+        }
+    }
+
+    /// Create an already finished entry (when the start never reached the API)
+    fn finished_request(&self, entry: &Entry, stop: DateTime<Utc>) -> Request {
+        let mut request = self.start_request(&entry.description, entry.start);
+        match self {
+            TrackingConfig::Toggl { .. } => {
+                request.body["stop"] = json!(timestamp(stop));
+                request.body["duration"] = json!((stop - entry.start).num_seconds().max(0));
+            }
+            TrackingConfig::Clockify { .. } => request.body["end"] = json!(timestamp(stop)),
+        }
+        request
+    }
+
+    /// Send a request; returns the response body. A rejected request is logged.
+    fn send(&self, request: &Request) -> Result<Value, SendError> {
+        let mut cmd = Command::new("curl");
+        // The status code goes after the body, to tell a refusal from an outage
+        cmd.args(["-s", "--max-time", REQUEST_TIMEOUT_SECS, "-w", "\n%{http_code}", "-X", &request.method])
+            .args(["-H", "Content-Type: application/json"]);
+        // The token goes to curl on stdin, not where `ps` shows it
+        let secret = match self {
+            TrackingConfig::Toggl { api_token, .. } => curl::option("user", &format!("{}:api_token", api_token)),
+            TrackingConfig::Clockify { api_key, .. } => curl::option("header", &format!("X-Api-Key: {}", api_key)),
+        };
+        if !request.body.is_null() {
+            cmd.arg("--data-binary").arg(request.body.to_string());
+        }
+        let output = curl::output(cmd.arg(&request.url), &secret).map_err(|_| SendError::Failed)?;
+        let body = String::from_utf8_lossy(&output.stdout);
+        let (contents, status) = body.rsplit_once('\n').unwrap_or(("", &body));
+
+        match status.trim() {
+            code if code.starts_with('2') => Ok(serde_json::from_str(contents).unwrap_or(Value::Null)),
+            code if code.starts_with('4') => {
+                let _ = append_log(&format!("{} {} returned {}, dropped: {}", request.method, request.url, code, contents));
+                Err(SendError::Rejected)
+            }
+            _ => Err(SendError::Failed),
+        }
+    }
+}
+
+/// Entry for the work session in progress
+struct Entry {
+    description: String,
+    start: DateTime<Utc>,
+    /// Id from the service; None when the start request didn't go through
+    id: Option<String>,
+}
+
+enum Message {
+    Start { description: String, at: DateTime<Utc> },
+    Stop { at: DateTime<Utc> },
+}
+
+/// Handle to the worker thread that talks to the service
+pub struct Tracker {
+    sender: Option<Sender<Message>>,
+    worker: Option<JoinHandle<()>>,
+    /// Whether an entry is running, as far as the app is concerned
+    running: bool,
+}
+
+impl Tracker {
+    pub fn new(config: &TrackingConfig) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let config = config.clone();
+        let worker = std::thread::spawn(move || work(&config, receiver));
+        Self {
+            sender: Some(sender),
+            worker: Some(worker),
+            running: false,
+        }
+    }
+
+    /// Start an entry for a work session (no-op if one is running)
+    pub fn start(&mut self, description: Option<&str>) {
+        if self.running {
+            return;
+        }
+        self.running = true;
+        let description = description
+            .filter(|d| !d.trim().is_empty())
+            .unwrap_or(DEFAULT_DESCRIPTION)
+            .to_string();
+        self.send(Message::Start {
+            description,
+            at: Utc::now(),
+        });
+    }
+
+    /// Stop the running entry (no-op if none is running)
+    pub fn stop(&mut self) {
+        if self.running {
+            self.running = false;
+            self.send(Message::Stop { at: Utc::now() });
+        }
+    }
+
+    fn send(&self, message: Message) {
+        if let Some(sender) = &self.sender {
+            let _ = sender.send(message);
+        }
+    }
+}
+
+/// Quitting mid-session stops the entry; waits a moment for the last requests
+/// (or their queueing), but not for a slow API
+impl Drop for Tracker {
+    fn drop(&mut self) {
+        self.stop();
+        self.sender = None;
+        let Some(worker) = self.worker.take() else { return };
+        let deadline = Instant::now() + QUIT_WAIT;
+        while !worker.is_finished() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        if worker.is_finished() {
+            let _ = worker.join();
+        }
+    }
+}
+
+fn work(config: &TrackingConfig, receiver: Receiver<Message>) {
+    let mut current: Option<Entry> = None;
+    for message in receiver {
+        // Catch up on anything queued while offline first, so entries stay in order
+        let online = flush_queue(config);
+        match message {
+            Message::Start { description, at } => {
+                let request = config.start_request(&description, at);
+                let id = if online {
+                    config.send(&request).ok().and_then(|response| entry_id(&response))
+                } else {
+                    None
+                };
+                current = Some(Entry {
+                    description,
+                    start: at,
+                    id,
+                });
+            }
+            Message::Stop { at } => {
+                let Some(entry) = current.take() else { continue };
+                let request = match &entry.id {
+                    Some(id) => config.stop_request(id, &entry, at),
+                    None => config.finished_request(&entry, at),
+                };
+                if !online || matches!(config.send(&request), Err(SendError::Failed)) {
+                    let _ = enqueue(&request);
+                }
+            }
+        }
+    }
+}
+
+/// Toggl ids are numbers, Clockify ids strings
+fn entry_id(response: &Value) -> Option<String> {
+    match &response["id"] {
+        Value::Number(id) => Some(id.to_string()),
+        Value::String(id) => Some(id.clone()),
+        _ => None,
+    }
+}
+
+fn queue_path() -> PathBuf {
+    paths::state_dir().join("tracking-queue.jsonl")
+}
+
+fn enqueue(request: &Request) -> io::Result<()> {
+    let path = queue_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{}", serde_json::to_string(request)?)
+}
+
+/// Add a timestamped line to `tracking.log` in the state directory
+fn append_log(text: &str) -> io::Result<()> {
+    let dir = paths::state_dir();
+    std::fs::create_dir_all(&dir)?;
+    let mut file = std::fs::OpenOptions::new().create(true).append(true).open(dir.join("tracking.log"))?;
+    writeln!(file, "{} {}", Local::now().format("%Y-%m-%d %H:%M:%S"), text)
+}
+
+/// Send queued requests in order; false if one failed (it and the rest stay queued)
+fn flush_queue(config: &TrackingConfig) -> bool {
+    let Ok(contents) = std::fs::read_to_string(queue_path()) else {
+        return true;
+    };
+    let queued: Vec<Request> = contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+    let rest = drain(queued, |request| config.send(request));
+
+    let lines: String = rest
+        .iter()
+        .filter_map(|request| serde_json::to_string(request).ok())
+        .map(|line| line + "\n")
+        .collect();
+    let _ = if lines.is_empty() {
+        std::fs::remove_file(queue_path())
+    } else {
+        std::fs::write(queue_path(), lines)
+    };
+    rest.is_empty()
+}
+
+/// Send `queued` in order, dropping what the service rejects; returns the
+/// requests left from the first one that couldn't be sent
+fn drain(queued: Vec<Request>, mut send: impl FnMut(&Request) -> Result<Value, SendError>) -> Vec<Request> {
+    let mut queued = queued.into_iter();
+    while let Some(request) = queued.next() {
+        if let Err(SendError::Failed) = send(&request) {
+            return std::iter::once(request).chain(queued).collect();
+        }
+    }
+    Vec::new()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entries_map_to_each_service() {
+        let start = DateTime::parse_from_rfc3339("2025-03-01T09:00:00Z").unwrap().with_timezone(&Utc);
+        let stop = start + chrono::Duration::minutes(25);
+        let entry = Entry {
+            description: "Write the report".to_string(),
+            start,
+            id: None,
+        };
+
+        let toggl = TrackingConfig::Toggl {
+            api_token: "token".to_string(),
+            workspace_id: 42,
+            project_id: None,
+        };
+        let request = toggl.start_request(&entry.description, start);
+        assert!(request.url.ends_with("/workspaces/42/time_entries"));
+        assert_eq!(request.body["description"], "Write the report");
+        assert_eq!(request.body["duration"], -1);
+        assert_eq!(toggl.finished_request(&entry, stop).body["duration"], 1500);
+        assert!(toggl.stop_request("7", &entry, stop).url.ends_with("/time_entries/7/stop"));
+        assert_eq!(entry_id(&json!({ "id": 7 })).as_deref(), Some("7"));
+
+        let clockify = TrackingConfig::Clockify {
+            api_key: "key".to_string(),
+            workspace_id: "ws".to_string(),
+            project_id: Some("p1".to_string()),
+        };
+        let request = clockify.stop_request("abc", &entry, stop);
+        assert_eq!(request.method, "PUT");
+        assert!(request.url.ends_with("/workspaces/ws/time-entries/abc"));
+        assert_eq!(request.body["start"], "2025-03-01T09:00:00Z");
+        assert_eq!(request.body["end"], "2025-03-01T09:25:00Z");
+        assert_eq!(request.body["projectId"], "p1");
+    }
+
+    #[test]
+    fn test_rejected_requests_leave_the_queue() {
+        let request = |url: &str| Request::new("PATCH", url.to_string(), Value::Null);
+        let queued = vec![request("deleted"), request("a"), request("b")];
+
+        // A 404 at the head is dropped and the rest still go
+        let mut sent = Vec::new();
+        let rest = drain(queued.clone(), |request| {
+            sent.push(request.url.clone());
+            if request.url == "deleted" {
+                Err(SendError::Rejected)
+            } else {
+                Ok(Value::Null)
+            }
+        });
+        assert!(rest.is_empty());
+        assert_eq!(sent, ["deleted", "a", "b"]);
+
+        // An outage keeps the request and everything after it
+        let rest = drain(queued.clone(), |request| {
+            if request.url == "a" {
+                Err(SendError::Failed)
+            } else {
+                Ok(Value::Null)
+            }
+        });
+        assert_eq!(rest, queued[1..]);
+    }
+}
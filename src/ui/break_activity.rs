@@ -0,0 +1,134 @@
+//! Break activity overlays: the breathing circle and the snake board
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Paragraph},
+};
+
+use crate::app::App;
+use crate::break_activity::{BreakActivity, Breathing, Snake, SNAKE_COLS, SNAKE_ROWS};
+
+pub fn draw(frame: &mut Frame, area: Rect, activity: &BreakActivity, app: &App) {
+    match activity {
+        BreakActivity::Breathing(breathing) => draw_breathing(frame, area, breathing, app),
+        BreakActivity::Snake(snake) => draw_snake(frame, area, snake, app),
+    }
+}
+
+/// Centered panel with the break's remaining time in the bottom border
+fn panel<'a>(title: &'a str, app: &App) -> Block<'a> {
+    let chrome = app.chrome();
+    let border = chrome.border(app.animation.current_theme.primary_color());
+    let secs = app.timer.remaining.as_secs();
+    Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border))
+        .title(title)
+        .title_style(Style::default().fg(border).bold())
+        .title_bottom(format!(" Break {:02}:{:02} ", secs / 60, secs % 60))
+        .style(Style::default().bg(chrome.panel))
+}
+
+fn centered(area: Rect, width: u16, height: u16) -> Rect {
+    let width = width.min(area.width);
+    let height = height.min(area.height);
+    Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    )
+}
+
+/// A circle that fills while breathing in and empties while breathing out
+fn draw_breathing(frame: &mut Frame, area: Rect, breathing: &Breathing, app: &App) {
+    let primary = app.animation.current_theme.primary_color();
+    let secondary = app.animation.current_theme.secondary_color();
+    let step = breathing.now();
+
+    let panel_area = centered(area, 40, 19);
+    frame.render_widget(Clear, panel_area);
+    let block = panel(" Breathe 4-7-8 ", app);
+    let inner = block.inner(panel_area);
+    frame.render_widget(block, panel_area);
+    if inner.height < 5 {
+        return;
+    }
+
+    // Circle above two lines of prompt; cells are about twice as tall as wide
+    let circle_area = Rect::new(inner.x, inner.y, inner.width, inner.height - 3);
+    let max_radius = (circle_area.height as f32 / 2.0 - 0.5).min(circle_area.width as f32 / 4.0 - 0.5);
+    let radius = (max_radius * step.size).max(0.6);
+    let cx = circle_area.x as f32 + circle_area.width as f32 / 2.0 - 0.5;
+    let cy = circle_area.y as f32 + circle_area.height as f32 / 2.0 - 0.5;
+
+    let buf = frame.buffer_mut();
+    for y in circle_area.top()..circle_area.bottom() {
+        for x in circle_area.left()..circle_area.right() {
+            let dx = (x as f32 - cx) / 2.0;
+            let dy = y as f32 - cy;
+            let dist = (dx * dx + dy * dy).sqrt();
+            if dist <= radius {
+                let color = if dist > radius - 1.0 { primary } else { secondary };
+                buf[(x, y)].set_char('█').set_fg(color);
+            }
+        }
+    }
+
+    let prompt_area = Rect::new(inner.x, inner.bottom() - 2, inner.width, 2);
+    frame.render_widget(
+        Paragraph::new(vec![
+            Line::from(Span::styled(
+                format!("{}  {}", step.phase.prompt(), step.secs_left),
+                Style::default().fg(primary).bold(),
+            )),
+            Line::from(Span::styled(
+                format!("breaths: {}   b / Esc: close", step.cycles),
+                Style::default().fg(app.chrome().muted),
+            )),
+        ])
+        .alignment(Alignment::Center),
+        prompt_area,
+    );
+}
+
+/// The board, two columns per cell
+fn draw_snake(frame: &mut Frame, area: Rect, snake: &Snake, app: &App) {
+    let primary = app.animation.current_theme.primary_color();
+    let panel_area = centered(area, SNAKE_COLS * 2 + 2, SNAKE_ROWS + 3);
+    frame.render_widget(Clear, panel_area);
+    let title = format!(" Snake  score {} ", snake.score());
+    let block = panel(&title, app);
+    let inner = block.inner(panel_area);
+    frame.render_widget(block, panel_area);
+
+    if inner.width < SNAKE_COLS * 2 || inner.height < SNAKE_ROWS + 1 {
+        frame.render_widget(
+            Paragraph::new("Make the terminal larger to play").alignment(Alignment::Center),
+            inner,
+        );
+        return;
+    }
+
+    let buf = frame.buffer_mut();
+    let mut put = |(x, y): (u16, u16), ch: &str, color: Color| {
+        buf.set_string(inner.x + x * 2, inner.y + y, ch, Style::default().fg(color));
+    };
+    put(snake.food(), "◆ ", Color::Rgb(255, 90, 90));
+    for (i, &cell) in snake.body().iter().enumerate() {
+        let color = if i == 0 { Color::White } else { primary };
+        put(cell, "██", color);
+    }
+
+    let help = if snake.is_alive() {
+        "arrows/hjkl: steer   g / Esc: close"
+    } else {
+        "Game over - Enter: again   g / Esc: close"
+    };
+    frame.render_widget(
+        Paragraph::new(help)
+            .style(Style::default().fg(app.chrome().muted))
+            .alignment(Alignment::Center),
+        Rect::new(inner.x, inner.y + SNAKE_ROWS, inner.width, 1),
+    );
+}
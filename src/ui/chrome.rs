@@ -0,0 +1,196 @@
+//! Colors of the UI chrome: boxes, dialogs, toasts and hints drawn over the themes
+//! Picked separately from the animation theme (`chrome` in config, `:chrome`),
+//! so the boxes can stay readable whatever is animating behind them.
+//! With `session_accents` on, borders take a color per session type instead of
+//! the theme's, warm for work and calm for breaks, to tell them apart at a glance.
+
+use ratatui::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::timer::TimerState;
+
+/// Tasks and pomodoro counts past their estimate, in every palette
+pub const OVERRUN_COLOR: Color = Color::Rgb(230, 120, 80);
+
+/// Named chrome palette
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Chrome {
+    /// Dark blue boxes with borders in the theme color
+    #[default]
+    Midnight,
+    /// Neutral grey boxes, for themes that clash with the blue
+    Slate,
+    /// Black boxes, white text and borders, bright hints
+    HighContrast,
+}
+
+/// The colors a chrome palette draws with
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Palette {
+    /// Box borders and titles; unset follows the theme's primary color
+    pub border: Option<Color>,
+    /// Fill of the overlay boxes (clock, session info, side panes, progress bar)
+    pub background: Color,
+    /// Fill of dialogs, toasts and full-screen panels
+    pub panel: Color,
+    /// Body text in boxes and dialogs
+    pub text: Color,
+    /// Help lines and secondary text in dialogs
+    pub muted: Color,
+    /// Key hints and quiet labels
+    pub hint: Color,
+}
+
+impl Palette {
+    /// Border color over a theme whose primary color is `accent`
+    pub fn border(&self, accent: Color) -> Color {
+        self.border.unwrap_or(accent)
+    }
+}
+
+impl Chrome {
+    pub fn all() -> &'static [Chrome] {
+        &[Chrome::Midnight, Chrome::Slate, Chrome::HighContrast]
+    }
+
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Chrome::Midnight => "Midnight",
+            Chrome::Slate => "Slate",
+            Chrome::HighContrast => "High Contrast",
+        }
+    }
+
+    /// Command-friendly identifier, e.g. "high-contrast"
+    pub fn slug(&self) -> String {
+        self.name().to_lowercase().replace(' ', "-")
+    }
+
+    /// Look up a palette by slug or slug prefix ("high", "slate")
+    pub fn from_name(query: &str) -> Option<Chrome> {
+        let query = query.trim().to_lowercase().replace(' ', "-");
+        if query.is_empty() {
+            return None;
+        }
+        let palettes = Self::all();
+        palettes
+            .iter()
+            .find(|c| c.slug() == query)
+            .or_else(|| palettes.iter().find(|c| c.slug().starts_with(&query)))
+            .copied()
+    }
+
+    pub fn palette(&self) -> Palette {
+        match self {
+            Chrome::Midnight => Palette {
+                border: None,
+                background: Color::Rgb(10, 10, 20),
+                panel: Color::Rgb(15, 15, 25),
+                text: Color::White,
+                muted: Color::Rgb(150, 150, 170),
+                hint: Color::Rgb(80, 80, 100),
+            },
+            Chrome::Slate => Palette {
+                border: None,
+                background: Color::Rgb(28, 30, 33),
+                panel: Color::Rgb(36, 38, 42),
+                text: Color::Rgb(230, 230, 230),
+                muted: Color::Rgb(165, 168, 172),
+                hint: Color::Rgb(115, 118, 124),
+            },
+            Chrome::HighContrast => Palette {
+                border: Some(Color::Rgb(255, 255, 255)),
+                background: Color::Rgb(0, 0, 0),
+                panel: Color::Rgb(0, 0, 0),
+                text: Color::Rgb(255, 255, 255),
+                muted: Color::Rgb(230, 230, 230),
+                hint: Color::Rgb(255, 230, 0),
+            },
+        }
+    }
+}
+
+/// Border colors by session type (`session_accents` in config.json)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionAccents {
+    pub enabled: bool,
+    /// Colors as `"#rrggbb"` or a name (`"red"`, `"lightblue"`)
+    pub work: String,
+    pub short_break: String,
+    pub long_break: String,
+}
+
+impl Default for SessionAccents {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            work: "#e8743b".to_string(),
+            short_break: "#3bb38a".to_string(),
+            long_break: "#4a90d9".to_string(),
+        }
+    }
+}
+
+impl SessionAccents {
+    /// Accent for the session in `state` (paused ones keep theirs); None when
+    /// off, idle or the color doesn't parse, leaving the chrome's own border
+    pub fn color(&self, state: &TimerState) -> Option<Color> {
+        if !self.enabled {
+            return None;
+        }
+        let color = match state {
+            TimerState::Idle => return None,
+            TimerState::Work { .. } => &self.work,
+            TimerState::ShortBreak { .. } => &self.short_break,
+            TimerState::LongBreak => &self.long_break,
+            TimerState::Paused(inner) => return self.color(inner),
+        };
+        color.parse().ok()
+    }
+
+    /// Colors that don't parse, to point out at startup; none while off
+    pub fn unreadable(&self) -> Vec<&str> {
+        if !self.enabled {
+            return Vec::new();
+        }
+        [&self.work, &self.short_break, &self.long_break]
+            .into_iter()
+            .filter(|color| color.parse::<Color>().is_err())
+            .map(String::as_str)
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chrome_lookup() {
+        assert_eq!(Chrome::from_name("high"), Some(Chrome::HighContrast));
+        assert_eq!(Chrome::from_name("High Contrast"), Some(Chrome::HighContrast));
+        assert_eq!(Chrome::from_name("slate"), Some(Chrome::Slate));
+        assert_eq!(Chrome::from_name("neon"), None);
+        assert_eq!(Chrome::default().palette().border(Color::Red), Color::Red);
+        assert_eq!(Chrome::HighContrast.palette().border(Color::Red), Color::Rgb(255, 255, 255));
+    }
+
+    #[test]
+    fn test_session_accents() {
+        let accents = SessionAccents { enabled: true, short_break: "green".to_string(), ..Default::default() };
+        let paused = TimerState::Paused(Box::new(TimerState::Work { lap: 1 }));
+        assert_eq!(accents.color(&paused), Some(Color::Rgb(0xe8, 0x74, 0x3b)));
+        assert_eq!(accents.color(&TimerState::ShortBreak { lap: 1 }), Some(Color::Green));
+        assert_eq!(accents.color(&TimerState::Idle), None);
+        assert_eq!(SessionAccents::default().color(&paused), None);
+
+        let typo = SessionAccents { long_break: "bleu".to_string(), ..accents.clone() };
+        assert_eq!(typo.unreadable(), ["bleu"]);
+        assert_eq!(typo.color(&TimerState::LongBreak), None);
+        assert!(accents.unreadable().is_empty());
+        assert!(SessionAccents { enabled: false, ..typo }.unreadable().is_empty());
+    }
+}
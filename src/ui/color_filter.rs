@@ -0,0 +1,213 @@
+//! Color post-processing over the finished frame
+//! Runs after every screen has drawn, so themes, overlays and widgets are all
+//! filtered the same way without knowing about it.
+
+use ratatui::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Strongest dimming allowed, in percent (keeps the digits readable)
+pub const MAX_DIM: u8 = 80;
+
+/// Range of the brightness hotkeys (`[` / `]`), in percent
+pub const MIN_BRIGHTNESS: u8 = 25;
+pub const MAX_BRIGHTNESS: u8 = 100;
+
+/// Color filter applied to every cell
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ColorFilter {
+    #[default]
+    None,
+    /// Warm tint for late-night use (less blue)
+    NightShift,
+    Grayscale,
+    Sepia,
+    /// Colorblind-safe remapping for red-green (green-weak) vision
+    Deuteranopia,
+    /// Colorblind-safe remapping for red-green (red-weak) vision
+    Protanopia,
+}
+
+impl ColorFilter {
+    pub fn all() -> &'static [ColorFilter] {
+        &[
+            ColorFilter::None,
+            ColorFilter::NightShift,
+            ColorFilter::Grayscale,
+            ColorFilter::Sepia,
+            ColorFilter::Deuteranopia,
+            ColorFilter::Protanopia,
+        ]
+    }
+
+    /// Display name
+    pub fn name(&self) -> &'static str {
+        match self {
+            ColorFilter::None => "None",
+            ColorFilter::NightShift => "Night Shift",
+            ColorFilter::Grayscale => "Grayscale",
+            ColorFilter::Sepia => "Sepia",
+            ColorFilter::Deuteranopia => "Deuteranopia",
+            ColorFilter::Protanopia => "Protanopia",
+        }
+    }
+
+    /// Command-friendly identifier, e.g. "night-shift"
+    pub fn slug(&self) -> String {
+        self.name().to_lowercase().replace(' ', "-")
+    }
+
+    /// Look up a filter by slug or slug prefix ("night", "gray")
+    pub fn from_name(query: &str) -> Option<ColorFilter> {
+        let query = query.trim().to_lowercase().replace(' ', "-");
+        if query.is_empty() {
+            return None;
+        }
+        let filters = Self::all();
+        filters
+            .iter()
+            .find(|f| f.slug() == query)
+            .or_else(|| filters.iter().find(|f| f.slug().starts_with(&query)))
+            .copied()
+    }
+
+    /// Next filter in the cycle
+    pub fn next(&self) -> ColorFilter {
+        let filters = Self::all();
+        let i = filters.iter().position(|f| f == self).unwrap_or(0);
+        filters[(i + 1) % filters.len()]
+    }
+
+    fn apply_rgb(&self, (r, g, b): (f32, f32, f32)) -> (f32, f32, f32) {
+        match self {
+            ColorFilter::None => (r, g, b),
+            ColorFilter::NightShift => (r, g * 0.85, b * 0.6),
+            ColorFilter::Grayscale => {
+                let luma = 0.299 * r + 0.587 * g + 0.114 * b;
+                (luma, luma, luma)
+            }
+            ColorFilter::Sepia => (
+                0.393 * r + 0.769 * g + 0.189 * b,
+                0.349 * r + 0.686 * g + 0.168 * b,
+                0.272 * r + 0.534 * g + 0.131 * b,
+            ),
+            ColorFilter::Deuteranopia | ColorFilter::Protanopia => daltonize(*self, (r, g, b)),
+        }
+    }
+}
+
+/// Filter settings (the `color_filter` key in config.json)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ColorFilterConfig {
+    pub filter: ColorFilter,
+    /// Darken every color by this many percent (0 = off, at most `MAX_DIM`)
+    pub dim: u8,
+}
+
+/// Filter every cell's foreground and background in place; `brightness` (percent,
+/// from the hotkeys) scales every color on top of the configured dimming
+pub fn apply(buf: &mut Buffer, config: &ColorFilterConfig, brightness: u8) {
+    let brightness = brightness.clamp(MIN_BRIGHTNESS, MAX_BRIGHTNESS) as f32 / 100.0
+        * (1.0 - config.dim.min(MAX_DIM) as f32 / 100.0);
+    if config.filter == ColorFilter::None && brightness >= 1.0 {
+        return;
+    }
+    let area = buf.area;
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let cell = &mut buf[(x, y)];
+            cell.fg = filter_color(cell.fg, config.filter, brightness);
+            cell.bg = filter_color(cell.bg, config.filter, brightness);
+        }
+    }
+}
+
+fn filter_color(color: Color, filter: ColorFilter, brightness: f32) -> Color {
+    // Reset keeps the terminal's own colors; indexed colors have no known RGB
+    let Some((r, g, b)) = to_rgb(color) else {
+        return color;
+    };
+    let (r, g, b) = filter.apply_rgb((r as f32, g as f32, b as f32));
+    let channel = |v: f32| (v * brightness).round().clamp(0.0, 255.0) as u8;
+    Color::Rgb(channel(r), channel(g), channel(b))
+}
+
+/// RGB for truecolor and the 16 named colors (xterm defaults)
+fn to_rgb(color: Color) -> Option<(u8, u8, u8)> {
+    Some(match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Black => (0, 0, 0),
+        Color::Red => (205, 0, 0),
+        Color::Green => (0, 205, 0),
+        Color::Yellow => (205, 205, 0),
+        Color::Blue => (0, 0, 238),
+        Color::Magenta => (205, 0, 205),
+        Color::Cyan => (0, 205, 205),
+        Color::Gray => (229, 229, 229),
+        Color::DarkGray => (127, 127, 127),
+        Color::LightRed => (255, 0, 0),
+        Color::LightGreen => (0, 255, 0),
+        Color::LightYellow => (255, 255, 0),
+        Color::LightBlue => (92, 92, 255),
+        Color::LightMagenta => (255, 0, 255),
+        Color::LightCyan => (0, 255, 255),
+        Color::White => (255, 255, 255),
+        Color::Reset | Color::Indexed(_) => return None,
+    })
+}
+
+/// Daltonize: simulate the deficiency in LMS space, then shift the lost
+/// red-green difference into channels that are still distinguishable
+fn daltonize(filter: ColorFilter, (r, g, b): (f32, f32, f32)) -> (f32, f32, f32) {
+    let l = 17.8824 * r + 43.5161 * g + 4.11935 * b;
+    let m = 3.45565 * r + 27.1554 * g + 3.86714 * b;
+    let s = 0.0299566 * r + 0.184309 * g + 1.46709 * b;
+
+    let (l, m) = match filter {
+        ColorFilter::Protanopia => (2.02344 * m - 2.52581 * s, m),
+        _ => (l, 0.494207 * l + 1.24827 * s),
+    };
+
+    let sim_r = 0.08094445 * l - 0.13050441 * m + 0.11672107 * s;
+    let sim_g = -0.010248533 * l + 0.05401933 * m - 0.11361471 * s;
+    let sim_b = -0.00036529694 * l - 0.0041216147 * m + 0.6935114 * s;
+
+    let (err_r, err_g, err_b) = (r - sim_r, g - sim_g, b - sim_b);
+    (r, g + 0.7 * err_r + err_g, b + 0.7 * err_r + err_b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_filters_recolor_cells() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 2, 1));
+        buf[(0, 0)].set_fg(Color::Rgb(200, 100, 50)).set_bg(Color::Reset);
+        buf[(1, 0)].set_fg(Color::White);
+
+        let config = ColorFilterConfig {
+            filter: ColorFilter::Grayscale,
+            dim: 50,
+        };
+        apply(&mut buf, &config, 100);
+
+        assert_eq!(buf[(0, 0)].fg, Color::Rgb(62, 62, 62));
+        assert_eq!(buf[(0, 0)].bg, Color::Reset);
+        assert_eq!(buf[(1, 0)].fg, Color::Rgb(128, 128, 128));
+
+        // The brightness hotkeys stack on the configured dimming
+        apply(&mut buf, &ColorFilterConfig::default(), 50);
+        assert_eq!(buf[(1, 0)].fg, Color::Rgb(64, 64, 64));
+    }
+
+    #[test]
+    fn test_daltonize_separates_red_and_green() {
+        let red = daltonize(ColorFilter::Deuteranopia, (255.0, 0.0, 0.0));
+        let green = daltonize(ColorFilter::Deuteranopia, (0.0, 255.0, 0.0));
+        // Red picks up blue so it no longer collapses onto green
+        assert!(red.2 > green.2 + 50.0);
+        assert_eq!(ColorFilter::from_name("night"), Some(ColorFilter::NightShift));
+    }
+}
@@ -0,0 +1,108 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::animation::{digits, DigitFont};
+use crate::app::App;
+
+/// Card size: widest font rendering "12:34" plus label and border
+const CARD_WIDTH: u16 = 47;
+const CARD_HEIGHT: u16 = 14;
+
+/// Cards per row at the given terminal width
+pub fn columns(width: u16) -> usize {
+    (width.saturating_sub(2) / CARD_WIDTH).max(1) as usize
+}
+
+pub fn draw(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    let primary = app.animation.current_theme.primary_color();
+    let chrome = app.chrome();
+
+    frame.render_widget(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(chrome.border(primary)))
+            .title(" Fonts ")
+            .title_style(Style::default().fg(chrome.border(primary)).bold())
+            .title_bottom(" ←↓↑→ Navigate  Enter Select  Esc Back ")
+            .style(Style::default().bg(chrome.panel)),
+        area,
+    );
+
+    let fonts = DigitFont::every();
+    let columns = columns(area.width);
+    let visible_rows = (area.height.saturating_sub(2) / CARD_HEIGHT).max(1) as usize;
+
+    // Scroll just enough to keep the selected row on screen
+    let selected_row = app.gallery_index / columns;
+    let first_row = selected_row.saturating_sub(visible_rows - 1);
+
+    let grid_width = CARD_WIDTH * columns as u16;
+    let grid_x = area.x + area.width.saturating_sub(grid_width) / 2;
+
+    for (i, &font) in fonts.iter().enumerate() {
+        let row = i / columns;
+        if row < first_row || row >= first_row + visible_rows {
+            continue;
+        }
+        let x = grid_x + (i % columns) as u16 * CARD_WIDTH;
+        let y = area.y + 1 + (row - first_row) as u16 * CARD_HEIGHT;
+        let card = Rect::new(x, y, CARD_WIDTH, CARD_HEIGHT).intersection(area);
+        let selected = i == app.gallery_index;
+        let current = font == app.animation.current_font;
+        draw_card(frame, card, font, selected, current, app);
+    }
+}
+
+fn draw_card(
+    frame: &mut Frame,
+    card: Rect,
+    font: DigitFont,
+    selected: bool,
+    current: bool,
+    app: &App,
+) {
+    let primary = app.animation.current_theme.primary_color();
+    let secondary = app.animation.current_theme.secondary_color();
+    let border_color = if selected { primary } else { Color::Rgb(50, 50, 65) };
+    let label = if current {
+        format!(" {} (current) ", font.name())
+    } else {
+        format!(" {} ", font.name())
+    };
+    let label_style = if selected {
+        Style::default().fg(primary).bold()
+    } else {
+        Style::default().fg(app.chrome().muted)
+    };
+
+    frame.render_widget(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border_color))
+            .title(label)
+            .title_style(label_style),
+        card,
+    );
+
+    // Only render the sample when the whole font fits inside the card
+    let inner = Rect::new(
+        card.x + 1,
+        card.y + 1,
+        card.width.saturating_sub(2),
+        card.height.saturating_sub(2),
+    );
+    let sample_width = font.width() * 4 + font.colon_width() + 4;
+    if inner.width >= sample_width && inner.height >= font.height() {
+        digits::render_time_with_font(frame, inner, 12, 34, primary, secondary, font);
+    } else {
+        frame.render_widget(
+            Paragraph::new("12:34")
+                .style(Style::default().fg(primary))
+                .alignment(Alignment::Center),
+            Rect::new(inner.x, inner.y + inner.height / 2, inner.width, 1.min(inner.height)),
+        );
+    }
+}
@@ -0,0 +1,207 @@
+//! Glyph profiles (`glyphs` in config.json) for terminals and fonts that can't
+//! show everything the themes draw: emoji, katakana and rare symbols come out
+//! as boxes or at the wrong width on Windows' console host, the Linux console
+//! and many bitmap fonts. After a frame is drawn, characters outside the
+//! profile are swapped for the current theme's own stand-ins, or else general
+//! ones. The basic profile also brings 24-bit colors down to the 256-color palette.
+
+use ratatui::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::animation::themes::ThemeType;
+
+/// Which characters the terminal can show
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Glyphs {
+    /// From the terminal and locale (see `detect`)
+    #[default]
+    Auto,
+    /// Everything, emoji included
+    Full,
+    /// Box drawing, blocks, shades and the common symbols of Windows console fonts
+    Extended,
+    /// ASCII (and accented letters)
+    Basic,
+}
+
+impl Glyphs {
+    /// The profile to use, `Auto` worked out from the environment
+    pub fn resolve(self) -> Glyphs {
+        match self {
+            Glyphs::Auto => detect(|name| std::env::var(name).ok()),
+            profile => profile,
+        }
+    }
+}
+
+/// The profile for the terminal described by `var` (an environment lookup)
+fn detect(var: impl Fn(&str) -> Option<String>) -> Glyphs {
+    let set = |name| var(name).is_some_and(|v| !v.is_empty());
+    if cfg!(target_os = "windows") {
+        // Windows Terminal, VS Code and the like draw everything; the console host's fonts don't
+        return if set("WT_SESSION") || set("TERM_PROGRAM") { Glyphs::Full } else { Glyphs::Extended };
+    }
+    // The Linux console's fonts have 256 or 512 glyphs
+    if var("TERM").as_deref() == Some("linux") {
+        return Glyphs::Basic;
+    }
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"].into_iter().find_map(|name| var(name).filter(|v| !v.is_empty()));
+    match locale.map(|l| l.to_uppercase()) {
+        Some(locale) if !locale.contains("UTF-8") && !locale.contains("UTF8") => Glyphs::Basic,
+        _ => Glyphs::Full,
+    }
+}
+
+/// Swap every character `profile` can't show, preferring `theme`'s stand-ins
+pub fn apply(buf: &mut Buffer, profile: Glyphs, theme: ThemeType) {
+    if matches!(profile, Glyphs::Full | Glyphs::Auto) {
+        return;
+    }
+    let basic = profile == Glyphs::Basic;
+    let area = buf.area;
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let cell = &mut buf[(x, y)];
+            let mut chars = cell.symbol().chars();
+            if let (Some(ch), None) = (chars.next(), chars.next()) {
+                if let Some(stand_in) = stand_in(ch, basic, theme) {
+                    cell.set_char(stand_in);
+                }
+            } else if cell.symbol().chars().count() > 1 {
+                // Grapheme clusters (emoji with modifiers) are never safe
+                cell.set_char(if basic { '*' } else { '☼' });
+            }
+            if basic {
+                cell.fg = to_palette(cell.fg);
+                cell.bg = to_palette(cell.bg);
+            }
+        }
+    }
+}
+
+/// What to draw instead of `ch`, or None when it can stay
+fn stand_in(ch: char, basic: bool, theme: ThemeType) -> Option<char> {
+    if ch.is_ascii() || (basic && ch.is_alphabetic() && (ch as u32) < 0x180) || (!basic && shows_extended(ch)) {
+        return None;
+    }
+    if let Some(&(_, extended, ascii)) = theme.glyph_fallbacks().iter().find(|(glyph, _, _)| *glyph == ch) {
+        return Some(if basic { ascii } else { extended }).filter(|&c| c != ch);
+    }
+    if basic {
+        return Some(ascii_for(ch));
+    }
+    Some(match ch {
+        '◆' | '◇' | '◈' | '❖' | '⬥' | '♦' => '♦',
+        '◉' | '◎' | '◐' | '◑' => '●',
+        '◯' | '◌' | '⬡' => '○',
+        '▶' | '▸' => '►',
+        '◢' | '◣' | '◤' | '◥' | '▰' => '█',
+        '▱' => '░',
+        '⏸' => '‖',
+        '✓' => '√',
+        '⇄' => '↔',
+        '∗' => '*',
+        '∘' => '°',
+        '∧' => '^',
+        '∼' | '∽' | '∿' => '~',
+        // Braille dots by how many are raised, as shades
+        '\u{2800}'..='\u{28ff}' => match (ch as u32 - 0x2800).count_ones() {
+            0 => ' ',
+            1..=2 => '░',
+            3..=5 => '▒',
+            _ => '▓',
+        },
+        _ => '*',
+    })
+}
+
+/// Characters of WGL4, the set Windows console fonts cover: Latin-1, box
+/// drawing, blocks and shades, and a few dozen symbols
+fn shows_extended(ch: char) -> bool {
+    matches!(ch, '\u{a0}'..='\u{17f}' | '\u{2500}'..='\u{259f}')
+        || "–—‘’“”•…‖↑↓←→↔↕∙√∞∟∩≈≡≤≥⌂■□▪▫▬▲►▼◄○●◘◙◊◦☺☻☼♀♂♠♣♥♦♪♫".contains(ch)
+}
+
+fn ascii_for(ch: char) -> char {
+    match ch {
+        '─' | '━' | '═' | '╌' | '╴' | '╶' | '╸' | '╺' | '▬' | '–' | '—' | '▔' => '-',
+        '│' | '┃' | '║' | '╵' | '╷' | '╹' | '╻' | '▏' | '▕' | '‖' | '⏸' => '|',
+        '╱' => '/',
+        '╲' => '\\',
+        '╳' | '×' => 'x',
+        '\u{2500}'..='\u{257f}' => '+',
+        '░' | '·' | '∙' | '•' | '◦' | '…' => '.',
+        '▒' => ':',
+        '\u{2580}'..='\u{259f}' | '■' | '▪' | '◢' | '◣' | '◤' | '◥' | '▰' => '#',
+        '○' | '◯' | '◌' | '◎' | '∘' | '°' | '⬡' | '□' | '▫' | '▱' => 'o',
+        '●' | '◉' | '◐' | '◑' => '@',
+        '▲' | '↑' | '∧' => '^',
+        '▼' | '↓' | '✓' => 'v',
+        '►' | '▶' | '▸' | '→' => '>',
+        '◄' | '←' => '<',
+        '↔' | '⇄' => '=',
+        '≈' | '∼' | '∽' | '∿' => '~',
+        '◆' | '◇' | '◈' | '❖' | '⬥' | '♦' | '♣' | '♠' => '+',
+        '‘' | '’' => '\'',
+        '“' | '”' => '"',
+        '⚠' => '!',
+        '\u{2800}' => ' ',
+        '\u{2801}'..='\u{28ff}' => ':',
+        _ => '*',
+    }
+}
+
+/// The nearest color of the 256-color palette's cube or gray ramp
+fn to_palette(color: Color) -> Color {
+    let Color::Rgb(r, g, b) = color else {
+        return color;
+    };
+    const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+    let level = |v: u8| (0..6).min_by_key(|&i| LEVELS[i].abs_diff(v)).unwrap_or(0);
+    let (ri, gi, bi) = (level(r), level(g), level(b));
+    let cube = (LEVELS[ri], LEVELS[gi], LEVELS[bi]);
+    let gray_index = ((r as u16 + g as u16 + b as u16) / 3).saturating_sub(3) / 10;
+    let gray_index = gray_index.min(23) as u8;
+    let gray = 8 + gray_index * 10;
+    let distance = |(cr, cg, cb): (u8, u8, u8)| {
+        [(cr, r), (cg, g), (cb, b)].iter().map(|&(a, b)| (a.abs_diff(b) as u32).pow(2)).sum::<u32>()
+    };
+    if distance((gray, gray, gray)) < distance(cube) {
+        Color::Indexed(232 + gray_index)
+    } else {
+        Color::Indexed(16 + 36 * ri as u8 + 6 * gi as u8 + bi as u8)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glyph_stand_ins() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 4, 1));
+        buf[(0, 0)].set_char('🍂').set_fg(Color::Rgb(180, 100, 40));
+        buf[(1, 0)].set_char('█');
+        buf[(2, 0)].set_char('⚘');
+        buf[(3, 0)].set_char('ア');
+
+        let mut extended = buf.clone();
+        apply(&mut extended, Glyphs::Extended, ThemeType::Nature);
+        let symbols: String = (0..4).map(|x| extended[(x, 0)].symbol().to_string()).collect();
+        assert_eq!(symbols, "♣█**");
+        assert_eq!(extended[(0, 0)].fg, Color::Rgb(180, 100, 40));
+
+        apply(&mut buf, Glyphs::Basic, ThemeType::Matrix);
+        let symbols: String = (0..4).map(|x| buf[(x, 0)].symbol().to_string()).collect();
+        assert_eq!(symbols, "*#*0");
+        assert_eq!(buf[(0, 0)].fg, Color::Indexed(130));
+
+        let env = |vars: &'static [(&'static str, &'static str)]| {
+            move |name: &str| vars.iter().find(|(key, _)| *key == name).map(|(_, value)| value.to_string())
+        };
+        assert_eq!(detect(env(&[("TERM", "linux")])), Glyphs::Basic);
+        assert_eq!(detect(env(&[("LANG", "C")])), if cfg!(windows) { Glyphs::Extended } else { Glyphs::Basic });
+        assert_eq!(detect(env(&[("LANG", "en_US.UTF-8"), ("TERM", "xterm-256color")])), if cfg!(windows) { Glyphs::Extended } else { Glyphs::Full });
+    }
+}
@@ -1,27 +1,54 @@
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph},
 };
 
+use crate::animation::themes::ThemeType;
 use crate::app::{App, MenuItem};
+use crate::ui::widgets::logo::{Logo, LOGO_HEIGHT, LOGO_WIDTH};
+use crate::ui::widgets::stats_glance::StatsGlance;
+use crate::ui::widgets::toast::ToastView;
+
+/// Rows under the panel for the banner: gap, label, preview strip
+const BANNER_HEIGHT: u16 = 5;
+
+/// Rows under the panel for today's stats: gap, two lines
+const GLANCE_HEIGHT: u16 = 3;
 
 pub fn draw(frame: &mut Frame, app: &App) {
     let area = frame.area();
 
     // Render animated theme preview as background
-    app.animation
-        .current_theme
-        .render_background(frame, area, app.animation.frame_index);
+    app.animation.render_background(frame, area);
 
-    // Calculate center position
-    let center_x = area.width / 2;
-    let center_y = area.height / 2;
+    // One row per entry, plus what "Continue cycle" picks up under it
+    let items = app.menu_items();
+    let continue_label = app.continue_label();
+    let menu_rows = items.len() as u16 + continue_label.is_some() as u16;
 
-    // Draw semi-transparent menu panel
     let panel_width = 30u16.min(area.width.saturating_sub(4));
-    let panel_height = 12u16.min(area.height.saturating_sub(4));
+    let panel_height = (menu_rows + 8).min(area.height.saturating_sub(4));
+
+    // Stack logo, panel, today's stats and theme-of-the-day banner, dropping
+    // extras that don't fit
+    let logo_rows = LOGO_HEIGHT + 1;
+    let show_logo =
+        area.width >= LOGO_WIDTH + 2 && area.height >= panel_height + logo_rows + 4;
+    let mut stack_height = panel_height + if show_logo { logo_rows } else { 0 };
+    let glance = app.day_summary.as_ref().filter(|_| app.config.menu_stats).map(StatsGlance::new);
+    let show_glance = glance.is_some() && area.height >= stack_height + GLANCE_HEIGHT + 4;
+    if show_glance {
+        stack_height += GLANCE_HEIGHT;
+    }
+    let show_banner = area.height >= stack_height + BANNER_HEIGHT + 4;
+    if show_banner {
+        stack_height += BANNER_HEIGHT;
+    }
+
+    let center_x = area.width / 2;
+    let stack_y = area.height.saturating_sub(stack_height) / 2;
     let panel_x = center_x.saturating_sub(panel_width / 2);
-    let panel_y = center_y.saturating_sub(panel_height / 2);
+    let panel_y = stack_y + if show_logo { logo_rows } else { 0 };
 
     let panel_area = Rect::new(
         panel_x,
@@ -32,19 +59,45 @@ pub fn draw(frame: &mut Frame, app: &App) {
 
     // Draw panel background with theme-colored border
     let primary = app.animation.current_theme.primary_color();
-    let bg_color = Color::Rgb(15, 15, 25);
+    let chrome = app.chrome();
 
     let panel = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(primary))
-        .style(Style::default().bg(bg_color));
+        .border_style(Style::default().fg(chrome.border(primary)))
+        .style(Style::default().bg(chrome.panel));
     frame.render_widget(panel, panel_area);
 
-    // Draw title centered in panel
+    let secondary = app.animation.current_theme.secondary_color();
+    if show_logo {
+        frame.render_widget(
+            Logo::new(app.animation.scene_frame(), primary, secondary),
+            Rect::new(area.x, stack_y, area.width, LOGO_HEIGHT),
+        );
+    }
+    if let Some(glance) = glance.filter(|_| show_glance) {
+        let (width, height) = glance.size();
+        let width = width.min(area.width);
+        let x = area.x + (area.width - width) / 2;
+        frame.render_widget(
+            glance.colors(chrome.text, chrome.muted),
+            Rect::new(x, panel_y + panel_height + 1, width, height),
+        );
+    }
+    if show_banner {
+        let banner_y = panel_y + panel_height + 1 + if show_glance { GLANCE_HEIGHT } else { 0 };
+        draw_theme_of_the_day(
+            frame,
+            app,
+            Rect::new(area.x, banner_y, area.width, BANNER_HEIGHT - 1),
+            panel_width,
+        );
+    }
+
+    // Plain title centered in panel when the logo doesn't fit
     let title = "pomowise";
     let title_x = panel_x + (panel_width.saturating_sub(title.len() as u16)) / 2;
     let title_y = panel_y + 2;
-    if title_y < area.height && title_x < area.width {
+    if !show_logo && title_y < area.height && title_x < area.width {
         let title_width = (title.len() as u16).min(area.width.saturating_sub(title_x));
         frame.render_widget(
             Paragraph::new(title).style(Style::default().fg(primary).bold()),
@@ -59,67 +112,165 @@ pub fn draw(frame: &mut Frame, app: &App) {
     if theme_y < area.height && theme_x < area.width {
         let theme_width = (theme_label.len() as u16).min(area.width.saturating_sub(theme_x));
         frame.render_widget(
-            Paragraph::new(theme_label).style(Style::default().fg(Color::DarkGray)),
+            Paragraph::new(theme_label).style(Style::default().fg(chrome.hint)),
             Rect::new(theme_x, theme_y, theme_width, 1),
         );
     }
 
+    // Break compliance (only meaningful with strict breaks on)
+    if let (true, Some(compliance)) = (app.config.strict_breaks, app.break_compliance) {
+        let label = format!("Breaks taken: {:.0}%", compliance * 100.0);
+        let x = panel_x + (panel_width.saturating_sub(label.len() as u16)) / 2;
+        let y = theme_y + 1;
+        if y < area.height && x < area.width {
+            let width = (label.len() as u16).min(area.width.saturating_sub(x));
+            frame.render_widget(
+                Paragraph::new(label).style(Style::default().fg(chrome.hint)),
+                Rect::new(x, y, width, 1),
+            );
+        }
+    }
+
     // Draw menu options
     let menu_y = panel_y + 6;
 
-    let start_style = if app.menu_selection == MenuItem::Start {
-        Style::default().fg(primary).bold()
-    } else {
-        Style::default().fg(Color::White)
-    };
-
-    let quit_style = if app.menu_selection == MenuItem::Quit {
-        Style::default().fg(primary).bold()
-    } else {
-        Style::default().fg(Color::White)
-    };
-
-    let start_prefix = if app.menu_selection == MenuItem::Start {
-        "> "
-    } else {
-        "  "
-    };
-    let quit_prefix = if app.menu_selection == MenuItem::Quit {
-        "> "
-    } else {
-        "  "
-    };
-
-    let start_text = format!("{}Start Pomodoro", start_prefix);
-    let quit_text = format!("{}Quit", quit_prefix);
-
-    let start_x = panel_x + (panel_width.saturating_sub(start_text.len() as u16)) / 2;
-    let quit_x = panel_x + (panel_width.saturating_sub(quit_text.len() as u16)) / 2;
-
-    if menu_y < area.height && start_x < area.width {
-        let width = (start_text.len() as u16).min(area.width.saturating_sub(start_x));
-        frame.render_widget(
-            Paragraph::new(start_text).style(start_style),
-            Rect::new(start_x, menu_y, width, 1),
-        );
+    let mut rows: Vec<(String, Style)> = Vec::new();
+    for item in items {
+        let selected = app.menu_selection == item;
+        let style = if selected {
+            Style::default().fg(primary).bold()
+        } else {
+            Style::default().fg(chrome.text)
+        };
+        let prefix = if selected { "> " } else { "  " };
+        let label = match item {
+            MenuItem::Continue => "Continue cycle".to_string(),
+            MenuItem::NewCycle => "New cycle".to_string(),
+            MenuItem::Custom => match &app.custom_minutes {
+                Some(minutes) => format!("Custom: {}_ min", minutes),
+                None => "Custom timer".to_string(),
+            },
+            MenuItem::Presets => match &app.config.preset {
+                Some(name) => format!("Preset: {}", name),
+                None => "Presets".to_string(),
+            },
+            MenuItem::Reminders => "Reminders".to_string(),
+            MenuItem::Fonts => "Font Gallery".to_string(),
+            MenuItem::Stats => "Stats".to_string(),
+            MenuItem::Quit => "Quit".to_string(),
+        };
+        rows.push((format!("{}{}", prefix, label), style));
+        if let (MenuItem::Continue, Some(next)) = (item, &continue_label) {
+            rows.push((next.clone(), Style::default().fg(chrome.hint)));
+        }
     }
-    if menu_y + 1 < area.height && quit_x < area.width {
-        let width = (quit_text.len() as u16).min(area.width.saturating_sub(quit_x));
-        frame.render_widget(
-            Paragraph::new(quit_text).style(quit_style),
-            Rect::new(quit_x, menu_y + 1, width, 1),
-        );
+
+    for (i, (text, style)) in rows.into_iter().enumerate() {
+        let text_width = text.chars().count() as u16;
+        let x = panel_x + (panel_width.saturating_sub(text_width)) / 2;
+        let y = menu_y + i as u16;
+        if y < area.height && x < area.width {
+            let width = text_width.min(area.width.saturating_sub(x));
+            frame.render_widget(
+                Paragraph::new(text).style(style),
+                Rect::new(x, y, width, 1),
+            );
+        }
     }
 
     // Draw controls hint at bottom of panel
-    let hint = "↑↓ Navigate  Enter Select";
+    let hint = if app.custom_minutes.is_some() {
+        "Minutes, Enter Start  Esc"
+    } else {
+        "↑↓ Navigate  Enter Select"
+    };
     let hint_x = panel_x + (panel_width.saturating_sub(hint.len() as u16)) / 2;
     let hint_y = panel_y + panel_height.saturating_sub(2);
     if hint_y < area.height && hint_x < area.width {
         let hint_width = (hint.len() as u16).min(area.width.saturating_sub(hint_x));
         frame.render_widget(
-            Paragraph::new(hint).style(Style::default().fg(Color::DarkGray)),
+            Paragraph::new(hint).style(Style::default().fg(chrome.hint)),
             Rect::new(hint_x, hint_y, hint_width, 1),
         );
     }
+
+    if let Some(selected) = app.preset_picker {
+        draw_preset_picker(frame, app, panel_area, selected);
+    }
+
+    // Reminders come on the menu too, near the bottom
+    if let Some(toast) = &app.toast {
+        let toast_area = Rect::new(area.x, area.bottom().saturating_sub(4), area.width, 3.min(area.height));
+        frame.render_widget(ToastView::new(toast, chrome.border(primary), chrome.panel).text(chrome.text), toast_area);
+    }
+}
+
+/// "No preset" and the installed presets over the menu panel, with their
+/// lengths and the selected one's description underneath
+fn draw_preset_picker(frame: &mut Frame, app: &App, panel: Rect, selected: usize) {
+    let chrome = app.chrome();
+    let primary = app.animation.current_theme.primary_color();
+    let rows = std::iter::once(("No preset".to_string(), "own settings".to_string()))
+        .chain(app.presets.iter().map(|preset| (preset.name.clone(), preset.lengths())));
+    let mut lines: Vec<Line> = rows
+        .enumerate()
+        .map(|(i, (name, lengths))| {
+            let marker = if i == selected { "▶ " } else { "  " };
+            let text = format!("{}{:<18} {}", marker, name, lengths);
+            if i == selected {
+                Line::from(Span::styled(text, Style::default().fg(primary).bold()))
+            } else {
+                Line::from(text)
+            }
+        })
+        .collect();
+    let preset = selected.checked_sub(1).and_then(|i| app.presets.get(i));
+    if let Some(preset) = preset.filter(|p| !p.description.is_empty()) {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(preset.description.clone(), Style::default().fg(chrome.hint))));
+    }
+
+    let area = frame.area();
+    let width = 36u16.min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let x = (panel.x + panel.width / 2).saturating_sub(width / 2);
+    let y = panel.y.min(area.height.saturating_sub(height));
+    let popup = Rect::new(x, y, width, height);
+    frame.render_widget(Clear, popup);
+    frame.render_widget(
+        Paragraph::new(lines).style(Style::default().fg(chrome.text)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(chrome.border(primary)))
+                .title(" Presets ")
+                .title_style(Style::default().fg(chrome.border(primary)).bold())
+                .title_bottom(" ↑↓ Enter Esc ")
+                .style(Style::default().bg(chrome.panel)),
+        ),
+        popup,
+    );
+}
+
+/// "Theme of the day" label with a live preview strip of that theme below it
+fn draw_theme_of_the_day(frame: &mut Frame, app: &App, area: Rect, strip_width: u16) {
+    let theme = ThemeType::of_the_day(app.now().date_naive());
+    let showing = theme == app.animation.current_theme;
+    let label = if showing {
+        format!("Theme of the day: {} (on now)", theme.name())
+    } else {
+        format!("Theme of the day: {}  t try", theme.name())
+    };
+    frame.render_widget(
+        Paragraph::new(label)
+            .style(Style::default().fg(theme.primary_color()))
+            .alignment(Alignment::Center),
+        Rect::new(area.x, area.y, area.width, 1),
+    );
+
+    // The background already shows it when it is the current theme
+    if !showing && area.height > 1 {
+        let strip_x = area.x + area.width.saturating_sub(strip_width) / 2;
+        let strip = Rect::new(strip_x, area.y + 1, strip_width.min(area.width), area.height - 1);
+        theme.render_background(frame, strip, app.animation.scene_frame(), &app.animation.params());
+    }
 }
@@ -0,0 +1,137 @@
+//! `--mini` layout for a tiny floating pane (20x5 and up)
+//! A state line, the time and a one-line progress bar on a plain background, no
+//! animation. While no session runs here it follows the one another instance
+//! publishes in its status file. Prompts and the other screens fall back to the
+//! single-line bar mode.
+
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Paragraph};
+
+use crate::animation::digits;
+use crate::app::{App, AppScreen};
+use crate::timer::{TimerSnapshot, TimerState};
+
+/// Small seven-segment digits, three cells by three rows
+const SMALL_DIGITS: [[&str; 3]; 10] = [
+    ["┌─┐", "│ │", "└─┘"],
+    ["  ╷", "  │", "  ╵"],
+    ["╶─┐", "┌─┘", "└─╴"],
+    ["╶─┐", " ─┤", "╶─┘"],
+    ["╷ ╷", "└─┤", "  ╵"],
+    ["┌─╴", "└─┐", "╶─┘"],
+    ["┌─╴", "├─┐", "└─┘"],
+    ["╶─┐", "  │", "  ╵"],
+    ["┌─┐", "├─┤", "└─┘"],
+    ["┌─┐", "└─┤", "╶─┘"],
+];
+
+/// `MM:SS` in small digits: four digits, three gaps and the colon
+const SMALL_WIDTH: u16 = 17;
+
+pub fn draw(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    let prompt_open = app.command_line.is_some()
+        || app.intention_input.is_some()
+        || app.pending_review.is_some()
+        || app.quit_confirm_open
+        || app.onboarding_step.is_some()
+        || app.awaiting_start;
+    let snapshot = match app.screen {
+        AppScreen::Timer => Some(app.timer.snapshot()),
+        AppScreen::Menu => app.mirrored.clone(),
+        _ => None,
+    };
+    let idle = app.screen == AppScreen::Menu && snapshot.is_none();
+    if prompt_open || (snapshot.is_none() && !idle) || area.height < 5 || area.width < SMALL_WIDTH {
+        super::draw_bar_mode(frame, app);
+        return;
+    }
+
+    let theme = &app.animation.current_theme;
+    let primary = theme.primary_color();
+    let chrome = app.chrome();
+    let dim = Style::default().fg(chrome.hint);
+    frame.render_widget(Block::default().style(Style::default().bg(chrome.panel)), area);
+
+    let (secs, progress, status) = match &snapshot {
+        Some(snapshot) => (
+            snapshot.remaining_secs,
+            snapshot.session_progress,
+            status_line(snapshot, app.screen == AppScreen::Menu, primary, dim),
+        ),
+        None => (
+            app.timer.work_length().as_secs(),
+            0.0,
+            Line::from(vec![
+                Span::styled("○ ", Style::default().fg(primary).bold()),
+                Span::styled("Enter: start", dim),
+            ]),
+        ),
+    };
+    let (minutes, seconds) = ((secs / 60) as u8, (secs % 60) as u8);
+
+    // The user's font when the pane has room for it, small digits otherwise
+    let font = app.animation.current_font;
+    let (font_width, font_height) = digits::timer_dimensions_for_font(font);
+    let digits_height = if area.width >= font_width && area.height >= font_height + 2 {
+        font_height
+    } else {
+        3
+    };
+    let top = area.y + (area.height - digits_height - 2) / 2;
+    let digits_area = Rect::new(area.x, top + 1, area.width, digits_height);
+
+    frame.render_widget(Paragraph::new(status).alignment(Alignment::Center), Rect::new(area.x, top, area.width, 1));
+    if digits_height == 3 {
+        draw_small_time(frame, digits_area, minutes, seconds, primary);
+    } else {
+        digits::render_time_with_font(frame, digits_area, minutes, seconds, primary, theme.secondary_color(), font);
+    }
+
+    let bar_width = area.width.saturating_sub(2) as usize;
+    let filled = (bar_width as f64 * progress.clamp(0.0, 1.0)) as usize;
+    let bar = Line::from(vec![
+        Span::styled("━".repeat(filled), Style::default().fg(primary)),
+        Span::styled("─".repeat(bar_width - filled), dim),
+    ]);
+    frame.render_widget(
+        Paragraph::new(bar).alignment(Alignment::Center),
+        Rect::new(area.x, digits_area.bottom(), area.width, 1),
+    );
+}
+
+/// `▶ Work 2/4`, with `⇄` when following another instance
+fn status_line(snapshot: &TimerSnapshot, mirrored: bool, primary: Color, dim: Style) -> Line<'static> {
+    let icon = if snapshot.is_paused { "⏸" } else { "▶" };
+    let name = snapshot.session_name.trim_end_matches(" (Paused)");
+    // Work sessions count the one in progress
+    let in_work = matches!(snapshot.state, TimerState::Work { .. })
+        || matches!(&snapshot.state, TimerState::Paused(inner) if matches!(**inner, TimerState::Work { .. }));
+    let done = (snapshot.cycle_position + u8::from(in_work)).min(4);
+    Line::from(vec![
+        Span::styled(format!("{} {} ", icon, name), Style::default().fg(primary).bold()),
+        Span::styled(format!("{}/4{}", done, if mirrored { " ⇄" } else { "" }), dim),
+    ])
+}
+
+fn draw_small_time(frame: &mut Frame, area: Rect, minutes: u8, seconds: u8, color: Color) {
+    // None is the colon
+    let glyphs = [Some(minutes / 10), Some(minutes % 10), None, Some(seconds / 10), Some(seconds % 10)];
+    let lines: Vec<Line> = (0..3)
+        .map(|row| {
+            let text: Vec<&str> = glyphs
+                .iter()
+                .map(|glyph| match glyph {
+                    Some(digit) => SMALL_DIGITS[*digit as usize % 10][row],
+                    None if row == 1 => ":",
+                    None => " ",
+                })
+                .collect();
+            Line::from(text.join(" "))
+        })
+        .collect();
+    frame.render_widget(
+        Paragraph::new(lines).style(Style::default().fg(color)).alignment(Alignment::Center),
+        area,
+    );
+}
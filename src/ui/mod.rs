@@ -1,14 +1,59 @@
+mod break_activity;
+pub mod chrome;
+pub mod color_filter;
+pub mod font_gallery;
+pub mod glyphs;
 mod menu;
+mod mini;
+mod onboarding;
+mod reminders;
+pub mod slow_link;
+mod stats;
 mod timer_view;
+pub mod transition;
 pub mod widgets;
 
+use ratatui::buffer::Buffer;
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Borders, Paragraph};
+use ratatui::widgets::{Block, Borders, Clear, Paragraph, Wrap};
 
-use crate::app::{App, AppScreen};
+use crate::alarm;
+use crate::animation::{letters, DigitFont, ThemeFailure};
+use crate::app::{App, AppScreen, ALERT_FRAMES};
 use crate::scaling::{MIN_WIDTH, MIN_HEIGHT};
+use crate::timer::TimerState;
 
 pub fn draw(frame: &mut Frame, app: &App) {
+    draw_screen(frame, app);
+    if let Some(failure) = &app.theme_error {
+        draw_theme_error(frame, failure, app);
+    }
+    if app.alert_takeover {
+        draw_alarm(frame, app);
+    }
+    if app.alert_frames > 0 {
+        draw_alert_pulse(frame.buffer_mut(), app);
+    }
+    app.finish_frame(frame.buffer_mut());
+    color_filter::apply(frame.buffer_mut(), &app.config.color_filter, app.brightness);
+    glyphs::apply(frame.buffer_mut(), app.glyphs, app.animation.current_theme);
+    if app.slow_link {
+        slow_link::coalesce(frame.buffer_mut());
+    }
+}
+
+fn draw_screen(frame: &mut Frame, app: &App) {
+    if app.mini {
+        mini::draw(frame, app);
+        return;
+    }
+
+    // Short panes get a single status line instead of the full UI
+    if app.scaling.is_bar_mode() {
+        draw_bar_mode(frame, app);
+        return;
+    }
+
     // Check if terminal is too small
     if app.scaling.is_too_small() {
         draw_too_small_warning(frame, app);
@@ -18,9 +63,201 @@ pub fn draw(frame: &mut Frame, app: &App) {
     match app.screen {
         AppScreen::Menu => menu::draw(frame, app),
         AppScreen::Timer => timer_view::draw(frame, app),
+        AppScreen::FontGallery => font_gallery::draw(frame, app),
+        AppScreen::Stats => stats::draw(frame, app),
+        AppScreen::WhatsNew => onboarding::draw_whats_new(frame, app),
+        AppScreen::Reminders => reminders::draw(frame, app),
+    }
+
+    if let Some(step) = app.onboarding_step {
+        onboarding::draw_walkthrough(frame, app, step);
     }
 }
 
+/// What went wrong with a theme that crashed, over whatever screen is showing
+fn draw_theme_error(frame: &mut Frame, failure: &ThemeFailure, app: &App) {
+    let area = frame.area();
+    let chrome = app.chrome();
+    let accent = Color::Rgb(230, 120, 80);
+    let width = 60u16.min(area.width);
+    let height = 10u16.min(area.height);
+    let dialog_area = Rect::new(
+        area.x + area.width.saturating_sub(width) / 2,
+        area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    );
+
+    frame.render_widget(Clear, dialog_area);
+    frame.render_widget(
+        Paragraph::new(vec![
+            Line::from(format!("The {} theme crashed while drawing:", failure.theme.name())),
+            Line::from(""),
+            Line::styled(failure.message.clone(), Style::default().fg(Color::Gray)),
+            Line::from(""),
+            Line::from("Switched to Minimal; it is left out of the rotation."),
+            Line::styled("Press any key", Style::default().fg(Color::DarkGray)),
+        ])
+        .alignment(Alignment::Center)
+        .wrap(Wrap { trim: true })
+        .style(Style::default().fg(chrome.text))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(accent))
+                .title(" Theme error ")
+                .title_style(Style::default().fg(accent).bold())
+                .style(Style::default().bg(chrome.panel)),
+        ),
+        dialog_area,
+    );
+}
+
+/// Alert: the whole screen washes over in the theme color a few times (for as
+/// long as the alarm screen is up)
+fn draw_alert_pulse(buf: &mut Buffer, app: &App) {
+    let elapsed = (ALERT_FRAMES - app.alert_frames) as f32;
+    // Three pulses, each rising and falling over a second
+    let strength = 0.45 * (elapsed / 10.0 * std::f32::consts::PI).sin().abs();
+    let color = app.animation.current_theme.primary_color();
+    let area = buf.area;
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            let cell = &mut buf[(x, y)];
+            cell.bg = transition::mix(cell.bg, color, strength);
+            cell.fg = transition::mix(cell.fg, color, strength / 2.0);
+        }
+    }
+}
+
+/// Alarm screen: the alert over everything, in the theme color with the message
+/// in block letters (plain text when they don't fit); the alert pulse goes on top
+fn draw_alarm(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    let chrome = app.chrome();
+    let color = app.animation.current_theme.primary_color();
+    let fill = transition::mix(chrome.panel, color, 0.4);
+    frame.render_widget(Clear, area);
+    frame.render_widget(Block::default().style(Style::default().bg(fill)), area);
+
+    let hint = Line::styled("any key: dismiss", Style::default().fg(chrome.text));
+    let middle = area.y + area.height / 2;
+    if letters::text_width(alarm::MESSAGE) <= area.width && area.height >= letters::LETTER_HEIGHT + 2 {
+        let top = middle.saturating_sub(letters::LETTER_HEIGHT / 2 + 1).max(area.y);
+        letters::render_text(frame, Rect::new(area.x, top, area.width, letters::LETTER_HEIGHT), alarm::MESSAGE, chrome.text);
+        let hint_y = (top + letters::LETTER_HEIGHT + 1).min(area.bottom() - 1);
+        frame.render_widget(Paragraph::new(hint).alignment(Alignment::Center), Rect::new(area.x, hint_y, area.width, 1));
+    } else {
+        let lines = vec![Line::styled(alarm::MESSAGE, Style::default().fg(chrome.text).bold()), hint];
+        let height = 2.min(area.height);
+        frame.render_widget(
+            Paragraph::new(lines).alignment(Alignment::Center),
+            Rect::new(area.x, middle.saturating_sub(1).max(area.y), area.width, height),
+        );
+    }
+}
+
+/// Single-line mode for short panes: `▶ Work 17:42 ████████░░ 2/4`
+fn draw_bar_mode(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    let theme = &app.animation.current_theme;
+    let primary = theme.primary_color();
+    let chrome = app.chrome();
+    let dim = Style::default().fg(chrome.hint);
+
+    frame.render_widget(
+        Block::default().style(Style::default().bg(chrome.panel)),
+        area,
+    );
+
+    let line = if let Some(step) = app.onboarding_step {
+        Line::from(vec![
+            Span::styled(format!("{} ", crate::onboarding::STEPS[step].title), Style::default().fg(primary)),
+            Span::styled("make the pane taller for the tour  Esc: skip", dim),
+        ])
+    } else if let Some(input) = &app.command_line {
+        Line::from(format!(":{}█", input))
+    } else if let Some(input) = &app.intention_input {
+        Line::from(vec![
+            Span::styled("Intention: ", Style::default().fg(primary)),
+            Span::raw(format!("{}█", input)),
+        ])
+    } else if app.pending_review.is_some() {
+        Line::from(vec![
+            Span::styled("How focused were you? ", Style::default().fg(primary)),
+            Span::styled("1-5  Esc: skip", dim),
+        ])
+    } else if app.quit_confirm_open {
+        Line::from(vec![
+            Span::styled("End current session? ", Style::default().fg(primary)),
+            Span::styled("y/n", dim),
+        ])
+    } else {
+        match app.screen {
+            AppScreen::Menu => Line::from(vec![
+                Span::styled("pomowise ", Style::default().fg(primary).bold()),
+                Span::styled("Enter: start  q: quit", dim),
+            ]),
+            AppScreen::FontGallery => Line::from(vec![
+                Span::styled(
+                    format!("Font: {} ", DigitFont::every()[app.gallery_index].name()),
+                    Style::default().fg(primary),
+                ),
+                Span::styled("←→ Enter Esc", dim),
+            ]),
+            AppScreen::Stats => Line::from(vec![
+                Span::styled("Stats ", Style::default().fg(primary)),
+                Span::styled("make the pane taller to see them  Esc: back", dim),
+            ]),
+            AppScreen::Reminders => Line::from(vec![
+                Span::styled("Reminders ", Style::default().fg(primary)),
+                Span::styled("make the pane taller to see them  Esc: back", dim),
+            ]),
+            AppScreen::WhatsNew => Line::from(vec![
+                Span::styled(format!("pomowise {} ", crate::onboarding::VERSION), Style::default().fg(primary)),
+                Span::styled("make the pane taller for what's new  Esc: back", dim),
+            ]),
+            AppScreen::Timer => bar_mode_timer_line(app, area.width, primary, dim),
+        }
+    };
+
+    frame.render_widget(Paragraph::new(line), Rect::new(area.x, area.y, area.width, 1));
+}
+
+fn bar_mode_timer_line(app: &App, width: u16, primary: Color, dim: Style) -> Line<'static> {
+    let timer = &app.timer;
+    let icon = if timer.is_paused() { "⏸" } else { "▶" };
+    let name = timer.session_name().trim_end_matches(" (Paused)");
+
+    if app.awaiting_start {
+        return Line::from(vec![
+            Span::styled(format!("{} {} ", icon, name), Style::default().fg(primary)),
+            Span::styled("Space: start", dim),
+        ]);
+    }
+
+    let secs = timer.remaining.as_secs();
+    let head = format!("{} {} {:02}:{:02} ", icon, name, secs / 60, secs % 60);
+
+    // Work sessions count the one in progress
+    let in_work = matches!(timer.state, TimerState::Work { .. })
+        || matches!(&timer.state, TimerState::Paused(inner) if matches!(**inner, TimerState::Work { .. }));
+    let done = (timer.cycle_position + u8::from(in_work)).min(4);
+    let tail = format!(" {}/4", done);
+
+    // The bar takes whatever is left, up to 20 cells
+    let used = (head.chars().count() + tail.len()) as u16;
+    let bar_width = width.saturating_sub(used).min(20) as usize;
+    let filled = (bar_width as f64 * timer.session_progress()) as usize;
+
+    Line::from(vec![
+        Span::styled(head, Style::default().fg(primary).bold()),
+        Span::styled("█".repeat(filled), Style::default().fg(primary)),
+        Span::styled("░".repeat(bar_width - filled), dim),
+        Span::styled(tail, dim),
+    ])
+}
+
 /// Draw a warning message when terminal is too small
 fn draw_too_small_warning(frame: &mut Frame, app: &App) {
     let area = frame.area();
@@ -0,0 +1,105 @@
+//! First-run walkthrough overlay and the "What's new" screen
+
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Padding, Paragraph, Wrap},
+};
+
+use crate::app::App;
+use crate::onboarding::STEPS;
+
+/// One walkthrough page in a centered panel over the current screen
+pub fn draw_walkthrough(frame: &mut Frame, app: &App, step: usize) {
+    let Some(page) = STEPS.get(step) else { return };
+    let area = frame.area();
+    let primary = app.animation.current_theme.primary_color();
+    let chrome = app.chrome();
+
+    let key_width = page.rows.iter().map(|(key, _)| key.chars().count()).max().unwrap_or(0);
+    let mut lines = Vec::new();
+    for (key, text) in page.rows {
+        if key.is_empty() {
+            lines.push(Line::from(*text));
+        } else {
+            lines.push(Line::from(vec![
+                Span::styled(format!("{:>width$}  ", key, width = key_width), Style::default().fg(primary).bold()),
+                Span::raw(*text),
+            ]));
+        }
+    }
+    lines.push(Line::from(""));
+    let last = step + 1 == STEPS.len();
+    let hint = if last { "Enter: done" } else { "Enter: next" };
+    lines.push(Line::from(Span::styled(
+        format!("{}  ←: back  Esc: skip", hint),
+        Style::default().fg(chrome.muted),
+    )));
+
+    let width = 72.min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let panel_area = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+    frame.render_widget(Clear, panel_area);
+    frame.render_widget(
+        Paragraph::new(lines).style(Style::default().fg(chrome.text)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(chrome.border(primary)))
+                .title(format!(" {} ", page.title))
+                .title_style(Style::default().fg(chrome.border(primary)).bold())
+                .title_bottom(format!(" {}/{} ", step + 1, STEPS.len()))
+                .padding(Padding::horizontal(1))
+                .style(Style::default().bg(chrome.panel)),
+        ),
+        panel_area,
+    );
+}
+
+/// Changelog entries since the last version seen, scrollable
+pub fn draw_whats_new(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    app.animation.render_background(frame, area);
+    let primary = app.animation.current_theme.primary_color();
+    let chrome = app.chrome();
+
+    let mut lines = Vec::new();
+    for release in &app.whats_new {
+        lines.push(Line::from(Span::styled(release.version.clone(), Style::default().fg(primary).bold())));
+        for note in &release.notes {
+            // Notes are markdown; the backticks around keys read fine without the markup
+            lines.push(Line::from(format!("  • {}", note.replace('`', ""))));
+        }
+        lines.push(Line::from(""));
+    }
+
+    let width = 80.min(area.width.saturating_sub(4));
+    let height = (area.height.saturating_sub(4)).min(lines.len() as u16 + 2);
+    let panel_area = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+    frame.render_widget(Clear, panel_area);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .scroll((app.whats_new_scroll, 0))
+            .style(Style::default().fg(chrome.text))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(chrome.border(primary)))
+                    .title(" What's new in pomowise ")
+                    .title_style(Style::default().fg(chrome.border(primary)).bold())
+                    .title_bottom(Span::styled(" ↑↓: scroll  Enter: close ", Style::default().fg(chrome.muted)))
+                    .padding(Padding::horizontal(1))
+                    .style(Style::default().bg(chrome.panel)),
+            ),
+        panel_area,
+    );
+}
@@ -0,0 +1,76 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Clear, Padding, Paragraph},
+};
+
+use crate::app::App;
+use crate::ui::widgets::toast::ToastView;
+
+/// The configured reminders, how often each comes and when it's next due
+pub fn draw(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    app.animation.render_background(frame, area);
+    let primary = app.animation.current_theme.primary_color();
+    let chrome = app.chrome();
+    let now = app.timer.clock().now();
+
+    let mut lines: Vec<Line> = app
+        .config
+        .reminders
+        .iter()
+        .enumerate()
+        .map(|(i, reminder)| {
+            let marker = if i == app.reminder_selection { "▶ " } else { "  " };
+            let when = match app.reminders.due_in(i, now) {
+                Some(left) => format!("every {} min · next in {} min", reminder.every_minutes, left.as_secs().div_ceil(60)),
+                None => format!("every {} min · off", reminder.every_minutes),
+            };
+            let style = match (i == app.reminder_selection, reminder.enabled) {
+                (true, _) => Style::default().fg(primary).bold(),
+                (false, true) => Style::default().fg(chrome.text),
+                (false, false) => Style::default().fg(chrome.muted),
+            };
+            Line::from(vec![
+                Span::styled(format!("{}{:<24}", marker, reminder.message), style),
+                Span::styled(when, Style::default().fg(chrome.hint)),
+            ])
+        })
+        .collect();
+    if lines.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "No reminders yet: add them under \"reminders\" in config.json",
+            Style::default().fg(chrome.hint),
+        )));
+    }
+
+    let width = 64.min(area.width.saturating_sub(4));
+    let height = (lines.len() as u16 + 2).min(area.height.saturating_sub(4));
+    let panel_area = Rect::new(
+        area.x + (area.width - width) / 2,
+        area.y + (area.height - height) / 2,
+        width,
+        height,
+    );
+    frame.render_widget(Clear, panel_area);
+    frame.render_widget(
+        Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(chrome.border(primary)))
+                .title(" Reminders ")
+                .title_style(Style::default().fg(chrome.border(primary)).bold())
+                .title_bottom(Span::styled(
+                    " Space on/off · +/- 5 min · r restart · Esc ",
+                    Style::default().fg(chrome.muted),
+                ))
+                .padding(Padding::horizontal(1))
+                .style(Style::default().bg(chrome.panel)),
+        ),
+        panel_area,
+    );
+
+    if let Some(toast) = &app.toast {
+        let toast_area = Rect::new(area.x, area.bottom().saturating_sub(5), area.width, 3.min(area.height));
+        frame.render_widget(ToastView::new(toast, chrome.border(primary), chrome.panel).text(chrome.text), toast_area);
+    }
+}
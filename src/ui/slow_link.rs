@@ -0,0 +1,106 @@
+//! Slow-link mode for SSH and other high-latency connections (`slow_link` in
+//! config.json, `--slow-link`): animated scenes draw twice a second, themes keep
+//! to their low-detail layers, and colors are coalesced into runs, so the
+//! terminal diff sends a few long runs instead of a color change per cell.
+
+use std::time::Duration;
+
+use ratatui::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Time between frames of an animated scene
+pub const FRAME_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Theme detail level (0-3) held to
+pub const DETAIL_CAP: u8 = 1;
+
+/// Colors are rounded to multiples of this per channel, so small shimmer between
+/// frames doesn't change cells
+const QUANTUM: u8 = 32;
+
+/// Whether to save bandwidth
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SlowLink {
+    /// Over SSH (`SSH_CONNECTION` or `SSH_TTY` set)
+    #[default]
+    Auto,
+    On,
+    Off,
+}
+
+impl SlowLink {
+    pub fn active(&self) -> bool {
+        match self {
+            SlowLink::On => true,
+            SlowLink::Off => false,
+            SlowLink::Auto => ["SSH_CONNECTION", "SSH_TTY"].iter().any(|v| std::env::var_os(v).is_some()),
+        }
+    }
+}
+
+/// Round every color and let each cell take on the color of the run to its left
+/// when it's within a step of it; blank cells keep the foreground run going
+pub fn coalesce(buf: &mut Buffer) {
+    let area = buf.area;
+    for y in area.top()..area.bottom() {
+        let (mut fg_run, mut bg_run) = (None, None);
+        for x in area.left()..area.right() {
+            let cell = &mut buf[(x, y)];
+            let blank = cell.symbol() == " ";
+            cell.bg = join_run(quantize(cell.bg), &mut bg_run);
+            cell.fg = match fg_run {
+                Some(run) if blank => run,
+                _ => join_run(quantize(cell.fg), &mut fg_run),
+            };
+        }
+    }
+}
+
+/// The run's color if `color` is close to it, else `color`, starting a new run
+fn join_run(color: Color, run: &mut Option<Color>) -> Color {
+    let close = match (color, *run) {
+        (Color::Rgb(r, g, b), Some(Color::Rgb(rr, rg, rb))) => {
+            r.abs_diff(rr) <= QUANTUM && g.abs_diff(rg) <= QUANTUM && b.abs_diff(rb) <= QUANTUM
+        }
+        (color, Some(run)) => color == run,
+        (_, None) => false,
+    };
+    if close {
+        return run.unwrap_or(color);
+    }
+    *run = Some(color);
+    color
+}
+
+fn quantize(color: Color) -> Color {
+    let step = |v: u8| ((v as u16 + QUANTUM as u16 / 2) / QUANTUM as u16 * QUANTUM as u16).min(255) as u8;
+    match color {
+        Color::Rgb(r, g, b) => Color::Rgb(step(r), step(g), step(b)),
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gradients_coalesce_into_runs() {
+        let mut buf = Buffer::empty(Rect::new(0, 0, 6, 1));
+        for x in 0..6u16 {
+            let shade = 100 + x as u8 * 6;
+            buf[(x, 0)].set_bg(Color::Rgb(shade, shade, shade));
+        }
+        buf[(5, 0)].set_bg(Color::Rgb(250, 20, 20));
+        buf[(2, 0)].set_symbol("█").set_fg(Color::Rgb(200, 90, 10));
+        coalesce(&mut buf);
+
+        let bg: Vec<Color> = (0..6).map(|x| buf[(x, 0)].bg).collect();
+        assert_eq!(bg[..5], [Color::Rgb(96, 96, 96); 5]);
+        assert_eq!(bg[5], Color::Rgb(255, 32, 32));
+        // Blank cells after the block carry its foreground on
+        assert_eq!(buf[(2, 0)].fg, Color::Rgb(192, 96, 0));
+        assert_eq!(buf[(4, 0)].fg, Color::Rgb(192, 96, 0));
+    }
+}
@@ -0,0 +1,215 @@
+use chrono::{DateTime, Local, NaiveDate};
+use ratatui::{
+    prelude::*,
+    widgets::{Block, Borders, Paragraph},
+};
+
+use crate::app::App;
+use crate::history::{self, HistoryMode, SessionKind, SessionOutcome, SessionRecord};
+use crate::ui::chrome::OVERRUN_COLOR;
+use crate::ui::widgets::toast::ToastView;
+
+/// Intentions listed under "Recent" (and estimated tasks)
+const RECENT_INTENTIONS: usize = 8;
+
+pub fn draw(frame: &mut Frame, app: &App) {
+    let area = frame.area();
+    let primary = app.animation.current_theme.primary_color();
+    let records = &app.stats_records;
+    let today = app.now().date_naive();
+    let week_start = today - chrono::Days::new(6);
+
+    let work: Vec<&SessionRecord> = records.iter().filter(|r| r.kind == SessionKind::Work).collect();
+    let today_work: Vec<&SessionRecord> = work.iter().copied().filter(|r| local_date(r) == today).collect();
+    let week_work: Vec<&SessionRecord> = work.iter().copied().filter(|r| local_date(r) >= week_start).collect();
+
+    let chrome = app.chrome();
+    let heading = Style::default().fg(primary).bold();
+    let dim = Style::default().fg(chrome.muted);
+    let mut lines = Vec::new();
+    let (title, notice) = match history::mode() {
+        HistoryMode::Plain => (" Stats ", None),
+        HistoryMode::Encrypted(_) => (" Stats (encrypted) ", None),
+        HistoryMode::Locked => (
+            " Stats (locked) ",
+            Some("History locked (no passphrase): this run only, nothing is saved"),
+        ),
+        HistoryMode::Incognito => (
+            " Stats (incognito) ",
+            Some("Incognito run: this run only, nothing is saved"),
+        ),
+    };
+    if let Some(notice) = notice {
+        lines.push(Line::from(Span::styled(notice, Style::default().fg(Color::Rgb(230, 180, 80)))));
+        lines.push(Line::from(""));
+    }
+    lines.extend([
+        Line::from(Span::styled("Focus", heading)),
+        summary_line("Today", &today_work),
+        summary_line("Last 7 days", &week_work),
+        summary_line("All time", &work),
+        Line::from(""),
+    ]);
+
+    // Blocks of the day template in use, ticked off once today's sessions fill them
+    if let Some(agenda) = &app.agenda {
+        lines.push(Line::from(Span::styled(format!("Today's template: {}", agenda.name), heading)));
+        for (index, block) in agenda.blocks.iter().enumerate() {
+            let done = agenda.done(index);
+            let (marker, style) = if done >= block.pomodoros {
+                ("✓", Style::default().fg(primary))
+            } else {
+                (" ", Style::default())
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {} {:<12}", marker, block.name), style),
+                Span::raw(format!("{}/{} ", done, block.pomodoros)),
+                Span::styled(block.shape(), dim),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    let categories = history::by_category(work.iter().copied());
+    if !categories.is_empty() {
+        lines.push(Line::from(Span::styled("Categories", heading)));
+        for (name, group) in &categories {
+            lines.push(summary_line(name, group));
+        }
+        lines.push(Line::from(""));
+    }
+
+    let projects = history::by_project(work.iter().copied());
+    if !projects.is_empty() {
+        lines.push(Line::from(Span::styled("Projects", heading)));
+        for (name, group) in &projects {
+            lines.push(summary_line(name, group));
+        }
+        lines.push(Line::from(""));
+    }
+    lines.push(Line::from(Span::styled("Focus rating", heading)));
+
+    match history::average_rating(records) {
+        Some(all) => {
+            let week = history::average_rating(week_work.iter().copied())
+                .map_or("-".to_string(), |avg| format!("{:.1}", avg));
+            lines.push(Line::from(format!("  Average {:.1} · last 7 days {}", all, week)));
+            for rating in (1..=5).rev() {
+                let count = records.iter().filter(|r| r.focus_rating == Some(rating)).count();
+                lines.push(Line::from(vec![
+                    Span::raw(format!("  {} ", rating)),
+                    Span::styled("█".repeat(count.min(30)), Style::default().fg(primary)),
+                    Span::styled(format!(" {}", count), dim),
+                ]));
+            }
+        }
+        None => lines.push(Line::from(Span::styled(
+            "  No ratings yet (turn on \"intentions\" in config.json)",
+            dim,
+        ))),
+    }
+
+    if let Some(compliance) = history::break_compliance(records) {
+        lines.push(Line::from(""));
+        lines.push(Line::from(vec![
+            Span::styled("Breaks taken in full ", heading),
+            Span::raw(format!("{:.0}%", compliance * 100.0)),
+        ]));
+    }
+
+    let tasks = history::estimated_tasks(records);
+    if !tasks.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Estimates", heading)));
+        for task in tasks.iter().take(RECENT_INTENTIONS) {
+            let count = format!("  {}/{}🍅", task.done, task.estimate.unwrap_or_default());
+            let (count_style, note) = match task.overrun() {
+                Some(over) => (Style::default().fg(OVERRUN_COLOR), format!("  over by {}", over)),
+                None => (Style::default().fg(primary), String::new()),
+            };
+            lines.push(Line::from(vec![
+                Span::raw(format!("  {}", task.task)),
+                Span::styled(count, count_style),
+                Span::styled(note, Style::default().fg(OVERRUN_COLOR)),
+            ]));
+        }
+    }
+
+    let recent: Vec<&SessionRecord> = work
+        .iter()
+        .rev()
+        .filter(|r| r.intention.is_some())
+        .take(RECENT_INTENTIONS)
+        .copied()
+        .collect();
+    if !recent.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Recent intentions", heading)));
+        for record in recent {
+            let when = local_time(record).map_or(String::new(), |t| t.format("%a %H:%M").to_string());
+            let rating = record
+                .focus_rating
+                .map_or(String::new(), |r| format!("  {}", stars(r)));
+            lines.push(Line::from(vec![
+                Span::styled(format!("  {}  ", when), dim),
+                Span::raw(record.intention.clone().unwrap_or_default()),
+                Span::styled(rating, Style::default().fg(primary)),
+            ]));
+        }
+    }
+
+    frame.render_widget(
+        Paragraph::new(lines)
+            .style(Style::default().fg(chrome.text))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(chrome.border(primary)))
+                    .title(title)
+                    .title_style(heading)
+                    .title_bottom(" s Share · Esc Back ")
+                    .style(Style::default().bg(chrome.panel)),
+            ),
+        area,
+    );
+    // Confirmation of `s` (copied, saved), near the bottom
+    if let Some(toast) = &app.toast {
+        let toast_area = Rect::new(area.x, area.bottom().saturating_sub(5), area.width, 3.min(area.height));
+        frame.render_widget(ToastView::new(toast, chrome.border(primary), chrome.panel).text(chrome.text), toast_area);
+    }
+}
+
+/// "  Today      3 sessions · 1h 15m"
+/// Sessions and net focus time, with the time spent paused on top when there was any
+fn summary_line(label: &str, work: &[&SessionRecord]) -> Line<'static> {
+    let completed = work.iter().filter(|r| r.outcome == SessionOutcome::Completed).count();
+    let secs: u64 = work.iter().map(|r| r.duration_secs).sum();
+    let paused: u64 = work.iter().map(|r| r.paused_secs).sum();
+    let mut line = Line::from(format!(
+        "  {:<12}{} sessions · {}h {:02}m",
+        label,
+        completed,
+        secs / 3600,
+        secs % 3600 / 60
+    ));
+    if paused >= 60 {
+        line.push_span(Span::styled(
+            format!(" (+{}h {:02}m paused)", paused / 3600, paused % 3600 / 60),
+            Style::default().fg(Color::Rgb(120, 120, 140)),
+        ));
+    }
+    line
+}
+
+fn stars(rating: u8) -> String {
+    let rating = rating.min(5) as usize;
+    format!("{}{}", "★".repeat(rating), "☆".repeat(5 - rating))
+}
+
+fn local_time(record: &SessionRecord) -> Option<DateTime<Local>> {
+    DateTime::from_timestamp(record.ended_at as i64, 0).map(|t| t.with_timezone(&Local))
+}
+
+fn local_date(record: &SessionRecord) -> NaiveDate {
+    local_time(record).map_or(NaiveDate::MIN, |t| t.date_naive())
+}
@@ -1,50 +1,544 @@
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, Gauge, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph, Wrap},
 };
 
-use crate::animation::digits;
-use crate::animation::themes::ThemeType;
-use crate::app::App;
+use crate::animation::{background, digits, letters, DigitFont};
+use crate::animation::digits::{DigitColorMode, LedDecay};
+use crate::animation::themes::{fireworks, ThemeType};
+use crate::app::{App, QuickAction, CELEBRATION_FRAMES};
 use crate::scaling::ScalingContext;
+use crate::quotes;
+use crate::ui::{break_activity, transition};
+use crate::ui::chrome::OVERRUN_COLOR;
+use crate::ui::widgets::agenda::AgendaPane;
+use crate::ui::widgets::clock_box::ClockBox;
+use crate::ui::widgets::event_log::{EventLogPane, PANE_WIDTH};
+use crate::ui::widgets::hint_bar::HintBar;
+use crate::ui::widgets::next_session::{NextSession, PREVIEW_WINDOW};
+use crate::ui::widgets::overlay::{OverlayLayout, Placement};
+use crate::ui::widgets::participants::Participants;
+use crate::ui::widgets::progress::{BarPosition, ProgressBar};
+use crate::ui::widgets::ring::ProgressRing;
+use crate::ui::widgets::session_info::SessionInfo;
+use crate::ui::widgets::theme_badge::ThemeBadge;
+use crate::ui::widgets::toast::ToastView;
+use crate::ui::widgets::zen_progress::ZenProgress;
+use crate::history::SessionKind;
+use crate::meeting;
+use crate::world_clock;
+use crate::timer::{PomodoroTimer, CYCLE_STEPS};
+
+/// Background for digits-only mode
+const DIGITS_ONLY_BG: Color = Color::Rgb(10, 10, 20);
 
 pub fn draw(frame: &mut Frame, app: &App) {
+    let chrome = app.chrome();
     let area = frame.area();
 
-    // Render the animated theme background
-    app.animation
-        .current_theme
-        .render_background(frame, area, app.animation.frame_index);
+    // Render the animated theme background (a solid fill in digits-only mode)
+    let digits_only = app.digits_only();
+    if digits_only {
+        background::fill(frame, area, DIGITS_ONLY_BG);
+    } else {
+        app.animation.render_background(frame, area);
+    }
 
     // Calculate timer area using scaling context
-    let timer_area = centered_timer_area(area, &app.scaling, app.animation.current_font);
-
-    // Render big digits
-    let time_secs = app.timer.remaining.as_secs();
-    let minutes = (time_secs / 60) as u8;
-    let seconds = (time_secs % 60) as u8;
-
-    digits::render_time_with_font(
-        frame,
-        timer_area,
-        minutes,
-        seconds,
-        app.animation.current_theme.primary_color(),
-        app.animation.current_theme.secondary_color(),
-        app.animation.current_font,
-    );
+    let font = app.display_font();
+    let timer_area = centered_timer_area(area, &app.scaling, font);
+
+    if app.awaiting_start {
+        // Manual transition: the next session waits behind an interstitial
+        draw_interstitial(frame, area, app);
+    } else if let Some(kind) = app.banner {
+        // A new session announces itself before the countdown shows
+        let text = match kind {
+            SessionKind::Work => "WORK",
+            SessionKind::ShortBreak => "BREAK",
+            SessionKind::LongBreak if letters::text_width("LONG BREAK") <= area.width => "LONG BREAK",
+            SessionKind::LongBreak => "BREAK",
+        };
+        let banner_area = Rect::new(area.x, timer_area.y, area.width, timer_area.height);
+        letters::render_text(frame, banner_area, text, digit_colors(app).0);
+    } else {
+        // Render big digits
+        let time_secs = app.timer.remaining.as_secs();
+        let minutes = (time_secs / 60) as u8;
+        let seconds = (time_secs % 60) as u8;
+        let (primary, secondary) = digit_colors(app);
+
+        // The final-countdown flash wins over the color mode
+        let mode = if app.timer.in_final_countdown() && !app.digits_only() {
+            DigitColorMode::Theme
+        } else {
+            app.config.digit_colors
+        };
+        let height = font.height() as usize;
+        let progress = app.timer.session_progress();
+        let frame_index = app.animation.scene_frame();
+
+        // Seven-segment digits keep their unlit segments as faint ghosts
+        let led = (font == DigitFont::SevenSegment)
+            .then(|| LedDecay::new(app.timer.remaining, app.timer.is_running()));
+        digits::render_time_with_effect(
+            frame,
+            timer_area,
+            minutes,
+            seconds,
+            |row| mode.row_color(primary, secondary, row, height, frame_index, progress),
+            secondary,
+            font,
+            // The other fonts roll their digits over as they change
+            |cell, ch, style| match &led {
+                Some(led) => led.apply(cell, ch, style),
+                None => app.digit_roll.apply(cell, ch, style, secondary, font),
+            },
+        );
+
+        // Progress ring around the digits, on top of or instead of the bar
+        let ring = &app.config.progress_ring;
+        if ring.visible && app.hints_visible {
+            let theme = &app.animation.current_theme;
+            let fill = app.config.progress_bar.fixed_color().unwrap_or(theme.primary_color());
+            // The digits fill the timer area's width, so leave them half a row's
+            // height each side: a column with the usual 2:1 cells
+            let gap = (app.scaling.cell_aspect / 2.0).round() as u16;
+            let ring_area = Rect::new(
+                timer_area.x.saturating_sub(gap + 1),
+                timer_area.y,
+                timer_area.width + 2 * (gap + 1),
+                timer_area.height,
+            )
+            .intersection(area);
+            frame.render_widget(
+                ProgressRing::new(progress).style(ring.style).colors(fill, theme.secondary_color()),
+                ring_area,
+            );
+        }
+    }
+
+    // The intention for this work session, just above the digits, with its
+    // pomodoro count against the estimate (`fix parser: 2/3🍅`)
+    if let (Some(intention), true) = (&app.current_intention, app.hints_visible) {
+        let y = timer_area.y.saturating_sub(2);
+        if y > 3 {
+            let style = Style::default().fg(app.animation.current_theme.secondary_color()).italic();
+            let mut spans = vec![Span::styled(intention.clone(), style)];
+            if let Some(task) = &app.task {
+                let estimate = task.estimate.unwrap_or_default() as usize;
+                let this_one = task.done + 1;
+                let count_style = if this_one > estimate { style.fg(OVERRUN_COLOR) } else { style };
+                spans.push(Span::styled(format!(": {}/{}🍅", this_one, estimate), count_style));
+            }
+            let line = Line::from(spans);
+            let width = (line.width() as u16).min(area.width);
+            frame.render_widget(
+                Paragraph::new(line),
+                Rect::new(area.x + area.width.saturating_sub(width) / 2, y, width, 1),
+            );
+        }
+    }
+
+    // Secondary clock: elapsed time and wall-clock end of the session
+    if app.show_elapsed && app.hints_visible && !app.awaiting_start {
+        draw_elapsed_line(frame, area, timer_area, app);
+    }
+    let below_elapsed = u16::from(app.show_elapsed);
+
+    // Local time and the configured zones, under the elapsed line
+    let world_clock = !app.world_clock.is_empty() && app.hints_visible && !app.awaiting_start;
+    if world_clock {
+        draw_world_clock(frame, area, timer_area.bottom() + below_elapsed, app);
+    }
+
+    // Quote of the session, fading out after its first seconds
+    if let (Some(quote), true, false) = (&app.quote, app.hints_visible, app.awaiting_start) {
+        let opacity = quotes::opacity(app.timer.elapsed().as_secs_f32());
+        if opacity > 0.0 {
+            let below = below_elapsed + u16::from(world_clock);
+            draw_quote(frame, area, timer_area.bottom() + 1 + below, quote, opacity, app);
+        }
+    }
+
+    // Work-complete celebration bursts over the theme and digits
+    if app.celebration_frames > 0 && !digits_only {
+        let elapsed = (CELEBRATION_FRAMES - app.celebration_frames) as usize;
+        fireworks::render_celebration(frame, area, elapsed);
+    }
 
     // Draw timer overlay info (respects scaling context)
     draw_timer_overlay(frame, area, app);
 
+    // Session events down the right side (`L`)
+    if app.config.event_log && app.hints_visible && area.width >= PANE_WIDTH * 2 {
+        let pane = EventLogPane::new(&app.event_log)
+            .colors(chrome.border(app.animation.current_theme.primary_color()), chrome.background);
+        let height = pane.height().min(area.height / 2);
+        frame.render_widget(
+            pane,
+            Rect::new(area.right() - PANE_WIDTH - 1, area.y + (area.height - height) / 2, PANE_WIDTH, height),
+        );
+    }
+
+    // Day template down the left side (`A` collapses it)
+    if let Some(agenda) = app.agenda.as_ref().filter(|_| app.hints_visible && area.width >= PANE_WIDTH * 2) {
+        let pane = AgendaPane::new(agenda)
+            .collapsed(app.agenda_collapsed)
+            .colors(chrome.border(app.animation.current_theme.primary_color()), chrome.background);
+        let height = pane.height().min(area.height / 2);
+        frame.render_widget(
+            pane,
+            Rect::new(area.x + 1, area.y + (area.height - height) / 2, PANE_WIDTH, height),
+        );
+    }
+
     // Draw theme selector if open
     if app.theme_selector_open {
         draw_theme_selector(frame, area, app);
     }
+
+    if let Some(selected) = app.goto_menu {
+        draw_goto_menu(frame, area, selected, app);
+    }
+
+    if let Some(selected) = app.quick_actions {
+        draw_quick_actions(frame, area, selected, app);
+    }
+
+    // Toast for theme/font/setting changes, in the upper third
+    if let Some(toast) = &app.toast {
+        let osd_area = Rect::new(area.x, area.y + area.height / 6, area.width, 3.min(area.height));
+        frame.render_widget(
+            ToastView::new(toast, chrome.border(app.animation.current_theme.primary_color()), chrome.panel)
+                .text(chrome.text),
+            osd_area,
+        );
+    }
+
+    // Coach tip in the lower third, clear of the setting toasts
+    if let Some(tip) = &app.coach_tip {
+        let tip_area = Rect::new(area.x, area.y + area.height * 2 / 3, area.width, 3.min(area.height));
+        frame.render_widget(
+            ToastView::new(tip, chrome.border(app.animation.current_theme.secondary_color()), chrome.panel)
+                .text(chrome.text),
+            tip_area,
+        );
+    }
+
+    // Breathing exercise or snake game over the break
+    if let Some(activity) = &app.break_activity {
+        break_activity::draw(frame, area, activity, app);
+    }
+
+    // Intention before a work session, focus rating after it
+    if let Some(input) = &app.intention_input {
+        draw_intention_prompt(frame, area, input, app);
+    } else if let Some(record) = &app.pending_review {
+        draw_review_prompt(frame, area, record.intention.as_deref(), app);
+    }
+
+    // Silent mode: what just happened, until a key is pressed
+    if let Some(event) = app.alert_banner.filter(|_| !app.alert_takeover) {
+        draw_alert_banner(frame, area, event.message(), app);
+    }
+
+    // Quit confirmation dialog
+    if app.quit_confirm_open {
+        draw_quit_confirm(frame, area, app);
+    }
+
+    // Command palette (or its last message) on the bottom line
+    draw_command_line(frame, area, app);
+}
+
+/// "Press Space to start Short Break" panel shown instead of the digits
+fn draw_interstitial(frame: &mut Frame, area: Rect, app: &App) {
+    let chrome = app.chrome();
+    let primary = app.animation.current_theme.primary_color();
+    let next = SessionKind::from_state(&app.timer.state).map_or("", |kind| kind.label());
+    let secs = app.timer.remaining.as_secs();
+    let prompt = format!("Press Space to start {}", next);
+    // Away for the whole break: offer another one
+    let extend = app
+        .break_extension
+        .map(|_| format!("e: {} more min of break", app.config.break_check.extend_minutes.max(1)));
+
+    let width = (prompt.chars().count() as u16 + 6).min(area.width);
+    let height = if extend.is_some() { 6u16 } else { 5 }.min(area.height);
+    let x = area.x + area.width.saturating_sub(width) / 2;
+    let y = area.y + area.height.saturating_sub(height) / 2;
+    let panel_area = Rect::new(x, y, width, height);
+
+    let mut lines = vec![
+        Line::from(Span::styled(prompt, Style::default().fg(primary).bold())),
+        Line::from(""),
+        Line::from(format!("{:02}:{:02}  ·  Tab: skip  q: menu", secs / 60, secs % 60)),
+    ];
+    lines.extend(extend.map(Line::from));
+
+    frame.render_widget(Clear, panel_area);
+    frame.render_widget(
+        Paragraph::new(lines)
+        .style(Style::default().fg(chrome.muted))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(chrome.border(primary)))
+                .title(" Up next ")
+                .title_style(Style::default().fg(chrome.border(primary)).bold())
+                .style(Style::default().bg(chrome.panel)),
+        ),
+        panel_area,
+    );
+}
+
+/// "What's your intention?" text prompt, centered over the held session
+fn draw_intention_prompt(frame: &mut Frame, area: Rect, input: &str, app: &App) {
+    let chrome = app.chrome();
+    let primary = app.animation.current_theme.primary_color();
+    let width = 50u16.min(area.width);
+    let height = 5u16.min(area.height);
+    let x = area.x + area.width.saturating_sub(width) / 2;
+    let y = area.y + area.height.saturating_sub(height) / 2;
+    let dialog_area = Rect::new(x, y, width, height);
+
+    // Keep the end of long input (and the cursor) in view
+    let visible = width.saturating_sub(5) as usize;
+    let skip = input.chars().count().saturating_sub(visible);
+    let shown: String = input.chars().skip(skip).collect();
+
+    frame.render_widget(Clear, dialog_area);
+    frame.render_widget(
+        Paragraph::new(vec![
+            Line::from(format!("{}█", shown)),
+            Line::from(""),
+            Line::from(Span::styled(
+                "Enter: start  Esc: skip  3🍅 or ~3: estimate",
+                Style::default().fg(chrome.muted),
+            )),
+        ])
+        .style(Style::default().fg(chrome.text))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(chrome.border(primary)))
+                .title(" What's your intention? ")
+                .title_style(Style::default().fg(chrome.border(primary)).bold())
+                .style(Style::default().bg(chrome.panel)),
+        ),
+        dialog_area,
+    );
+}
+
+/// Bold banner across the top of the screen
+fn draw_alert_banner(frame: &mut Frame, area: Rect, message: &str, app: &App) {
+    let chrome = app.chrome();
+    let primary = app.animation.current_theme.primary_color();
+    let banner = Rect::new(area.x, area.y + area.height / 8, area.width, 3.min(area.height));
+    frame.render_widget(Clear, banner);
+    frame.render_widget(
+        Paragraph::new(vec![
+            Line::from(""),
+            Line::from(vec![
+                Span::styled(format!("● {}", message), Style::default().bold()),
+                Span::raw("   any key: dismiss"),
+            ]),
+        ])
+        .alignment(Alignment::Center)
+        .style(Style::default().fg(chrome.panel).bg(primary)),
+        banner,
+    );
+}
+
+/// Focus rating box in the lower third; the break keeps running underneath
+fn draw_review_prompt(frame: &mut Frame, area: Rect, intention: Option<&str>, app: &App) {
+    let chrome = app.chrome();
+    let primary = app.animation.current_theme.primary_color();
+    let mut lines = Vec::new();
+    if let Some(intention) = intention {
+        lines.push(Line::from(Span::styled(
+            intention.to_string(),
+            Style::default().fg(chrome.muted).italic(),
+        )));
+    }
+    lines.push(Line::from(vec![
+        Span::styled("1", Style::default().fg(primary).bold()),
+        Span::raw(" - "),
+        Span::styled("5", Style::default().fg(primary).bold()),
+        Span::raw("   Esc: skip"),
+    ]));
+
+    let width = 40u16.min(area.width);
+    let height = (lines.len() as u16 + 2).min(area.height);
+    let x = area.x + area.width.saturating_sub(width) / 2;
+    let y = area.y + (area.height * 2 / 3).min(area.height.saturating_sub(height));
+    let dialog_area = Rect::new(x, y, width, height);
+
+    frame.render_widget(Clear, dialog_area);
+    frame.render_widget(
+        Paragraph::new(lines)
+            .style(Style::default().fg(chrome.text))
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(chrome.border(primary)))
+                    .title(" How focused were you? ")
+                    .title_style(Style::default().fg(chrome.border(primary)).bold())
+                    .style(Style::default().bg(chrome.panel)),
+            ),
+        dialog_area,
+    );
+}
+
+/// Small centered "End current session? y/n" dialog
+fn draw_quit_confirm(frame: &mut Frame, area: Rect, app: &App) {
+    let chrome = app.chrome();
+    let primary = app.animation.current_theme.primary_color();
+    let width = 30u16.min(area.width);
+    let height = 5u16.min(area.height);
+    let x = area.x + area.width.saturating_sub(width) / 2;
+    let y = area.y + area.height.saturating_sub(height) / 2;
+    let dialog_area = Rect::new(x, y, width, height);
+
+    frame.render_widget(Clear, dialog_area);
+    frame.render_widget(
+        Paragraph::new(vec![
+            Line::from("End current session?"),
+            Line::from(""),
+            Line::from(vec![
+                Span::styled("y", Style::default().fg(primary).bold()),
+                Span::raw(": end   "),
+                Span::styled("n", Style::default().fg(primary).bold()),
+                Span::raw(": keep going"),
+            ]),
+        ])
+        .style(Style::default().fg(chrome.text))
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(chrome.border(primary)))
+                .title(" Paused ")
+                .title_style(Style::default().fg(chrome.border(primary)).bold())
+                .style(Style::default().bg(chrome.panel)),
+        ),
+        dialog_area,
+    );
+}
+
+/// Vim-style `:` prompt on the last line, replacing the progress bar border
+fn draw_command_line(frame: &mut Frame, area: Rect, app: &App) {
+    let chrome = app.chrome();
+    let (text, style) = if let Some(line) = &app.command_line {
+        (
+            format!(":{}█", line),
+            Style::default().fg(chrome.text).bg(chrome.panel),
+        )
+    } else if let Some(message) = &app.command_message {
+        (
+            message.clone(),
+            Style::default().fg(Color::Rgb(255, 150, 100)).bg(chrome.panel),
+        )
+    } else {
+        return;
+    };
+
+    let y = area.y + area.height.saturating_sub(1);
+    frame.render_widget(Paragraph::new(text).style(style), Rect::new(area.x, y, area.width, 1));
+}
+
+/// Small "elapsed 12:18 · ends 14:35" line just below the big digits
+fn draw_elapsed_line(frame: &mut Frame, area: Rect, timer_area: Rect, app: &App) {
+    let y = timer_area.y + timer_area.height;
+    // Keep clear of the hint line and progress bar
+    if y + 4 >= area.height {
+        return;
+    }
+
+    let elapsed = app.timer.elapsed().as_secs();
+    let end_at = app.now()
+        + chrono::Duration::seconds(app.timer.remaining.as_secs() as i64);
+    let text = format!(
+        "elapsed {:02}:{:02} · ends {}",
+        elapsed / 60,
+        elapsed % 60,
+        end_at.format("%H:%M")
+    );
+
+    let text_len = text.chars().count() as u16;
+    let x = area.x + area.width.saturating_sub(text_len) / 2;
+    frame.render_widget(
+        Paragraph::new(text).style(Style::default().fg(app.animation.current_theme.secondary_color())),
+        Rect::new(x, y, text_len.min(area.width), 1),
+    );
+}
+
+/// One centered line of clocks: here and in each `world_clock` zone
+fn draw_world_clock(frame: &mut Frame, area: Rect, y: u16, app: &App) {
+    // Keep clear of the hint line and progress bar
+    if y + 4 >= area.bottom() {
+        return;
+    }
+    let text = world_clock::row(app.now(), &app.world_clock);
+    let width = (text.chars().count() as u16).min(area.width);
+    frame.render_widget(
+        Paragraph::new(text).style(Style::default().fg(app.animation.current_theme.secondary_color())),
+        Rect::new(area.x + area.width.saturating_sub(width) / 2, y, width, 1),
+    );
+}
+
+/// Up to two centered lines of quote, its color blended into whatever is behind it
+fn draw_quote(frame: &mut Frame, area: Rect, y: u16, quote: &str, opacity: f32, app: &App) {
+    // Keep clear of the hint line and progress bar
+    if y + 5 >= area.bottom() {
+        return;
+    }
+    let max_width = area.width.saturating_sub(8).min(72);
+    let len = quote.chars().count() as u16;
+    let width = len.min(max_width);
+    let rect = Rect::new(area.x + area.width.saturating_sub(width) / 2, y, width, if len > width { 2 } else { 1 });
+    frame.render_widget(
+        Paragraph::new(quote).alignment(Alignment::Center).wrap(Wrap { trim: true }),
+        rect,
+    );
+
+    let color = app.animation.current_theme.secondary_color();
+    let buf = frame.buffer_mut();
+    for y in rect.top()..rect.bottom() {
+        for x in rect.left()..rect.right() {
+            let cell = &mut buf[(x, y)];
+            cell.fg = transition::mix(cell.bg, color, opacity);
+            cell.modifier.insert(Modifier::ITALIC);
+        }
+    }
+}
+
+/// Digit colors, pulsing during the final countdown of a session
+fn digit_colors(app: &App) -> (Color, Color) {
+    let theme = &app.animation.current_theme;
+    let primary = theme.primary_color();
+    let secondary = theme.secondary_color();
+
+    // Digits-only mode and reduced motion don't flash either
+    if !app.timer.in_final_countdown() || app.digits_only() || app.animation.reduced_motion() {
+        return (primary, secondary);
+    }
+
+    // Flash white right after each second changes, then invert on odd seconds
+    if app.timer.remaining.subsec_millis() > 700 {
+        (Color::White, primary)
+    } else if app.timer.remaining.as_secs() % 2 == 1 {
+        (secondary, primary)
+    } else {
+        (primary, secondary)
+    }
 }
 
 /// Calculate a centered area for the timer digits based on current font
-fn centered_timer_area(area: Rect, scaling: &ScalingContext, font: crate::animation::DigitFont) -> Rect {
+fn centered_timer_area(area: Rect, scaling: &ScalingContext, font: DigitFont) -> Rect {
     // Calculate actual size needed for current font
     let font_width = font.width();
     let font_height = font.height();
@@ -66,7 +560,22 @@ fn centered_timer_area(area: Rect, scaling: &ScalingContext, font: crate::animat
     )
 }
 
+/// "Work · Deep Work (Lap 2/10)"
+fn session_label(app: &App) -> String {
+    let lap_info = if app.timer.total_laps() > 0 {
+        format!(" (Lap {}/{})", app.timer.current_lap(), app.timer.total_laps())
+    } else {
+        String::new()
+    };
+    let category = match (&app.timer.category, SessionKind::from_state(&app.timer.state)) {
+        (Some(category), Some(SessionKind::Work)) => format!(" · {}", category),
+        _ => String::new(),
+    };
+    format!("{}{}{}", app.timer.session_name(), category, lap_info)
+}
+
 fn draw_timer_overlay(frame: &mut Frame, area: Rect, app: &App) {
+    let chrome = app.chrome();
     // Early exit for very small terminals
     if area.width < 20 || area.height < 10 {
         return;
@@ -75,32 +584,37 @@ fn draw_timer_overlay(frame: &mut Frame, area: Rect, app: &App) {
     let scaling = &app.scaling;
     let theme = &app.animation.current_theme;
     let primary = theme.primary_color();
-    let bg_color = Color::Rgb(10, 10, 20);
+    let bg_color = chrome.background;
     let progress = app.timer.session_progress();
 
-    // In compact mode, skip some UI elements
-    let show_session_info = scaling.show_session_info;
-    let show_hints = scaling.show_hints;
-
-    // ZEN MODE: When hints are hidden, only show minimal discrete progress
+    // ZEN MODE: When hints are hidden, only what `zen` in config keeps, without boxes
     if !app.hints_visible {
-        // Ultra-discrete progress line at very bottom (1px tall, no border)
-        let filled_width = (area.width as f64 * progress) as u16;
-
-        // Very subtle progress indicator - just a thin line
+        let zen = &app.config.zen;
         let dim_primary = match primary {
             Color::Rgb(r, g, b) => Color::Rgb(r / 3, g / 3, b / 3),
             _ => Color::Rgb(40, 40, 50),
         };
 
-        // Draw filled portion
-        for x in 0..filled_width {
+        // Ultra-discrete progress line at very bottom (1px tall, no border)
+        if zen.progress {
             frame.render_widget(
-                Paragraph::new("▁").style(Style::default().fg(dim_primary)),
-                Rect::new(area.x + x, area.y + area.height - 1, 1, 1),
+                ZenProgress::new(progress).color(dim_primary),
+                Rect::new(area.x, area.bottom() - 1, area.width, 1),
             );
         }
 
+        let quiet = theme.secondary_color();
+        if zen.clock {
+            let clock = ClockBox::new(app.timer.remaining).bare().colors(quiet, bg_color);
+            let (width, _) = clock.size();
+            frame.render_widget(clock, Rect::new(area.width.saturating_sub(width + 2), 1, width, 1));
+        }
+        if zen.session {
+            let info = SessionInfo::new(session_label(app)).bare().colors(quiet, bg_color);
+            let width = info.size().0.min(area.width.saturating_sub(10));
+            frame.render_widget(info, Rect::new(2, 1, width, 1));
+        }
+
         // Flash message when first hidden
         if app.hint_flash_frames > 0 {
             let flash = "h: show UI";
@@ -116,128 +630,135 @@ fn draw_timer_overlay(frame: &mut Frame, area: Rect, app: &App) {
         return; // Exit early - zen mode shows nothing else
     }
 
-    // NORMAL MODE: Full UI with all info panels
+    // NORMAL MODE: Full UI with all info panels, each widget where `overlay` puts it
 
-    // Timer display in top-right (small digital clock)
-    let time_secs = app.timer.remaining.as_secs();
-    let minutes = time_secs / 60;
-    let seconds = time_secs % 60;
-    let time_str = format!("{:02}:{:02}", minutes, seconds);
+    // Rows taken by the progress bar at each edge
+    let bar = &app.config.progress_bar;
+    let (top, bottom) = match bar.position {
+        BarPosition::Top => (bar.rows(), 0),
+        BarPosition::Bottom => (0, bar.rows()),
+    };
+    let placement = &app.config.overlay;
+    let mut layout = OverlayLayout::new(area, top, bottom);
 
-    let time_x = area.width.saturating_sub(11);
+    let clock = ClockBox::new(app.timer.remaining).colors(chrome.border(primary), bg_color);
+    let (width, height) = clock.size();
+    if let Some(rect) = layout.place(placement.clock, width, height) {
+        frame.render_widget(clock, rect);
+    }
 
-    let timer_bg = Block::default()
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(primary))
-        .style(Style::default().bg(bg_color));
-    let timer_box_width = 10.min(area.width.saturating_sub(time_x.saturating_sub(1)));
-    frame.render_widget(
-        timer_bg,
-        Rect::new(time_x.saturating_sub(1), 0, timer_box_width, 3),
-    );
+    // The session coming up, under the clock once this one nears its end
+    let ending = app.timer.remaining <= PREVIEW_WINDOW;
+    if let Some(step) = app.timer.next_step().filter(|_| ending || app.config.always_show_next) {
+        let next = NextSession::new(&PomodoroTimer::step_name(step), app.timer.step_length(step))
+            .colors(chrome.muted, bg_color);
+        let (width, height) = next.size();
+        if let Some(rect) = layout.place(placement.next, width, height) {
+            frame.render_widget(next, rect);
+        }
+    }
 
-    frame.render_widget(
-        Paragraph::new(time_str)
-            .style(Style::default().fg(primary).bold())
-            .alignment(Alignment::Center),
-        Rect::new(time_x, 1, 8.min(area.width.saturating_sub(time_x)), 1),
-    );
+    // Session info (hidden in compact mode)
+    if scaling.show_session_info {
+        let mut info = SessionInfo::new(session_label(app)).colors(chrome.border(primary), bg_color);
+        // With the secondary clock on (`e`), when the cycle reaches its long break
+        if let Some(left) = app.timer.cycle_remaining().filter(|_| app.show_elapsed) {
+            let end_at = app.now() + chrono::Duration::seconds(left.as_secs() as i64);
+            info = info.detail(format!("cycle ends {}", end_at.format("%H:%M")));
+        }
+        let (width, height) = info.size();
+        if let Some(rect) = layout.place(placement.session, width, height) {
+            frame.render_widget(info, rect);
+        }
+    }
 
-    // Session info in top-left (hidden in compact mode)
-    let info_width = if show_session_info {
-        let session_name = app.timer.session_name();
-        let lap_info = if app.timer.total_laps() > 0 {
-            format!(" (Lap {}/{})", app.timer.current_lap(), app.timer.total_laps())
-        } else {
-            String::new()
-        };
-        let session_str = format!("{}{}", session_name, lap_info);
+    let name = theme.name();
+    let badge = ThemeBadge::new(name).colors(chrome.hint, bg_color);
+    let (width, height) = badge.size();
+    if let Some(rect) = layout.place(placement.theme, width, height) {
+        frame.render_widget(badge, rect);
+    }
 
-        let info_width = (session_str.len() as u16 + 4).min(area.width);
-        let info_bg = Block::default()
-            .borders(Borders::ALL)
-            .border_style(Style::default().fg(primary))
-            .style(Style::default().bg(bg_color));
-        frame.render_widget(info_bg, Rect::new(0, 0, info_width, 3));
+    // Controls hint (hidden in compact mode or when scaling says to hide)
+    if scaling.show_hints {
+        let hints = HintBar::new(area.width).color(chrome.hint);
+        let (width, height) = hints.size();
+        if let Some(rect) = layout.place(placement.hints, width, height) {
+            frame.render_widget(hints, rect);
+        }
+    }
 
+    // Session progress bar (configurable edge, height and style)
+    if bar.visible {
         frame.render_widget(
-            Paragraph::new(session_str).style(Style::default().fg(primary)),
-            Rect::new(2, 1, info_width.saturating_sub(4), 1),
+            ProgressBar::new(progress)
+                .style(bar.style)
+                .colors(bar.fixed_color().unwrap_or(primary), theme.secondary_color())
+                .background(bg_color),
+            bar.area(area),
         );
+    }
 
-        info_width
-    } else {
-        0
+    // Status lines go with the clock, team mode with the session info
+    let status_lines = match placement.clock {
+        Placement::Hidden => Placement::TopRight,
+        clock => clock,
+    };
+    let mut status_line = |text: String, color: Color| {
+        if let Some(rect) = layout.place(status_lines, text.chars().count() as u16, 1) {
+            frame.render_widget(Paragraph::new(text).style(Style::default().fg(color)), rect);
+        }
     };
 
-    // Theme name indicator (top center)
-    let theme_name = format!(" {} ", theme.name());
-    let theme_width = theme_name.len() as u16 + 2;
-    let theme_x = area.width.saturating_sub(theme_width) / 2;
-    if theme_x > info_width && theme_x + theme_width < time_x.saturating_sub(1) {
-        frame.render_widget(
-            Paragraph::new(theme_name)
-                .style(Style::default().fg(Color::DarkGray).bg(bg_color)),
-            Rect::new(theme_x, 0, theme_width, 1),
+    // Meeting mode: countdown to the target under the clock
+    if let Some(meeting) = &app.meeting {
+        let now = app.now();
+        let plan = meeting.plan(now, &app.timer);
+        let (fit, color) = if plan.next_fits || plan.pomodoros > 0 {
+            (format!("{} fit", plan.pomodoros), theme.secondary_color())
+        } else {
+            ("none fit".to_string(), Color::Rgb(230, 120, 80))
+        };
+        let text = format!(
+            "{} in {} · {}",
+            meeting.target.format("%H:%M"),
+            meeting::format_left(meeting.time_left(now)),
+            fit
         );
+        status_line(text, color);
     }
 
-    // Progress bar at bottom (full style with border)
-    let gauge = Gauge::default()
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .border_style(Style::default().fg(primary))
-                .style(Style::default().bg(bg_color)),
-        )
-        .gauge_style(
-            Style::default()
-                .fg(primary)
-                .bg(theme.secondary_color()),
-        )
-        .ratio(progress);
-    frame.render_widget(
-        gauge,
-        Rect::new(0, area.height.saturating_sub(3), area.width, 3.min(area.height)),
-    );
+    // Time this session has spent paused, under the clock
+    let paused = app.timer.paused_time().as_secs();
+    if paused > 0 && !app.awaiting_start {
+        status_line(format!("paused {}:{:02} total", paused / 60, paused % 60), chrome.hint);
+    }
 
     // Auto-rotate indicator (when disabled)
     if !app.auto_rotate {
-        let lock_text = "[theme locked]";
-        let lock_x = area.width.saturating_sub(lock_text.len() as u16 + 2);
-        if lock_x > 0 {
-            frame.render_widget(
-                Paragraph::new(lock_text).style(Style::default().fg(Color::Rgb(100, 80, 80))),
-                Rect::new(lock_x, 3, lock_text.len() as u16, 1),
-            );
-        }
+        status_line("[theme locked]".to_string(), Color::Rgb(100, 80, 80));
     }
 
-    // Controls hint (hidden in compact mode or when scaling says to hide)
-    if show_hints {
-        let hint_y = area.height.saturating_sub(4);
-        if hint_y > 3 {
-            // Shorter hint for smaller terminals
-            let hint = if area.width < 70 {
-                "Space:Pause r:Reset t:Theme h:Zen q:Menu"
-            } else {
-                "Space: Pause  r: Reset  Tab: Skip  t: Themes  f: Font  a: Auto  h: Zen  q: Menu"
-            };
-            let hint_len = hint.len() as u16;
-            let hint_x = area.width.saturating_sub(hint_len) / 2;
-            let hint_width = hint_len.min(area.width.saturating_sub(hint_x));
-            frame.render_widget(
-                Paragraph::new(hint).style(Style::default().fg(Color::Rgb(80, 80, 100))),
-                Rect::new(hint_x, hint_y, hint_width, 1),
-            );
+    // Team mode: who shares this timer, under the session info
+    if let Some(team) = &app.team {
+        let others = team.participants();
+        let list = Participants::new(team.room(), team.name(), &others).colors(chrome.border(primary), bg_color);
+        let (width, height) = list.size();
+        let side = match placement.session {
+            Placement::Hidden => Placement::TopLeft,
+            session => session,
+        };
+        if let Some(rect) = layout.place(side, width.min(area.width / 3), height) {
+            frame.render_widget(list, rect);
         }
     }
 }
 
 fn draw_theme_selector(frame: &mut Frame, area: Rect, app: &App) {
+    let chrome = app.chrome();
     let themes = ThemeType::all();
     let primary = app.animation.current_theme.primary_color();
-    let bg_color = Color::Rgb(15, 15, 25);
+    let bg_color = chrome.panel;
 
     // Panel dimensions
     let panel_width = 24u16.min(area.width.saturating_sub(4));
@@ -257,9 +778,9 @@ fn draw_theme_selector(frame: &mut Frame, area: Rect, app: &App) {
     // Draw panel background
     let panel = Block::default()
         .borders(Borders::ALL)
-        .border_style(Style::default().fg(primary))
+        .border_style(Style::default().fg(chrome.border(primary)))
         .title(" Themes ")
-        .title_style(Style::default().fg(primary).bold())
+        .title_style(Style::default().fg(chrome.border(primary)).bold())
         .title_bottom(" ↑↓ Enter Esc ")
         .style(Style::default().bg(bg_color));
     frame.render_widget(panel, panel_area);
@@ -278,7 +799,7 @@ fn draw_theme_selector(frame: &mut Frame, area: Rect, app: &App) {
         let style = if is_selected {
             Style::default().fg(primary).bold()
         } else {
-            Style::default().fg(Color::White)
+            Style::default().fg(chrome.text)
         };
 
         let text_x = panel_x + 2;
@@ -293,3 +814,83 @@ fn draw_theme_selector(frame: &mut Frame, area: Rect, app: &App) {
     }
 
 }
+
+/// "Go to" panel listing the sessions of one cycle, the current one marked
+fn draw_goto_menu(frame: &mut Frame, area: Rect, selected: usize, app: &App) {
+    let chrome = app.chrome();
+    let primary = app.animation.current_theme.primary_color();
+    let current = app.timer.cycle_step();
+
+    let lines: Vec<Line> = (0..CYCLE_STEPS)
+        .map(|step| {
+            let marker = if step == selected { "▶ " } else { "  " };
+            let now = if Some(step) == current { "  (now)" } else { "" };
+            let text = format!("{}{} {}{}", marker, step + 1, PomodoroTimer::step_name(step), now);
+            if step == selected {
+                Line::from(Span::styled(text, Style::default().fg(primary).bold()))
+            } else {
+                Line::from(text)
+            }
+        })
+        .collect();
+
+    let width = 30u16.min(area.width);
+    let height = (CYCLE_STEPS as u16 + 2).min(area.height);
+    let panel_area = Rect::new(
+        area.x + area.width.saturating_sub(width) / 2,
+        area.y + area.height.saturating_sub(height) / 2,
+        width,
+        height,
+    );
+    frame.render_widget(Clear, panel_area);
+    frame.render_widget(
+        Paragraph::new(lines).style(Style::default().fg(chrome.text)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(chrome.border(primary)))
+                .title(" Go to ")
+                .title_style(Style::default().fg(chrome.border(primary)).bold())
+                .title_bottom(" ↑↓ 1-8 Enter Esc ")
+                .style(Style::default().bg(chrome.panel)),
+        ),
+        panel_area,
+    );
+}
+
+/// Quick actions popup in the top right corner, numbered for a single key press
+fn draw_quick_actions(frame: &mut Frame, area: Rect, selected: usize, app: &App) {
+    let chrome = app.chrome();
+    let primary = app.animation.current_theme.primary_color();
+    let actions = QuickAction::all();
+
+    let lines: Vec<Line> = actions
+        .iter()
+        .enumerate()
+        .map(|(i, action)| {
+            let marker = if i == selected { "▶ " } else { "  " };
+            let text = format!("{}{} {}", marker, i + 1, action.label(app));
+            if i == selected {
+                Line::from(Span::styled(text, Style::default().fg(primary).bold()))
+            } else {
+                Line::from(text)
+            }
+        })
+        .collect();
+
+    let width = 22u16.min(area.width);
+    let height = (actions.len() as u16 + 2).min(area.height.saturating_sub(1));
+    let panel_area = Rect::new(area.x + area.width.saturating_sub(width + 1), area.y + 1.min(area.height), width, height);
+    frame.render_widget(Clear, panel_area);
+    frame.render_widget(
+        Paragraph::new(lines).style(Style::default().fg(chrome.text)).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(chrome.border(primary)))
+                .title(" Quick actions ")
+                .title_style(Style::default().fg(chrome.border(primary)).bold())
+                .title_bottom(" ↑↓ 1-6 Esc ")
+                .style(Style::default().bg(chrome.panel)),
+        ),
+        panel_area,
+    );
+}
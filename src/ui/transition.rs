@@ -0,0 +1,146 @@
+//! Short animated transitions between screens
+//! Every frame is remembered before post-processing; when the screen changes,
+//! the next few frames blend that last picture of the old screen into the new
+//! one, cell by cell, by sliding it out or cross-fading.
+
+use ratatui::buffer::Buffer;
+use ratatui::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Frames a transition lasts (0.5 s at 10 FPS)
+pub const TRANSITION_FRAMES: u32 = 5;
+
+/// How screen changes are animated (the `transitions` key in config.json)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TransitionStyle {
+    /// The new screen pushes the old one out sideways
+    #[default]
+    Slide,
+    /// Colors blend from the old screen into the new one
+    Fade,
+    /// Instant cut
+    Off,
+}
+
+/// A transition in progress
+pub struct Transition {
+    /// Last frame of the screen being left
+    from: Buffer,
+    style: TransitionStyle,
+    /// Slide direction: forward enters from the right, back from the left
+    forward: bool,
+    pub frames_left: u32,
+}
+
+impl Transition {
+    pub fn new(from: Buffer, style: TransitionStyle, forward: bool) -> Self {
+        Self {
+            from,
+            style,
+            forward,
+            frames_left: TRANSITION_FRAMES,
+        }
+    }
+
+    /// Blend the old screen into the freshly drawn `buf`
+    pub fn apply(&self, buf: &mut Buffer) {
+        if self.from.area != buf.area {
+            return;
+        }
+        let linear = 1.0 - self.frames_left as f32 / (TRANSITION_FRAMES + 1) as f32;
+        // Ease out: quick start, gentle landing
+        let t = 1.0 - (1.0 - linear).powi(2);
+        match self.style {
+            TransitionStyle::Slide => self.slide(buf, t),
+            TransitionStyle::Fade => self.fade(buf, t),
+            TransitionStyle::Off => {}
+        }
+    }
+
+    fn slide(&self, buf: &mut Buffer, t: f32) {
+        let area = buf.area;
+        let width = area.width as i32;
+        // Columns of the new screen already in view
+        let shown = (width as f32 * t).round() as i32;
+        let new = buf.clone();
+        for y in area.top()..area.bottom() {
+            for col in 0..width {
+                // Forward: old content moves left and the new enters from the right
+                let (source, from_new) = if self.forward {
+                    if col < width - shown {
+                        (col + shown, false)
+                    } else {
+                        (col - (width - shown), true)
+                    }
+                } else if col < shown {
+                    (col + (width - shown), true)
+                } else {
+                    (col - shown, false)
+                };
+                let source = (area.x + source as u16, y);
+                let cell = if from_new { &new[source] } else { &self.from[source] };
+                buf[(area.x + col as u16, y)] = cell.clone();
+            }
+        }
+    }
+
+    fn fade(&self, buf: &mut Buffer, t: f32) {
+        let area = buf.area;
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let old = &self.from[(x, y)];
+                let cell = &mut buf[(x, y)];
+                // Glyphs swap halfway; colors blend all along
+                if t < 0.5 {
+                    let (fg, bg) = (cell.fg, cell.bg);
+                    *cell = old.clone();
+                    cell.fg = mix(old.fg, fg, t);
+                    cell.bg = mix(old.bg, bg, t);
+                } else {
+                    cell.fg = mix(old.fg, cell.fg, t);
+                    cell.bg = mix(old.bg, cell.bg, t);
+                }
+            }
+        }
+    }
+}
+
+/// Linear blend of two truecolor colors; anything else snaps at the halfway point
+pub(crate) fn mix(from: Color, to: Color, t: f32) -> Color {
+    match (from, to) {
+        (Color::Rgb(r1, g1, b1), Color::Rgb(r2, g2, b2)) => {
+            let channel = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+            Color::Rgb(channel(r1, r2), channel(g1, g2), channel(b1, b2))
+        }
+        _ if t < 0.5 => from,
+        _ => to,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_slide_pushes_old_screen_out() {
+        let area = Rect::new(0, 0, 10, 1);
+        let mut old = Buffer::empty(area);
+        old.set_string(0, 0, "oooooooooo", Style::default());
+        let mut new = Buffer::empty(area);
+        new.set_string(0, 0, "nnnnnnnnnn", Style::default());
+
+        let mut transition = Transition::new(old, TransitionStyle::Slide, true);
+        transition.frames_left = TRANSITION_FRAMES;
+        let mut buf = new.clone();
+        transition.apply(&mut buf);
+        let row: String = (0..10).map(|x| buf[(x, 0)].symbol()).collect();
+        // Old on the left, new coming in from the right
+        assert!(row.starts_with('o') && row.ends_with('n'));
+
+        transition.frames_left = 0;
+        let mut buf = new.clone();
+        transition.apply(&mut buf);
+        assert_eq!(buf, new);
+    }
+}
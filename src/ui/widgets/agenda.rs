@@ -0,0 +1,87 @@
+//! Side pane walking through the day template: finished blocks ticked off, the
+//! block in progress with its count, and the ones still to come
+
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::agenda::Agenda;
+
+pub struct AgendaPane<'a> {
+    agenda: &'a Agenda,
+    /// Only the block in progress
+    collapsed: bool,
+    accent: Color,
+    background: Color,
+}
+
+impl<'a> AgendaPane<'a> {
+    pub fn new(agenda: &'a Agenda) -> Self {
+        Self {
+            agenda,
+            collapsed: false,
+            accent: Color::White,
+            background: Color::Reset,
+        }
+    }
+
+    pub fn collapsed(mut self, collapsed: bool) -> Self {
+        self.collapsed = collapsed;
+        self
+    }
+
+    pub fn colors(mut self, accent: Color, background: Color) -> Self {
+        self.accent = accent;
+        self.background = background;
+        self
+    }
+
+    /// Height needed to show every line
+    pub fn height(&self) -> u16 {
+        let rows = if self.collapsed { 1 } else { self.agenda.blocks.len() };
+        rows as u16 + 2
+    }
+
+    /// "✓ Morning       4×50/10", "▸ Afternoon  2/6 25/5"
+    fn block_line(&self, index: usize, width: usize) -> Line<'static> {
+        let block = &self.agenda.blocks[index];
+        let done = self.agenda.done(index);
+        let dim = Style::default().fg(Color::DarkGray);
+        let (marker, style, detail) = if done >= block.pomodoros {
+            ("✓ ", dim, block.shape())
+        } else if self.agenda.current() == Some(index) {
+            let detail = format!("{}/{} {}/{}", done, block.pomodoros, block.work_minutes, block.break_minutes);
+            ("▸ ", Style::default().fg(self.accent).bold(), detail)
+        } else {
+            ("  ", Style::default().fg(Color::White), block.shape())
+        };
+        let name_width = width.saturating_sub(2 + detail.chars().count() + 1);
+        let name: String = block.name.chars().take(name_width).collect();
+        Line::from(vec![
+            Span::styled(marker, style),
+            Span::styled(format!("{:<width$} ", name, width = name_width), style),
+            Span::styled(detail, dim),
+        ])
+    }
+}
+
+impl Widget for AgendaPane<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let width = area.width.saturating_sub(2) as usize;
+        let lines: Vec<Line> = match (self.collapsed, self.agenda.current()) {
+            (true, None) => vec![Line::styled("All blocks done", Style::default().fg(Color::DarkGray))],
+            (true, Some(current)) => vec![self.block_line(current, width)],
+            (false, _) => (0..self.agenda.blocks.len()).map(|i| self.block_line(i, width)).collect(),
+        };
+
+        Clear.render(area, buf);
+        Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(self.accent))
+                    .title(format!(" {} ", self.agenda.name))
+                    .style(Style::default().bg(self.background)),
+            )
+            .render(area, buf);
+    }
+}
@@ -0,0 +1,92 @@
+//! Small boxed countdown shown in a corner of the timer screen while the big
+//! digits fill the middle
+
+use std::time::Duration;
+
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+/// Box size, border included
+pub const WIDTH: u16 = 10;
+pub const HEIGHT: u16 = 3;
+
+pub struct ClockBox {
+    remaining: Duration,
+    /// Just the time, without the box (zen mode)
+    bare: bool,
+    accent: Color,
+    background: Color,
+}
+
+impl ClockBox {
+    pub fn new(remaining: Duration) -> Self {
+        Self {
+            remaining,
+            bare: false,
+            accent: Color::White,
+            background: Color::Reset,
+        }
+    }
+
+    pub fn bare(mut self) -> Self {
+        self.bare = true;
+        self
+    }
+
+    pub fn colors(mut self, accent: Color, background: Color) -> Self {
+        self.accent = accent;
+        self.background = background;
+        self
+    }
+
+    /// Width and height it needs
+    pub fn size(&self) -> (u16, u16) {
+        if self.bare {
+            (self.text().len() as u16, 1)
+        } else {
+            (WIDTH, HEIGHT)
+        }
+    }
+
+    /// "24:59"
+    fn text(&self) -> String {
+        let secs = self.remaining.as_secs();
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+impl Widget for ClockBox {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if self.bare {
+            Paragraph::new(self.text()).style(Style::default().fg(self.accent)).render(area, buf);
+            return;
+        }
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(self.accent))
+            .style(Style::default().bg(self.background));
+        let inner = block.inner(area);
+        block.render(area, buf);
+        Paragraph::new(self.text())
+            .style(Style::default().fg(self.accent).bold())
+            .alignment(Alignment::Center)
+            .render(inner, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clock_box_renders_the_countdown() {
+        let area = Rect::new(0, 0, WIDTH, HEIGHT);
+        let mut buf = Buffer::empty(area);
+        ClockBox::new(Duration::from_secs(24 * 60 + 9)).render(area, &mut buf);
+        let middle: String = (0..WIDTH).map(|x| buf[(x, 1)].symbol().to_string()).collect();
+        assert_eq!(middle, "│  24:09 │");
+
+        let bare = ClockBox::new(Duration::from_secs(61)).bare();
+        assert_eq!(bare.size(), (5, 1));
+    }
+}
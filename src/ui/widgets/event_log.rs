@@ -0,0 +1,72 @@
+//! Side pane listing this run's session events ("09:14 Paused"), newest last
+
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+use crate::events::EventLog;
+
+/// Pane width, enough for "09:25 Short Break left early"
+pub const PANE_WIDTH: u16 = 30;
+
+pub struct EventLogPane<'a> {
+    log: &'a EventLog,
+    accent: Color,
+    background: Color,
+}
+
+impl<'a> EventLogPane<'a> {
+    pub fn new(log: &'a EventLog) -> Self {
+        Self {
+            log,
+            accent: Color::White,
+            background: Color::Reset,
+        }
+    }
+
+    pub fn colors(mut self, accent: Color, background: Color) -> Self {
+        self.accent = accent;
+        self.background = background;
+        self
+    }
+
+    /// Height needed to show every entry (clamped by the caller's area)
+    pub fn height(&self) -> u16 {
+        self.log.entries().len().max(1) as u16 + 2
+    }
+}
+
+impl Widget for EventLogPane<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // Scrolled to the end: the newest entries that fit
+        let rows = area.height.saturating_sub(2) as usize;
+        let time_style = Style::default().fg(Color::DarkGray);
+        let mut lines: Vec<Line> = self
+            .log
+            .entries()
+            .rev()
+            .take(rows)
+            .map(|event| {
+                Line::from(vec![
+                    Span::styled(event.at.format("%H:%M ").to_string(), time_style),
+                    Span::raw(event.describe()),
+                ])
+            })
+            .collect();
+        lines.reverse();
+        if lines.is_empty() {
+            lines.push(Line::styled("Nothing yet", time_style));
+        }
+
+        Clear.render(area, buf);
+        Paragraph::new(lines)
+            .style(Style::default().fg(Color::White))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(self.accent))
+                    .title(" Events ")
+                    .style(Style::default().bg(self.background)),
+            )
+            .render(area, buf);
+    }
+}
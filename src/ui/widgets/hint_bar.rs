@@ -0,0 +1,43 @@
+//! Line of key hints for the timer screen, shortened on narrow terminals
+
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+
+/// Below this width the short hint is used
+const FULL_HINT_WIDTH: u16 = 70;
+
+const FULL_HINT: &str = "Space: Pause  r: Reset  Tab: Skip  t: Themes  f: Font  a: Auto  h: Zen  q: Menu  .: More";
+const SHORT_HINT: &str = "Space:Pause r:Reset t:Theme h:Zen q:Menu .:More";
+
+const HINT_COLOR: Color = Color::Rgb(80, 80, 100);
+
+pub struct HintBar {
+    hint: &'static str,
+    color: Color,
+}
+
+impl HintBar {
+    /// The hint that suits a screen `screen_width` columns wide
+    pub fn new(screen_width: u16) -> Self {
+        let hint = if screen_width < FULL_HINT_WIDTH { SHORT_HINT } else { FULL_HINT };
+        Self { hint, color: HINT_COLOR }
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+
+    /// Width and height it needs
+    pub fn size(&self) -> (u16, u16) {
+        (self.hint.len() as u16, 1)
+    }
+}
+
+impl Widget for HintBar {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(self.hint)
+            .style(Style::default().fg(self.color))
+            .render(area, buf);
+    }
+}
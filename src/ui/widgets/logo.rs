@@ -0,0 +1,99 @@
+//! Animated "pomowise" wordmark for the menu: a color gradient sweeps across
+//! the letters while a wave lifts them one column at a time.
+
+use ratatui::prelude::*;
+
+use crate::animation::math::fast_sin;
+
+/// Letter glyphs, three rows each
+const LETTERS: [[&str; 3]; 8] = [
+    ["█▀█", "█▀▀", "▀  "],
+    ["█▀█", "█ █", "▀▀▀"],
+    ["█▀▄▀█", "█ ▀ █", "▀   ▀"],
+    ["█▀█", "█ █", "▀▀▀"],
+    ["█   █", "█ ▄ █", "▀▀ ▀▀"],
+    ["█", "█", "▀"],
+    ["█▀▀", "▀▀█", "▀▀▀"],
+    ["█▀▀", "█▀▀", "▀▀▀"],
+];
+
+/// Width of the wordmark in cells
+pub const LOGO_WIDTH: u16 = 33;
+
+/// Height including the extra row the wave lifts letters into
+pub const LOGO_HEIGHT: u16 = 4;
+
+pub struct Logo {
+    frame_index: usize,
+    primary: Color,
+    secondary: Color,
+}
+
+impl Logo {
+    pub fn new(frame_index: usize, primary: Color, secondary: Color) -> Self {
+        Self {
+            frame_index,
+            primary,
+            secondary,
+        }
+    }
+}
+
+impl Widget for Logo {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.width < LOGO_WIDTH || area.height < LOGO_HEIGHT {
+            return;
+        }
+        let t = self.frame_index as f32 * 0.3;
+        let x0 = area.x + (area.width - LOGO_WIDTH) / 2;
+
+        for (row, line) in logo_rows().iter().enumerate() {
+            for (col, ch) in line.chars().enumerate() {
+                if ch == ' ' {
+                    continue;
+                }
+                let phase = col as f32 * 0.25 - t;
+                let color = blend(self.primary, self.secondary, (fast_sin(phase) + 1.0) / 2.0);
+                // Columns under the wave crest sit one row higher
+                let lift = if fast_sin(phase * 0.5) > 0.8 { 0 } else { 1 };
+                let x = x0 + col as u16;
+                let y = area.y + row as u16 + lift;
+                buf[(x, y)].set_char(ch).set_fg(color);
+            }
+        }
+    }
+}
+
+/// The three rows of the wordmark, letters separated by one space
+fn logo_rows() -> [String; 3] {
+    std::array::from_fn(|row| {
+        LETTERS
+            .iter()
+            .map(|letter| letter[row])
+            .collect::<Vec<_>>()
+            .join(" ")
+    })
+}
+
+/// Mix two RGB colors (`amount` 0.0 = `a`, 1.0 = `b`); other colors stay `a`
+fn blend(a: Color, b: Color, amount: f32) -> Color {
+    match (a, b) {
+        (Color::Rgb(r1, g1, b1), Color::Rgb(r2, g2, b2)) => {
+            let mix = |from: u8, to: u8| (from as f32 + (to as f32 - from as f32) * amount) as u8;
+            Color::Rgb(mix(r1, r2), mix(g1, g2), mix(b1, b2))
+        }
+        _ => a,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_logo_width_matches_glyphs() {
+        for row in logo_rows() {
+            assert_eq!(row.chars().count() as u16, LOGO_WIDTH);
+        }
+    }
+}
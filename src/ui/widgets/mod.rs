@@ -1 +1,17 @@
-// Widget exports - currently empty, can be extended later
+//! Reusable widgets for the timer and menu screens
+
+pub mod agenda;
+pub mod clock_box;
+pub mod event_log;
+pub mod hint_bar;
+pub mod logo;
+pub mod next_session;
+pub mod overlay;
+pub mod participants;
+pub mod progress;
+pub mod ring;
+pub mod session_info;
+pub mod stats_glance;
+pub mod theme_badge;
+pub mod toast;
+pub mod zen_progress;
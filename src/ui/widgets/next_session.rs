@@ -0,0 +1,46 @@
+//! Session coming up after this one ("Next: Short Break #2 (5:00)"), a quiet
+//! label by the clock so transitions are never a surprise
+
+use std::time::Duration;
+
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+
+/// Time before a session ends from which the next one is previewed
+pub const PREVIEW_WINDOW: Duration = Duration::from_secs(3 * 60);
+
+pub struct NextSession {
+    label: String,
+    color: Color,
+    background: Color,
+}
+
+impl NextSession {
+    pub fn new(name: &str, length: Duration) -> Self {
+        let secs = length.as_secs();
+        Self {
+            label: format!("Next: {} ({}:{:02})", name, secs / 60, secs % 60),
+            color: Color::DarkGray,
+            background: Color::Reset,
+        }
+    }
+
+    pub fn colors(mut self, color: Color, background: Color) -> Self {
+        self.color = color;
+        self.background = background;
+        self
+    }
+
+    /// Width and height it needs
+    pub fn size(&self) -> (u16, u16) {
+        (self.label.chars().count() as u16 + 2, 1)
+    }
+}
+
+impl Widget for NextSession {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(format!(" {} ", self.label))
+            .style(Style::default().fg(self.color).bg(self.background))
+            .render(area, buf);
+    }
+}
@@ -0,0 +1,143 @@
+//! Where the timer screen's small widgets go (the `overlay` key in config.json)
+//! Each widget asks `OverlayLayout` for room at its placement: widgets sharing a
+//! corner stack away from the screen edge, and one that would run into another
+//! is left out rather than drawn over it.
+
+use ratatui::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Columns kept clear between the right-hand widgets and the screen edge
+const RIGHT_MARGIN: u16 = 2;
+
+/// Spot on the top or bottom edge of the timer screen, or nowhere
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Placement {
+    TopLeft,
+    TopCenter,
+    TopRight,
+    BottomLeft,
+    BottomCenter,
+    BottomRight,
+    Hidden,
+}
+
+impl Placement {
+    /// Position in `OverlayLayout::stacked`
+    fn slot(self) -> Option<usize> {
+        match self {
+            Placement::TopLeft => Some(0),
+            Placement::TopCenter => Some(1),
+            Placement::TopRight => Some(2),
+            Placement::BottomLeft => Some(3),
+            Placement::BottomCenter => Some(4),
+            Placement::BottomRight => Some(5),
+            Placement::Hidden => None,
+        }
+    }
+}
+
+/// Placement of each timer screen widget
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OverlayConfig {
+    /// Small boxed countdown
+    pub clock: Placement,
+    /// Session name, category and lap
+    pub session: Placement,
+    /// Name of the current theme
+    pub theme: Placement,
+    /// Key hints
+    pub hints: Placement,
+    /// Session coming up next, near the end of each session (see `always_show_next`)
+    pub next: Placement,
+}
+
+impl Default for OverlayConfig {
+    fn default() -> Self {
+        Self {
+            clock: Placement::TopRight,
+            session: Placement::TopLeft,
+            theme: Placement::TopCenter,
+            hints: Placement::BottomCenter,
+            next: Placement::TopRight,
+        }
+    }
+}
+
+/// Hands out room along the top and bottom edges of the timer screen
+pub struct OverlayLayout {
+    area: Rect,
+    /// First row below anything attached to the top edge (the progress bar)
+    top: u16,
+    /// Last row above anything attached to the bottom edge, and off the last line
+    bottom: u16,
+    /// Rows taken so far at each placement
+    stacked: [u16; 6],
+    placed: Vec<Rect>,
+}
+
+impl OverlayLayout {
+    /// `top_rows` and `bottom_rows` are taken by the progress bar
+    pub fn new(area: Rect, top_rows: u16, bottom_rows: u16) -> Self {
+        Self {
+            area,
+            top: area.y + top_rows,
+            bottom: area.bottom().saturating_sub(bottom_rows.max(1) + 1),
+            stacked: [0; 6],
+            placed: Vec::new(),
+        }
+    }
+
+    /// Room for a widget of the given size, or None when it is hidden or
+    /// would run into one placed earlier
+    pub fn place(&mut self, placement: Placement, width: u16, height: u16) -> Option<Rect> {
+        let slot = placement.slot()?;
+        let area = self.area;
+        let width = width.min(area.width);
+        let x = match slot % 3 {
+            0 => area.x,
+            1 => area.x + (area.width - width) / 2,
+            _ => area.right().saturating_sub(width + RIGHT_MARGIN).max(area.x),
+        };
+        let y = if slot < 3 {
+            self.top + self.stacked[slot]
+        } else {
+            (self.bottom + 1).checked_sub(self.stacked[slot] + height)?
+        };
+        let rect = Rect::new(x, y, width, height);
+
+        if rect.y < self.top || rect.bottom() > self.bottom + 1 {
+            return None;
+        }
+        // Keep a column between neighbours on the same rows
+        let padded = Rect::new(x.saturating_sub(1), y, width + 2, height);
+        if self.placed.iter().any(|other| other.intersects(padded)) {
+            return None;
+        }
+        self.stacked[slot] += height;
+        self.placed.push(rect);
+        Some(rect)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overlay_layout_stacks_and_skips_collisions() {
+        let mut layout = OverlayLayout::new(Rect::new(0, 0, 80, 24), 0, 3);
+        assert_eq!(layout.place(Placement::TopRight, 10, 3), Some(Rect::new(68, 0, 10, 3)));
+        assert_eq!(layout.place(Placement::TopLeft, 30, 3), Some(Rect::new(0, 0, 30, 3)));
+        // Under the clock
+        assert_eq!(layout.place(Placement::TopRight, 14, 1), Some(Rect::new(64, 3, 14, 1)));
+        // Too wide to sit between the two boxes
+        assert_eq!(layout.place(Placement::TopCenter, 40, 1), None);
+        assert_eq!(layout.place(Placement::TopCenter, 12, 1), Some(Rect::new(34, 0, 12, 1)));
+        // Above the bar, off its own row
+        assert_eq!(layout.place(Placement::BottomCenter, 40, 1), Some(Rect::new(20, 20, 40, 1)));
+        assert_eq!(layout.place(Placement::BottomLeft, 10, 3), Some(Rect::new(0, 18, 10, 3)));
+        assert_eq!(layout.place(Placement::Hidden, 10, 3), None);
+    }
+}
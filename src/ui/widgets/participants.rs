@@ -0,0 +1,76 @@
+//! Team mode participant list: who is sharing this timer
+
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+pub struct Participants<'a> {
+    room: &'a str,
+    you: &'a str,
+    others: &'a [String],
+    accent: Color,
+    background: Color,
+}
+
+impl<'a> Participants<'a> {
+    pub fn new(room: &'a str, you: &'a str, others: &'a [String]) -> Self {
+        Self {
+            room,
+            you,
+            others,
+            accent: Color::White,
+            background: Color::Reset,
+        }
+    }
+
+    pub fn colors(mut self, accent: Color, background: Color) -> Self {
+        self.accent = accent;
+        self.background = background;
+        self
+    }
+
+    /// Size needed to show every name (clamped by the caller's area)
+    pub fn size(&self) -> (u16, u16) {
+        let title = self.title().chars().count();
+        let widest = self
+            .others
+            .iter()
+            .map(|name| name.chars().count())
+            .chain([self.you.chars().count() + 6]) // " (you)"
+            .max()
+            .unwrap_or(0);
+        let width = (widest + 4).max(title + 2) as u16;
+        let height = self.others.len() as u16 + 3;
+        (width, height)
+    }
+
+    fn title(&self) -> String {
+        format!(" Team: {} ", self.room)
+    }
+}
+
+impl Widget for Participants<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let dot = Span::styled("● ", Style::default().fg(self.accent));
+        let mut lines = vec![Line::from(vec![
+            dot.clone(),
+            Span::raw(self.you.to_string()),
+            Span::styled(" (you)", Style::default().fg(Color::DarkGray)),
+        ])];
+        lines.extend(
+            self.others
+                .iter()
+                .map(|name| Line::from(vec![dot.clone(), Span::raw(name.clone())])),
+        );
+
+        Paragraph::new(lines)
+            .style(Style::default().fg(Color::White))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(self.accent))
+                    .title(self.title())
+                    .style(Style::default().bg(self.background)),
+            )
+            .render(area, buf);
+    }
+}
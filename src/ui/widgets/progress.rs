@@ -0,0 +1,216 @@
+//! Session progress bar
+//! Rendered by the timer view; every visual aspect comes from `ProgressBarConfig`.
+
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Gauge};
+use serde::{Deserialize, Serialize};
+
+/// Screen edge the bar is attached to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BarPosition {
+    Top,
+    Bottom,
+}
+
+/// How the filled part is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BarStyle {
+    /// Solid gauge (bordered when 3 rows tall)
+    Block,
+    /// Fill blends from the track color into the fill color
+    Gradient,
+    /// Thin braille dots with half-cell resolution
+    Braille,
+}
+
+/// Progress bar settings (the `progress_bar` key in config.json)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProgressBarConfig {
+    pub visible: bool,
+    /// Height in rows (1-3)
+    pub thickness: u16,
+    pub position: BarPosition,
+    pub style: BarStyle,
+    /// Fixed fill color ("#rrggbb" or a color name); unset follows the theme
+    pub color: Option<String>,
+}
+
+impl Default for ProgressBarConfig {
+    fn default() -> Self {
+        Self {
+            visible: true,
+            thickness: 3,
+            position: BarPosition::Bottom,
+            style: BarStyle::Block,
+            color: None,
+        }
+    }
+}
+
+impl ProgressBarConfig {
+    /// Rows the bar occupies (0 when hidden)
+    pub fn rows(&self) -> u16 {
+        if self.visible {
+            self.thickness.clamp(1, 3)
+        } else {
+            0
+        }
+    }
+
+    /// Where the bar goes within the screen area
+    pub fn area(&self, area: Rect) -> Rect {
+        let rows = self.rows().min(area.height);
+        let y = match self.position {
+            BarPosition::Top => area.y,
+            BarPosition::Bottom => area.y + area.height - rows,
+        };
+        Rect::new(area.x, y, area.width, rows)
+    }
+
+    /// Configured fixed color, if it parses
+    pub fn fixed_color(&self) -> Option<Color> {
+        self.color.as_deref().and_then(|c| c.parse().ok())
+    }
+}
+
+/// Horizontal progress bar widget
+pub struct ProgressBar {
+    ratio: f64,
+    style: BarStyle,
+    fill: Color,
+    track: Color,
+    background: Color,
+}
+
+impl ProgressBar {
+    pub fn new(ratio: f64) -> Self {
+        Self {
+            ratio: ratio.clamp(0.0, 1.0),
+            style: BarStyle::Block,
+            fill: Color::White,
+            track: Color::DarkGray,
+            background: Color::Reset,
+        }
+    }
+
+    pub fn style(mut self, style: BarStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Filled and unfilled colors
+    pub fn colors(mut self, fill: Color, track: Color) -> Self {
+        self.fill = fill;
+        self.track = track;
+        self
+    }
+
+    pub fn background(mut self, background: Color) -> Self {
+        self.background = background;
+        self
+    }
+
+    fn render_gradient(&self, area: Rect, buf: &mut Buffer) {
+        let filled = (area.width as f64 * self.ratio) as u16;
+        for x in 0..area.width {
+            let (ch, fg) = if x < filled {
+                let t = if area.width > 1 { x as f32 / (area.width - 1) as f32 } else { 1.0 };
+                ('█', blend(self.track, self.fill, 0.3 + t * 0.7))
+            } else {
+                ('░', self.track)
+            };
+            for y in area.top()..area.bottom() {
+                buf[(area.x + x, y)].set_char(ch).set_fg(fg).set_bg(self.background);
+            }
+        }
+    }
+
+    fn render_braille(&self, area: Rect, buf: &mut Buffer) {
+        // Two dot columns per cell
+        let half_cells = (area.width as f64 * 2.0 * self.ratio) as u16;
+        // The dots sit on the bar's last row so a taller bar stays thin
+        let row = area.bottom() - 1;
+        for x in 0..area.width {
+            let (ch, fg) = match half_cells.saturating_sub(x * 2) {
+                0 => ('⠤', self.track),
+                1 => ('⡇', self.fill),
+                _ => ('⣿', self.fill),
+            };
+            for y in area.top()..area.bottom() {
+                let cell = &mut buf[(area.x + x, y)];
+                cell.set_bg(self.background);
+                if y == row {
+                    cell.set_char(ch).set_fg(fg);
+                } else {
+                    cell.set_char(' ');
+                }
+            }
+        }
+    }
+}
+
+impl Widget for ProgressBar {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() {
+            return;
+        }
+        match self.style {
+            BarStyle::Block => {
+                let mut gauge = Gauge::default()
+                    .gauge_style(Style::default().fg(self.fill).bg(self.track))
+                    .ratio(self.ratio);
+                if area.height >= 3 {
+                    gauge = gauge.block(
+                        Block::default()
+                            .borders(Borders::ALL)
+                            .border_style(Style::default().fg(self.fill))
+                            .style(Style::default().bg(self.background)),
+                    );
+                }
+                gauge.render(area, buf);
+            }
+            BarStyle::Gradient => self.render_gradient(area, buf),
+            BarStyle::Braille => self.render_braille(area, buf),
+        }
+    }
+}
+
+/// Linear mix of two RGB colors (non-RGB colors snap at the midpoint)
+fn blend(from: Color, to: Color, t: f32) -> Color {
+    match (from, to) {
+        (Color::Rgb(r1, g1, b1), Color::Rgb(r2, g2, b2)) => {
+            let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t) as u8;
+            Color::Rgb(mix(r1, r2), mix(g1, g2), mix(b1, b2))
+        }
+        _ if t < 0.5 => from,
+        _ => to,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_braille_half_cell_resolution() {
+        let area = Rect::new(0, 0, 4, 1);
+        let mut buf = Buffer::empty(area);
+        // 3 of 8 half cells filled
+        ProgressBar::new(0.375).style(BarStyle::Braille).render(area, &mut buf);
+        let row: String = (0..4).map(|x| buf[(x, 0)].symbol().to_string()).collect();
+        assert_eq!(row, "⣿⡇⠤⠤");
+    }
+
+    #[test]
+    fn test_bar_area_follows_position() {
+        let screen = Rect::new(0, 0, 80, 24);
+        let mut config = ProgressBarConfig::default();
+        assert_eq!(config.area(screen), Rect::new(0, 21, 80, 3));
+        config.position = BarPosition::Top;
+        config.thickness = 9;
+        assert_eq!(config.area(screen), Rect::new(0, 0, 80, 3));
+    }
+}
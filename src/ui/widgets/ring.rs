@@ -0,0 +1,166 @@
+//! Progress ring: a border around the timer digits that fills clockwise from
+//! the top center over the session, in half-cell steps along its edges
+
+use ratatui::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Corner shape of the ring
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RingStyle {
+    /// Light lines with arcs at the corners; the fill is the same line in the fill color
+    Round,
+    /// Square corners; the fill is drawn in heavy lines over a light track
+    Square,
+}
+
+/// Progress ring settings (the `progress_ring` key in config.json)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProgressRingConfig {
+    pub visible: bool,
+    pub style: RingStyle,
+}
+
+impl Default for ProgressRingConfig {
+    fn default() -> Self {
+        Self {
+            visible: false,
+            style: RingStyle::Round,
+        }
+    }
+}
+
+/// Where a border cell sits on the ring
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Edge {
+    Top,
+    TopRight,
+    Right,
+    BottomRight,
+    Bottom,
+    BottomLeft,
+    Left,
+    TopLeft,
+}
+
+/// Rectangular ring widget drawn on the border cells of its area
+pub struct ProgressRing {
+    ratio: f64,
+    style: RingStyle,
+    fill: Color,
+    track: Color,
+}
+
+impl ProgressRing {
+    pub fn new(ratio: f64) -> Self {
+        Self {
+            ratio: ratio.clamp(0.0, 1.0),
+            style: RingStyle::Round,
+            fill: Color::White,
+            track: Color::DarkGray,
+        }
+    }
+
+    pub fn style(mut self, style: RingStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Filled and unfilled colors
+    pub fn colors(mut self, fill: Color, track: Color) -> Self {
+        self.fill = fill;
+        self.track = track;
+        self
+    }
+
+    /// Line for a cell: full, half (the leading half in the direction of travel) or track
+    fn glyph(&self, edge: Edge, filled_halves: usize) -> (char, Color) {
+        let heavy = self.style == RingStyle::Square && filled_halves > 0;
+        let ch = match (edge, filled_halves, heavy) {
+            (Edge::Top, 1, true) => '╸',
+            (Edge::Top, 1, false) => '╴',
+            (Edge::Right, 1, true) => '╹',
+            (Edge::Right, 1, false) => '╵',
+            (Edge::Bottom, 1, true) => '╺',
+            (Edge::Bottom, 1, false) => '╶',
+            (Edge::Left, 1, true) => '╻',
+            (Edge::Left, 1, false) => '╷',
+            (Edge::Top | Edge::Bottom, _, true) => '━',
+            (Edge::Top | Edge::Bottom, _, false) => '─',
+            (Edge::Left | Edge::Right, _, true) => '┃',
+            (Edge::Left | Edge::Right, _, false) => '│',
+            (Edge::TopRight, _, true) => '┓',
+            (Edge::BottomRight, _, true) => '┛',
+            (Edge::BottomLeft, _, true) => '┗',
+            (Edge::TopLeft, _, true) => '┏',
+            (corner, _, false) => match (corner, self.style) {
+                (Edge::TopRight, RingStyle::Round) => '╮',
+                (Edge::BottomRight, RingStyle::Round) => '╯',
+                (Edge::BottomLeft, RingStyle::Round) => '╰',
+                (_, RingStyle::Round) => '╭',
+                (Edge::TopRight, _) => '┐',
+                (Edge::BottomRight, _) => '┘',
+                (Edge::BottomLeft, _) => '└',
+                _ => '┌',
+            },
+        };
+        (ch, if filled_halves > 0 { self.fill } else { self.track })
+    }
+}
+
+/// Border cells of `area` clockwise, starting at the middle of the top edge
+fn perimeter(area: Rect) -> Vec<(Position, Edge)> {
+    let (left, top) = (area.left(), area.top());
+    let (right, bottom) = (area.right() - 1, area.bottom() - 1);
+    let mut cells = vec![(Position::new(left, top), Edge::TopLeft)];
+    cells.extend((left + 1..right).map(|x| (Position::new(x, top), Edge::Top)));
+    cells.push((Position::new(right, top), Edge::TopRight));
+    cells.extend((top + 1..bottom).map(|y| (Position::new(right, y), Edge::Right)));
+    cells.push((Position::new(right, bottom), Edge::BottomRight));
+    cells.extend((left + 1..right).rev().map(|x| (Position::new(x, bottom), Edge::Bottom)));
+    cells.push((Position::new(left, bottom), Edge::BottomLeft));
+    cells.extend((top + 1..bottom).rev().map(|y| (Position::new(left, y), Edge::Left)));
+    cells.rotate_left((area.width / 2) as usize);
+    cells
+}
+
+impl Widget for ProgressRing {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = area.intersection(buf.area);
+        if area.width < 2 || area.height < 2 {
+            return;
+        }
+        let cells = perimeter(area);
+        let filled = (cells.len() as f64 * 2.0 * self.ratio) as usize;
+        for (i, (position, edge)) in cells.into_iter().enumerate() {
+            let (ch, color) = self.glyph(edge, filled.saturating_sub(i * 2).min(2));
+            buf[position].set_char(ch).set_fg(color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rows(buf: &Buffer) -> Vec<String> {
+        let area = buf.area;
+        (0..area.height)
+            .map(|y| (0..area.width).map(|x| buf[(x, y)].symbol().to_string()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_ring_fills_clockwise_from_top_center() {
+        let area = Rect::new(0, 0, 4, 3);
+        let mut buf = Buffer::empty(area);
+        // 11 of 20 half cells: five full cells, then half of the sixth
+        ProgressRing::new(0.55).style(RingStyle::Square).render(area, &mut buf);
+        assert_eq!(rows(&buf), ["┌─━┓", "│  ┃", "└╺━┛"]);
+
+        let mut buf = Buffer::empty(area);
+        ProgressRing::new(0.0).render(area, &mut buf);
+        assert_eq!(rows(&buf), ["╭──╮", "│  │", "╰──╯"]);
+    }
+}
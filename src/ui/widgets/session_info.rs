@@ -0,0 +1,78 @@
+//! Boxed session name in a corner of the timer screen ("Work · Deep Work (Lap 2/10)")
+
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+pub struct SessionInfo {
+    label: String,
+    /// Quieter second line under the label
+    detail: Option<String>,
+    /// Just the label, without the box (zen mode)
+    bare: bool,
+    accent: Color,
+    background: Color,
+}
+
+impl SessionInfo {
+    pub fn new(label: String) -> Self {
+        Self {
+            label,
+            detail: None,
+            bare: false,
+            accent: Color::White,
+            background: Color::Reset,
+        }
+    }
+
+    pub fn detail(mut self, detail: String) -> Self {
+        self.detail = Some(detail);
+        self
+    }
+
+    pub fn bare(mut self) -> Self {
+        self.bare = true;
+        self
+    }
+
+    pub fn colors(mut self, accent: Color, background: Color) -> Self {
+        self.accent = accent;
+        self.background = background;
+        self
+    }
+
+    /// Width and height it needs for the whole label
+    pub fn size(&self) -> (u16, u16) {
+        let width = self.label.chars().count() as u16;
+        if self.bare {
+            return (width, 1);
+        }
+        match &self.detail {
+            Some(detail) => (width.max(detail.chars().count() as u16) + 4, 4),
+            None => (width + 4, 3),
+        }
+    }
+}
+
+impl Widget for SessionInfo {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let style = Style::default().fg(self.accent);
+        if self.bare {
+            Paragraph::new(self.label).style(style).render(area, buf);
+            return;
+        }
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(style)
+            .style(Style::default().bg(self.background))
+            .render(area, buf);
+        // A space of padding inside the border
+        let text = Rect::new(area.x + 2, area.y + 1, area.width.saturating_sub(4), 1);
+        Paragraph::new(self.label).style(style).render(text.intersection(area), buf);
+        if let Some(detail) = self.detail {
+            let below = Rect { y: text.y + 1, ..text };
+            Paragraph::new(detail)
+                .style(Style::default().fg(Color::DarkGray))
+                .render(below.intersection(area), buf);
+        }
+    }
+}
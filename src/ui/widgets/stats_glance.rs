@@ -0,0 +1,57 @@
+//! Today's pomodoros and focus time with the current streak, a glance at the
+//! stats from the menu before starting
+
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+
+use crate::summary::DaySummary;
+
+pub struct StatsGlance {
+    today: String,
+    streak: String,
+    text: Color,
+    muted: Color,
+}
+
+impl StatsGlance {
+    pub fn new(summary: &DaySummary) -> Self {
+        let pomodoros = match summary.pomodoros {
+            1 => "1 pomodoro".to_string(),
+            n => format!("{} pomodoros", n),
+        };
+        let focus = summary.focus_secs / 60;
+        let streak = match summary.streak {
+            0 => "No streak going, start one today".to_string(),
+            1 => "Streak: 1 day".to_string(),
+            days => format!("Streak: {} days", days),
+        };
+        Self {
+            today: format!("Today: {} · {}h {:02}m focus", pomodoros, focus / 60, focus % 60),
+            streak,
+            text: Color::White,
+            muted: Color::DarkGray,
+        }
+    }
+
+    pub fn colors(mut self, text: Color, muted: Color) -> Self {
+        self.text = text;
+        self.muted = muted;
+        self
+    }
+
+    /// Width and height it needs
+    pub fn size(&self) -> (u16, u16) {
+        let width = self.today.chars().count().max(self.streak.chars().count());
+        (width as u16, 2)
+    }
+}
+
+impl Widget for StatsGlance {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let lines = vec![
+            Line::styled(self.today, Style::default().fg(self.text)),
+            Line::styled(self.streak, Style::default().fg(self.muted)),
+        ];
+        Paragraph::new(lines).alignment(Alignment::Center).render(area, buf);
+    }
+}
@@ -0,0 +1,39 @@
+//! Name of the current theme, a quiet label on the timer screen's top edge
+
+use ratatui::prelude::*;
+use ratatui::widgets::Paragraph;
+
+pub struct ThemeBadge<'a> {
+    name: &'a str,
+    color: Color,
+    background: Color,
+}
+
+impl<'a> ThemeBadge<'a> {
+    pub fn new(name: &'a str) -> Self {
+        Self {
+            name,
+            color: Color::DarkGray,
+            background: Color::Reset,
+        }
+    }
+
+    pub fn colors(mut self, color: Color, background: Color) -> Self {
+        self.color = color;
+        self.background = background;
+        self
+    }
+
+    /// Width and height it needs
+    pub fn size(&self) -> (u16, u16) {
+        (self.name.len() as u16 + 4, 1)
+    }
+}
+
+impl Widget for ThemeBadge<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Paragraph::new(format!(" {} ", self.name))
+            .style(Style::default().fg(self.color).bg(self.background))
+            .render(area, buf);
+    }
+}
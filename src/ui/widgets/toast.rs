@@ -0,0 +1,112 @@
+//! Transient on-screen message ("Theme: Synthwave") that fades out on its own
+
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Clear, Paragraph};
+
+/// Toast lifetime (~1.5 s at 10 FPS)
+pub const TOAST_FRAMES: u32 = 15;
+
+/// Frames at the end of the lifetime spent fading out
+const FADE_FRAMES: u32 = 5;
+
+/// A message with a frame countdown; kept in app state and ticked with the animation
+#[derive(Debug, Clone)]
+pub struct Toast {
+    message: String,
+    frames_left: u32,
+}
+
+impl Toast {
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            message: message.into(),
+            frames_left: TOAST_FRAMES,
+        }
+    }
+
+    /// A toast that stays up for `frames` instead of the usual lifetime
+    pub fn lasting(message: impl Into<String>, frames: u32) -> Self {
+        Self {
+            message: message.into(),
+            frames_left: frames,
+        }
+    }
+
+    /// Advance one frame; returns false once the toast has expired
+    pub fn tick(&mut self) -> bool {
+        self.frames_left = self.frames_left.saturating_sub(1);
+        self.frames_left > 0
+    }
+
+    /// 1.0 while fully visible, falling to 0.0 over the fade
+    pub fn opacity(&self) -> f32 {
+        (self.frames_left as f32 / FADE_FRAMES as f32).min(1.0)
+    }
+}
+
+/// Renders a toast centered in the given area
+pub struct ToastView<'a> {
+    toast: &'a Toast,
+    accent: Color,
+    background: Color,
+    text: Color,
+}
+
+impl<'a> ToastView<'a> {
+    pub fn new(toast: &'a Toast, accent: Color, background: Color) -> Self {
+        Self {
+            toast,
+            accent,
+            background,
+            text: Color::White,
+        }
+    }
+
+    /// Message color (white by default)
+    pub fn text(mut self, text: Color) -> Self {
+        self.text = text;
+        self
+    }
+}
+
+impl Widget for ToastView<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let width = (self.toast.message.chars().count() as u16 + 4).min(area.width);
+        let height = 3.min(area.height);
+        let rect = Rect::new(
+            area.x + area.width.saturating_sub(width) / 2,
+            area.y + area.height.saturating_sub(height) / 2,
+            width,
+            height,
+        );
+
+        let opacity = self.toast.opacity();
+        let accent = fade(self.accent, self.background, opacity);
+        let text = fade(self.text, self.background, opacity);
+
+        Clear.render(rect, buf);
+        Paragraph::new(self.toast.message.as_str())
+            .style(Style::default().fg(text).bold())
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(accent))
+                    .style(Style::default().bg(self.background)),
+            )
+            .render(rect, buf);
+    }
+}
+
+/// Blend `color` toward `background` as opacity drops (named colors map to RGB first)
+fn fade(color: Color, background: Color, opacity: f32) -> Color {
+    let rgb = |c: Color| match c {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::White => (255, 255, 255),
+        _ => (128, 128, 128),
+    };
+    let (r1, g1, b1) = rgb(color);
+    let (r2, g2, b2) = rgb(background);
+    let mix = |a: u8, b: u8| (b as f32 + (a as f32 - b as f32) * opacity) as u8;
+    Color::Rgb(mix(r1, r2), mix(g1, g2), mix(b1, b2))
+}
@@ -0,0 +1,35 @@
+//! Zen mode's progress: a thin line along one row, no border or track
+
+use ratatui::prelude::*;
+
+pub struct ZenProgress {
+    /// 0.0 to 1.0
+    progress: f64,
+    color: Color,
+}
+
+impl ZenProgress {
+    pub fn new(progress: f64) -> Self {
+        Self {
+            progress: progress.clamp(0.0, 1.0),
+            color: Color::DarkGray,
+        }
+    }
+
+    pub fn color(mut self, color: Color) -> Self {
+        self.color = color;
+        self
+    }
+}
+
+impl Widget for ZenProgress {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.height == 0 {
+            return;
+        }
+        let filled = (area.width as f64 * self.progress) as u16;
+        for x in area.x..area.x + filled {
+            buf[(x, area.y)].set_symbol("▁").set_fg(self.color);
+        }
+    }
+}
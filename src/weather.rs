@@ -0,0 +1,198 @@
+//! Local weather for the Landscape and Seasonal themes, from open-meteo (no API
+//! key needed). A background thread asks for the current conditions every
+//! `refresh_minutes` through curl. The last answer is kept in weather.json in the
+//! state directory, so an offline start still shows the recent sky; failed
+//! fetches keep the last known conditions until they are too old to trust.
+
+use std::io;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::Once;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::paths;
+
+/// Known conditions older than this are dropped and the themes go back to fair weather
+const STALE_AFTER: Duration = Duration::from_secs(3 * 3600);
+
+/// Per-request limit, so a hanging connection doesn't delay the next try
+const REQUEST_TIMEOUT_SECS: &str = "10";
+
+/// Fewest minutes between fetches, whatever is configured
+const MIN_REFRESH_MINUTES: u64 = 10;
+
+/// `Conditions` as 1 + its position in `CONDITIONS`; 0 while unknown
+static CURRENT: AtomicU8 = AtomicU8::new(0);
+
+static WATCH: Once = Once::new();
+
+/// Where to take the weather from (the `weather` key in config.json)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeatherConfig {
+    pub latitude: f64,
+    pub longitude: f64,
+    /// Minutes between fetches (at least 10)
+    #[serde(default = "default_refresh_minutes")]
+    pub refresh_minutes: u64,
+}
+
+fn default_refresh_minutes() -> u64 {
+    30
+}
+
+/// What the sky is doing, as far as the themes care
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Conditions {
+    Clear,
+    Cloudy,
+    Fog,
+    Rain,
+    Storm,
+    Snow,
+}
+
+/// Every condition, in declaration order
+const CONDITIONS: [Conditions; 6] = [
+    Conditions::Clear,
+    Conditions::Cloudy,
+    Conditions::Fog,
+    Conditions::Rain,
+    Conditions::Storm,
+    Conditions::Snow,
+];
+
+impl Conditions {
+    /// From a WMO weather interpretation code, as open-meteo reports them
+    pub fn from_code(code: u8) -> Self {
+        match code {
+            0 | 1 => Conditions::Clear,
+            45 | 48 => Conditions::Fog,
+            51..=67 | 80..=82 => Conditions::Rain,
+            71..=77 | 85 | 86 => Conditions::Snow,
+            95..=99 => Conditions::Storm,
+            _ => Conditions::Cloudy,
+        }
+    }
+
+    /// Grey sky, no sun
+    pub fn is_overcast(self) -> bool {
+        self != Conditions::Clear
+    }
+}
+
+/// The conditions last fetched, None until there are some (or when they went stale)
+pub fn current() -> Option<Conditions> {
+    let index = CURRENT.load(Ordering::Relaxed) as usize;
+    index.checked_sub(1).map(|i| CONDITIONS[i])
+}
+
+fn set_current(conditions: Option<Conditions>) {
+    CURRENT.store(conditions.map_or(0, |c| c as u8 + 1), Ordering::Relaxed);
+}
+
+/// Last answer, kept for offline starts
+#[derive(Debug, Serialize, Deserialize)]
+struct Cached {
+    code: u8,
+    /// Unix timestamp (seconds)
+    fetched_at: u64,
+}
+
+impl Cached {
+    fn path() -> PathBuf {
+        paths::state_dir().join("weather.json")
+    }
+
+    fn load() -> Option<Self> {
+        let json = std::fs::read_to_string(Self::path()).ok()?;
+        serde_json::from_str(&json).ok()
+    }
+
+    fn save(&self) -> io::Result<()> {
+        let path = Self::path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string(self)?)
+    }
+
+    fn is_fresh(&self) -> bool {
+        now_secs().saturating_sub(self.fetched_at) <= STALE_AFTER.as_secs()
+    }
+}
+
+/// Start following the weather; later calls (another embedded app) do nothing
+pub fn watch(config: &WeatherConfig) {
+    let config = config.clone();
+    WATCH.call_once(move || {
+        let mut known = Cached::load().filter(Cached::is_fresh);
+        set_current(known.as_ref().map(|c| Conditions::from_code(c.code)));
+        let interval = Duration::from_secs(config.refresh_minutes.max(MIN_REFRESH_MINUTES) * 60);
+        std::thread::spawn(move || loop {
+            match fetch(&config) {
+                Ok(code) => {
+                    let cached = Cached { code, fetched_at: now_secs() };
+                    let _ = cached.save();
+                    known = Some(cached);
+                }
+                // Offline: keep what we knew while it is recent enough
+                Err(_) => known = known.take().filter(Cached::is_fresh),
+            }
+            set_current(known.as_ref().map(|c| Conditions::from_code(c.code)));
+            std::thread::sleep(interval);
+        });
+    });
+}
+
+/// The current WMO weather code at the configured spot
+fn fetch(config: &WeatherConfig) -> io::Result<u8> {
+    let url = format!(
+        "https://api.open-meteo.com/v1/forecast?latitude={}&longitude={}&current=weather_code",
+        config.latitude, config.longitude
+    );
+    let output = Command::new("curl")
+        .args(["-sSf", "--max-time", REQUEST_TIMEOUT_SECS, &url])
+        .output()?;
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(io::Error::other(format!("open-meteo: {}", error.trim())));
+    }
+    parse_code(&String::from_utf8_lossy(&output.stdout))
+}
+
+/// `current.weather_code` of an open-meteo forecast response
+fn parse_code(body: &str) -> io::Result<u8> {
+    let response: serde_json::Value = serde_json::from_str(body)?;
+    response["current"]["weather_code"]
+        .as_u64()
+        .and_then(|code| u8::try_from(code).ok())
+        .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "open-meteo: no current weather_code"))
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_weather_codes() {
+        let body = r#"{"latitude":52.52,"current":{"time":"2025-01-08T10:00","interval":900,"weather_code":73}}"#;
+        assert_eq!(parse_code(body).unwrap(), 73);
+        assert!(parse_code(r#"{"error":true,"reason":"Latitude must be in range"}"#).is_err());
+
+        assert_eq!(Conditions::from_code(73), Conditions::Snow);
+        assert_eq!(Conditions::from_code(61), Conditions::Rain);
+        assert_eq!(Conditions::from_code(95), Conditions::Storm);
+        assert_eq!(Conditions::from_code(3), Conditions::Cloudy);
+        assert!(!Conditions::from_code(0).is_overcast());
+    }
+}
@@ -0,0 +1,220 @@
+//! Window manager focus feed, for pausing work on distracting workspaces or apps
+//! Subscribes to the i3/sway IPC socket (`$SWAYSOCK` or `$I3SOCK`) or Hyprland's
+//! event socket (`$HYPRLAND_INSTANCE_SIGNATURE`) on a background thread and
+//! reports every change of focused workspace or window. The `distractions` key in
+//! config.json lists which of them pause a running work session.
+
+use std::io;
+use std::sync::mpsc::{self, Receiver};
+
+use serde::{Deserialize, Serialize};
+
+/// Workspaces and apps that pause a work session while focused
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DistractionConfig {
+    /// Workspace names ("9", "chat")
+    pub workspaces: Vec<String>,
+    /// Window classes or Wayland app ids, any case ("firefox", "discord")
+    pub apps: Vec<String>,
+}
+
+impl DistractionConfig {
+    /// What makes `focus` a distraction ("discord", "workspace 9"), if anything
+    pub fn matches(&self, focus: &Focus) -> Option<String> {
+        if let Some(app) = focus.app.as_deref().filter(|app| self.apps.iter().any(|a| a.eq_ignore_ascii_case(app))) {
+            return Some(app.to_lowercase());
+        }
+        focus
+            .workspace
+            .as_deref()
+            .filter(|name| self.workspaces.iter().any(|w| w == name))
+            .map(|name| format!("workspace {}", name))
+    }
+}
+
+/// What the window manager has focused (None until it has said)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Focus {
+    pub workspace: Option<String>,
+    pub app: Option<String>,
+}
+
+/// A running subscription; the reader thread ends with the window manager
+pub struct WindowManager {
+    changes: Receiver<Focus>,
+}
+
+impl WindowManager {
+    /// Connect to the window manager this session runs under
+    pub fn connect() -> io::Result<Self> {
+        let (sender, changes) = mpsc::channel();
+        platform::subscribe(sender)?;
+        Ok(Self { changes })
+    }
+
+    /// The newest focus, if it changed since the last call
+    pub fn poll(&self) -> Option<Focus> {
+        self.changes.try_iter().last()
+    }
+}
+
+/// Apply one i3/sway event; true if the focus changed
+fn apply_i3_event(focus: &mut Focus, kind: u32, payload: &[u8]) -> bool {
+    let Ok(event) = serde_json::from_slice::<serde_json::Value>(payload) else {
+        return false;
+    };
+    if event["change"] != "focus" {
+        return false;
+    }
+    match kind {
+        I3_WORKSPACE_EVENT => {
+            // The window focus that follows (if the workspace has one) fills the app in
+            focus.workspace = event["current"]["name"].as_str().map(str::to_string);
+            focus.app = None;
+        }
+        I3_WINDOW_EVENT => {
+            let container = &event["container"];
+            let app = container["app_id"].as_str().or_else(|| container["window_properties"]["class"].as_str());
+            focus.app = app.map(str::to_string);
+        }
+        _ => return false,
+    }
+    true
+}
+
+/// Apply one line of Hyprland's event socket ("workspace>>3"); true if the focus changed
+fn apply_hyprland_line(focus: &mut Focus, line: &str) -> bool {
+    let Some((event, data)) = line.split_once(">>") else {
+        return false;
+    };
+    match event {
+        "workspace" => {
+            focus.workspace = Some(data.to_string());
+            focus.app = None;
+        }
+        // "activewindow>>class,title"; an empty class is an empty workspace
+        "activewindow" => {
+            let class = data.split(',').next().unwrap_or_default();
+            focus.app = (!class.is_empty()).then(|| class.to_string());
+        }
+        _ => return false,
+    }
+    true
+}
+
+const I3_MAGIC: &[u8] = b"i3-ipc";
+const I3_SUBSCRIBE: u32 = 2;
+const I3_WORKSPACE_EVENT: u32 = 0x8000_0000;
+const I3_WINDOW_EVENT: u32 = 0x8000_0003;
+
+#[cfg(unix)]
+mod platform {
+    use std::io::{self, BufRead, BufReader, Read, Write};
+    use std::os::unix::net::UnixStream;
+    use std::path::PathBuf;
+    use std::sync::mpsc::Sender;
+
+    use super::{apply_hyprland_line, apply_i3_event, Focus, I3_MAGIC, I3_SUBSCRIBE};
+
+    pub fn subscribe(sender: Sender<Focus>) -> io::Result<()> {
+        if let Some(socket) = ["SWAYSOCK", "I3SOCK"].into_iter().find_map(std::env::var_os) {
+            let mut stream = UnixStream::connect(socket)?;
+            send_i3(&mut stream, I3_SUBSCRIBE, br#"["workspace","window"]"#)?;
+            std::thread::spawn(move || {
+                let mut focus = Focus::default();
+                while let Ok((kind, payload)) = read_i3(&mut stream) {
+                    if apply_i3_event(&mut focus, kind, &payload) && sender.send(focus.clone()).is_err() {
+                        break;
+                    }
+                }
+            });
+            return Ok(());
+        }
+
+        if let Some(signature) = std::env::var_os("HYPRLAND_INSTANCE_SIGNATURE") {
+            // Newer Hyprland keeps its sockets under the runtime dir, older under /tmp
+            let runtime = std::env::var_os("XDG_RUNTIME_DIR").map(PathBuf::from);
+            let socket = [runtime, Some(PathBuf::from("/tmp"))]
+                .into_iter()
+                .flatten()
+                .map(|dir| dir.join("hypr").join(&signature).join(".socket2.sock"))
+                .find(|path| path.exists())
+                .ok_or_else(|| io::Error::other("Hyprland event socket not found"))?;
+            let stream = UnixStream::connect(socket)?;
+            std::thread::spawn(move || {
+                let mut focus = Focus::default();
+                for line in BufReader::new(stream).lines().map_while(Result::ok) {
+                    if apply_hyprland_line(&mut focus, &line) && sender.send(focus.clone()).is_err() {
+                        break;
+                    }
+                }
+            });
+            return Ok(());
+        }
+
+        Err(io::Error::other("no i3, sway or Hyprland session found"))
+    }
+
+    /// Message: magic, payload length and type (both native-endian u32), payload
+    fn send_i3(stream: &mut UnixStream, kind: u32, payload: &[u8]) -> io::Result<()> {
+        let mut message = I3_MAGIC.to_vec();
+        message.extend_from_slice(&(payload.len() as u32).to_ne_bytes());
+        message.extend_from_slice(&kind.to_ne_bytes());
+        message.extend_from_slice(payload);
+        stream.write_all(&message)
+    }
+
+    fn read_i3(stream: &mut UnixStream) -> io::Result<(u32, Vec<u8>)> {
+        let mut header = [0u8; 14];
+        stream.read_exact(&mut header)?;
+        if &header[..6] != I3_MAGIC {
+            return Err(io::Error::other("not an i3 IPC message"));
+        }
+        let length = u32::from_ne_bytes([header[6], header[7], header[8], header[9]]);
+        let kind = u32::from_ne_bytes([header[10], header[11], header[12], header[13]]);
+        let mut payload = vec![0u8; length as usize];
+        stream.read_exact(&mut payload)?;
+        Ok((kind, payload))
+    }
+}
+
+#[cfg(not(unix))]
+mod platform {
+    use std::io;
+    use std::sync::mpsc::Sender;
+
+    use super::Focus;
+
+    pub fn subscribe(_sender: Sender<Focus>) -> io::Result<()> {
+        Err(io::Error::other("window manager integration needs i3, sway or Hyprland"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_focus_follows_window_manager_events() {
+        let distractions = DistractionConfig {
+            workspaces: vec!["9".to_string()],
+            apps: vec!["Discord".to_string()],
+        };
+        let mut focus = Focus::default();
+
+        assert!(apply_hyprland_line(&mut focus, "workspace>>9"));
+        assert_eq!(distractions.matches(&focus).as_deref(), Some("workspace 9"));
+        assert!(apply_hyprland_line(&mut focus, "workspace>>2"));
+        assert!(apply_hyprland_line(&mut focus, "activewindow>>discord,General - Discord"));
+        assert_eq!(distractions.matches(&focus).as_deref(), Some("discord"));
+        assert!(!apply_hyprland_line(&mut focus, "openwindow>>abc,2,kitty,kitty"));
+
+        let window = br#"{"change":"focus","container":{"app_id":null,"window_properties":{"class":"kitty"}}}"#;
+        assert!(apply_i3_event(&mut focus, I3_WINDOW_EVENT, window));
+        assert_eq!(focus.app.as_deref(), Some("kitty"));
+        assert_eq!(distractions.matches(&focus), None);
+        let workspace = br#"{"change":"init","current":{"name":"9"}}"#;
+        assert!(!apply_i3_event(&mut focus, I3_WORKSPACE_EVENT, workspace));
+    }
+}
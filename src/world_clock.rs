@@ -0,0 +1,89 @@
+//! World clock row (`world_clock` in config.json): the local time and up to three
+//! other time zones on one line under the digits, for planning sessions around
+//! colleagues' hours. Zones are IANA names, optionally labelled: `"Asia/Tokyo"`
+//! shows as "Tokyo", `"Ana=America/Sao_Paulo"` as "Ana".
+
+use chrono::{DateTime, Local, TimeZone};
+use chrono_tz::Tz;
+
+/// Zones shown besides the local time
+pub const MAX_ZONES: usize = 3;
+
+/// A configured time zone and what the row calls it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Zone {
+    pub label: String,
+    tz: Tz,
+}
+
+impl Zone {
+    /// Parse `Zone/Name` or `Label=Zone/Name`
+    pub fn parse(entry: &str) -> Result<Self, String> {
+        let (label, name) = match entry.split_once('=') {
+            Some((label, name)) => (Some(label.trim()), name.trim()),
+            None => (None, entry.trim()),
+        };
+        let tz: Tz = name.parse().map_err(|_| format!("unknown time zone \"{}\"", name))?;
+        let label = match label.filter(|l| !l.is_empty()) {
+            Some(label) => label.to_string(),
+            None => name.rsplit('/').next().unwrap_or(name).replace('_', " "),
+        };
+        Ok(Self { label, tz })
+    }
+}
+
+/// The configured zones, up to `MAX_ZONES`, and messages for the entries that aren't any
+pub fn parse_zones(entries: &[String]) -> (Vec<Zone>, Vec<String>) {
+    let mut zones = Vec::new();
+    let mut errors = Vec::new();
+    for entry in entries {
+        match Zone::parse(entry) {
+            Ok(zone) => zones.push(zone),
+            Err(e) => errors.push(e),
+        }
+    }
+    if zones.len() > MAX_ZONES {
+        errors.push(format!("only the first {} zones are shown", MAX_ZONES));
+        zones.truncate(MAX_ZONES);
+    }
+    (zones, errors)
+}
+
+/// `14:05 here · New York 08:05 · Tokyo 22:05 +1`: a zone a day ahead of or
+/// behind the local date gets the difference
+pub fn row(now: DateTime<Local>, zones: &[Zone]) -> String {
+    let mut parts = vec![format!("{} here", now.format("%H:%M"))];
+    for zone in zones {
+        let there = zone.tz.from_utc_datetime(&now.naive_utc());
+        let days = (there.date_naive() - now.date_naive()).num_days();
+        let day = match days {
+            0 => String::new(),
+            d => format!(" {:+}", d),
+        };
+        parts.push(format!("{} {}{}", zone.label, there.format("%H:%M"), day));
+    }
+    parts.join(" · ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_world_clock_row() {
+        let (zones, errors) = parse_zones(&[
+            "America/New_York".to_string(),
+            "Ana=Asia/Tokyo".to_string(),
+            "Mars/Olympus".to_string(),
+        ]);
+        assert_eq!(zones.iter().map(|z| z.label.as_str()).collect::<Vec<_>>(), ["New York", "Ana"]);
+        assert_eq!(errors, ["unknown time zone \"Mars/Olympus\""]);
+
+        let now = chrono::Utc.with_ymd_and_hms(2026, 1, 15, 20, 30, 0).unwrap().with_timezone(&Local);
+        let row = row(now, &zones);
+        assert!(row.contains("New York 15:30"), "{}", row);
+        // Tokyo is into the next day already; whether it gets "+1" depends on where this runs
+        assert!(row.contains("Ana 05:30"), "{}", row);
+        assert!(row.starts_with(&format!("{} here", now.format("%H:%M"))));
+    }
+}
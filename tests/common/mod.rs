@@ -0,0 +1,25 @@
+//! Shared by the integration tests
+
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, OnceLock};
+
+use pomowise::paths;
+
+/// A fresh directory for this test run's pomowise files, set as `POMOWISE_HOME`
+/// once before any app is built, so no test reads or writes the user's own
+pub fn isolated_home() -> &'static Path {
+    static HOME: OnceLock<PathBuf> = OnceLock::new();
+    HOME.get_or_init(|| {
+        let dir = std::env::temp_dir().join(format!("pomowise-tests-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).expect("test home");
+        std::env::set_var(paths::HOME_VAR, &dir);
+        dir
+    })
+}
+
+/// Held by tests that save config.json, so none reads back another's
+pub fn config_file() -> MutexGuard<'static, ()> {
+    static LOCK: Mutex<()> = Mutex::new(());
+    LOCK.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
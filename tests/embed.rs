@@ -0,0 +1,449 @@
+//! Headless tests of the embeddable app, rendered through ratatui's `TestBackend`
+
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use pomowise::clock::{MockClock, SharedClock};
+use pomowise::config::{AutoContinueConfig, Config};
+use pomowise::timer::TimerState;
+use pomowise::PomowiseApp;
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::widgets::Paragraph;
+use ratatui::Terminal;
+
+mod common;
+
+fn key(code: KeyCode) -> Event {
+    Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+}
+
+/// App with default settings, keeping its files in the test run's own directory
+fn headless_app() -> PomowiseApp {
+    headless_app_with(Config::default(), pomowise::clock::system())
+}
+
+fn headless_app_with(config: Config, clock: SharedClock) -> PomowiseApp {
+    common::isolated_home();
+    PomowiseApp::with_clock(config, clock)
+}
+
+fn row_text(buffer: &Buffer, y: u16) -> String {
+    (0..buffer.area.width).map(|x| buffer[(x, y)].symbol()).collect()
+}
+
+fn screen_text(buffer: &Buffer) -> String {
+    (0..buffer.area.height).map(|y| row_text(buffer, y) + "\n").collect()
+}
+
+#[test]
+fn test_menu_starts_a_work_session() {
+    let mut app = headless_app();
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+
+    terminal.draw(|frame| app.draw(frame)).unwrap();
+    assert!(screen_text(terminal.backend().buffer()).contains("New cycle"));
+
+    app.handle_event(&key(KeyCode::Enter));
+    app.tick();
+    assert!(app.in_session());
+    assert!(app.timer().is_running());
+
+    terminal.draw(|frame| app.draw(frame)).unwrap();
+    assert!(screen_text(terminal.backend().buffer()).contains("Work (Lap 1/10)"));
+}
+
+#[test]
+fn test_custom_timer_from_the_menu() {
+    let mut app = headless_app_with(Config::default(), MockClock::new().shared());
+    app.handle_event(&key(KeyCode::Down));
+    app.handle_event(&key(KeyCode::Enter));
+    for c in ['4', 'x', '5'] {
+        app.handle_event(&key(KeyCode::Char(c)));
+    }
+    app.handle_event(&key(KeyCode::Enter));
+    assert!(app.in_session());
+    assert_eq!(app.timer().remaining, std::time::Duration::from_secs(45 * 60));
+
+    // Only that session: the next work session is back to the usual length
+    app.handle_event(&key(KeyCode::Tab));
+    app.tick();
+    app.handle_event(&key(KeyCode::Tab));
+    app.tick();
+    assert!(matches!(app.timer().state, TimerState::Work { .. }));
+    assert_eq!(app.timer().remaining, std::time::Duration::from_secs(25 * 60));
+}
+
+#[test]
+fn test_renders_inside_a_pane() {
+    let mut app = headless_app();
+    let mut terminal = Terminal::new(TestBackend::new(120, 30)).unwrap();
+    let pane = Rect::new(40, 0, 80, 30);
+
+    terminal
+        .draw(|frame| {
+            frame.render_widget(Paragraph::new("host app"), Rect::new(0, 0, 40, 1));
+            frame.render_widget(&mut app, pane);
+        })
+        .unwrap();
+
+    let buffer = terminal.backend().buffer();
+    assert!(row_text(buffer, 0).starts_with("host app"));
+    // The menu is centered in the pane, not the whole screen
+    let menu_row = (0..30)
+        .map(|y| row_text(buffer, y))
+        .find(|row| row.contains("New cycle"))
+        .expect("menu rendered in pane");
+    let column = menu_row.find("New cycle").unwrap();
+    assert!(menu_row[..column].chars().count() > 60);
+}
+
+#[test]
+fn test_quit_from_menu() {
+    let mut app = headless_app();
+    app.handle_event(&key(KeyCode::Char('q')));
+    assert!(app.should_quit());
+}
+
+#[test]
+fn test_static_scene_skips_unchanged_frames() {
+    let mut app = headless_app();
+    app.set_mini(true);
+    assert!(app.needs_redraw());
+    // At most one wall-clock second ticks over in between
+    assert!((0..3).any(|_| !app.needs_redraw()));
+
+    app.handle_event(&key(KeyCode::Char('x')));
+    assert!(app.needs_redraw());
+}
+
+#[test]
+fn test_static_scene_sleeps_until_the_next_second() {
+    let mut app = headless_app();
+    assert_eq!(app.next_wake(), Duration::from_millis(100));
+    app.set_mini(true);
+    assert!(app.next_wake() > Duration::ZERO);
+    assert!(app.next_wake() <= Duration::from_millis(1005));
+}
+
+#[test]
+fn test_event_log_lists_session_events() {
+    let config = Config { event_log: true, ..Config::default() };
+    let mut app = headless_app_with(config, pomowise::clock::system());
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+
+    app.handle_event(&key(KeyCode::Enter));
+    app.tick();
+    app.handle_event(&key(KeyCode::Char(' ')));
+    app.tick();
+
+    terminal.draw(|frame| app.draw(frame)).unwrap();
+    let text = screen_text(terminal.backend().buffer());
+    assert!(text.contains("Events"));
+    assert!(text.contains("Work started"));
+    assert!(text.contains("Paused"));
+}
+
+#[test]
+fn test_skipping_a_session_that_never_started_records_nothing() {
+    let clock = MockClock::new();
+    let config = Config {
+        event_log: true,
+        auto_continue: AutoContinueConfig { to_break: false, to_work: false },
+        ..Config::default()
+    };
+    let mut app = headless_app_with(config, clock.shared());
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    app.handle_event(&key(KeyCode::Enter));
+    app.tick();
+    clock.advance(app.timer().remaining);
+    app.tick();
+
+    // The break waits behind "Up next"; skipping it leaves no session behind
+    app.handle_event(&key(KeyCode::Tab));
+    app.tick();
+    terminal.draw(|frame| app.draw(frame)).unwrap();
+    let text = screen_text(terminal.backend().buffer());
+    assert!(text.contains("Work complete"));
+    assert!(!text.contains("skipped"));
+}
+
+#[test]
+fn test_strict_break_cannot_be_shortened() {
+    let clock = MockClock::new();
+    let config = Config {
+        strict_breaks: true,
+        auto_continue: AutoContinueConfig { to_break: true, to_work: false },
+        ..Config::default()
+    };
+    let mut app = headless_app_with(config, clock.shared());
+    app.handle_event(&key(KeyCode::Enter));
+    app.tick();
+    clock.advance(app.timer().remaining);
+    app.tick();
+    assert!(app.timer().is_running());
+    let remaining = app.timer().remaining;
+
+    app.handle_event(&key(KeyCode::Char(':')));
+    for c in "length 0:01".chars() {
+        app.handle_event(&key(KeyCode::Char(c)));
+    }
+    app.handle_event(&key(KeyCode::Enter));
+    app.tick();
+    assert_eq!(app.timer().remaining, remaining);
+}
+
+#[test]
+fn test_quick_actions_add_five_minutes() {
+    let mut app = headless_app();
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    app.handle_event(&key(KeyCode::Enter));
+    app.tick();
+    let before = app.timer().remaining;
+
+    app.handle_event(&key(KeyCode::Char('.')));
+    terminal.draw(|frame| app.draw(frame)).unwrap();
+    let text = screen_text(terminal.backend().buffer());
+    assert!(text.contains("Quick actions"));
+    assert!(text.contains("+5 min"));
+
+    app.handle_event(&key(KeyCode::Char('2')));
+    assert!(app.timer().remaining > before + Duration::from_secs(4 * 60));
+    assert!(app.timer().is_running());
+}
+
+#[test]
+fn test_holding_space_opens_quick_actions() {
+    let mut app = headless_app();
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    app.handle_event(&key(KeyCode::Enter));
+    app.tick();
+
+    // Autorepeat: three presses in quick succession
+    for _ in 0..3 {
+        app.handle_event(&key(KeyCode::Char(' ')));
+    }
+    assert!(app.timer().is_running());
+    terminal.draw(|frame| app.draw(frame)).unwrap();
+    assert!(screen_text(terminal.backend().buffer()).contains("Quick actions"));
+}
+
+#[test]
+fn test_no_pause_reminder_while_up_next_waits() {
+    let clock = MockClock::new();
+    let config = Config {
+        pause_reminder_minutes: 1,
+        auto_continue: AutoContinueConfig { to_break: true, to_work: false },
+        ..Config::default()
+    };
+    let mut app = headless_app_with(config, clock.shared());
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    app.handle_event(&key(KeyCode::Enter));
+    app.tick();
+    app.handle_event(&key(KeyCode::Tab));
+    app.tick();
+
+    // The break runs out; work waits behind "Up next"
+    clock.advance(app.timer().remaining);
+    app.tick();
+    clock.advance(Duration::from_secs(2 * 60));
+    app.tick();
+    terminal.draw(|frame| app.draw(frame)).unwrap();
+    let text = screen_text(terminal.backend().buffer());
+    assert!(text.contains("Up next"));
+    assert!(!text.contains("Still paused"));
+
+    // A real pause still gets its reminder
+    app.handle_event(&key(KeyCode::Char(' ')));
+    app.tick();
+    app.handle_event(&key(KeyCode::Char(' ')));
+    app.tick();
+    assert!(app.timer().is_paused());
+    clock.advance(Duration::from_secs(2 * 60));
+    app.tick();
+    terminal.draw(|frame| app.draw(frame)).unwrap();
+    assert!(screen_text(terminal.backend().buffer()).contains("Still paused"));
+}
+
+#[test]
+fn test_break_checks_nag_while_typing_and_extend_after_away() {
+    let clock = MockClock::new();
+    let mut config = Config {
+        auto_continue: AutoContinueConfig { to_break: true, to_work: false },
+        ..Config::default()
+    };
+    config.break_check.enabled = true;
+    let mut app = headless_app_with(config, clock.shared());
+    let idle = Arc::new(Mutex::new(Some(Duration::from_secs(1))));
+    let source = Arc::clone(&idle);
+    app.set_idle_source(move || *source.lock().unwrap());
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    app.handle_event(&key(KeyCode::Enter));
+    app.tick();
+    app.handle_event(&key(KeyCode::Tab));
+    app.tick();
+    assert!(app.timer().is_running());
+
+    // Still typing half a minute into the break: nudged to step away
+    clock.advance(Duration::from_secs(31));
+    app.tick();
+    terminal.draw(|frame| app.draw(frame)).unwrap();
+    assert!(screen_text(terminal.backend().buffer()).contains("step away"));
+
+    // Gone the whole break: it ends unnoticed and can be taken again
+    *idle.lock().unwrap() = Some(Duration::from_secs(10 * 60));
+    clock.advance(app.timer().remaining);
+    app.tick();
+    terminal.draw(|frame| app.draw(frame)).unwrap();
+    assert!(screen_text(terminal.backend().buffer()).contains("more min of break"));
+
+    app.handle_event(&key(KeyCode::Char('e')));
+    app.tick();
+    assert!(app.timer().is_running());
+    assert!(!matches!(app.timer().state, TimerState::Work { .. }));
+    assert_eq!(app.timer().remaining, Duration::from_secs(5 * 60));
+}
+
+#[test]
+fn test_space_dismisses_the_silent_banner_and_starts_the_break() {
+    let clock = MockClock::new();
+    let config = Config {
+        silent: true,
+        auto_continue: AutoContinueConfig { to_break: false, to_work: false },
+        ..Config::default()
+    };
+    let mut app = headless_app_with(config, clock.shared());
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    app.handle_event(&key(KeyCode::Enter));
+    app.tick();
+    clock.advance(app.timer().remaining);
+    app.tick();
+    terminal.draw(|frame| app.draw(frame)).unwrap();
+    assert!(screen_text(terminal.backend().buffer()).contains("any key: dismiss"));
+    assert!(app.timer().is_paused());
+
+    app.handle_event(&key(KeyCode::Char(' ')));
+    app.tick();
+    terminal.draw(|frame| app.draw(frame)).unwrap();
+    assert!(!screen_text(terminal.backend().buffer()).contains("any key: dismiss"));
+    assert!(app.timer().is_running());
+}
+
+#[test]
+fn test_preset_applies_over_the_config_until_no_preset() {
+    common::isolated_home();
+    let _config_file = common::config_file();
+    std::fs::create_dir_all(pomowise::preset::dir()).unwrap();
+    std::fs::write(
+        pomowise::preset::dir().join("deep-work.toml"),
+        "format = 1\nname = \"Deep Work\"\nfont = \"lcd\"\n\n[durations]\nwork = 50\nshort_break = 10\nlong_break = 30\n\n\
+         [sounds]\ncountdown_ticks = true\nmusic.work = { player = \"noise\", color = \"brown\" }\n",
+    )
+    .unwrap();
+    let mut app = headless_app();
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+
+    // Lengths of our own first, kept through leaving the session
+    app.handle_event(&key(KeyCode::Enter));
+    app.tick();
+    app.handle_event(&key(KeyCode::Char(':')));
+    for c in "work 40".chars() {
+        app.handle_event(&key(KeyCode::Char(c)));
+    }
+    app.handle_event(&key(KeyCode::Enter));
+    app.handle_event(&key(KeyCode::Char('q')));
+    app.handle_event(&key(KeyCode::Char('y')));
+    app.tick();
+    assert!(!app.in_session());
+    assert_eq!(app.timer().work_duration, Duration::from_secs(40 * 60));
+
+    // Down the menu to Presets and pick the preset under "No preset"
+    for _ in 0..8 {
+        terminal.draw(|frame| app.draw(frame)).unwrap();
+        if screen_text(terminal.backend().buffer()).contains("> Presets") {
+            break;
+        }
+        app.handle_event(&key(KeyCode::Down));
+    }
+    app.handle_event(&key(KeyCode::Enter));
+    terminal.draw(|frame| app.draw(frame)).unwrap();
+    assert!(screen_text(terminal.backend().buffer()).contains("No preset"));
+    app.handle_event(&key(KeyCode::Down));
+    app.handle_event(&key(KeyCode::Enter));
+    assert_eq!(app.timer().work_duration, Duration::from_secs(50 * 60));
+
+    // Only the choice is saved, not the preset's font and sounds
+    let saved: Config = serde_json::from_str(&std::fs::read_to_string(Config::path()).unwrap()).unwrap();
+    assert_eq!(saved.preset.as_deref(), Some("Deep Work"));
+    assert_eq!(saved.font, None);
+    assert!(saved.music.is_empty());
+    assert!(!saved.countdown_ticks);
+
+    app.handle_event(&key(KeyCode::Enter));
+    app.handle_event(&key(KeyCode::Up));
+    app.handle_event(&key(KeyCode::Enter));
+    assert_eq!(app.timer().work_duration, Duration::from_secs(40 * 60));
+    let saved: Config = serde_json::from_str(&std::fs::read_to_string(Config::path()).unwrap()).unwrap();
+    assert_eq!(saved.preset, None);
+}
+
+#[test]
+fn test_category_waits_for_the_next_work_session() {
+    let config = Config {
+        auto_continue: AutoContinueConfig { to_break: true, to_work: true },
+        ..Config::default()
+    };
+    let mut app = headless_app_with(config, MockClock::new().shared());
+    app.handle_event(&key(KeyCode::Enter));
+    app.tick();
+
+    // Under way: the session keeps its label
+    app.handle_event(&key(KeyCode::Char('w')));
+    app.tick();
+    assert_eq!(app.timer().category, None);
+
+    // Through the break to the next work session, which starts with it
+    app.handle_event(&key(KeyCode::Tab));
+    app.tick();
+    app.handle_event(&key(KeyCode::Tab));
+    app.tick();
+    assert!(app.timer().is_running());
+    assert_eq!(app.timer().category.as_deref(), Some("Deep Work"));
+}
+
+#[test]
+fn test_theme_fonts_follow_their_theme() {
+    let _config_file = common::config_file();
+    let mut app = headless_app();
+    let mut terminal = Terminal::new(TestBackend::new(100, 30)).unwrap();
+    assert!(app.set_theme("minimal"));
+    app.handle_event(&key(KeyCode::Enter));
+    app.tick();
+    // The adaptive font for this size
+    terminal.draw(|frame| app.draw(frame)).unwrap();
+    let adaptive = app.font();
+
+    // `f` picks a font for this theme only
+    app.handle_event(&key(KeyCode::Char('f')));
+    app.tick();
+    let picked = app.font();
+    assert_ne!(picked, adaptive);
+    assert!(app.set_theme("matrix"));
+    app.tick();
+    assert_eq!(app.font(), adaptive);
+    assert!(app.set_theme("minimal"));
+    app.tick();
+    assert_eq!(app.font(), picked);
+
+    // `F` forgets it again
+    app.handle_event(&Event::Key(KeyEvent::new(KeyCode::Char('F'), KeyModifiers::SHIFT)));
+    app.tick();
+    assert_eq!(app.font(), adaptive);
+    assert!(app.set_theme("matrix"));
+    app.tick();
+    assert!(app.set_theme("minimal"));
+    app.tick();
+    assert_eq!(app.font(), adaptive);
+}
@@ -0,0 +1,88 @@
+//! Layout snapshots of the main screens at fixed sizes, theme, frame and clock.
+//! Only the characters are compared, so palette tweaks don't churn the snapshots
+//! but moved or clipped elements do. After an intended layout change, review and
+//! accept the new output with `cargo insta review` (or `INSTA_UPDATE=always`).
+
+use chrono::{Local, TimeZone};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use insta::assert_snapshot;
+use pomowise::config::Config;
+use pomowise::PomowiseApp;
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+
+mod common;
+
+fn key(code: KeyCode) -> Event {
+    Event::Key(KeyEvent::new(code, KeyModifiers::NONE))
+}
+
+/// Headless app on a static theme, with the clock and animation pinned
+fn frozen_app() -> PomowiseApp {
+    common::isolated_home();
+    // Whatever terminal runs the tests: every glyph, every frame
+    let config: Config = serde_json::from_str(r#"{"glyphs": "full", "slow_link": "off"}"#).unwrap();
+    let mut app = PomowiseApp::with_config(config);
+    assert!(app.set_theme("minimal"));
+    app.set_frame_index(12);
+    app.freeze_clock(Local.with_ymd_and_hms(2025, 3, 14, 9, 30, 0).single());
+    app
+}
+
+/// A work session on the timer screen, never ticked so it reads 25:00
+fn timer_app() -> PomowiseApp {
+    let mut app = frozen_app();
+    app.handle_event(&key(KeyCode::Enter));
+    assert!(app.in_session());
+    app
+}
+
+fn render(app: &mut PomowiseApp, width: u16, height: u16) -> String {
+    let mut terminal = Terminal::new(TestBackend::new(width, height)).unwrap();
+    terminal.draw(|frame| app.draw(frame)).unwrap();
+    terminal.backend().to_string()
+}
+
+#[test]
+fn snapshot_menu() {
+    let mut app = frozen_app();
+    assert_snapshot!(render(&mut app, 100, 30));
+}
+
+#[test]
+fn snapshot_timer() {
+    let mut app = timer_app();
+    assert_snapshot!(render(&mut app, 100, 30));
+}
+
+#[test]
+fn snapshot_timer_compact() {
+    let mut app = timer_app();
+    assert_snapshot!(render(&mut app, 60, 20));
+}
+
+#[test]
+fn snapshot_zen_mode() {
+    let mut app = timer_app();
+    app.handle_event(&key(KeyCode::Char('h')));
+    assert_snapshot!(render(&mut app, 100, 30));
+}
+
+#[test]
+fn snapshot_theme_selector() {
+    let mut app = timer_app();
+    app.handle_event(&key(KeyCode::Char('t')));
+    assert_snapshot!(render(&mut app, 100, 30));
+}
+
+#[test]
+fn snapshot_too_small() {
+    let mut app = frozen_app();
+    assert_snapshot!(render(&mut app, 36, 16));
+}
+
+#[test]
+fn snapshot_bar_mode() {
+    let mut app = timer_app();
+    assert_snapshot!(render(&mut app, 80, 3));
+}